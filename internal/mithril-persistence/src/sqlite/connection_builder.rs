@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 use slog::Logger;
-use sqlite::{Connection, ConnectionThreadSafe};
+use sqlite::{Connection, ConnectionThreadSafe, OpenFlags};
 
 use mithril_common::StdResult;
 
@@ -31,6 +31,9 @@ pub enum ConnectionOptions {
     ///
     /// This option take priority over [ConnectionOptions::EnableForeignKeys] if both are enabled.
     ForceDisableForeignKeys,
+
+    /// Open the database in read only mode, the database file must already exist
+    ReadOnly,
 }
 
 impl ConnectionBuilder {
@@ -78,13 +81,20 @@ impl ConnectionBuilder {
 
     /// Build a connection based on the builder configuration
     pub fn build(self) -> StdResult<ConnectionThreadSafe> {
-        let connection =
-            Connection::open_thread_safe(&self.connection_path).with_context(|| {
-                format!(
-                    "SQLite initialization: could not open connection with string '{}'.",
-                    self.connection_path.display()
-                )
-            })?;
+        let connection = if self.options.contains(&ConnectionOptions::ReadOnly) {
+            Connection::open_thread_safe_with_flags(
+                &self.connection_path,
+                OpenFlags::new().with_read_only().with_full_mutex(),
+            )
+        } else {
+            Connection::open_thread_safe(&self.connection_path)
+        }
+        .with_context(|| {
+            format!(
+                "SQLite initialization: could not open connection with string '{}'.",
+                self.connection_path.display()
+            )
+        })?;
 
         if self
             .options
@@ -235,6 +245,48 @@ mod tests {
         assert_eq!(Value::Integer(NORMAL_SYNCHRONOUS_FLAG), synchronous_flag);
     }
 
+    #[test]
+    fn read_only_connection_can_read_but_not_write_an_existing_database() {
+        let filepath = TempDir::create(
+            "mithril_test_database",
+            "read_only_connection_can_read_but_not_write_an_existing_database",
+        )
+        .join("db.sqlite3");
+        ConnectionBuilder::open_file(&filepath)
+            .with_migrations(vec![SqlMigration::new(
+                1,
+                "create table first(id integer); insert into first(id) values (42);",
+            )])
+            .build()
+            .unwrap();
+
+        let connection = ConnectionBuilder::open_file(&filepath)
+            .with_options(&[ConnectionOptions::ReadOnly])
+            .build()
+            .unwrap();
+
+        let value = execute_single_cell_query(&connection, "select id from first;");
+        assert_eq!(Value::Integer(42), value);
+        connection
+            .execute("insert into first(id) values (43);")
+            .expect_err("Writing with a read only connection should fail");
+    }
+
+    #[test]
+    fn read_only_connection_fails_if_the_database_does_not_exist() {
+        let filepath = TempDir::create(
+            "mithril_test_database",
+            "read_only_connection_fails_if_the_database_does_not_exist",
+        )
+        .join("db.sqlite3");
+
+        ConnectionBuilder::open_file(&filepath)
+            .with_options(&[ConnectionOptions::ReadOnly])
+            .build()
+            .expect_err("Opening a missing database in read only mode should fail");
+        assert!(!filepath.exists());
+    }
+
     #[test]
     fn builder_apply_given_migrations() {
         let connection = ConnectionBuilder::open_memory()
@@ -2,10 +2,9 @@ use super::{AdapterError, StoreAdapter};
 use anyhow::anyhow;
 use async_trait::async_trait;
 
-/// A [StoreAdapter] that store one fixed data record, for testing purpose.
+/// A [StoreAdapter] that store its records in a vector, for testing purpose.
 pub struct DumbStoreAdapter<K, R> {
-    last_key: Option<K>,
-    last_value: Option<R>,
+    records: Vec<(K, R)>,
     error: Option<String>,
 }
 
@@ -13,8 +12,7 @@ impl<K, R> DumbStoreAdapter<K, R> {
     /// DumbStoreAdapter factory
     pub fn new() -> Self {
         Self {
-            last_key: None,
-            last_value: None,
+            records: Vec::new(),
             error: None,
         }
     }
@@ -48,11 +46,21 @@ where
         key: &Self::Key,
         record: &Self::Record,
     ) -> Result<(), AdapterError> {
-        let key = key.clone();
-        let record = record.clone();
+        match self.records.iter_mut().find(|(k, _)| k == key) {
+            Some((_, stored_record)) => *stored_record = record.clone(),
+            None => self.records.push((key.clone(), record.clone())),
+        }
 
-        self.last_key = Some(key);
-        self.last_value = Some(record);
+        Ok(())
+    }
+
+    async fn store_records(
+        &mut self,
+        records: &[(Self::Key, Self::Record)],
+    ) -> Result<(), AdapterError> {
+        for (key, record) in records {
+            self.store_record(key, record).await?;
+        }
 
         Ok(())
     }
@@ -60,41 +68,32 @@ where
     async fn get_record(&self, key: &Self::Key) -> Result<Option<Self::Record>, AdapterError> {
         match &self.error {
             Some(error) => Err(AdapterError::GeneralError(anyhow!(error.clone()))),
-            None => {
-                if self.record_exists(key).await? {
-                    Ok(self.last_value.as_ref().cloned())
-                } else {
-                    Ok(None)
-                }
-            }
+            None => Ok(self
+                .records
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, record)| record.clone())),
         }
     }
 
     async fn record_exists(&self, key: &Self::Key) -> Result<bool, AdapterError> {
-        Ok(self.last_key.is_some() && self.last_key.as_ref().unwrap() == key)
+        Ok(self.records.iter().any(|(k, _)| k == key))
     }
 
     async fn get_last_n_records(
         &self,
         how_many: usize,
     ) -> Result<Vec<(Self::Key, Self::Record)>, AdapterError> {
-        if how_many > 0 {
-            match &self.last_key {
-                Some(_key) => Ok(vec![(
-                    self.last_key.as_ref().cloned().unwrap(),
-                    self.last_value.as_ref().cloned().unwrap(),
-                )]),
-                None => Ok(Vec::new()),
-            }
-        } else {
-            Ok(Vec::new())
-        }
+        Ok(self.records.iter().rev().take(how_many).cloned().collect())
+    }
+
+    async fn get_all_records(&self) -> Result<Vec<(Self::Key, Self::Record)>, AdapterError> {
+        self.get_last_n_records(self.records.len()).await
     }
 
     async fn remove(&mut self, key: &Self::Key) -> Result<Option<Self::Record>, AdapterError> {
         if let Some(record) = self.get_record(key).await? {
-            self.last_key = None;
-            self.last_value = None;
+            self.records.retain(|(k, _)| k != key);
 
             Ok(Some(record))
         } else {
@@ -103,11 +102,9 @@ where
     }
 
     async fn get_iter(&self) -> Result<Box<dyn Iterator<Item = Self::Record> + '_>, AdapterError> {
-        let mut values = vec![];
-        if let Some(value) = &self.last_value {
-            values.push(value.clone());
-        }
-        Ok(Box::new(values.into_iter()))
+        Ok(Box::new(
+            self.records.iter().rev().map(|(_, record)| record.clone()),
+        ))
     }
 }
 
@@ -225,6 +222,20 @@ mod tests {
         assert_eq!(0, records.count());
     }
 
+    #[tokio::test]
+    async fn test_store_records_keeps_all_of_them() {
+        let mut adapter: DumbStoreAdapter<u64, String> = DumbStoreAdapter::new();
+        adapter
+            .store_records(&[(1, "record 1".to_string()), (2, "record 2".to_string())])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![(2, "record 2".to_string()), (1, "record 1".to_string())],
+            adapter.get_all_records().await.unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn test_return_error_calling_get_record() {
         let adapter: DumbStoreAdapter<String, String> =
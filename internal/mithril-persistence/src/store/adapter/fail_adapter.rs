@@ -44,6 +44,15 @@ where
         )))
     }
 
+    async fn store_records(
+        &mut self,
+        _records: &[(Self::Key, Self::Record)],
+    ) -> Result<(), AdapterError> {
+        Err(AdapterError::GeneralError(anyhow!(
+            "Fail adapter always fails"
+        )))
+    }
+
     async fn get_record(&self, _key: &Self::Key) -> Result<Option<Self::Record>, AdapterError> {
         Err(AdapterError::GeneralError(anyhow!(
             "Fail adapter always fails"
@@ -65,6 +74,12 @@ where
         )))
     }
 
+    async fn get_all_records(&self) -> Result<Vec<(Self::Key, Self::Record)>, AdapterError> {
+        Err(AdapterError::GeneralError(anyhow!(
+            "Fail adapter always fails"
+        )))
+    }
+
     async fn remove(&mut self, _key: &Self::Key) -> Result<Option<Self::Record>, AdapterError> {
         Err(AdapterError::GeneralError(anyhow!(
             "Fail adapter always fails"
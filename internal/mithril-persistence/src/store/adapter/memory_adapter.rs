@@ -61,6 +61,23 @@ where
         Ok(())
     }
 
+    async fn store_records(
+        &mut self,
+        records: &[(Self::Key, Self::Record)],
+    ) -> Result<(), AdapterError> {
+        let mut index = self.index.clone();
+        let mut values = self.values.clone();
+        for (key, record) in records {
+            if values.insert(key.clone(), record.clone()).is_none() {
+                index.push(key.clone());
+            }
+        }
+        self.index = index;
+        self.values = values;
+
+        Ok(())
+    }
+
     async fn get_record(&self, key: &Self::Key) -> Result<Option<Self::Record>, AdapterError> {
         match self.values.get(key) {
             Some(val) => Ok(Some(val.clone())),
@@ -85,6 +102,10 @@ where
             .collect())
     }
 
+    async fn get_all_records(&self) -> Result<Vec<(Self::Key, Self::Record)>, AdapterError> {
+        self.get_last_n_records(self.index.len()).await
+    }
+
     async fn remove(&mut self, key: &Self::Key) -> Result<Option<Self::Record>, AdapterError> {
         self.index.retain(|k| *k != *key);
 
@@ -240,4 +261,25 @@ mod tests {
             values
         );
     }
+
+    #[tokio::test]
+    async fn store_records_then_get_all_records() {
+        let mut adapter = init_adapter(2);
+        adapter
+            .store_records(&[
+                (2, "updated value 2".to_string()),
+                (3, "value 3".to_string()),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                (3, "value 3".to_string()),
+                (2, "updated value 2".to_string()),
+                (1, "value 1".to_string()),
+            ],
+            adapter.get_all_records().await.unwrap()
+        );
+    }
 }
@@ -106,6 +106,68 @@ where
         Ok(statement)
     }
 
+    fn insert_record(&self, key: &K, record: &V) -> Result<()>
+    where
+        V: Serialize,
+    {
+        let sql = format!(
+            "insert into {} (key_hash, key, value) values (?1, ?2, ?3) on conflict (key_hash) do update set value = excluded.value",
+            self.table
+        );
+        let value = serde_json::to_string(record).map_err(|e| {
+            AdapterError::GeneralError(
+                anyhow!(e)
+                    .context("SQLite adapter error: could not serialize value before insertion"),
+            )
+        })?;
+        let mut statement = self
+            .connection
+            .prepare(sql)
+            .map_err(|e| AdapterError::InitializationError(e.into()))?;
+        statement
+            .bind((1, self.get_hash_from_key(key)?.as_str()))
+            .map_err(|e| AdapterError::InitializationError(e.into()))?;
+        statement
+            .bind((2, self.serialize_key(key)?.as_str()))
+            .map_err(|e| AdapterError::InitializationError(e.into()))?;
+        statement
+            .bind((3, value.as_str()))
+            .map_err(|e| AdapterError::InitializationError(e.into()))?;
+        let _ = statement
+            .next()
+            .map_err(|e| AdapterError::ParsingDataError(e.into()))?;
+
+        Ok(())
+    }
+
+    fn fetch_key_values(&self, mut statement: Statement) -> Result<Vec<(K, V)>>
+    where
+        K: DeserializeOwned,
+    {
+        let mut results = Vec::new();
+        while State::Row
+            == statement
+                .next()
+                .map_err(|e| AdapterError::QueryError(e.into()))?
+        {
+            let key = statement
+                .read::<String, _>(0)
+                .map_err(|e| AdapterError::QueryError(e.into()))
+                .and_then(|v| {
+                    serde_json::from_str(&v).map_err(|e| AdapterError::ParsingDataError(e.into()))
+                })?;
+            let value = statement
+                .read::<String, _>(1)
+                .map_err(|e| AdapterError::QueryError(e.into()))
+                .and_then(|v| {
+                    serde_json::from_str(&v).map_err(|e| AdapterError::ParsingDataError(e.into()))
+                })?;
+            results.push((key, value));
+        }
+
+        Ok(results)
+    }
+
     fn fetch_maybe_one_value(&self, mut statement: Statement) -> Result<Option<V>> {
         let mut retries = Some(NB_RETRIES_ON_LOCK);
         let mut result = statement.next();
@@ -148,34 +210,27 @@ where
     type Record = V;
 
     async fn store_record(&mut self, key: &Self::Key, record: &Self::Record) -> Result<()> {
-        let sql = format!(
-            "insert into {} (key_hash, key, value) values (?1, ?2, ?3) on conflict (key_hash) do update set value = excluded.value",
-            self.table
-        );
-        let value = serde_json::to_string(record).map_err(|e| {
-            AdapterError::GeneralError(
-                anyhow!(e)
-                    .context("SQLite adapter error: could not serialize value before insertion"),
-            )
-        })?;
-        let mut statement = self
-            .connection
-            .prepare(sql)
-            .map_err(|e| AdapterError::InitializationError(e.into()))?;
-        statement
-            .bind((1, self.get_hash_from_key(key)?.as_str()))
-            .map_err(|e| AdapterError::InitializationError(e.into()))?;
-        statement
-            .bind((2, self.serialize_key(key)?.as_str()))
-            .map_err(|e| AdapterError::InitializationError(e.into()))?;
-        statement
-            .bind((3, value.as_str()))
-            .map_err(|e| AdapterError::InitializationError(e.into()))?;
-        let _ = statement
-            .next()
-            .map_err(|e| AdapterError::ParsingDataError(e.into()))?;
+        self.insert_record(key, record)
+    }
 
-        Ok(())
+    async fn store_records(&mut self, records: &[(Self::Key, Self::Record)]) -> Result<()> {
+        self.connection
+            .execute("begin transaction")
+            .map_err(|e| AdapterError::QueryError(e.into()))?;
+        let result = records
+            .iter()
+            .try_for_each(|(key, record)| self.insert_record(key, record))
+            .and_then(|()| {
+                self.connection
+                    .execute("commit")
+                    .map_err(|e| AdapterError::QueryError(e.into()))
+            });
+        if result.is_err() {
+            // The transaction is still active when an insertion or the commit failed.
+            let _ = self.connection.execute("rollback");
+        }
+
+        result
     }
 
     async fn get_record(&self, key: &Self::Key) -> Result<Option<Self::Record>> {
@@ -232,6 +287,19 @@ where
         Ok(results)
     }
 
+    async fn get_all_records(&self) -> Result<Vec<(Self::Key, Self::Record)>> {
+        let sql = format!(
+            "select cast(key as text) as key, cast(value as text) as value from {} order by ROWID desc",
+            self.table
+        );
+        let statement = self
+            .connection
+            .prepare(sql)
+            .map_err(|e| AdapterError::InitializationError(e.into()))?;
+
+        self.fetch_key_values(statement)
+    }
+
     async fn remove(&mut self, key: &Self::Key) -> Result<Option<Self::Record>> {
         let sql = format!(
             "delete from {} where key_hash = ?1 returning value",
@@ -478,4 +546,83 @@ mod tests {
             values
         );
     }
+
+    #[tokio::test]
+    async fn test_get_all_records() {
+        let test_name = "test_get_all_records";
+        let mut adapter = init_db(&get_file_path(test_name), None);
+        assert!(adapter.get_all_records().await.unwrap().is_empty());
+
+        adapter.store_record(&1, &"one".to_string()).await.unwrap();
+        adapter.store_record(&2, &"two".to_string()).await.unwrap();
+        adapter
+            .store_record(&3, &"three".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                (3_u64, "three".to_string()),
+                (2_u64, "two".to_string()),
+                (1_u64, "one".to_string()),
+            ],
+            adapter
+                .get_all_records()
+                .await
+                .expect("get all records should not fail")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_store_records() {
+        let test_name = "test_store_records";
+        let mut adapter = init_db(&get_file_path(test_name), None);
+        adapter.store_record(&1, &"one".to_string()).await.unwrap();
+
+        adapter
+            .store_records(&[(1, "updated one".to_string()), (2, "two".to_string())])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                (2_u64, "two".to_string()),
+                (1_u64, "updated one".to_string())
+            ],
+            adapter.get_all_records().await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_store_records_stores_none_of_them_if_one_fails() {
+        let test_name = "test_store_records_stores_none_of_them_if_one_fails";
+        let filepath = get_file_path(test_name);
+        let connection = Connection::open_thread_safe(&filepath).unwrap();
+        connection
+            .execute(format!(
+                r#"create table {TABLE_NAME} (key_hash text primary key, key json not null, value json not null check (value != '"invalid"'))"#
+            ))
+            .unwrap();
+        let mut adapter: SQLiteAdapter<u64, String> =
+            SQLiteAdapter::new(TABLE_NAME, Arc::new(connection)).unwrap();
+        adapter.store_record(&1, &"one".to_string()).await.unwrap();
+
+        adapter
+            .store_records(&[
+                (1, "updated one".to_string()),
+                (2, "two".to_string()),
+                (3, "invalid".to_string()),
+            ])
+            .await
+            .expect_err("storing an invalid record should fail");
+
+        assert_eq!(
+            vec![(1_u64, "one".to_string())],
+            adapter.get_all_records().await.unwrap()
+        );
+        adapter
+            .store_record(&2, &"two".to_string())
+            .await
+            .expect("the transaction should have been rolled back");
+    }
 }
@@ -42,6 +42,12 @@ pub trait StoreAdapter: Sync + Send {
         record: &Self::Record,
     ) -> Result<(), AdapterError>;
 
+    /// Store the given `records` at once: if one of them can not be stored, none of them is.
+    async fn store_records(
+        &mut self,
+        records: &[(Self::Key, Self::Record)],
+    ) -> Result<(), AdapterError>;
+
     /// Get the record stored using the given `key`.
     async fn get_record(&self, key: &Self::Key) -> Result<Option<Self::Record>, AdapterError>;
 
@@ -54,6 +60,9 @@ pub trait StoreAdapter: Sync + Send {
         how_many: usize,
     ) -> Result<Vec<(Self::Key, Self::Record)>, AdapterError>;
 
+    /// Get all the records in the store, from the latest to the oldest.
+    async fn get_all_records(&self) -> Result<Vec<(Self::Key, Self::Record)>, AdapterError>;
+
     /// remove values from store
     ///
     /// if the value exists it is returned by the adapter otherwise None is returned
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use mithril_common::entities::{Epoch, StakeDistribution};
+use mithril_common::signable_builder::StakeDistributionRetriever;
 use mithril_common::StdResult;
 use tokio::sync::RwLock;
 
@@ -79,6 +80,13 @@ impl StakeStorer for StakeStore {
     }
 }
 
+#[async_trait]
+impl StakeDistributionRetriever for StakeStore {
+    async fn retrieve(&self, epoch: Epoch) -> StdResult<Option<StakeDistribution>> {
+        self.get_stakes(epoch).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::adapter::MemoryAdapter;
@@ -167,4 +175,28 @@ mod tests {
             .unwrap();
         assert!(store.get_stakes(Epoch(1)).await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn retrieve_with_no_stakes_returns_none() {
+        let store = init_store(0, 0, None);
+
+        let result = store.retrieve(Epoch(1)).await.unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn retrieve_returns_stake_distribution() {
+        let store = init_store(2, 2, None);
+
+        let stake_distribution = store.retrieve(Epoch(2)).await.unwrap();
+
+        assert_eq!(
+            Some(StakeDistribution::from([
+                ("1".to_string(), 101),
+                ("2".to_string(), 201)
+            ])),
+            stake_distribution
+        );
+    }
 }
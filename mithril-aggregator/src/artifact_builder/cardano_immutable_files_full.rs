@@ -1,4 +1,4 @@
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use semver::Version;
 use slog_scope::{debug, warn};
@@ -31,22 +31,25 @@ pub enum CardanoImmutableFilesFullArtifactError {
 pub struct CardanoImmutableFilesFullArtifactBuilder {
     cardano_node_version: Version,
     snapshotter: Arc<dyn Snapshotter>,
-    snapshot_uploader: Arc<dyn SnapshotUploader>,
+    snapshot_uploaders: Vec<Arc<dyn SnapshotUploader>>,
     compression_algorithm: CompressionAlgorithm,
 }
 
 impl CardanoImmutableFilesFullArtifactBuilder {
     /// CardanoImmutableFilesFull artifact builder factory
+    ///
+    /// The snapshot archive is uploaded with each of the given uploaders, in order, the first
+    /// one being the main location and the others its mirrors.
     pub fn new(
         cardano_node_version: &Version,
         snapshotter: Arc<dyn Snapshotter>,
-        snapshot_uploader: Arc<dyn SnapshotUploader>,
+        snapshot_uploaders: Vec<Arc<dyn SnapshotUploader>>,
         compression_algorithm: CompressionAlgorithm,
     ) -> Self {
         Self {
             cardano_node_version: cardano_node_version.clone(),
             snapshotter,
-            snapshot_uploader,
+            snapshot_uploaders,
             compression_algorithm,
         }
     }
@@ -84,10 +87,20 @@ impl CardanoImmutableFilesFullArtifactBuilder {
         ongoing_snapshot: &OngoingSnapshot,
     ) -> StdResult<Vec<SnapshotLocation>> {
         debug!("CardanoImmutableFilesFullArtifactBuilder: upload snapshot archive");
-        let location = self
-            .snapshot_uploader
-            .upload_snapshot(ongoing_snapshot.get_file_path())
-            .await;
+        let mut locations = vec![];
+        let mut last_error = None;
+        for snapshot_uploader in &self.snapshot_uploaders {
+            match snapshot_uploader
+                .upload_snapshot(ongoing_snapshot.get_file_path())
+                .await
+            {
+                Ok(location) => locations.push(location),
+                Err(error) => {
+                    warn!(" > Snapshot upload failure: {:?}", error);
+                    last_error = Some(error);
+                }
+            }
+        }
 
         if let Err(error) = tokio::fs::remove_file(ongoing_snapshot.get_file_path()).await {
             warn!(
@@ -96,7 +109,13 @@ impl CardanoImmutableFilesFullArtifactBuilder {
             );
         }
 
-        Ok(vec![location?])
+        if locations.is_empty() {
+            return Err(last_error
+                .unwrap_or_else(|| anyhow!("No snapshot uploader available"))
+                .context("None of the snapshot uploads succeeded"));
+        }
+
+        Ok(locations)
     }
 
     async fn create_snapshot(
@@ -185,7 +204,7 @@ mod tests {
             CardanoImmutableFilesFullArtifactBuilder::new(
                 &Version::parse("1.0.0").unwrap(),
                 dumb_snapshotter.clone(),
-                dumb_snapshot_uploader.clone(),
+                vec![dumb_snapshot_uploader.clone()],
                 CompressionAlgorithm::Zstandard,
             );
         let artifact = cardano_immutable_files_full_artifact_builder
@@ -222,7 +241,7 @@ mod tests {
             CardanoImmutableFilesFullArtifactBuilder::new(
                 &Version::parse("1.0.0").unwrap(),
                 Arc::new(DumbSnapshotter::new()),
-                Arc::new(DumbSnapshotUploader::new()),
+                vec![Arc::new(DumbSnapshotUploader::new())],
                 CompressionAlgorithm::default(),
             );
 
@@ -246,7 +265,7 @@ mod tests {
             CardanoImmutableFilesFullArtifactBuilder::new(
                 &Version::parse("1.0.0").unwrap(),
                 Arc::new(DumbSnapshotter::new()),
-                Arc::new(DumbSnapshotUploader::new()),
+                vec![Arc::new(DumbSnapshotUploader::new())],
                 CompressionAlgorithm::Gzip,
             );
 
@@ -273,7 +292,7 @@ mod tests {
                 CardanoImmutableFilesFullArtifactBuilder::new(
                     &Version::parse("1.0.0").unwrap(),
                     Arc::new(DumbSnapshotter::new()),
-                    Arc::new(DumbSnapshotUploader::new()),
+                    vec![Arc::new(DumbSnapshotUploader::new())],
                     algorithm,
                 );
 
@@ -314,7 +333,7 @@ mod tests {
             CardanoImmutableFilesFullArtifactBuilder::new(
                 &Version::parse("1.0.0").unwrap(),
                 Arc::new(DumbSnapshotter::new()),
-                Arc::new(snapshot_uploader),
+                vec![Arc::new(snapshot_uploader)],
                 CompressionAlgorithm::default(),
             );
 
@@ -328,4 +347,109 @@ mod tests {
             "Ongoing snapshot file should have been removed even after upload failure"
         );
     }
+
+    #[tokio::test]
+    async fn upload_snapshot_archive_with_all_uploaders_and_keep_their_locations_in_order() {
+        let file = NamedTempFile::new().unwrap();
+        let snapshot = OngoingSnapshot::new(file.path().to_path_buf(), 7331);
+        let snapshot_uploaders: Vec<Arc<dyn SnapshotUploader>> = ["main", "mirror-1", "mirror-2"]
+            .into_iter()
+            .map(|name| {
+                let mut snapshot_uploader = MockSnapshotUploader::new();
+                snapshot_uploader
+                    .expect_upload_snapshot()
+                    .return_once(move |_| Ok(format!("https://{name}/snapshot.tar.gz")))
+                    .once();
+                Arc::new(snapshot_uploader) as Arc<dyn SnapshotUploader>
+            })
+            .collect();
+
+        let cardano_immutable_files_full_artifact_builder =
+            CardanoImmutableFilesFullArtifactBuilder::new(
+                &Version::parse("1.0.0").unwrap(),
+                Arc::new(DumbSnapshotter::new()),
+                snapshot_uploaders,
+                CompressionAlgorithm::default(),
+            );
+
+        let locations = cardano_immutable_files_full_artifact_builder
+            .upload_snapshot_archive(&snapshot)
+            .await
+            .expect("Snapshot upload should not fail");
+
+        assert_eq!(
+            vec![
+                "https://main/snapshot.tar.gz".to_string(),
+                "https://mirror-1/snapshot.tar.gz".to_string(),
+                "https://mirror-2/snapshot.tar.gz".to_string(),
+            ],
+            locations
+        );
+    }
+
+    #[tokio::test]
+    async fn upload_snapshot_archive_skip_the_locations_of_failing_uploaders() {
+        let file = NamedTempFile::new().unwrap();
+        let snapshot = OngoingSnapshot::new(file.path().to_path_buf(), 7331);
+        let mut failing_snapshot_uploader = MockSnapshotUploader::new();
+        failing_snapshot_uploader
+            .expect_upload_snapshot()
+            .return_once(|_| Err(anyhow!("an error")))
+            .once();
+        let mut snapshot_uploader = MockSnapshotUploader::new();
+        snapshot_uploader
+            .expect_upload_snapshot()
+            .return_once(|_| Ok("https://mirror/snapshot.tar.gz".to_string()))
+            .once();
+
+        let cardano_immutable_files_full_artifact_builder =
+            CardanoImmutableFilesFullArtifactBuilder::new(
+                &Version::parse("1.0.0").unwrap(),
+                Arc::new(DumbSnapshotter::new()),
+                vec![
+                    Arc::new(failing_snapshot_uploader),
+                    Arc::new(snapshot_uploader),
+                ],
+                CompressionAlgorithm::default(),
+            );
+
+        let locations = cardano_immutable_files_full_artifact_builder
+            .upload_snapshot_archive(&snapshot)
+            .await
+            .expect("Snapshot upload should not fail if at least one uploader succeed");
+
+        assert_eq!(
+            vec!["https://mirror/snapshot.tar.gz".to_string()],
+            locations
+        );
+    }
+
+    #[tokio::test]
+    async fn upload_snapshot_archive_fails_when_all_uploaders_fail() {
+        let file = NamedTempFile::new().unwrap();
+        let snapshot = OngoingSnapshot::new(file.path().to_path_buf(), 7331);
+        let snapshot_uploaders: Vec<Arc<dyn SnapshotUploader>> = (0..2)
+            .map(|_| {
+                let mut snapshot_uploader = MockSnapshotUploader::new();
+                snapshot_uploader
+                    .expect_upload_snapshot()
+                    .return_once(|_| Err(anyhow!("an error")))
+                    .once();
+                Arc::new(snapshot_uploader) as Arc<dyn SnapshotUploader>
+            })
+            .collect();
+
+        let cardano_immutable_files_full_artifact_builder =
+            CardanoImmutableFilesFullArtifactBuilder::new(
+                &Version::parse("1.0.0").unwrap(),
+                Arc::new(DumbSnapshotter::new()),
+                snapshot_uploaders,
+                CompressionAlgorithm::default(),
+            );
+
+        cardano_immutable_files_full_artifact_builder
+            .upload_snapshot_archive(&snapshot)
+            .await
+            .expect_err("Snapshot upload should fail when all uploaders fail");
+    }
 }
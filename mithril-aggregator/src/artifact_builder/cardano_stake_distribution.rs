@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+
+use super::ArtifactBuilder;
+use mithril_common::{
+    entities::{CardanoStakeDistribution, Certificate, Epoch},
+    signable_builder::StakeDistributionRetriever,
+    StdResult,
+};
+
+/// A [CardanoStakeDistributionArtifact] builder
+pub struct CardanoStakeDistributionArtifactBuilder {
+    stake_distribution_retriever: Arc<dyn StakeDistributionRetriever>,
+}
+
+impl CardanoStakeDistributionArtifactBuilder {
+    /// CardanoStakeDistribution artifact builder factory
+    pub fn new(stake_distribution_retriever: Arc<dyn StakeDistributionRetriever>) -> Self {
+        Self {
+            stake_distribution_retriever,
+        }
+    }
+}
+
+#[async_trait]
+impl ArtifactBuilder<Epoch, CardanoStakeDistribution> for CardanoStakeDistributionArtifactBuilder {
+    async fn compute_artifact(
+        &self,
+        epoch: Epoch,
+        _certificate: &Certificate,
+    ) -> StdResult<CardanoStakeDistribution> {
+        let stake_distribution = self
+            .stake_distribution_retriever
+            .retrieve(epoch.offset_to_recording_epoch())
+            .await?
+            .ok_or_else(|| anyhow!("No stake distribution found for epoch '{}'", epoch))?;
+
+        Ok(CardanoStakeDistribution::new(epoch, stake_distribution))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::{entities::StakeDistribution, test_utils::fake_data};
+    use mithril_persistence::store::StakeStorer;
+
+    use crate::database::{repository::StakePoolStore, test_helper::main_db_connection};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn compute_artifact_returns_valid_artifact_and_retrieve_with_epoch_offset() {
+        let epoch = Epoch(1);
+        let certificate = fake_data::certificate("whatever".to_string());
+        let stake_distribution = StakeDistribution::from([("pool-123".to_string(), 123)]);
+        let stake_store = StakePoolStore::new(Arc::new(main_db_connection().unwrap()), None);
+        stake_store
+            .save_stakes(
+                epoch.offset_to_recording_epoch(),
+                stake_distribution.clone(),
+            )
+            .await
+            .unwrap();
+        let builder = CardanoStakeDistributionArtifactBuilder::new(Arc::new(stake_store));
+
+        let cardano_stake_distribution =
+            builder.compute_artifact(epoch, &certificate).await.unwrap();
+
+        let expected = CardanoStakeDistribution::new(epoch, stake_distribution);
+        assert_eq!(cardano_stake_distribution, expected);
+    }
+
+    #[tokio::test]
+    async fn compute_artifact_returns_error_if_no_stakes_found_for_epoch() {
+        let epoch = Epoch(1);
+        let certificate = fake_data::certificate("whatever".to_string());
+        let stake_store = StakePoolStore::new(Arc::new(main_db_connection().unwrap()), None);
+        let builder = CardanoStakeDistributionArtifactBuilder::new(Arc::new(stake_store));
+
+        builder
+            .compute_artifact(epoch, &certificate)
+            .await
+            .expect_err("Should return error");
+    }
+}
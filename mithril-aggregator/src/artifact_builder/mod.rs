@@ -1,10 +1,12 @@
 //! The module used for building artifact
 mod cardano_immutable_files_full;
+mod cardano_stake_distribution;
 mod cardano_transactions;
 mod interface;
 mod mithril_stake_distribution;
 
 pub use cardano_immutable_files_full::*;
+pub use cardano_stake_distribution::*;
 pub use cardano_transactions::*;
 pub use interface::*;
 pub use mithril_stake_distribution::*;
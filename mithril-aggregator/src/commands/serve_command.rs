@@ -7,7 +7,7 @@ use std::time::Duration;
 use std::{net::IpAddr, path::PathBuf};
 use tokio::{sync::oneshot, task::JoinSet};
 
-use crate::{dependency_injection::DependenciesBuilder, Configuration};
+use crate::{dependency_injection::DependenciesBuilder, Configuration, MetricsServer};
 
 const SQLITE_MONITORING_FILE: &str = "monitoring.sqlite3";
 
@@ -42,6 +42,18 @@ pub struct ServeCommand {
     /// Will be ignored on (pre)production networks.
     #[clap(long)]
     allow_unparsable_block: bool,
+
+    /// Enable metrics HTTP server (Prometheus endpoint on /metrics).
+    #[clap(long)]
+    enable_metrics_server: bool,
+
+    /// Metrics HTTP server IP.
+    #[clap(long)]
+    metrics_server_ip: Option<String>,
+
+    /// Metrics HTTP server listening port.
+    #[clap(long)]
+    metrics_server_port: Option<u16>,
 }
 
 impl Source for ServeCommand {
@@ -74,6 +86,24 @@ impl Source for ServeCommand {
                 ),
             );
         }
+        if self.enable_metrics_server {
+            result.insert(
+                "enable_metrics_server".to_string(),
+                Value::new(Some(&namespace), ValueKind::from(true)),
+            );
+        }
+        if let Some(metrics_server_ip) = self.metrics_server_ip.clone() {
+            result.insert(
+                "metrics_server_ip".to_string(),
+                Value::new(Some(&namespace), ValueKind::from(metrics_server_ip)),
+            );
+        }
+        if let Some(metrics_server_port) = self.metrics_server_port {
+            result.insert(
+                "metrics_server_port".to_string(),
+                Value::new(Some(&namespace), ValueKind::from(metrics_server_port)),
+            );
+        }
 
         Ok(result)
     }
@@ -88,6 +118,10 @@ impl ServeCommand {
             .try_deserialize()
             .with_context(|| "configuration deserialize error")?;
         debug!("SERVE command"; "config" => format!("{config:?}"));
+        config.check_transactions_pruning_retained_block_ranges()?;
+        if let Err(error) = config.check_protocol_parameters_security() {
+            warn!("Unsafe protocol parameters configured"; "error" => ?error);
+        }
         let mut dependencies_builder = DependenciesBuilder::new(config.clone());
 
         // start servers
@@ -140,32 +174,48 @@ impl ServeCommand {
             Ok(())
         });
 
-        // Create a SignersImporter only if the `cexplorer_pools_url` is provided in the config.
-        if let Some(cexplorer_pools_url) = config.cexplorer_pools_url {
-            match dependencies_builder
-                .create_signer_importer(&cexplorer_pools_url)
+        // start the metrics HTTP server
+        let (metrics_server_shutdown_tx, metrics_server_shutdown_rx) = oneshot::channel();
+        if config.enable_metrics_server {
+            let metrics_service = dependencies_builder
+                .get_metrics_service()
                 .await
-            {
-                Ok(service) => {
-                    join_set.spawn(async move {
-                        // Wait 5s to let the other services the time to start before running
-                        // the first import.
-                        tokio::time::sleep(Duration::from_secs(5)).await;
-                        service
-                            .run_forever(Duration::from_secs(
-                                // Import interval are in minutes
-                                config.signer_importer_run_interval * 60,
-                            ))
-                            .await;
-                        Ok(())
-                    });
-                }
-                Err(error) => {
-                    warn!(
-                        "Failed to build the `SignersImporter`:\n url to import `{}`\n Error: {:?}",
-                        cexplorer_pools_url, error
-                    );
-                }
+                .with_context(|| "Dependencies Builder can not create metrics service")?;
+            let metrics_server = MetricsServer::new(
+                &config.metrics_server_ip,
+                config.metrics_server_port,
+                metrics_service,
+            );
+            join_set.spawn(async move {
+                metrics_server
+                    .start(metrics_server_shutdown_rx)
+                    .await
+                    .map_err(|e| e.to_string())
+            });
+        }
+
+        // Create a SignersImporter only if a source to import the signers from is configured.
+        match dependencies_builder.create_signer_importer().await {
+            Ok(None) => {}
+            Ok(Some(service)) => {
+                join_set.spawn(async move {
+                    // Wait 5s to let the other services the time to start before running
+                    // the first import.
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    service
+                        .run_forever(Duration::from_secs(
+                            // Import interval are in minutes
+                            config.signer_importer_run_interval * 60,
+                        ))
+                        .await;
+                    Ok(())
+                });
+            }
+            Err(error) => {
+                warn!(
+                    "Failed to build the `SignersImporter`:\n Error: {:?}",
+                    error
+                );
             }
         }
 
@@ -179,6 +229,7 @@ impl ServeCommand {
         // stop servers
         join_set.shutdown().await;
         let _ = shutdown_tx.send(());
+        let _ = metrics_server_shutdown_tx.send(());
 
         info!("Event store is finishing...");
         event_store_thread.await.unwrap();
@@ -1,7 +1,10 @@
 use anyhow::anyhow;
 use config::{ConfigError, Map, Source, Value, ValueKind};
 use mithril_common::chain_observer::ChainObserverType;
-use mithril_common::crypto_helper::ProtocolGenesisSigner;
+use mithril_common::crypto_helper::{
+    ProtocolGenesisSigner, ProtocolParameters as StmProtocolParameters, ProtocolParametersSecurity,
+    ProtocolSecurityRequirements,
+};
 use mithril_common::era::adapters::EraReaderAdapterType;
 use mithril_doc::{Documenter, DocumenterDefault, StructDoc};
 use serde::{Deserialize, Serialize};
@@ -13,6 +16,7 @@ use mithril_common::entities::{
     CompressionAlgorithm, HexEncodedGenesisVerificationKey, ProtocolParameters, SignedEntityType,
     SignedEntityTypeDiscriminants, TimePoint,
 };
+use mithril_common::messages::MAX_TRANSACTIONS_HASHES_BY_REQUEST;
 use mithril_common::{CardanoNetwork, StdResult};
 
 /// Different kinds of execution environments
@@ -40,6 +44,30 @@ impl FromStr for ExecutionEnvironment {
     }
 }
 
+/// Secret value of the configuration, redacted when the configuration is displayed
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ConfigurationSecret(String);
+
+impl ConfigurationSecret {
+    /// Expose the secret value
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for ConfigurationSecret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl std::fmt::Debug for ConfigurationSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ConfigurationSecret(**redacted**)")
+    }
+}
+
 /// Aggregator configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Documenter)]
 pub struct Configuration {
@@ -80,15 +108,90 @@ pub struct Configuration {
     pub protocol_parameters: ProtocolParameters,
 
     /// Type of snapshot uploader to use
-    #[example = "`gcp` or `local`"]
+    #[example = "`gcp`, `s3` or `local`"]
     pub snapshot_uploader_type: SnapshotUploaderType,
 
-    /// Bucket name where the snapshots are stored if snapshot_uploader_type is Gcp
+    /// Types of the snapshot uploaders used as mirrors of the main one (comma separated list)
+    ///
+    /// The snapshots are uploaded to each of those mirrors after the main uploader and all the
+    /// resulting locations are recorded, in order, in the snapshot artifacts.
+    /// Each type can be listed once, and `local` only if the main uploader is not local.
+    #[example = "`local` or `s3,local`"]
+    pub snapshot_mirror_uploader_types: Option<String>,
+
+    /// Bucket name where the snapshots are stored if snapshot_uploader_type is Gcp or S3
     pub snapshot_bucket_name: Option<String>,
 
     /// Use CDN domain to construct snapshot urls if snapshot_uploader_type is Gcp
     pub snapshot_use_cdn_domain: bool,
 
+    /// Endpoint of the S3 compatible storage if snapshot_uploader_type is S3 (AWS S3 is used if not set)
+    #[example = "`http://localhost:9000`"]
+    pub snapshot_s3_endpoint: Option<String>,
+
+    /// Region of the bucket if snapshot_uploader_type is S3
+    #[example = "`us-east-1`"]
+    pub snapshot_s3_region: String,
+
+    /// Access key id of the S3 credentials if snapshot_uploader_type is S3
+    ///
+    /// If not set, the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` environment variables are used.
+    pub snapshot_s3_access_key_id: Option<String>,
+
+    /// Secret access key of the S3 credentials if snapshot_uploader_type is S3
+    pub snapshot_s3_secret_access_key: Option<ConfigurationSecret>,
+
+    /// Path prefix of the snapshots in the bucket if snapshot_uploader_type is S3
+    #[example = "`preview/snapshots`"]
+    pub snapshot_s3_path_prefix: Option<String>,
+
+    /// Public url under which the bucket objects are served if snapshot_uploader_type is S3
+    ///
+    /// If not set the url is computed from the endpoint (or the AWS S3 bucket url) and the bucket name.
+    #[example = "`https://cdn.mithril.network`"]
+    pub snapshot_s3_public_url: Option<String>,
+
+    /// Size in bytes of the parts of the multipart uploads if snapshot_uploader_type is S3
+    ///
+    /// Snapshots bigger than this size are uploaded in multiple parts, must be at least 5MiB.
+    #[example = "`104857600`"]
+    pub snapshot_s3_multipart_part_size: u64,
+
+    /// Bucket name where the snapshots are stored if a mirror uploader is Gcp or S3
+    ///
+    /// The mirror uploaders have their own storage settings, the `snapshot_mirror_*` parameters
+    /// are used instead of their `snapshot_*` counterparts of the main uploader.
+    pub snapshot_mirror_bucket_name: Option<String>,
+
+    /// Use CDN domain to construct snapshot urls if a mirror uploader is Gcp
+    pub snapshot_mirror_use_cdn_domain: bool,
+
+    /// Endpoint of the S3 compatible storage if a mirror uploader is S3 (AWS S3 is used if not set)
+    #[example = "`http://localhost:9000`"]
+    pub snapshot_mirror_s3_endpoint: Option<String>,
+
+    /// Region of the bucket if a mirror uploader is S3 (`snapshot_s3_region` is used if not set)
+    #[example = "`us-east-1`"]
+    pub snapshot_mirror_s3_region: Option<String>,
+
+    /// Access key id of the S3 credentials if a mirror uploader is S3
+    ///
+    /// If not set, the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` environment variables are used.
+    pub snapshot_mirror_s3_access_key_id: Option<String>,
+
+    /// Secret access key of the S3 credentials if a mirror uploader is S3
+    pub snapshot_mirror_s3_secret_access_key: Option<ConfigurationSecret>,
+
+    /// Path prefix of the snapshots in the bucket if a mirror uploader is S3
+    #[example = "`preview/snapshots`"]
+    pub snapshot_mirror_s3_path_prefix: Option<String>,
+
+    /// Public url under which the bucket objects are served if a mirror uploader is S3
+    ///
+    /// If not set the url is computed from the endpoint (or the AWS S3 bucket url) and the bucket name.
+    #[example = "`https://mirror.cdn.mithril.network`"]
+    pub snapshot_mirror_s3_public_url: Option<String>,
+
     /// Server listening IP
     pub server_ip: String,
 
@@ -149,10 +252,83 @@ pub struct Configuration {
     /// Time interval at which the signers in [Self::cexplorer_pools_url] will be imported (in minutes).
     pub signer_importer_run_interval: u64,
 
+    /// Source from which the signers are imported.
+    #[example = "`cexplorer`, `file` or `json-http`"]
+    pub signer_importer_source_type: SignerImporterSourceType,
+
+    /// Parameters of the signers importer source, a JSON encoded structure that depends on
+    /// [Self::signer_importer_source_type]:
+    /// - `file`: `{"path": "/path/to/pools.json"}` (`.json` or `.csv` file)
+    /// - `json-http`: `{"url": "https://...", "items_path": "$", "pool_id_path": "pool_id_bech32", "ticker_path": "ticker"}`,
+    ///   fetched by pages of `page_size` items (`1000` by default, `null` to fetch it at once)
+    pub signer_importer_source_params: Option<String>,
+
     /// If set no error is returned in case of unparsable block and an error log is written instead.
     ///
     /// Will be ignored on (pre)production networks.
     pub allow_unparsable_block: bool,
+
+    /// Enable metrics server (Prometheus endpoint on /metrics).
+    pub enable_metrics_server: bool,
+
+    /// Metrics HTTP Server IP.
+    pub metrics_server_ip: String,
+
+    /// Metrics HTTP Server listening port.
+    pub metrics_server_port: u16,
+
+    /// Maximum number of transaction hashes allowed by request to the prover of the Cardano transactions
+    pub cardano_transactions_prover_max_hashes_allowed_by_request: usize,
+
+    /// If set, the Cardano transactions that are not in the last
+    /// [Self::transactions_pruning_retained_block_ranges] block ranges are pruned from the
+    /// database after each import.
+    ///
+    /// Proofs can't be computed anymore for the pruned transactions.
+    pub enable_transaction_pruning: bool,
+
+    /// Number of block ranges, ending with the last one whose Merkle root has been computed,
+    /// for which the Cardano transactions are kept when pruning.
+    pub transactions_pruning_retained_block_ranges: u64,
+
+    /// If set, the Cardano transactions are imported by following the chain of the Cardano node
+    /// with the chain-sync mini-protocol up to the last completed immutable file, instead of
+    /// reading the immutable files.
+    ///
+    /// Experimental: all the nodes of a Mithril network must use the same block scanner,
+    /// otherwise they won't compute the same Merkle roots.
+    pub enable_chain_sync_block_scanner: bool,
+}
+
+/// Settings of the storage where a snapshot uploader copies the snapshots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotStorageSettings {
+    /// Name of the configuration parameter of the bucket name, used to report its absence
+    pub bucket_name_parameter: &'static str,
+
+    /// Bucket name where the snapshots are stored if the uploader is Gcp or S3
+    pub bucket_name: Option<String>,
+
+    /// Use CDN domain to construct snapshot urls if the uploader is Gcp
+    pub use_cdn_domain: bool,
+
+    /// Endpoint of the S3 compatible storage if the uploader is S3
+    pub s3_endpoint: Option<String>,
+
+    /// Region of the bucket if the uploader is S3
+    pub s3_region: String,
+
+    /// Access key id of the S3 credentials if the uploader is S3
+    pub s3_access_key_id: Option<String>,
+
+    /// Secret access key of the S3 credentials if the uploader is S3
+    pub s3_secret_access_key: Option<ConfigurationSecret>,
+
+    /// Path prefix of the snapshots in the bucket if the uploader is S3
+    pub s3_path_prefix: Option<String>,
+
+    /// Public url under which the bucket objects are served if the uploader is S3
+    pub s3_public_url: Option<String>,
 }
 
 /// Uploader needed to copy the snapshot once computed.
@@ -161,10 +337,41 @@ pub struct Configuration {
 pub enum SnapshotUploaderType {
     /// Uploader to GCP storage.
     Gcp,
+    /// Uploader to a S3 compatible storage.
+    S3,
     /// Uploader to local storage.
     Local,
 }
 
+impl FromStr for SnapshotUploaderType {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gcp" => Ok(Self::Gcp),
+            "s3" => Ok(Self::S3),
+            "local" => Ok(Self::Local),
+            _ => Err(ConfigError::Message(format!(
+                "Unknown snapshot uploader type '{s}'"
+            ))),
+        }
+    }
+}
+
+/// Source from which the signers importer retrieves the signers and their pool tickers.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SignerImporterSourceType {
+    /// CExplorer list of pools, fetched from [Configuration::cexplorer_pools_url].
+    #[serde(rename = "cexplorer")]
+    CExplorer,
+    /// Local JSON or CSV file of pool ids and tickers.
+    #[serde(rename = "file")]
+    File,
+    /// Any HTTP source returning a JSON document, ie: a Koios `pool_list` endpoint.
+    #[serde(rename = "json-http")]
+    JsonHttp,
+}
+
 /// [Zstandard][CompressionAlgorithm::Zstandard] specific parameters
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ZstandardCompressionParameters {
@@ -205,8 +412,24 @@ impl Configuration {
                 phi_f: 0.95,
             },
             snapshot_uploader_type: SnapshotUploaderType::Local,
+            snapshot_mirror_uploader_types: None,
             snapshot_bucket_name: None,
             snapshot_use_cdn_domain: false,
+            snapshot_s3_endpoint: None,
+            snapshot_s3_region: "us-east-1".to_string(),
+            snapshot_s3_access_key_id: None,
+            snapshot_s3_secret_access_key: None,
+            snapshot_s3_path_prefix: None,
+            snapshot_s3_public_url: None,
+            snapshot_s3_multipart_part_size: 100 * 1024 * 1024,
+            snapshot_mirror_bucket_name: None,
+            snapshot_mirror_use_cdn_domain: false,
+            snapshot_mirror_s3_endpoint: None,
+            snapshot_mirror_s3_region: None,
+            snapshot_mirror_s3_access_key_id: None,
+            snapshot_mirror_s3_secret_access_key: None,
+            snapshot_mirror_s3_path_prefix: None,
+            snapshot_mirror_s3_public_url: None,
             server_ip: "0.0.0.0".to_string(),
             server_port: 8000,
             run_interval: 5000,
@@ -224,7 +447,16 @@ impl Configuration {
             zstandard_parameters: Some(ZstandardCompressionParameters::default()),
             cexplorer_pools_url: None,
             signer_importer_run_interval: 1,
+            signer_importer_source_type: SignerImporterSourceType::CExplorer,
+            signer_importer_source_params: None,
             allow_unparsable_block: false,
+            enable_metrics_server: false,
+            metrics_server_ip: "0.0.0.0".to_string(),
+            metrics_server_port: 9090,
+            cardano_transactions_prover_max_hashes_allowed_by_request: 100,
+            enable_transaction_pruning: false,
+            transactions_pruning_retained_block_ranges: 150,
+            enable_chain_sync_block_scanner: false,
         }
     }
 
@@ -260,6 +492,118 @@ impl Configuration {
             .map(|limit| if limit > 3 { limit as u64 } else { 3 })
     }
 
+    /// Storage settings of the main snapshot uploader
+    pub fn snapshot_storage_settings(&self) -> SnapshotStorageSettings {
+        SnapshotStorageSettings {
+            bucket_name_parameter: "snapshot_bucket_name",
+            bucket_name: self.snapshot_bucket_name.clone(),
+            use_cdn_domain: self.snapshot_use_cdn_domain,
+            s3_endpoint: self.snapshot_s3_endpoint.clone(),
+            s3_region: self.snapshot_s3_region.clone(),
+            s3_access_key_id: self.snapshot_s3_access_key_id.clone(),
+            s3_secret_access_key: self.snapshot_s3_secret_access_key.clone(),
+            s3_path_prefix: self.snapshot_s3_path_prefix.clone(),
+            s3_public_url: self.snapshot_s3_public_url.clone(),
+        }
+    }
+
+    /// Storage settings of the mirror snapshot uploaders
+    ///
+    /// Only the region of the bucket falls back to the main uploader setting, the bucket,
+    /// endpoint and credentials of the mirrors are never shared with the main uploader.
+    pub fn snapshot_mirror_storage_settings(&self) -> SnapshotStorageSettings {
+        SnapshotStorageSettings {
+            bucket_name_parameter: "snapshot_mirror_bucket_name",
+            bucket_name: self.snapshot_mirror_bucket_name.clone(),
+            use_cdn_domain: self.snapshot_mirror_use_cdn_domain,
+            s3_endpoint: self.snapshot_mirror_s3_endpoint.clone(),
+            s3_region: self
+                .snapshot_mirror_s3_region
+                .clone()
+                .unwrap_or_else(|| self.snapshot_s3_region.clone()),
+            s3_access_key_id: self.snapshot_mirror_s3_access_key_id.clone(),
+            s3_secret_access_key: self.snapshot_mirror_s3_secret_access_key.clone(),
+            s3_path_prefix: self.snapshot_mirror_s3_path_prefix.clone(),
+            s3_public_url: self.snapshot_mirror_s3_public_url.clone(),
+        }
+    }
+
+    /// Create the list of the snapshot uploader types used as mirrors, in the order of the
+    /// configuration parameter `snapshot_mirror_uploader_types`.
+    ///
+    /// The mirrors share the `snapshot_mirror_*` storage settings, an error is returned if a type
+    /// is unknown, listed twice, or is `local` while the main uploader is already local (they would
+    /// upload to the same directory).
+    pub fn list_snapshot_mirror_uploader_types(&self) -> StdResult<Vec<SnapshotUploaderType>> {
+        let mut uploader_types = vec![];
+        let uploader_type_names = self
+            .snapshot_mirror_uploader_types
+            .clone()
+            .unwrap_or_default();
+
+        for name in uploader_type_names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            let uploader_type = SnapshotUploaderType::from_str(name)?;
+            if uploader_types.contains(&uploader_type) {
+                return Err(anyhow!(
+                    "snapshot_mirror_uploader_types lists the '{name}' mirror uploader type more than once, the mirrors of a same type would share the same storage settings"
+                ));
+            }
+            if uploader_type == SnapshotUploaderType::Local
+                && self.snapshot_uploader_type == SnapshotUploaderType::Local
+            {
+                return Err(anyhow!(
+                    "snapshot_mirror_uploader_types can not list a 'local' mirror uploader when the main snapshot uploader is also local"
+                ));
+            }
+            uploader_types.push(uploader_type);
+        }
+
+        Ok(uploader_types)
+    }
+
+    /// Check that at least one block range is retained when pruning the Cardano transactions,
+    /// pruning all of them would prevent computing the Merkle root of the next block range.
+    pub fn check_transactions_pruning_retained_block_ranges(&self) -> StdResult<()> {
+        if self.transactions_pruning_retained_block_ranges < 1 {
+            return Err(anyhow!(
+                "transactions_pruning_retained_block_ranges must be at least 1, got {}",
+                self.transactions_pruning_retained_block_ranges
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check that the [protocol parameters][Self::protocol_parameters] meet the default
+    /// [security requirements][ProtocolSecurityRequirements], an error describing the computed
+    /// security is returned otherwise.
+    pub fn check_protocol_parameters_security(&self) -> StdResult<()> {
+        let requirements = ProtocolSecurityRequirements::default();
+        let protocol_parameters: StmProtocolParameters = self.protocol_parameters.clone().into();
+        let security = ProtocolParametersSecurity::compute(
+            &protocol_parameters,
+            requirements.adversarial_stake_fraction,
+        )?;
+
+        if !security.meets(&requirements) {
+            return Err(anyhow!(
+                "Protocol parameters {:?} are not safe against an adversary holding {}% of the stake: forgery probability of 2^{:.1} (at most 2^-{} expected) and liveness probability of {:.4} (at least {} expected)",
+                self.protocol_parameters,
+                requirements.adversarial_stake_fraction * 100.0,
+                security.forgery_probability_log2,
+                requirements.security_bits,
+                security.liveness_probability,
+                requirements.liveness_probability,
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Create the deduplicated list of allowed signed entity types discriminants.
     ///
     /// By default, the list contains the MithrilStakeDistribution and the CardanoImmutableFilesFull.
@@ -351,13 +695,46 @@ pub struct DefaultConfiguration {
     /// Use CDN domain to construct snapshot urls default setting (if snapshot_uploader_type is Gcp)
     pub snapshot_use_cdn_domain: String,
 
+    /// Use CDN domain to construct snapshot urls default setting (if a mirror uploader is Gcp)
+    pub snapshot_mirror_use_cdn_domain: String,
+
+    /// Region of the bucket default setting (if snapshot_uploader_type is S3)
+    pub snapshot_s3_region: String,
+
+    /// Size in bytes of the parts of the multipart uploads default setting (if snapshot_uploader_type is S3)
+    pub snapshot_s3_multipart_part_size: u64,
+
     /// Signer importer run interval default setting
     pub signer_importer_run_interval: u64,
 
+    /// Signer importer source type default setting
+    pub signer_importer_source_type: String,
+
     /// If set no error is returned in case of unparsable block and an error log is written instead.
     ///
     /// Will be ignored on (pre)production networks.
     pub allow_unparsable_block: String,
+
+    /// Enable metrics server (Prometheus endpoint on /metrics).
+    pub enable_metrics_server: String,
+
+    /// Metrics HTTP Server IP.
+    pub metrics_server_ip: String,
+
+    /// Metrics HTTP Server listening port.
+    pub metrics_server_port: u16,
+
+    /// Cardano transactions prover max hashes allowed by request default setting
+    pub cardano_transactions_prover_max_hashes_allowed_by_request: u64,
+
+    /// Cardano transactions pruning enabled
+    pub enable_transaction_pruning: String,
+
+    /// Number of block ranges for which the Cardano transactions are kept when pruning
+    pub transactions_pruning_retained_block_ranges: u64,
+
+    /// Chain-sync block scanner enabled
+    pub enable_chain_sync_block_scanner: String,
 }
 
 impl Default for DefaultConfiguration {
@@ -376,8 +753,20 @@ impl Default for DefaultConfiguration {
             disable_digests_cache: "false".to_string(),
             snapshot_compression_algorithm: "zstandard".to_string(),
             snapshot_use_cdn_domain: "false".to_string(),
+            snapshot_mirror_use_cdn_domain: "false".to_string(),
+            snapshot_s3_region: "us-east-1".to_string(),
+            snapshot_s3_multipart_part_size: 100 * 1024 * 1024,
             signer_importer_run_interval: 720,
+            signer_importer_source_type: "cexplorer".to_string(),
             allow_unparsable_block: "false".to_string(),
+            enable_metrics_server: "false".to_string(),
+            metrics_server_ip: "0.0.0.0".to_string(),
+            metrics_server_port: 9090,
+            cardano_transactions_prover_max_hashes_allowed_by_request:
+                MAX_TRANSACTIONS_HASHES_BY_REQUEST as u64,
+            enable_transaction_pruning: "false".to_string(),
+            transactions_pruning_retained_block_ranges: 150,
+            enable_chain_sync_block_scanner: "false".to_string(),
         }
     }
 }
@@ -469,6 +858,24 @@ impl Source for DefaultConfiguration {
                 ValueKind::from(myself.snapshot_use_cdn_domain),
             ),
         );
+        result.insert(
+            "snapshot_mirror_use_cdn_domain".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.snapshot_mirror_use_cdn_domain),
+            ),
+        );
+        result.insert(
+            "snapshot_s3_region".to_string(),
+            Value::new(Some(&namespace), ValueKind::from(myself.snapshot_s3_region)),
+        );
+        result.insert(
+            "snapshot_s3_multipart_part_size".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.snapshot_s3_multipart_part_size),
+            ),
+        );
         result.insert(
             "signer_importer_run_interval".to_string(),
             Value::new(
@@ -476,6 +883,13 @@ impl Source for DefaultConfiguration {
                 ValueKind::from(myself.signer_importer_run_interval),
             ),
         );
+        result.insert(
+            "signer_importer_source_type".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.signer_importer_source_type),
+            ),
+        );
         result.insert(
             "allow_unparsable_block".to_string(),
             Value::new(
@@ -483,6 +897,52 @@ impl Source for DefaultConfiguration {
                 ValueKind::from(myself.allow_unparsable_block),
             ),
         );
+        result.insert(
+            "enable_metrics_server".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.enable_metrics_server),
+            ),
+        );
+        result.insert(
+            "metrics_server_ip".to_string(),
+            Value::new(Some(&namespace), ValueKind::from(myself.metrics_server_ip)),
+        );
+        result.insert(
+            "metrics_server_port".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.metrics_server_port),
+            ),
+        );
+        result.insert(
+            "cardano_transactions_prover_max_hashes_allowed_by_request".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.cardano_transactions_prover_max_hashes_allowed_by_request),
+            ),
+        );
+        result.insert(
+            "enable_transaction_pruning".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.enable_transaction_pruning),
+            ),
+        );
+        result.insert(
+            "transactions_pruning_retained_block_ranges".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.transactions_pruning_retained_block_ranges),
+            ),
+        );
+        result.insert(
+            "enable_chain_sync_block_scanner".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.enable_chain_sync_block_scanner),
+            ),
+        );
 
         Ok(result)
     }
@@ -663,4 +1123,168 @@ mod test {
             signed_entity_types
         );
     }
+
+    #[test]
+    fn list_snapshot_mirror_uploader_types_is_empty_without_specific_configuration() {
+        let config = Configuration {
+            snapshot_mirror_uploader_types: None,
+            ..Configuration::new_sample()
+        };
+
+        assert!(config
+            .list_snapshot_mirror_uploader_types()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn list_snapshot_mirror_uploader_types_keep_configuration_order_including_the_main_type() {
+        let config = Configuration {
+            snapshot_uploader_type: SnapshotUploaderType::Gcp,
+            snapshot_mirror_uploader_types: Some("s3, gcp,local".to_string()),
+            ..Configuration::new_sample()
+        };
+
+        assert_eq!(
+            vec![
+                SnapshotUploaderType::S3,
+                SnapshotUploaderType::Gcp,
+                SnapshotUploaderType::Local
+            ],
+            config.list_snapshot_mirror_uploader_types().unwrap()
+        );
+    }
+
+    #[test]
+    fn list_snapshot_mirror_uploader_types_fails_with_duplicated_type() {
+        let config = Configuration {
+            snapshot_uploader_type: SnapshotUploaderType::Gcp,
+            snapshot_mirror_uploader_types: Some("s3,local,s3".to_string()),
+            ..Configuration::new_sample()
+        };
+
+        config
+            .list_snapshot_mirror_uploader_types()
+            .expect_err("Duplicated snapshot mirror uploader type should fail");
+    }
+
+    #[test]
+    fn list_snapshot_mirror_uploader_types_fails_with_local_mirror_of_a_local_main_uploader() {
+        let config = Configuration {
+            snapshot_uploader_type: SnapshotUploaderType::Local,
+            snapshot_mirror_uploader_types: Some("local".to_string()),
+            ..Configuration::new_sample()
+        };
+
+        config
+            .list_snapshot_mirror_uploader_types()
+            .expect_err("Local mirror of a local main uploader should fail");
+    }
+
+    #[test]
+    fn list_snapshot_mirror_uploader_types_fails_with_unknown_type() {
+        let config = Configuration {
+            snapshot_mirror_uploader_types: Some("s3,unknown".to_string()),
+            ..Configuration::new_sample()
+        };
+
+        config
+            .list_snapshot_mirror_uploader_types()
+            .expect_err("Unknown snapshot uploader type should fail");
+    }
+
+    #[test]
+    fn check_protocol_parameters_security_fails_with_sample_parameters() {
+        let config = Configuration::new_sample();
+
+        config
+            .check_protocol_parameters_security()
+            .expect_err("Sample protocol parameters should not meet the security requirements");
+    }
+
+    #[test]
+    fn check_protocol_parameters_security_succeeds_with_mainnet_parameters() {
+        let config = Configuration {
+            protocol_parameters: ProtocolParameters {
+                k: 2422,
+                m: 20973,
+                phi_f: 0.2,
+            },
+            ..Configuration::new_sample()
+        };
+
+        config.check_protocol_parameters_security().unwrap();
+    }
+
+    #[test]
+    fn configuration_secret_is_redacted_when_debug_formatted() {
+        let config = Configuration {
+            snapshot_s3_secret_access_key: Some("my-secret-key".to_string().into()),
+            ..Configuration::new_sample()
+        };
+
+        let debug = format!("{config:?}");
+
+        assert!(
+            !debug.contains("my-secret-key"),
+            "secret leaked in: {debug}"
+        );
+        assert!(debug.contains("**redacted**"));
+    }
+
+    #[test]
+    fn snapshot_mirror_storage_settings_do_not_reuse_the_main_storage_settings() {
+        let config = Configuration {
+            snapshot_bucket_name: Some("main-bucket".to_string()),
+            snapshot_s3_endpoint: Some("http://main:9000".to_string()),
+            snapshot_s3_region: "eu-west-3".to_string(),
+            snapshot_s3_access_key_id: Some("main-access-key".to_string()),
+            snapshot_s3_secret_access_key: Some("main-secret-key".to_string().into()),
+            snapshot_mirror_bucket_name: Some("mirror-bucket".to_string()),
+            ..Configuration::new_sample()
+        };
+
+        let settings = config.snapshot_mirror_storage_settings();
+
+        assert_eq!(
+            SnapshotStorageSettings {
+                bucket_name_parameter: "snapshot_mirror_bucket_name",
+                bucket_name: Some("mirror-bucket".to_string()),
+                use_cdn_domain: false,
+                s3_endpoint: None,
+                s3_region: "eu-west-3".to_string(),
+                s3_access_key_id: None,
+                s3_secret_access_key: None,
+                s3_path_prefix: None,
+                s3_public_url: None,
+            },
+            settings
+        );
+    }
+
+    #[test]
+    fn check_transactions_pruning_retained_block_ranges_rejects_zero() {
+        let config = Configuration {
+            transactions_pruning_retained_block_ranges: 0,
+            ..Configuration::new_sample()
+        };
+
+        config
+            .check_transactions_pruning_retained_block_ranges()
+            .expect_err("Retaining no block range should be rejected");
+    }
+
+    #[test]
+    fn check_transactions_pruning_retained_block_ranges_accepts_one_or_more() {
+        for retained_block_ranges in [1, 150] {
+            let config = Configuration {
+                transactions_pruning_retained_block_ranges: retained_block_ranges,
+                ..Configuration::new_sample()
+            };
+
+            config
+                .check_transactions_pruning_retained_block_ranges()
+                .unwrap();
+        }
+    }
 }
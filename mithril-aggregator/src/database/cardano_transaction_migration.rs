@@ -70,6 +70,19 @@ create table block_range_root (
     merkle_root   text      not null,
     primary key (start, end)
 );
+"#,
+        ),
+        // Migration 6
+        // Enable incremental vacuum so the space freed when pruning transactions can be reclaimed
+        // without rebuilding the whole database.
+        // Note: changing the `auto_vacuum` mode of an existing database requires a full vacuum,
+        // which can last up to an hour on a mainnet database (see the upgrade notes of the
+        // changelog and of the node documentation).
+        SqlMigration::new(
+            6,
+            r#"
+pragma auto_vacuum = incremental;
+vacuum;
 "#,
         ),
     ]
@@ -1,7 +1,10 @@
+use anyhow::Context;
 use sqlite::{ConnectionThreadSafe, Value};
 
 #[cfg(test)]
 use mithril_common::entities::Epoch;
+use mithril_common::entities::SignedEntityTypeDiscriminants;
+use mithril_common::messages::ListQueryParameters;
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::{
     EntityCursor, GetAllCondition, Provider, SourceAlias, SqLiteEntity, WhereCondition,
@@ -27,6 +30,53 @@ impl<'client> GetCertificateRecordProvider<'client> {
         ))
     }
 
+    fn condition_by_signed_entity_type(
+        &self,
+        signed_entity_type: &SignedEntityTypeDiscriminants,
+    ) -> StdResult<WhereCondition> {
+        Ok(WhereCondition::new(
+            "signed_entity_type_id = ?*",
+            vec![Value::Integer(signed_entity_type.index() as i64)],
+        ))
+    }
+
+    fn condition_by_list_parameters(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> StdResult<WhereCondition> {
+        let mut condition = WhereCondition::default();
+        if let Some(before) = &parameters.before {
+            condition = condition.and_where(WhereCondition::new(
+                "ROWID < (select ROWID from certificate where certificate_id = ?*)",
+                vec![Value::String(before.to_owned())],
+            ));
+        }
+        if let Some(from_epoch) = parameters.from_epoch {
+            condition = condition.and_where(WhereCondition::new(
+                "epoch >= ?*",
+                vec![Value::Integer(from_epoch.try_into()?)],
+            ));
+        }
+        if let Some(to_epoch) = parameters.to_epoch {
+            condition = condition.and_where(WhereCondition::new(
+                "epoch <= ?*",
+                vec![Value::Integer(to_epoch.try_into()?)],
+            ));
+        }
+        if let Some(signed_entity_type) = &parameters.signed_entity_type {
+            condition =
+                condition.and_where(self.condition_by_signed_entity_type(signed_entity_type)?);
+        }
+        if let Some(immutable_file_number) = parameters.immutable_file_number {
+            condition = condition.and_where(WhereCondition::new(
+                "json_extract(signed_entity_beacon, '$.immutable_file_number') = ?*",
+                vec![Value::Integer(immutable_file_number.try_into()?)],
+            ));
+        }
+
+        Ok(condition)
+    }
+
     #[cfg(test)]
     fn condition_by_epoch(&self, epoch: &Epoch) -> StdResult<WhereCondition> {
         Ok(WhereCondition::new(
@@ -46,6 +96,50 @@ impl<'client> GetCertificateRecordProvider<'client> {
         Ok(certificate_record)
     }
 
+    /// Get CertificateRecords for a given signed entity type.
+    pub fn get_by_signed_entity_type(
+        &self,
+        signed_entity_type: &SignedEntityTypeDiscriminants,
+    ) -> StdResult<EntityCursor<CertificateRecord>> {
+        let filters = self.condition_by_signed_entity_type(signed_entity_type)?;
+        let certificate_record = self.find(filters)?;
+
+        Ok(certificate_record)
+    }
+
+    /// Get CertificateRecords matching the filters of the given list parameters.
+    ///
+    /// The pagination (`before`, `limit` and `offset`) of the parameters is applied by the query.
+    pub fn get_by_list_parameters(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> StdResult<EntityCursor<CertificateRecord>> {
+        let (condition, mut params) = self.condition_by_list_parameters(parameters)?.expand();
+        // A negative limit means no limit for SQLite
+        let limit: i64 = match parameters.limit {
+            Some(limit) => limit.try_into()?,
+            None => -1,
+        };
+        let offset: i64 = parameters.offset.unwrap_or(0).try_into()?;
+        let sql = format!(
+            "{} limit ?{} offset ?{}",
+            self.get_definition(&condition),
+            params.len() + 1,
+            params.len() + 2
+        );
+        params.push(Value::Integer(limit));
+        params.push(Value::Integer(offset));
+
+        let cursor = self
+            .client
+            .prepare(&sql)
+            .with_context(|| format!("Prepare query error: SQL=`{sql}`"))?
+            .into_iter()
+            .bind(&params[..])?;
+
+        Ok(EntityCursor::new(cursor))
+    }
+
     #[cfg(test)]
     /// Get CertificateRecords for a given Epoch.
     pub fn get_by_epoch(&self, epoch: &Epoch) -> StdResult<EntityCursor<CertificateRecord>> {
@@ -119,4 +213,43 @@ mod tests {
             .collect();
         assert_eq!(expected_certificate_records, certificate_records);
     }
+
+    #[test]
+    fn test_get_certificate_records_by_list_parameters_paginates_in_the_query() {
+        let (certificates, _) = setup_certificate_chain(20, 7);
+        let connection = main_db_connection().unwrap();
+        insert_certificate_records(&connection, certificates.clone());
+        let provider = GetCertificateRecordProvider::new(&connection);
+
+        let certificate_records: Vec<CertificateRecord> = provider
+            .get_by_list_parameters(&ListQueryParameters::default().with_offset(15))
+            .unwrap()
+            .collect();
+        let expected_certificate_records: Vec<CertificateRecord> = certificates
+            .iter()
+            .rev()
+            .skip(15)
+            .map(|c| c.to_owned().into())
+            .collect();
+        assert_eq!(expected_certificate_records, certificate_records);
+
+        let certificate_records: Vec<CertificateRecord> = provider
+            .get_by_list_parameters(
+                &ListQueryParameters::default()
+                    .with_from_epoch(Epoch(2))
+                    .with_offset(2)
+                    .with_limit(4),
+            )
+            .unwrap()
+            .collect();
+        let expected_certificate_records: Vec<CertificateRecord> = certificates
+            .iter()
+            .rev()
+            .filter(|c| c.epoch >= Epoch(2))
+            .skip(2)
+            .take(4)
+            .map(|c| c.to_owned().into())
+            .collect();
+        assert_eq!(expected_certificate_records, certificate_records);
+    }
 }
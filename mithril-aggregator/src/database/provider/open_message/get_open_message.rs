@@ -38,6 +38,10 @@ impl<'client> GetOpenMessageProvider<'client> {
         ))
     }
 
+    pub fn get_pending_condition(&self) -> WhereCondition {
+        WhereCondition::new("is_certified = false and is_expired = false", vec![])
+    }
+
     pub fn get_expired_entity_type_condition(&self, now: &str) -> WhereCondition {
         WhereCondition::new("expires_at < ?*", vec![Value::String(now.to_string())])
     }
@@ -1,6 +1,8 @@
+use anyhow::Context;
 use sqlite::Value;
 
 use mithril_common::entities::SignedEntityTypeDiscriminants;
+use mithril_common::messages::ListQueryParameters;
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::{
     EntityCursor, Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
@@ -54,6 +56,43 @@ impl<'client> GetSignedEntityRecordProvider<'client> {
         ))
     }
 
+    fn condition_by_list_parameters(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> StdResult<WhereCondition> {
+        // The beacon of the signed entities is either an epoch or a json object with an epoch
+        const BEACON_EPOCH: &str =
+            "cast(coalesce(json_extract(beacon, '$.epoch'), beacon) as integer)";
+
+        let mut condition = WhereCondition::default();
+        if let Some(before) = &parameters.before {
+            condition = condition.and_where(WhereCondition::new(
+                "ROWID < (select ROWID from signed_entity where signed_entity_id = ?*)",
+                vec![Value::String(before.to_owned())],
+            ));
+        }
+        if let Some(from_epoch) = parameters.from_epoch {
+            condition = condition.and_where(WhereCondition::new(
+                &format!("{BEACON_EPOCH} >= ?*"),
+                vec![Value::Integer(from_epoch.try_into()?)],
+            ));
+        }
+        if let Some(to_epoch) = parameters.to_epoch {
+            condition = condition.and_where(WhereCondition::new(
+                &format!("{BEACON_EPOCH} <= ?*"),
+                vec![Value::Integer(to_epoch.try_into()?)],
+            ));
+        }
+        if let Some(immutable_file_number) = parameters.immutable_file_number {
+            condition = condition.and_where(WhereCondition::new(
+                "json_extract(beacon, '$.immutable_file_number') = ?*",
+                vec![Value::Integer(immutable_file_number.try_into()?)],
+            ));
+        }
+
+        Ok(condition)
+    }
+
     /// Get SignedEntityRecords for a given signed_entity id.
     pub fn get_by_signed_entity_id(
         &self,
@@ -97,6 +136,44 @@ impl<'client> GetSignedEntityRecordProvider<'client> {
 
         Ok(signed_entity_record)
     }
+
+    /// Get SignedEntityRecords for a given signed entity type matching the filters of the given
+    /// list parameters.
+    ///
+    /// The pagination (`before`, `limit` and `offset`) of the parameters is applied by the query.
+    pub fn get_by_signed_entity_type_and_list_parameters(
+        &self,
+        signed_entity_type: &SignedEntityTypeDiscriminants,
+        parameters: &ListQueryParameters,
+    ) -> StdResult<EntityCursor<SignedEntityRecord>> {
+        let (condition, mut params) = self
+            .condition_by_signed_entity_type(signed_entity_type)?
+            .and_where(self.condition_by_list_parameters(parameters)?)
+            .expand();
+        // A negative limit means no limit for SQLite
+        let limit: i64 = match parameters.limit {
+            Some(limit) => limit.try_into()?,
+            None => -1,
+        };
+        let offset: i64 = parameters.offset.unwrap_or(0).try_into()?;
+        let sql = format!(
+            "{} limit ?{} offset ?{}",
+            self.get_definition(&condition),
+            params.len() + 1,
+            params.len() + 2
+        );
+        params.push(Value::Integer(limit));
+        params.push(Value::Integer(offset));
+
+        let cursor = self
+            .client
+            .prepare(&sql)
+            .with_context(|| format!("Prepare query error: SQL=`{sql}`"))?
+            .into_iter()
+            .bind(&params[..])?;
+
+        Ok(EntityCursor::new(cursor))
+    }
 }
 
 #[cfg(test)]
@@ -2,8 +2,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use mithril_common::crypto_helper::ProtocolParameters;
-use mithril_common::entities::{CardanoDbBeacon, Epoch, SignedEntity, SignedEntityType, Snapshot};
+use mithril_common::entities::{
+    CardanoDbBeacon, Epoch, SignedEntity, SignedEntityType, Snapshot, StakeDistribution,
+};
 use mithril_common::messages::{
+    CardanoStakeDistributionListItemMessage, CardanoStakeDistributionMessage,
     CardanoTransactionSnapshotListItemMessage, CardanoTransactionSnapshotMessage,
     MithrilStakeDistributionListItemMessage, MithrilStakeDistributionMessage,
     SignerWithStakeMessagePart, SnapshotListItemMessage, SnapshotMessage,
@@ -213,6 +216,50 @@ impl TryFrom<SignedEntityRecord> for CardanoTransactionSnapshotListItemMessage {
     }
 }
 
+impl TryFrom<SignedEntityRecord> for CardanoStakeDistributionMessage {
+    type Error = StdError;
+
+    fn try_from(value: SignedEntityRecord) -> Result<Self, Self::Error> {
+        #[derive(Deserialize)]
+        struct TmpCardanoStakeDistribution {
+            hash: String,
+            epoch: Epoch,
+            stake_distribution: StakeDistribution,
+        }
+        let artifact = serde_json::from_str::<TmpCardanoStakeDistribution>(&value.artifact)?;
+        let cardano_stake_distribution_message = CardanoStakeDistributionMessage {
+            epoch: artifact.epoch,
+            stake_distribution: artifact.stake_distribution,
+            hash: artifact.hash,
+            certificate_hash: value.certificate_id,
+            created_at: value.created_at,
+        };
+
+        Ok(cardano_stake_distribution_message)
+    }
+}
+
+impl TryFrom<SignedEntityRecord> for CardanoStakeDistributionListItemMessage {
+    type Error = StdError;
+
+    fn try_from(value: SignedEntityRecord) -> Result<Self, Self::Error> {
+        #[derive(Deserialize)]
+        struct TmpCardanoStakeDistribution {
+            hash: String,
+            epoch: Epoch,
+        }
+        let artifact = serde_json::from_str::<TmpCardanoStakeDistribution>(&value.artifact)?;
+        let message = CardanoStakeDistributionListItemMessage {
+            epoch: artifact.epoch,
+            hash: artifact.hash,
+            certificate_hash: value.certificate_id,
+            created_at: value.created_at,
+        };
+
+        Ok(message)
+    }
+}
+
 impl TryFrom<SignedEntityRecord> for SnapshotListItemMessage {
     type Error = StdError;
 
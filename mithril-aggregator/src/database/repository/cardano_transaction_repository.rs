@@ -15,12 +15,12 @@ use mithril_persistence::sqlite::{Provider, SqliteConnection, WhereCondition};
 use sqlite::Value;
 
 use crate::database::provider::{
-    GetBlockRangeRootProvider, GetCardanoTransactionProvider,
-    GetIntervalWithoutBlockRangeRootProvider, InsertBlockRangeRootProvider,
-    InsertCardanoTransactionProvider,
+    DeleteBlockRangeRootProvider, DeleteCardanoTransactionProvider, GetBlockRangeRootProvider,
+    GetCardanoTransactionProvider, GetIntervalWithoutBlockRangeRootProvider,
+    InsertBlockRangeRootProvider, InsertCardanoTransactionProvider,
 };
 use crate::database::record::{BlockRangeRootRecord, CardanoTransactionRecord};
-use crate::services::{TransactionStore, TransactionsRetriever};
+use crate::services::{TransactionPruner, TransactionStore, TransactionsRetriever};
 
 #[cfg(test)]
 use mithril_persistence::sqlite::GetAllProvider;
@@ -177,6 +177,63 @@ impl CardanoTransactionRepository {
         }
     }
 
+    async fn get_highest_block_range_root_end(&self) -> StdResult<Option<BlockNumber>> {
+        let sql = "select max(end) as highest from block_range_root;";
+        match self
+            .connection
+            .prepare(sql)
+            .with_context(|| {
+                format!(
+                    "Prepare query error: SQL=`{}`",
+                    &sql.replace('\n', " ").trim()
+                )
+            })?
+            .iter()
+            .next()
+        {
+            None => Ok(None),
+            Some(row) => {
+                let highest = row?.read::<Option<i64>, _>(0);
+                highest
+                    .map(u64::try_from)
+                    .transpose()
+                    .with_context(||
+                        format!("Integer field max(end) (value={highest:?}) is incompatible with u64 representation.")
+                    )
+            }
+        }
+    }
+
+    /// Prune the transactions that are not in the last `number_of_block_ranges_to_keep` block
+    /// ranges whose merkle root have been computed, returns the number of pruned transactions.
+    ///
+    /// The block range roots are never pruned since they are needed to compute the merkle root
+    /// of the whole transactions set, the transactions above the last block range root are kept
+    /// too since their block range root is yet to be computed.
+    /// The space freed in the database file is reclaimed using an incremental vacuum.
+    pub async fn prune_transactions(
+        &self,
+        number_of_block_ranges_to_keep: u64,
+    ) -> StdResult<usize> {
+        let highest_block_range_end = match self.get_highest_block_range_root_end().await? {
+            Some(block_number) => block_number,
+            None => return Ok(0),
+        };
+        let threshold = highest_block_range_end
+            .saturating_sub(number_of_block_ranges_to_keep.saturating_mul(BlockRange::LENGTH));
+
+        let provider = DeleteCardanoTransactionProvider::new(&self.connection);
+        let pruned_transactions = provider.prune(threshold)?.count();
+
+        if pruned_transactions > 0 {
+            self.connection
+                .execute("pragma incremental_vacuum;")
+                .with_context(|| "CardanoTransactionRepository can not vacuum the database")?;
+        }
+
+        Ok(pruned_transactions)
+    }
+
     #[cfg(test)]
     pub(crate) async fn get_all(&self) -> StdResult<Vec<CardanoTransaction>> {
         let provider = GetCardanoTransactionProvider::new(&self.connection);
@@ -293,6 +350,42 @@ impl TransactionStore for CardanoTransactionRepository {
         }
         Ok(())
     }
+
+    async fn get_highest_block_transaction_up_to(
+        &self,
+        immutable_file_number: ImmutableFileNumber,
+    ) -> StdResult<Option<CardanoTransaction>> {
+        match self
+            .get_highest_block_number_for_immutable_number(immutable_file_number)
+            .await?
+        {
+            None => Ok(None),
+            Some(block_number) => {
+                let provider = GetCardanoTransactionProvider::new(&self.connection);
+                let filters = provider
+                    .get_transaction_between_blocks_condition(block_number..block_number + 1);
+                let mut transactions = provider.find(filters)?;
+
+                Ok(transactions.next().map(|record| record.into()))
+            }
+        }
+    }
+
+    async fn remove_rolled_back_transactions_and_block_range(
+        &self,
+        block_number: Option<BlockNumber>,
+    ) -> StdResult<()> {
+        self.connection.execute("BEGIN TRANSACTION;")?;
+
+        let provider = DeleteCardanoTransactionProvider::new(&self.connection);
+        provider.rollback_to_block_number(block_number)?.count();
+        let provider = DeleteBlockRangeRootProvider::new(&self.connection);
+        provider.rollback_to_block_number(block_number)?.count();
+
+        self.connection.execute("END TRANSACTION;")?;
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -336,6 +429,14 @@ impl TransactionsRetriever for CardanoTransactionRepository {
     }
 }
 
+#[async_trait]
+impl TransactionPruner for CardanoTransactionRepository {
+    async fn prune(&self, number_of_block_ranges_to_keep: u64) -> StdResult<usize> {
+        self.prune_transactions(number_of_block_ranges_to_keep)
+            .await
+    }
+}
+
 #[async_trait]
 impl BlockRangeRootRetriever for CardanoTransactionRepository {
     async fn retrieve_block_range_roots(
@@ -815,4 +916,149 @@ mod tests {
             record
         );
     }
+
+    #[tokio::test]
+    async fn repository_prune_transactions_without_block_range_root_does_nothing() {
+        let connection = Arc::new(cardano_tx_db_connection().unwrap());
+        let repository = CardanoTransactionRepository::new(connection);
+        repository
+            .create_transaction("tx-1", 10, 50, "block-1", 99)
+            .await
+            .unwrap();
+
+        let pruned_transactions = repository.prune_transactions(0).await.unwrap();
+
+        assert_eq!(0, pruned_transactions);
+        assert_eq!(1, repository.get_all().await.unwrap().len());
+    }
+
+    #[tokio::test]
+    async fn repository_prune_transactions_keep_the_given_number_of_block_ranges() {
+        let connection = Arc::new(cardano_tx_db_connection().unwrap());
+        let repository = CardanoTransactionRepository::new(connection);
+        let transactions: Vec<CardanoTransaction> = (0..4)
+            .map(|i| {
+                let block_number = i * BlockRange::LENGTH;
+                CardanoTransaction::new(
+                    format!("tx-hash-{block_number}"),
+                    block_number,
+                    block_number * 10,
+                    format!("block-hash-{block_number}"),
+                    i,
+                )
+            })
+            .collect();
+        repository
+            .create_transactions(transactions.clone())
+            .await
+            .unwrap();
+        let block_range_roots: Vec<(BlockRange, MKTreeNode)> = (0..3)
+            .map(|i| {
+                (
+                    BlockRange::from_block_number(i * BlockRange::LENGTH),
+                    MKTreeNode::from_hex("AAAA").unwrap(),
+                )
+            })
+            .collect();
+        repository
+            .store_block_range_roots(block_range_roots.clone())
+            .await
+            .unwrap();
+
+        let pruned_transactions = repository.prune_transactions(1).await.unwrap();
+
+        assert_eq!(2, pruned_transactions);
+        assert_eq!(
+            transactions[2..].to_vec(),
+            repository.get_all().await.unwrap()
+        );
+        assert_eq!(
+            block_range_roots.len(),
+            repository.get_all_block_range_root().unwrap().len()
+        );
+    }
+
+    #[tokio::test]
+    async fn repository_get_highest_block_transaction_up_to() {
+        let connection = Arc::new(cardano_tx_db_connection().unwrap());
+        let repository = CardanoTransactionRepository::new(connection);
+        let transactions = vec![
+            CardanoTransaction::new("tx-hash-1", 10, 50, "block-hash-10", 1),
+            CardanoTransaction::new("tx-hash-2", 20, 51, "block-hash-20", 2),
+            CardanoTransaction::new("tx-hash-3", 30, 52, "block-hash-30", 4),
+        ];
+        repository
+            .create_transactions(transactions.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            None,
+            repository
+                .get_highest_block_transaction_up_to(0)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            Some(transactions[1].clone()),
+            repository
+                .get_highest_block_transaction_up_to(3)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            Some(transactions[2].clone()),
+            repository
+                .get_highest_block_transaction_up_to(4)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn repository_remove_rolled_back_transactions_and_block_range() {
+        let connection = Arc::new(cardano_tx_db_connection().unwrap());
+        let repository = CardanoTransactionRepository::new(connection);
+        let transactions = vec![
+            CardanoTransaction::new("tx-hash-1", 10, 50, "block-hash-10", 1),
+            CardanoTransaction::new("tx-hash-2", 20, 51, "block-hash-20", 2),
+            CardanoTransaction::new("tx-hash-3", 30, 52, "block-hash-30", 3),
+        ];
+        repository
+            .create_transactions(transactions.clone())
+            .await
+            .unwrap();
+        repository
+            .store_block_range_roots(vec![
+                (
+                    BlockRange::from_block_number(0),
+                    MKTreeNode::from_hex("AAAA").unwrap(),
+                ),
+                (
+                    BlockRange::from_block_number(BlockRange::LENGTH),
+                    MKTreeNode::from_hex("BBBB").unwrap(),
+                ),
+            ])
+            .await
+            .unwrap();
+
+        repository
+            .remove_rolled_back_transactions_and_block_range(Some(20))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            transactions[0..2].to_vec(),
+            repository.get_all().await.unwrap()
+        );
+        assert_eq!(
+            vec![BlockRange::from_block_number(0)],
+            repository
+                .get_all_block_range_root()
+                .unwrap()
+                .into_iter()
+                .map(|record| record.range)
+                .collect::<Vec<_>>()
+        );
+    }
 }
@@ -5,7 +5,8 @@ use async_trait::async_trait;
 use sqlite::ConnectionThreadSafe;
 
 use mithril_common::certificate_chain::{CertificateRetriever, CertificateRetrieverError};
-use mithril_common::entities::{Certificate, Epoch};
+use mithril_common::entities::{Certificate, Epoch, SignedEntityTypeDiscriminants};
+use mithril_common::messages::ListQueryParameters;
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::{GetAllProvider, Provider};
 
@@ -48,6 +49,34 @@ impl CertificateRepository {
         Ok(cursor.take(last_n).map(|v| v.into()).collect())
     }
 
+    /// Return the certificates matching the given list parameters, most recent first.
+    pub async fn get_certificates_by_list_parameters<T>(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> StdResult<Vec<T>>
+    where
+        T: From<CertificateRecord>,
+    {
+        let provider = GetCertificateRecordProvider::new(&self.connection);
+        let cursor = provider.get_by_list_parameters(parameters)?;
+
+        Ok(cursor.map(|v| v.into()).collect())
+    }
+
+    /// Return the latest certificate issued for the given signed entity type if any.
+    pub async fn get_latest_certificate_for_signed_entity_type<T>(
+        &self,
+        signed_entity_type: &SignedEntityTypeDiscriminants,
+    ) -> StdResult<Option<T>>
+    where
+        T: From<CertificateRecord>,
+    {
+        let provider = GetCertificateRecordProvider::new(&self.connection);
+        let mut cursor = provider.get_by_signed_entity_type(signed_entity_type)?;
+
+        Ok(cursor.next().map(|v| v.into()))
+    }
+
     /// Return the first certificate signed per epoch as the reference
     /// certificate for this Epoch. This will be the parent certificate for all
     /// other certificates issued within this Epoch.
@@ -117,6 +146,7 @@ impl CertificateRetriever for CertificateRepository {
 #[cfg(test)]
 mod tests {
     use mithril_common::crypto_helper::tests_setup::setup_certificate_chain;
+    use mithril_common::entities::SignedEntityType;
 
     use crate::database::test_helper::{insert_certificate_records, main_db_connection};
     use crate::dependency_injection::DependenciesBuilder;
@@ -265,6 +295,117 @@ mod tests {
         assert_eq!(expected, latest_certificates);
     }
 
+    #[tokio::test]
+    async fn repository_get_certificates_by_list_parameters() {
+        let (certificates, _) = setup_certificate_chain(20, 3);
+        let mut deps = DependenciesBuilder::new(Configuration::new_sample());
+        let connection = deps.get_sqlite_connection().await.unwrap();
+        insert_certificate_records(&connection, certificates.clone());
+        let repository = CertificateRepository::new(connection);
+
+        let parameters = ListQueryParameters::default()
+            .with_from_epoch(Epoch(2))
+            .with_to_epoch(Epoch(4))
+            .with_offset(1)
+            .with_limit(3);
+        let listed_certificates: Vec<Certificate> = repository
+            .get_certificates_by_list_parameters(&parameters)
+            .await
+            .unwrap();
+        let expected: Vec<Certificate> = certificates
+            .iter()
+            .rev()
+            .filter(|c| c.epoch >= Epoch(2) && c.epoch <= Epoch(4))
+            .skip(1)
+            .take(3)
+            .cloned()
+            .collect();
+        assert_eq!(expected, listed_certificates);
+
+        let parameters = ListQueryParameters::default()
+            .with_before(certificates[10].hash.clone())
+            .with_limit(3);
+        let listed_certificates: Vec<Certificate> = repository
+            .get_certificates_by_list_parameters(&parameters)
+            .await
+            .unwrap();
+        let expected: Vec<Certificate> = certificates[7..10].iter().rev().cloned().collect();
+        assert_eq!(expected, listed_certificates);
+
+        let beacon_immutable_file_number = |certificate: &Certificate| match certificate
+            .signed_entity_type()
+        {
+            SignedEntityType::CardanoImmutableFilesFull(beacon)
+            | SignedEntityType::CardanoTransactions(beacon) => Some(beacon.immutable_file_number),
+            _ => None,
+        };
+        let immutable_file_number = certificates
+            .iter()
+            .find_map(beacon_immutable_file_number)
+            .expect("the certificate chain should contain a certificate with a cardano db beacon");
+        let parameters =
+            ListQueryParameters::default().with_immutable_file_number(immutable_file_number);
+        let listed_certificates: Vec<Certificate> = repository
+            .get_certificates_by_list_parameters(&parameters)
+            .await
+            .unwrap();
+        let expected: Vec<Certificate> = certificates
+            .iter()
+            .rev()
+            .filter(|c| beacon_immutable_file_number(c) == Some(immutable_file_number))
+            .cloned()
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(expected, listed_certificates);
+
+        let parameters = ListQueryParameters::default()
+            .with_signed_entity_type(SignedEntityTypeDiscriminants::MithrilStakeDistribution);
+        let listed_certificates: Vec<Certificate> = repository
+            .get_certificates_by_list_parameters(&parameters)
+            .await
+            .unwrap();
+        let expected: Vec<Certificate> = certificates
+            .iter()
+            .rev()
+            .filter(|c| {
+                SignedEntityTypeDiscriminants::from(&c.signed_entity_type())
+                    == SignedEntityTypeDiscriminants::MithrilStakeDistribution
+            })
+            .cloned()
+            .collect();
+        assert_eq!(expected, listed_certificates);
+    }
+
+    #[tokio::test]
+    async fn repository_get_latest_certificate_for_signed_entity_type() {
+        let (certificates, _) = setup_certificate_chain(5, 2);
+        let mut deps = DependenciesBuilder::new(Configuration::new_sample());
+        let connection = deps.get_sqlite_connection().await.unwrap();
+        insert_certificate_records(&connection, certificates.clone());
+
+        let repository = CertificateRepository::new(connection);
+        for discriminant in [
+            SignedEntityTypeDiscriminants::MithrilStakeDistribution,
+            SignedEntityTypeDiscriminants::CardanoStakeDistribution,
+            SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+            SignedEntityTypeDiscriminants::CardanoTransactions,
+        ] {
+            let latest_certificate = repository
+                .get_latest_certificate_for_signed_entity_type::<Certificate>(&discriminant)
+                .await
+                .unwrap();
+            let expected = certificates
+                .iter()
+                .rev()
+                .find(|c| {
+                    SignedEntityTypeDiscriminants::from(&c.signed_entity_type()) == discriminant
+                })
+                .cloned();
+
+            assert_eq!(expected, latest_certificate, "{discriminant:?}");
+        }
+    }
+
     #[tokio::test]
     async fn get_master_certificate_no_certificate_recorded_returns_none() {
         let mut deps = DependenciesBuilder::new(Configuration::new_sample());
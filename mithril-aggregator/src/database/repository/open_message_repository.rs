@@ -69,6 +69,14 @@ impl OpenMessageRepository {
         Ok(messages.next())
     }
 
+    /// Return the [OpenMessageRecord] that are neither certified nor expired.
+    pub async fn get_pending_open_messages(&self) -> StdResult<Vec<OpenMessageRecord>> {
+        let provider = GetOpenMessageProvider::new(&self.connection);
+        let messages = provider.find(provider.get_pending_condition())?;
+
+        Ok(messages.collect())
+    }
+
     /// Create a new [OpenMessageRecord] in the database.
     pub async fn create_open_message(
         &self,
@@ -237,6 +245,50 @@ mod tests {
         assert!(open_message_result.is_some());
     }
 
+    #[tokio::test]
+    async fn repository_get_pending_open_messages() {
+        let connection = get_connection().await;
+        let repository = OpenMessageRepository::new(connection.clone());
+        let mut certified_open_message = repository
+            .create_open_message(
+                Epoch(1),
+                &SignedEntityType::MithrilStakeDistribution(Epoch(1)),
+                &ProtocolMessage::new(),
+            )
+            .await
+            .unwrap();
+        certified_open_message.is_certified = true;
+        repository
+            .update_open_message(&certified_open_message)
+            .await
+            .unwrap();
+        let mut expired_open_message = repository
+            .create_open_message(
+                Epoch(1),
+                &SignedEntityType::CardanoStakeDistribution(Epoch(1)),
+                &ProtocolMessage::new(),
+            )
+            .await
+            .unwrap();
+        expired_open_message.is_expired = true;
+        repository
+            .update_open_message(&expired_open_message)
+            .await
+            .unwrap();
+        let pending_open_message = repository
+            .create_open_message(
+                Epoch(2),
+                &SignedEntityType::MithrilStakeDistribution(Epoch(2)),
+                &ProtocolMessage::new(),
+            )
+            .await
+            .unwrap();
+
+        let open_messages = repository.get_pending_open_messages().await.unwrap();
+
+        assert_eq!(vec![pending_open_message], open_messages);
+    }
+
     #[tokio::test]
     async fn repository_create_open_message() {
         let connection = get_connection().await;
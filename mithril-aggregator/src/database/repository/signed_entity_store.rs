@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use mockall::automock;
 
 use mithril_common::entities::SignedEntityTypeDiscriminants;
+use mithril_common::messages::ListQueryParameters;
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::SqliteConnection;
 use mithril_persistence::store::adapter::AdapterError;
@@ -47,6 +48,14 @@ pub trait SignedEntityStorer: Sync + Send {
         total: usize,
     ) -> StdResult<Vec<SignedEntityRecord>>;
 
+    /// Get signed entities of the given signed entity type matching the given list parameters,
+    /// most recent first
+    async fn get_signed_entities_by_type_and_list_parameters(
+        &self,
+        signed_entity_type_id: &SignedEntityTypeDiscriminants,
+        parameters: &ListQueryParameters,
+    ) -> StdResult<Vec<SignedEntityRecord>>;
+
     /// Perform an update for all the given signed entities.
     async fn update_signed_entities(
         &self,
@@ -134,6 +143,23 @@ impl SignedEntityStorer for SignedEntityStore {
         Ok(signed_entities)
     }
 
+    async fn get_signed_entities_by_type_and_list_parameters(
+        &self,
+        signed_entity_type_id: &SignedEntityTypeDiscriminants,
+        parameters: &ListQueryParameters,
+    ) -> StdResult<Vec<SignedEntityRecord>> {
+        let provider = GetSignedEntityRecordProvider::new(&self.connection);
+        let cursor = provider
+            .get_by_signed_entity_type_and_list_parameters(signed_entity_type_id, parameters)
+            .with_context(|| {
+                format!("get signed entities by type and list parameters failure, type: {signed_entity_type_id:?}")
+            })
+            .map_err(AdapterError::GeneralError)?;
+        let signed_entities: Vec<SignedEntityRecord> = cursor.collect();
+
+        Ok(signed_entities)
+    }
+
     async fn update_signed_entities(
         &self,
         signed_entities: Vec<SignedEntityRecord>,
@@ -151,7 +177,9 @@ impl SignedEntityStorer for SignedEntityStore {
 
 #[cfg(test)]
 mod tests {
-    use mithril_common::entities::{MithrilStakeDistribution, SignedEntity, Snapshot};
+    use mithril_common::entities::{
+        CardanoDbBeacon, Epoch, MithrilStakeDistribution, SignedEntity, SignedEntityType, Snapshot,
+    };
 
     use crate::database::test_helper::{insert_signed_entities, main_db_connection};
 
@@ -274,6 +302,78 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_signed_entities_by_type_and_list_parameters() {
+        let mut records: Vec<SignedEntityRecord> = SignedEntityRecord::fake_records(5)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, mut record)| {
+                let epoch = idx as u64 + 1;
+                record.signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(
+                    CardanoDbBeacon::new("devnet", epoch, epoch * 10),
+                );
+                record
+            })
+            .collect();
+        let mithril_stake_distribution_record = SignedEntityRecord {
+            signed_entity_id: "mithril-stake-distribution".to_string(),
+            signed_entity_type: SignedEntityType::MithrilStakeDistribution(Epoch(3)),
+            certificate_id: "certificate-msd".to_string(),
+            ..records[0].clone()
+        };
+        records.push(mithril_stake_distribution_record.clone());
+        let connection = main_db_connection().unwrap();
+        insert_signed_entities(&connection, records.clone()).unwrap();
+        let store = SignedEntityStore::new(Arc::new(connection));
+
+        let queried_records = store
+            .get_signed_entities_by_type_and_list_parameters(
+                &SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+                &ListQueryParameters::default()
+                    .with_from_epoch(Epoch(2))
+                    .with_to_epoch(Epoch(4))
+                    .with_offset(1)
+                    .with_limit(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(vec![records[2].clone()], queried_records);
+
+        let queried_records = store
+            .get_signed_entities_by_type_and_list_parameters(
+                &SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+                &ListQueryParameters::default()
+                    .with_before(records[3].signed_entity_id.clone())
+                    .with_limit(2),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![records[2].clone(), records[1].clone()],
+            queried_records
+        );
+
+        let queried_records = store
+            .get_signed_entities_by_type_and_list_parameters(
+                &SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+                &ListQueryParameters::default().with_immutable_file_number(50),
+            )
+            .await
+            .unwrap();
+        assert_eq!(vec![records[4].clone()], queried_records);
+
+        let queried_records = store
+            .get_signed_entities_by_type_and_list_parameters(
+                &SignedEntityTypeDiscriminants::MithrilStakeDistribution,
+                &ListQueryParameters::default()
+                    .with_from_epoch(Epoch(3))
+                    .with_to_epoch(Epoch(3)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(vec![mithril_stake_distribution_record], queried_records);
+    }
+
     #[tokio::test]
     async fn update_only_given_entities() {
         let mut signed_entity_records = SignedEntityRecord::fake_records(5);
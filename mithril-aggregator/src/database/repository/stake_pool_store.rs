@@ -5,6 +5,7 @@ use anyhow::Context;
 use async_trait::async_trait;
 
 use mithril_common::entities::{Epoch, StakeDistribution};
+use mithril_common::signable_builder::StakeDistributionRetriever;
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::SqliteConnection;
 use mithril_persistence::store::adapter::AdapterError;
@@ -83,6 +84,13 @@ impl StakeStorer for StakePoolStore {
     }
 }
 
+#[async_trait]
+impl StakeDistributionRetriever for StakePoolStore {
+    async fn retrieve(&self, epoch: Epoch) -> StdResult<Option<StakeDistribution>> {
+        self.get_stakes(epoch).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::database::test_helper::{insert_stake_pool, main_db_connection};
@@ -116,4 +124,30 @@ mod tests {
             "Stakes at epoch 2 should still exist",
         );
     }
+
+    #[tokio::test]
+    async fn retrieve_with_no_stakes_returns_none() {
+        let connection = main_db_connection().unwrap();
+        let store = StakePoolStore::new(Arc::new(connection), None);
+
+        let result = store.retrieve(Epoch(1)).await.unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn retrieve_returns_stake_distribution() {
+        let stake_distribution_to_retrieve =
+            StakeDistribution::from([("pool-123".to_string(), 123)]);
+        let connection = main_db_connection().unwrap();
+        let store = StakePoolStore::new(Arc::new(connection), None);
+        store
+            .save_stakes(Epoch(1), stake_distribution_to_retrieve.clone())
+            .await
+            .unwrap();
+
+        let stake_distribution = store.retrieve(Epoch(1)).await.unwrap();
+
+        assert_eq!(stake_distribution, Some(stake_distribution_to_retrieve));
+    }
 }
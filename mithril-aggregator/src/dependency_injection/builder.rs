@@ -1,7 +1,9 @@
 use anyhow::Context;
+use s3::{creds::Credentials, Bucket, Region};
 use semver::Version;
+use serde::Deserialize;
 use slog::Logger;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use tokio::{
     sync::{
         mpsc::{UnboundedReceiver, UnboundedSender},
@@ -13,7 +15,7 @@ use warp::Filter;
 
 use mithril_common::{
     api_version::APIVersionProvider,
-    cardano_block_scanner::{BlockScanner, CardanoBlockScanner},
+    cardano_block_scanner::{BlockScanner, CardanoBlockScanner, ChainSyncBlockScanner},
     certificate_chain::{CertificateVerifier, MithrilCertificateVerifier},
     chain_observer::{CardanoCliRunner, ChainObserver, ChainObserverBuilder, FakeObserver},
     crypto_helper::{
@@ -30,9 +32,9 @@ use mithril_common::{
         EraChecker, EraMarker, EraReader, EraReaderAdapter, SupportedEra,
     },
     signable_builder::{
-        CardanoImmutableFilesFullSignableBuilder, CardanoTransactionsSignableBuilder,
-        MithrilSignableBuilderService, MithrilStakeDistributionSignableBuilder,
-        SignableBuilderService,
+        CardanoImmutableFilesFullSignableBuilder, CardanoStakeDistributionSignableBuilder,
+        CardanoTransactionsSignableBuilder, MithrilSignableBuilderService,
+        MithrilStakeDistributionSignableBuilder, SignableBuilderService, TransactionsImporter,
     },
     TimePointProvider, TimePointProviderImpl,
 };
@@ -44,8 +46,8 @@ use mithril_persistence::{
 
 use crate::{
     artifact_builder::{
-        CardanoImmutableFilesFullArtifactBuilder, CardanoTransactionsArtifactBuilder,
-        MithrilStakeDistributionArtifactBuilder,
+        CardanoImmutableFilesFullArtifactBuilder, CardanoStakeDistributionArtifactBuilder,
+        CardanoTransactionsArtifactBuilder, MithrilStakeDistributionArtifactBuilder,
     },
     configuration::ExecutionEnvironment,
     database::repository::{
@@ -60,17 +62,22 @@ use crate::{
         MithrilEpochService, MithrilMessageService, MithrilProverService,
         MithrilSignedEntityService, MithrilStakeDistributionService, MithrilTickerService,
         ProverService, SignedEntityService, StakeDistributionService, TickerService,
-        TransactionStore,
+        TransactionStore, TransactionsImporterWithPruner,
+    },
+    tools::{
+        CExplorerSignerRetriever, GcpFileUploader, GenesisToolsDependency, JsonHttpSignerRetriever,
+        JsonSignerMapping, LocalFileSignerRetriever, S3FileUploader, SignersImporter,
+        SignersImporterRetriever,
     },
-    tools::{CExplorerSignerRetriever, GcpFileUploader, GenesisToolsDependency, SignersImporter},
     AggregatorConfig, AggregatorRunner, AggregatorRuntime, CertificatePendingStore,
     CompressedArchiveSnapshotter, Configuration, DependencyContainer, DumbSnapshotUploader,
-    DumbSnapshotter, LocalSnapshotUploader, MithrilSignerRegisterer, MultiSigner, MultiSignerImpl,
-    ProtocolParametersStorer, RemoteSnapshotUploader, SnapshotUploader, SnapshotUploaderType,
+    DumbSnapshotter, LocalSnapshotUploader, MetricsService, MithrilSignerRegisterer, MultiSigner,
+    MultiSignerImpl, ProtocolParametersStorer, RemoteSnapshotUploader, S3SnapshotUploader,
+    SignerImporterSourceType, SnapshotStorageSettings, SnapshotUploader, SnapshotUploaderType,
     Snapshotter, SnapshotterCompressionAlgorithm, VerificationKeyStorer,
 };
 
-use super::{DependenciesBuilderError, EpochServiceWrapper, Result};
+use super::{DependenciesBuilderError, EpochServiceWrapper, Result, RuntimeStateWrapper};
 
 const SQLITE_FILE: &str = "aggregator.sqlite3";
 const SQLITE_FILE_CARDANO_TRANSACTION: &str = "cardano-transaction.sqlite3";
@@ -213,6 +220,12 @@ pub struct DependenciesBuilder {
 
     /// Prover service
     pub prover_service: Option<Arc<dyn ProverService>>,
+
+    /// Metrics service
+    pub metrics_service: Option<Arc<MetricsService>>,
+
+    /// Runtime state
+    pub runtime_state: Option<RuntimeStateWrapper>,
 }
 
 impl DependenciesBuilder {
@@ -260,6 +273,8 @@ impl DependenciesBuilder {
             signed_entity_storer: None,
             message_service: None,
             prover_service: None,
+            metrics_service: None,
+            runtime_state: None,
         }
     }
 
@@ -366,34 +381,153 @@ impl DependenciesBuilder {
 
     async fn build_snapshot_uploader(&mut self) -> Result<Arc<dyn SnapshotUploader>> {
         if self.configuration.environment == ExecutionEnvironment::Production {
-            match self.configuration.snapshot_uploader_type {
-                SnapshotUploaderType::Gcp => {
-                    let bucket = self
-                        .configuration
-                        .snapshot_bucket_name
-                        .to_owned()
-                        .ok_or_else(|| {
-                            DependenciesBuilderError::MissingConfiguration(
-                                "snapshot_bucket_name".to_string(),
-                            )
-                        })?;
-
-                    Ok(Arc::new(RemoteSnapshotUploader::new(
-                        Box::new(GcpFileUploader::new(bucket.clone())),
-                        bucket,
-                        self.configuration.snapshot_use_cdn_domain,
-                    )))
-                }
-                SnapshotUploaderType::Local => Ok(Arc::new(LocalSnapshotUploader::new(
-                    self.configuration.get_server_url(),
-                    &self.configuration.snapshot_directory,
-                ))),
-            }
+            self.build_snapshot_uploader_for_type(
+                self.configuration.snapshot_uploader_type,
+                &self.configuration.snapshot_storage_settings(),
+            )
         } else {
             Ok(Arc::new(DumbSnapshotUploader::new()))
         }
     }
 
+    fn build_snapshot_uploader_for_type(
+        &self,
+        uploader_type: SnapshotUploaderType,
+        storage_settings: &SnapshotStorageSettings,
+    ) -> Result<Arc<dyn SnapshotUploader>> {
+        match uploader_type {
+            SnapshotUploaderType::Gcp => {
+                let bucket = storage_settings.bucket_name.to_owned().ok_or_else(|| {
+                    DependenciesBuilderError::MissingConfiguration(
+                        storage_settings.bucket_name_parameter.to_string(),
+                    )
+                })?;
+
+                Ok(Arc::new(RemoteSnapshotUploader::new(
+                    Box::new(GcpFileUploader::new(bucket.clone())),
+                    bucket,
+                    storage_settings.use_cdn_domain,
+                )))
+            }
+            SnapshotUploaderType::S3 => self.build_s3_snapshot_uploader(storage_settings),
+            SnapshotUploaderType::Local => Ok(Arc::new(LocalSnapshotUploader::new(
+                self.configuration.get_server_url(),
+                &self.configuration.snapshot_directory,
+            ))),
+        }
+    }
+
+    /// Build the list of [SnapshotUploader]: the main one followed by its mirrors
+    async fn build_snapshot_uploaders(&mut self) -> Result<Vec<Arc<dyn SnapshotUploader>>> {
+        let mut snapshot_uploaders = vec![self.get_snapshot_uploader().await?];
+
+        if self.configuration.environment == ExecutionEnvironment::Production {
+            let mirror_storage_settings = self.configuration.snapshot_mirror_storage_settings();
+            for uploader_type in self.configuration.list_snapshot_mirror_uploader_types()? {
+                snapshot_uploaders.push(
+                    self.build_snapshot_uploader_for_type(uploader_type, &mirror_storage_settings)?,
+                );
+            }
+        }
+
+        Ok(snapshot_uploaders)
+    }
+
+    /// Build the S3 credentials from the given configuration values, or from the
+    /// `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` environment variables if none is set.
+    fn build_s3_credentials(
+        access_key_id: Option<&str>,
+        secret_access_key: Option<&str>,
+    ) -> Result<Credentials> {
+        match (access_key_id, secret_access_key) {
+            (Some(access_key_id), Some(secret_access_key)) => Credentials::new(
+                Some(access_key_id),
+                Some(secret_access_key),
+                None,
+                None,
+                None,
+            )
+            .map_err(|e| DependenciesBuilderError::Initialization {
+                message: "Invalid S3 credentials".to_string(),
+                error: Some(e.into()),
+            }),
+            (None, None) => {
+                Credentials::from_env().map_err(|e| DependenciesBuilderError::Initialization {
+                    message:
+                        "Missing AWS_ACCESS_KEY_ID or AWS_SECRET_ACCESS_KEY environment variable"
+                            .to_string(),
+                    error: Some(e.into()),
+                })
+            }
+            _ => Err(DependenciesBuilderError::Initialization {
+                message: "Both the S3 access key id and secret access key must be configured"
+                    .to_string(),
+                error: None,
+            }),
+        }
+    }
+
+    fn build_s3_snapshot_uploader(
+        &self,
+        storage_settings: &SnapshotStorageSettings,
+    ) -> Result<Arc<dyn SnapshotUploader>> {
+        let bucket_name = storage_settings.bucket_name.to_owned().ok_or_else(|| {
+            DependenciesBuilderError::MissingConfiguration(
+                storage_settings.bucket_name_parameter.to_string(),
+            )
+        })?;
+        let region_name = storage_settings.s3_region.clone();
+        let endpoint = storage_settings.s3_endpoint.clone();
+        let multipart_part_size = self.configuration.snapshot_s3_multipart_part_size;
+        if multipart_part_size < S3FileUploader::MIN_MULTIPART_PART_SIZE {
+            return Err(DependenciesBuilderError::Initialization {
+                message: format!(
+                    "snapshot_s3_multipart_part_size must be at least {} bytes, got {multipart_part_size}",
+                    S3FileUploader::MIN_MULTIPART_PART_SIZE
+                ),
+                error: None,
+            });
+        }
+
+        let region = match &endpoint {
+            Some(endpoint) => Region::Custom {
+                region: region_name.clone(),
+                endpoint: endpoint.clone(),
+            },
+            None => region_name
+                .parse::<Region>()
+                .with_context(|| format!("Invalid S3 region: '{region_name}'"))?,
+        };
+        let credentials = Self::build_s3_credentials(
+            storage_settings.s3_access_key_id.as_deref(),
+            storage_settings
+                .s3_secret_access_key
+                .as_ref()
+                .map(|secret| secret.expose()),
+        )?;
+        let mut bucket = Bucket::new(&bucket_name, region, credentials)
+            .with_context(|| "S3 bucket creation failed")?;
+        if endpoint.is_some() {
+            // S3 compatible storages (ie: MinIO) are not expected to support virtual-hosted style
+            bucket = bucket.with_path_style();
+        }
+
+        let public_url = storage_settings.s3_public_url.clone().unwrap_or_else(|| {
+            S3SnapshotUploader::default_public_url(&bucket_name, &region_name, endpoint.as_deref())
+        });
+        let path_prefix = storage_settings.s3_path_prefix.clone();
+
+        Ok(Arc::new(S3SnapshotUploader::new(
+            Box::new(S3FileUploader::new(
+                bucket,
+                path_prefix.clone(),
+                multipart_part_size,
+            )),
+            public_url,
+            path_prefix,
+        )))
+    }
+
     /// Get a [SnapshotUploader]
     pub async fn get_snapshot_uploader(&mut self) -> Result<Arc<dyn SnapshotUploader>> {
         if self.snapshot_uploader.is_none() {
@@ -562,7 +696,8 @@ impl DependenciesBuilder {
                     cardano_node_socket_path,
                     cardano_network,
                     Some(cardano_cli_runner),
-                );
+                )
+                .with_logger(self.get_logger().await?);
 
                 chain_observer_builder
                     .build()
@@ -714,14 +849,26 @@ impl DependenciesBuilder {
     }
 
     async fn build_block_scanner(&mut self) -> Result<Arc<dyn BlockScanner>> {
-        let block_scanner = CardanoBlockScanner::new(
-            self.get_logger().await?,
-            self.configuration
-                .get_network()?
-                .compute_allow_unparsable_block(self.configuration.allow_unparsable_block)?,
-        );
+        let allow_unparsable_block = self
+            .configuration
+            .get_network()?
+            .compute_allow_unparsable_block(self.configuration.allow_unparsable_block)?;
+        let block_scanner: Arc<dyn BlockScanner> =
+            if self.configuration.enable_chain_sync_block_scanner {
+                Arc::new(ChainSyncBlockScanner::new(
+                    &self.configuration.cardano_node_socket_path,
+                    self.configuration.get_network()?,
+                    allow_unparsable_block,
+                    self.get_logger().await?,
+                ))
+            } else {
+                Arc::new(CardanoBlockScanner::new(
+                    self.get_logger().await?,
+                    allow_unparsable_block,
+                ))
+            };
 
-        Ok(Arc::new(block_scanner))
+        Ok(block_scanner)
     }
 
     /// Block scanner
@@ -1054,24 +1201,42 @@ impl DependenciesBuilder {
             &self.configuration.db_directory,
             self.get_logger().await?,
         ));
-        let transactions_importer = Arc::new(CardanoTransactionsImporter::new(
-            self.get_block_scanner().await?,
-            self.get_transaction_store().await?,
-            &self.configuration.db_directory,
-            // Rescan the last immutable when importing transactions, it may have been partially imported
-            Some(1),
-            self.get_logger().await?,
-        ));
+        let transactions_importer: Arc<dyn TransactionsImporter> =
+            Arc::new(CardanoTransactionsImporter::new(
+                self.get_block_scanner().await?,
+                self.get_transaction_store().await?,
+                &self.configuration.db_directory,
+                // Rescan the last immutable when importing transactions, it may have been partially imported
+                Some(1),
+                self.get_logger().await?,
+            ));
+        let transactions_importer: Arc<dyn TransactionsImporter> =
+            if self.configuration.enable_transaction_pruning {
+                Arc::new(TransactionsImporterWithPruner::new(
+                    self.configuration
+                        .transactions_pruning_retained_block_ranges,
+                    self.get_transaction_repository().await?,
+                    transactions_importer,
+                    self.get_metrics_service().await?,
+                    self.get_logger().await?,
+                ))
+            } else {
+                transactions_importer
+            };
         let block_range_root_retriever = self.get_transaction_repository().await?;
         let cardano_transactions_builder = Arc::new(CardanoTransactionsSignableBuilder::new(
             transactions_importer,
             block_range_root_retriever,
             self.get_logger().await?,
         ));
+        let cardano_stake_distribution_builder = Arc::new(
+            CardanoStakeDistributionSignableBuilder::new(self.get_stake_store().await?),
+        );
         let signable_builder_service = Arc::new(MithrilSignableBuilderService::new(
             mithril_stake_distribution_builder,
             immutable_signable_builder,
             cardano_transactions_builder,
+            cardano_stake_distribution_builder,
         ));
 
         Ok(signable_builder_service)
@@ -1094,23 +1259,27 @@ impl DependenciesBuilder {
         let mithril_stake_distribution_artifact_builder =
             Arc::new(MithrilStakeDistributionArtifactBuilder::new(epoch_service));
         let snapshotter = self.build_snapshotter().await?;
-        let snapshot_uploader = self.build_snapshot_uploader().await?;
+        let snapshot_uploaders = self.build_snapshot_uploaders().await?;
         let cardano_node_version = Version::parse(&self.configuration.cardano_node_version)
             .map_err(|e| DependenciesBuilderError::Initialization { message: format!("Could not parse configuration setting 'cardano_node_version' value '{}' as Semver.", self.configuration.cardano_node_version), error: Some(e.into()) })?;
         let cardano_immutable_files_full_artifact_builder =
             Arc::new(CardanoImmutableFilesFullArtifactBuilder::new(
                 &cardano_node_version,
                 snapshotter,
-                snapshot_uploader,
+                snapshot_uploaders,
                 self.configuration.snapshot_compression_algorithm,
             ));
         let cardano_transactions_artifact_builder =
             Arc::new(CardanoTransactionsArtifactBuilder::new());
+        let stake_store = self.get_stake_store().await?;
+        let cardano_stake_distribution_artifact_builder =
+            Arc::new(CardanoStakeDistributionArtifactBuilder::new(stake_store));
         let signed_entity_service = Arc::new(MithrilSignedEntityService::new(
             signed_entity_storer,
             mithril_stake_distribution_artifact_builder,
             cardano_immutable_files_full_artifact_builder,
             cardano_transactions_artifact_builder,
+            cardano_stake_distribution_artifact_builder,
         ));
 
         Ok(signed_entity_service)
@@ -1203,6 +1372,8 @@ impl DependenciesBuilder {
             block_scanner: self.get_block_scanner().await?,
             transaction_store: self.get_transaction_store().await?,
             prover_service: self.get_prover_service().await?,
+            metrics_service: self.get_metrics_service().await?,
+            runtime_state: self.get_runtime_state().await?,
         };
 
         Ok(dependency_manager)
@@ -1225,10 +1396,14 @@ impl DependenciesBuilder {
                 "Dependencies Builder can not get Cardano network while creating aggregator runner"
             })?,
         );
+        let metrics_service = dependency_container.metrics_service.clone();
+        let runtime_state = dependency_container.runtime_state.clone();
         let runtime = AggregatorRuntime::new(
             config,
             None,
             Arc::new(AggregatorRunner::new(dependency_container)),
+            metrics_service,
+            runtime_state,
         )
         .await
         .map_err(|e| DependenciesBuilderError::Initialization {
@@ -1267,16 +1442,67 @@ impl DependenciesBuilder {
         Ok(dependencies)
     }
 
-    /// Create a [SignersImporter] instance.
-    pub async fn create_signer_importer(
-        &mut self,
-        cexplorer_pools_url: &str,
-    ) -> Result<SignersImporter> {
-        let retriever =
-            CExplorerSignerRetriever::new(cexplorer_pools_url, Some(Duration::from_secs(30)))?;
+    /// Create a [SignersImporter] instance, `None` if no source is configured to import the
+    /// signers from.
+    pub async fn create_signer_importer(&mut self) -> Result<Option<SignersImporter>> {
+        #[derive(Deserialize)]
+        struct FileSourceParams {
+            path: PathBuf,
+        }
+
+        #[derive(Deserialize)]
+        struct JsonHttpSourceParams {
+            url: String,
+            #[serde(default = "default_page_size")]
+            page_size: Option<usize>,
+            #[serde(flatten)]
+            mapping: JsonSignerMapping,
+        }
+
+        fn default_page_size() -> Option<usize> {
+            Some(JsonHttpSignerRetriever::DEFAULT_PAGE_SIZE)
+        }
+
+        let timeout = Some(Duration::from_secs(30));
+        let source_params = || {
+            self.configuration
+                .signer_importer_source_params
+                .as_deref()
+                .ok_or_else(|| {
+                    DependenciesBuilderError::MissingConfiguration(
+                        "signer_importer_source_params".to_string(),
+                    )
+                })
+        };
+        let retriever: Arc<dyn SignersImporterRetriever> = match self
+            .configuration
+            .signer_importer_source_type
+        {
+            SignerImporterSourceType::CExplorer => match &self.configuration.cexplorer_pools_url {
+                Some(cexplorer_pools_url) => {
+                    Arc::new(CExplorerSignerRetriever::new(cexplorer_pools_url, timeout)?)
+                }
+                None => return Ok(None),
+            },
+            SignerImporterSourceType::File => {
+                let params: FileSourceParams = serde_json::from_str(source_params()?)
+                    .with_context(|| "Invalid signer importer file source parameters")?;
+                Arc::new(LocalFileSignerRetriever::new(&params.path))
+            }
+            SignerImporterSourceType::JsonHttp => {
+                let params: JsonHttpSourceParams = serde_json::from_str(source_params()?)
+                    .with_context(|| "Invalid signer importer JSON HTTP source parameters")?;
+                Arc::new(JsonHttpSignerRetriever::new(
+                    params.url,
+                    params.mapping,
+                    params.page_size,
+                    timeout,
+                )?)
+            }
+        };
         let persister = self.get_signer_store().await?;
 
-        Ok(SignersImporter::new(Arc::new(retriever), persister))
+        Ok(Some(SignersImporter::new(retriever, persister)))
     }
 
     /// Create [TickerService] instance.
@@ -1381,6 +1607,40 @@ impl DependenciesBuilder {
         Ok(self.prover_service.as_ref().cloned().unwrap())
     }
 
+    /// Create a [MetricsService] instance.
+    pub async fn build_metrics_service(&self) -> Result<Arc<MetricsService>> {
+        let metrics_service =
+            MetricsService::new().map_err(|e| DependenciesBuilderError::Initialization {
+                message: "Cannot initialize metrics service.".to_string(),
+                error: Some(e),
+            })?;
+
+        Ok(Arc::new(metrics_service))
+    }
+
+    /// [MetricsService] service
+    pub async fn get_metrics_service(&mut self) -> Result<Arc<MetricsService>> {
+        if self.metrics_service.is_none() {
+            self.metrics_service = Some(self.build_metrics_service().await?);
+        }
+
+        Ok(self.metrics_service.as_ref().cloned().unwrap())
+    }
+
+    /// Create a [RuntimeStateWrapper] instance.
+    pub async fn build_runtime_state(&self) -> Result<RuntimeStateWrapper> {
+        Ok(Arc::new(RwLock::new("idle".to_string())))
+    }
+
+    /// [RuntimeStateWrapper] shared state
+    pub async fn get_runtime_state(&mut self) -> Result<RuntimeStateWrapper> {
+        if self.runtime_state.is_none() {
+            self.runtime_state = Some(self.build_runtime_state().await?);
+        }
+
+        Ok(self.runtime_state.as_ref().cloned().unwrap())
+    }
+
     /// Remove the dependencies builder from memory to release Arc instances.
     pub async fn vanish(self) {
         self.drop_sqlite_connections().await;
@@ -30,7 +30,7 @@ use crate::{
     },
     signer_registerer::SignerRecorder,
     snapshot_uploaders::SnapshotUploader,
-    CertificatePendingStore, ProtocolParametersStorer, SignerRegisterer,
+    CertificatePendingStore, MetricsService, ProtocolParametersStorer, SignerRegisterer,
     SignerRegistrationRoundOpener, Snapshotter, VerificationKeyStorer,
 };
 
@@ -40,6 +40,10 @@ pub type MultiSignerWrapper = Arc<RwLock<dyn MultiSigner>>;
 /// EpochServiceWrapper wraps a [EpochService]
 pub type EpochServiceWrapper = Arc<RwLock<dyn EpochService>>;
 
+/// RuntimeStateWrapper shares the name of the current state of the
+/// [AggregatorRuntime][crate::AggregatorRuntime] state machine
+pub type RuntimeStateWrapper = Arc<RwLock<String>>;
+
 /// DependencyManager handles the dependencies
 pub struct DependencyContainer {
     /// Configuration structure.
@@ -155,6 +159,12 @@ pub struct DependencyContainer {
 
     /// Prover service
     pub prover_service: Arc<dyn ProverService>,
+
+    /// Metrics service
+    pub metrics_service: Arc<MetricsService>,
+
+    /// Runtime state
+    pub runtime_state: RuntimeStateWrapper,
 }
 
 #[doc(hidden)]
@@ -1,5 +1,6 @@
 use crate::http_server::routes::middlewares;
 use crate::DependencyContainer;
+use mithril_common::messages::ListQueryParameters;
 use std::sync::Arc;
 use warp::Filter;
 
@@ -16,6 +17,7 @@ fn artifact_cardano_transactions(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("artifact" / "cardano-transactions")
         .and(warp::get())
+        .and(warp::query::<ListQueryParameters>())
         .and(middlewares::with_http_message_service(dependency_manager))
         .and_then(handlers::list_artifacts)
 }
@@ -33,6 +35,7 @@ fn artifact_cardano_transaction_by_id(
 pub mod handlers {
     use crate::http_server::routes::reply;
     use crate::services::MessageService;
+    use mithril_common::messages::ListQueryParameters;
 
     use slog_scope::{debug, warn};
     use std::convert::Infallible;
@@ -43,12 +46,13 @@ pub mod handlers {
 
     /// List Cardano Transactions set artifacts
     pub async fn list_artifacts(
+        parameters: ListQueryParameters,
         http_message_service: Arc<dyn MessageService>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: artifacts");
 
         match http_message_service
-            .get_cardano_transaction_list_message(LIST_MAX_ITEMS)
+            .get_cardano_transaction_list_message(&parameters.with_max_limit(LIST_MAX_ITEMS))
             .await
         {
             Ok(message) => Ok(reply::json(&message, StatusCode::OK)),
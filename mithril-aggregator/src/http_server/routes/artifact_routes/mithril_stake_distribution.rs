@@ -1,5 +1,6 @@
 use crate::http_server::routes::middlewares;
 use crate::DependencyContainer;
+use mithril_common::messages::ListQueryParameters;
 use std::sync::Arc;
 use warp::Filter;
 
@@ -17,6 +18,7 @@ fn artifact_mithril_stake_distributions(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("artifact" / "mithril-stake-distributions")
         .and(warp::get())
+        .and(warp::query::<ListQueryParameters>())
         .and(middlewares::with_http_message_service(dependency_manager))
         .and_then(handlers::list_artifacts)
 }
@@ -34,6 +36,7 @@ fn artifact_mithril_stake_distribution_by_id(
 pub mod handlers {
     use crate::http_server::routes::reply;
     use crate::services::MessageService;
+    use mithril_common::messages::ListQueryParameters;
 
     use slog_scope::{debug, warn};
     use std::convert::Infallible;
@@ -44,12 +47,13 @@ pub mod handlers {
 
     /// List MithrilStakeDistribution artifacts
     pub async fn list_artifacts(
+        parameters: ListQueryParameters,
         http_message_service: Arc<dyn MessageService>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: artifacts");
 
         match http_message_service
-            .get_mithril_stake_distribution_list_message(LIST_MAX_ITEMS)
+            .get_mithril_stake_distribution_list_message(&parameters.with_max_limit(LIST_MAX_ITEMS))
             .await
         {
             Ok(message) => Ok(reply::json(&message, StatusCode::OK)),
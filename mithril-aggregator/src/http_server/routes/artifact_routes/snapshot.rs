@@ -1,6 +1,7 @@
 use crate::http_server::routes::middlewares;
 use crate::http_server::SERVER_BASE_PATH;
 use crate::DependencyContainer;
+use mithril_common::messages::ListQueryParameters;
 use std::sync::Arc;
 use warp::hyper::Uri;
 use warp::Filter;
@@ -24,6 +25,7 @@ fn artifact_cardano_full_immutable_snapshots(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("artifact" / "snapshots")
         .and(warp::get())
+        .and(warp::query::<ListQueryParameters>())
         .and(middlewares::with_http_message_service(dependency_manager))
         .and_then(handlers::list_artifacts)
 }
@@ -93,6 +95,7 @@ mod handlers {
     use crate::http_server::SERVER_BASE_PATH;
     use crate::services::MessageService;
     use crate::{services::SignedEntityService, Configuration};
+    use mithril_common::messages::ListQueryParameters;
     use slog_scope::{debug, warn};
     use std::convert::Infallible;
     use std::str::FromStr;
@@ -103,12 +106,13 @@ mod handlers {
 
     /// List Snapshot artifacts
     pub async fn list_artifacts(
+        parameters: ListQueryParameters,
         http_message_service: Arc<dyn MessageService>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: artifacts");
 
         match http_message_service
-            .get_snapshot_list_message(LIST_MAX_ITEMS)
+            .get_snapshot_list_message(&parameters.with_max_limit(LIST_MAX_ITEMS))
             .await
         {
             Ok(message) => Ok(reply::json(&message, StatusCode::OK)),
@@ -290,6 +294,37 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_snapshots_get_forwards_query_parameters_with_a_bounded_limit() {
+        let mut mock_http_message_service = MockMessageService::new();
+        mock_http_message_service
+            .expect_get_snapshot_list_message()
+            .withf(|parameters| {
+                parameters
+                    == &ListQueryParameters::default()
+                        .with_limit(3)
+                        .with_offset(6)
+                        .with_immutable_file_number(1200)
+            })
+            .return_once(|_| Ok(vec![]))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.message_service = Arc::new(mock_http_message_service);
+
+        let method = Method::GET.as_str();
+        let path = "/artifact/snapshots";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{path}?limit=3&offset=6&immutable_file_number=1200"
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+    }
+
     #[tokio::test]
     async fn test_snapshots_get_ko() {
         let mut mock_http_message_service = MockMessageService::new();
@@ -1,5 +1,6 @@
 use crate::http_server::routes::middlewares;
 use crate::DependencyContainer;
+use mithril_common::messages::ListQueryParameters;
 use std::sync::Arc;
 use warp::Filter;
 
@@ -33,6 +34,7 @@ fn certificate_certificates(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("certificates")
         .and(warp::get())
+        .and(warp::query::<ListQueryParameters>())
         .and(middlewares::with_http_message_service(dependency_manager))
         .and_then(handlers::certificate_certificates)
 }
@@ -53,6 +55,7 @@ mod handlers {
         CertificatePendingStore, Configuration, ToCertificatePendingMessageAdapter,
     };
 
+    use mithril_common::messages::ListQueryParameters;
     use mithril_common::TimePointProvider;
     use slog_scope::{debug, warn};
     use std::convert::Infallible;
@@ -95,12 +98,13 @@ mod handlers {
 
     /// List all Certificates
     pub async fn certificate_certificates(
+        parameters: ListQueryParameters,
         http_message_service: Arc<dyn MessageService>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: certificate_certificates",);
 
         match http_message_service
-            .get_certificate_list_message(LIST_MAX_ITEMS)
+            .get_certificate_list_message(&parameters.with_max_limit(LIST_MAX_ITEMS))
             .await
         {
             Ok(certificates) => Ok(reply::json(&certificates, StatusCode::OK)),
@@ -139,7 +143,7 @@ mod handlers {
 mod tests {
     use anyhow::anyhow;
     use mithril_common::{
-        entities::CertificatePending,
+        entities::{CertificatePending, Epoch, SignedEntityTypeDiscriminants},
         test_utils::{apispec::APISpec, fake_data},
     };
     use mithril_persistence::store::adapter::DumbStoreAdapter;
@@ -288,6 +292,41 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_certificate_certificates_forwards_query_parameters_with_a_bounded_limit() {
+        let mut dependency_manager = initialize_dependencies().await;
+        let mut message_service = MockMessageService::new();
+        message_service
+            .expect_get_certificate_list_message()
+            .withf(|parameters| {
+                parameters
+                    == &ListQueryParameters::default()
+                        .with_limit(handlers::LIST_MAX_ITEMS)
+                        .with_offset(5)
+                        .with_from_epoch(Epoch(2))
+                        .with_to_epoch(Epoch(4))
+                        .with_signed_entity_type(
+                            SignedEntityTypeDiscriminants::MithrilStakeDistribution,
+                        )
+            })
+            .return_once(|_| Ok(vec![]))
+            .once();
+        dependency_manager.message_service = Arc::new(message_service);
+
+        let method = Method::GET.as_str();
+        let path = "/certificates";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{path}?limit=500&offset=5&from_epoch=2&to_epoch=4&signed_entity_type=MithrilStakeDistribution"
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+    }
+
     #[tokio::test]
     async fn test_certificate_when_error_retrieving_certificates_returns_ko_500() {
         let mut dependency_manager = initialize_dependencies().await;
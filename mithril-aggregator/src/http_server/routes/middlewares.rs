@@ -1,15 +1,15 @@
 use crate::{
-    database::repository::SignerGetter,
-    dependency_injection::EpochServiceWrapper,
+    database::repository::{CertificateRepository, OpenMessageRepository, SignerGetter},
+    dependency_injection::{EpochServiceWrapper, RuntimeStateWrapper},
     event_store::{EventMessage, TransmitterService},
     services::{
         CertifierService, MessageService, ProverService, SignedEntityService, TickerService,
     },
-    CertificatePendingStore, Configuration, DependencyContainer, SignerRegisterer,
+    CertificatePendingStore, Configuration, DependencyContainer, MetricsService, SignerRegisterer,
     VerificationKeyStorer,
 };
 
-use mithril_common::{api_version::APIVersionProvider, TimePointProvider};
+use mithril_common::{api_version::APIVersionProvider, era::EraChecker, TimePointProvider};
 use std::convert::Infallible;
 use std::sync::Arc;
 use warp::Filter;
@@ -111,3 +111,38 @@ pub fn with_prover_service(
 ) -> impl Filter<Extract = (Arc<dyn ProverService>,), Error = Infallible> + Clone {
     warp::any().map(move || dependency_manager.prover_service.clone())
 }
+
+/// With Metrics service
+pub fn with_metrics_service(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<MetricsService>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.metrics_service.clone())
+}
+
+/// With certificate repository
+pub fn with_certificate_repository(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<CertificateRepository>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.certificate_repository.clone())
+}
+
+/// With open message repository
+pub fn with_open_message_repository(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<OpenMessageRepository>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.open_message_repository.clone())
+}
+
+/// With era checker
+pub fn with_era_checker(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<EraChecker>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.era_checker.clone())
+}
+
+/// With runtime state
+pub fn with_runtime_state(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (RuntimeStateWrapper,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.runtime_state.clone())
+}
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use warp::Filter;
 
+use mithril_common::messages::CardanoTransactionsProofsRequestMessage;
+
 use crate::http_server::routes::middlewares;
 use crate::DependencyContainer;
 
@@ -16,10 +18,38 @@ impl CardanoTransactionProofQueryParams {
     }
 }
 
+/// Check that there is at least one and no more than `max_hashes` transaction hashes and that they
+/// are all hex encoded 32 bytes hashes.
+fn validate_transaction_hashes(
+    transaction_hashes: &[String],
+    max_hashes: usize,
+) -> Result<(), String> {
+    if transaction_hashes.is_empty() {
+        return Err("At least one transaction hash is required".to_string());
+    }
+    if transaction_hashes.len() > max_hashes {
+        return Err(format!(
+            "Too many transaction hashes: {} given while the maximum allowed is {max_hashes}",
+            transaction_hashes.len()
+        ));
+    }
+    if let Some(invalid_hash) = transaction_hashes
+        .iter()
+        .find(|hash| hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()))
+    {
+        return Err(format!(
+            "Invalid transaction hash '{invalid_hash}': expected a 64 characters hex encoded hash"
+        ));
+    }
+
+    Ok(())
+}
+
 pub fn routes(
     dependency_manager: Arc<DependencyContainer>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    proof_cardano_transaction(dependency_manager)
+    proof_cardano_transaction(dependency_manager.clone())
+        .or(post_proof_cardano_transaction(dependency_manager))
 }
 
 /// GET /proof/cardano-transaction
@@ -29,6 +59,7 @@ fn proof_cardano_transaction(
     warp::path!("proof" / "cardano-transaction")
         .and(warp::get())
         .and(warp::query::<CardanoTransactionProofQueryParams>())
+        .and(middlewares::with_config(dependency_manager.clone()))
         .and(middlewares::with_signed_entity_service(
             dependency_manager.clone(),
         ))
@@ -36,10 +67,32 @@ fn proof_cardano_transaction(
         .and_then(handlers::proof_cardano_transaction)
 }
 
+/// POST /proof/cardano-transaction
+fn post_proof_cardano_transaction(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    let max_body_size = CardanoTransactionsProofsRequestMessage::max_json_body_size(
+        dependency_manager
+            .config
+            .cardano_transactions_prover_max_hashes_allowed_by_request,
+    );
+
+    warp::path!("proof" / "cardano-transaction")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(max_body_size))
+        .and(warp::body::json())
+        .and(middlewares::with_config(dependency_manager.clone()))
+        .and(middlewares::with_signed_entity_service(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_prover_service(dependency_manager))
+        .and_then(handlers::post_proof_cardano_transaction)
+}
+
 mod handlers {
     use mithril_common::{
         entities::{CardanoTransactionsSnapshot, SignedEntity},
-        messages::CardanoTransactionsProofsMessage,
+        messages::{CardanoTransactionsProofsMessage, CardanoTransactionsProofsRequestMessage},
         StdResult,
     };
     use slog_scope::{debug, warn};
@@ -50,16 +103,17 @@ mod handlers {
         http_server::routes::reply,
         message_adapters::ToCardanoTransactionsProofsMessageAdapter,
         services::{ProverService, SignedEntityService},
-        unwrap_to_internal_server_error,
+        unwrap_to_internal_server_error, Configuration,
     };
 
-    use super::CardanoTransactionProofQueryParams;
+    use super::{validate_transaction_hashes, CardanoTransactionProofQueryParams};
 
     pub async fn proof_cardano_transaction(
         transaction_parameters: CardanoTransactionProofQueryParams,
+        config: Configuration,
         signed_entity_service: Arc<dyn SignedEntityService>,
         prover_service: Arc<dyn ProverService>,
-    ) -> Result<impl warp::Reply, Infallible> {
+    ) -> Result<Box<dyn warp::Reply>, Infallible> {
         let transaction_hashes = transaction_parameters
             .split_transactions_hashes()
             .iter()
@@ -70,6 +124,52 @@ mod handlers {
             transaction_parameters.transaction_hashes
         );
 
+        compute_proofs(
+            transaction_hashes,
+            config,
+            signed_entity_service,
+            prover_service,
+        )
+        .await
+    }
+
+    pub async fn post_proof_cardano_transaction(
+        request_message: CardanoTransactionsProofsRequestMessage,
+        config: Configuration,
+        signed_entity_service: Arc<dyn SignedEntityService>,
+        prover_service: Arc<dyn ProverService>,
+    ) -> Result<Box<dyn warp::Reply>, Infallible> {
+        debug!(
+            "⇄ HTTP SERVER: post_proof_cardano_transaction";
+            "number_of_transaction_hashes" => request_message.transaction_hashes.len()
+        );
+
+        compute_proofs(
+            request_message.transaction_hashes,
+            config,
+            signed_entity_service,
+            prover_service,
+        )
+        .await
+    }
+
+    async fn compute_proofs(
+        transaction_hashes: Vec<String>,
+        config: Configuration,
+        signed_entity_service: Arc<dyn SignedEntityService>,
+        prover_service: Arc<dyn ProverService>,
+    ) -> Result<Box<dyn warp::Reply>, Infallible> {
+        if let Err(error) = validate_transaction_hashes(
+            &transaction_hashes,
+            config.cardano_transactions_prover_max_hashes_allowed_by_request,
+        ) {
+            warn!("proof_cardano_transaction::bad_request"; "error" => &error);
+            return Ok(reply::bad_request(
+                "invalid_transaction_hashes".to_string(),
+                error,
+            ));
+        }
+
         match unwrap_to_internal_server_error!(
             signed_entity_service
                 .get_last_cardano_transaction_snapshot()
@@ -120,6 +220,7 @@ mod tests {
         entities::{
             CardanoDbBeacon, CardanoTransactionsSetProof, CardanoTransactionsSnapshot, SignedEntity,
         },
+        messages::CardanoTransactionsProofsRequestMessage,
         test_utils::apispec::APISpec,
     };
 
@@ -136,6 +237,9 @@ mod tests {
         services::MockProverService, Configuration,
     };
 
+    const TX_1: &str = "6dbb104ed68481ef829a26a20142916d17985e01774d72d72c2f6dbb104ed684";
+    const TX_2: &str = "d17985e01774d72d72c2f6dbb104ed68481ef829a26a20142916d17985e01774";
+
     fn setup_router(
         dependency_manager: Arc<DependencyContainer>,
     ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
@@ -208,7 +312,7 @@ mod tests {
         let response = request()
             .method(method)
             .path(&format!(
-                "/{SERVER_BASE_PATH}{path}?transaction_hashes=tx-123,tx-456"
+                "/{SERVER_BASE_PATH}{path}?transaction_hashes={TX_1},{TX_2}"
             ))
             .reply(&setup_router(Arc::new(dependency_manager)))
             .await;
@@ -237,7 +341,7 @@ mod tests {
         let response = request()
             .method(method)
             .path(&format!(
-                "/{SERVER_BASE_PATH}{path}?transaction_hashes=tx-123,tx-456"
+                "/{SERVER_BASE_PATH}{path}?transaction_hashes={TX_1},{TX_2}"
             ))
             .reply(&setup_router(Arc::new(dependency_manager)))
             .await;
@@ -271,7 +375,7 @@ mod tests {
         let response = request()
             .method(method)
             .path(&format!(
-                "/{SERVER_BASE_PATH}{path}?transaction_hashes=tx-123,tx-456"
+                "/{SERVER_BASE_PATH}{path}?transaction_hashes={TX_1},{TX_2}"
             ))
             .reply(&setup_router(Arc::new(dependency_manager)))
             .await;
@@ -287,4 +391,137 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[tokio::test]
+    async fn proof_cardano_transaction_with_an_invalid_hash_returns_bad_request() {
+        let config = Configuration::new_sample();
+        let mut builder = DependenciesBuilder::new(config);
+        let dependency_manager = builder.build_dependency_container().await.unwrap();
+
+        let method = Method::GET.as_str();
+        let path = "/proof/cardano-transaction";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{path}?transaction_hashes={TX_1},not-an-hash"
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::BAD_REQUEST,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn post_proof_cardano_transaction_ok() {
+        let config = Configuration::new_sample();
+        let mut builder = DependenciesBuilder::new(config);
+        let mut dependency_manager = builder.build_dependency_container().await.unwrap();
+        let mut mock_signed_entity_service = MockSignedEntityService::new();
+        mock_signed_entity_service
+            .expect_get_last_cardano_transaction_snapshot()
+            .returning(|| Ok(Some(SignedEntity::<CardanoTransactionsSnapshot>::dummy())));
+        dependency_manager.signed_entity_service = Arc::new(mock_signed_entity_service);
+
+        let mut mock_prover_service = MockProverService::new();
+        mock_prover_service
+            .expect_compute_transactions_proofs()
+            .withf(|_, hashes| hashes == [TX_1.to_string(), TX_2.to_string()])
+            .returning(|_, _| Ok(vec![CardanoTransactionsSetProof::dummy()]));
+        dependency_manager.prover_service = Arc::new(mock_prover_service);
+
+        let method = Method::POST.as_str();
+        let path = "/proof/cardano-transaction";
+        let message = CardanoTransactionsProofsRequestMessage::new(&[TX_1, TX_2]);
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn post_proof_cardano_transaction_with_too_many_hashes_returns_bad_request() {
+        let mut config = Configuration::new_sample();
+        config.cardano_transactions_prover_max_hashes_allowed_by_request = 1;
+        let mut builder = DependenciesBuilder::new(config);
+        let dependency_manager = builder.build_dependency_container().await.unwrap();
+
+        let method = Method::POST.as_str();
+        let path = "/proof/cardano-transaction";
+        let message = CardanoTransactionsProofsRequestMessage::new(&[TX_1, TX_2]);
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::BAD_REQUEST,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn post_proof_cardano_transaction_with_a_body_too_large_is_rejected() {
+        let mut config = Configuration::new_sample();
+        config.cardano_transactions_prover_max_hashes_allowed_by_request = 1;
+        let mut builder = DependenciesBuilder::new(config);
+        let dependency_manager = builder.build_dependency_container().await.unwrap();
+
+        let message = CardanoTransactionsProofsRequestMessage::new(&[TX_1; 20]);
+
+        let response = request()
+            .method(Method::POST.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/proof/cardano-transaction"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, response.status());
+    }
+
+    #[test]
+    fn validate_transaction_hashes_checks_count_and_format() {
+        let hashes = |hashes: &[&str]| hashes.iter().map(|h| h.to_string()).collect::<Vec<_>>();
+
+        validate_transaction_hashes(&hashes(&[TX_1, TX_2]), 2).unwrap();
+        validate_transaction_hashes(&hashes(&[]), 2).expect_err("No hashes should fail");
+        validate_transaction_hashes(&hashes(&[TX_1, TX_2]), 1)
+            .expect_err("Too many hashes should fail");
+        validate_transaction_hashes(&hashes(&["tx-123"]), 2)
+            .expect_err("Hash that is not hex encoded should fail");
+        validate_transaction_hashes(&hashes(&[&TX_1[..62]]), 2)
+            .expect_err("Hash with a wrong length should fail");
+    }
 }
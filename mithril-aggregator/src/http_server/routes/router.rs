@@ -1,6 +1,6 @@
 use crate::http_server::routes::{
     artifact_routes, certificate_routes, epoch_routes, root_routes, signatures_routes,
-    signer_routes, statistics_routes,
+    signer_routes, statistics_routes, status_routes,
 };
 use crate::http_server::SERVER_BASE_PATH;
 use crate::DependencyContainer;
@@ -17,6 +17,36 @@ use warp::{Filter, Rejection, Reply};
 
 use super::{middlewares, proof_routes};
 
+/// Templates of the aggregator routes, relative to the [SERVER_BASE_PATH], used to label the
+/// HTTP requests metrics without creating a label for each requested hash or digest.
+const ROUTE_TEMPLATES: &[&str] = &[
+    "/",
+    "/certificate-pending",
+    "/certificates",
+    "/certificate/{hash}",
+    "/artifact/snapshots",
+    "/artifact/snapshot/{digest}",
+    "/artifact/snapshot/{digest}/download",
+    "/artifact/mithril-stake-distributions",
+    "/artifact/mithril-stake-distribution/{hash}",
+    "/artifact/cardano-stake-distributions",
+    "/artifact/cardano-stake-distribution/{hash}",
+    "/artifact/cardano-transactions",
+    "/artifact/cardano-transaction/{hash}",
+    "/snapshots",
+    "/snapshot/{digest}",
+    "/snapshot_download/{filename}",
+    "/proof/cardano-transaction",
+    "/register-signer",
+    "/register-signatures",
+    "/signers/tickers",
+    "/signers/registered/{epoch}",
+    "/epoch-settings",
+    "/statistics/snapshot",
+    "/status",
+    "/health",
+];
+
 #[derive(Debug)]
 pub struct VersionMismatchError;
 
@@ -35,6 +65,7 @@ pub fn routes(
         .allow_any_origin()
         .allow_headers(vec!["content-type", MITHRIL_API_VERSION_HEADER])
         .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+    let metrics_service = dependency_manager.metrics_service.clone();
 
     warp::any()
         .and(header_must_be(
@@ -49,6 +80,9 @@ pub fn routes(
                 .or(artifact_routes::mithril_stake_distribution::routes(
                     dependency_manager.clone(),
                 ))
+                .or(artifact_routes::cardano_stake_distribution::routes(
+                    dependency_manager.clone(),
+                ))
                 .or(artifact_routes::cardano_transaction::routes(
                     dependency_manager.clone(),
                 ))
@@ -57,6 +91,7 @@ pub fn routes(
                 .or(signatures_routes::routes(dependency_manager.clone()))
                 .or(epoch_routes::routes(dependency_manager.clone()))
                 .or(statistics_routes::routes(dependency_manager.clone()))
+                .or(status_routes::routes(dependency_manager.clone()))
                 .or(root_routes::routes(dependency_manager.clone()))
                 .with(cors),
         )
@@ -72,6 +107,38 @@ pub fn routes(
                     .to_string(),
             )
         })
+        .with(warp::log::custom(move |info| {
+            metrics_service.http_request_duration_seconds_observe(
+                info.method().as_str(),
+                route_template(info.path()),
+                info.status().as_u16(),
+                info.elapsed(),
+            )
+        }))
+}
+
+/// Find the template of the route matching the given request path, `unknown` if none match.
+fn route_template(path: &str) -> &'static str {
+    let path = path
+        .strip_prefix(&format!("/{SERVER_BASE_PATH}"))
+        .unwrap_or(path);
+    let path_segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    ROUTE_TEMPLATES
+        .iter()
+        .find(|template| {
+            let template_segments: Vec<&str> = template.trim_matches('/').split('/').collect();
+
+            template_segments.len() == path_segments.len()
+                && template_segments.iter().zip(&path_segments).all(
+                    |(template_segment, path_segment)| {
+                        template_segment == path_segment
+                            || (template_segment.starts_with('{') && !path_segment.is_empty())
+                    },
+                )
+        })
+        .copied()
+        .unwrap_or("unknown")
 }
 
 /// API Version verification
@@ -185,4 +252,34 @@ mod tests {
             .await
             .expect(r#"request with the good version "0.1.2" should not be rejected"#);
     }
+
+    #[test]
+    fn route_template_replaces_parameters_of_known_routes() {
+        assert_eq!("/", route_template("/aggregator"));
+        assert_eq!("/", route_template("/aggregator/"));
+        assert_eq!("/certificates", route_template("/aggregator/certificates"));
+        assert_eq!(
+            "/certificate/{hash}",
+            route_template("/aggregator/certificate/abc123")
+        );
+        assert_eq!(
+            "/artifact/snapshot/{digest}/download",
+            route_template("/aggregator/artifact/snapshot/abc123/download")
+        );
+        assert_eq!(
+            "/signers/registered/{epoch}",
+            route_template("/aggregator/signers/registered/12")
+        );
+    }
+
+    #[test]
+    fn route_template_of_unknown_routes_is_unknown() {
+        assert_eq!("unknown", route_template("/aggregator/whatever"));
+        assert_eq!("unknown", route_template("/aggregator/certificate/"));
+        assert_eq!(
+            "unknown",
+            route_template("/aggregator/certificate/abc123/whatever")
+        );
+        assert_eq!("unknown", route_template("/not-the-aggregator"));
+    }
 }
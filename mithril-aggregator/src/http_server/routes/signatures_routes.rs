@@ -19,7 +19,8 @@ fn register_signatures(
         .and(middlewares::with_certifier_service(
             dependency_manager.clone(),
         ))
-        .and(middlewares::with_ticker_service(dependency_manager))
+        .and(middlewares::with_ticker_service(dependency_manager.clone()))
+        .and(middlewares::with_metrics_service(dependency_manager))
         .and_then(handlers::register_signatures)
 }
 
@@ -38,6 +39,7 @@ mod handlers {
         http_server::routes::reply,
         message_adapters::FromRegisterSingleSignatureAdapter,
         services::{CertifierService, CertifierServiceError, TickerService},
+        MetricsService,
     };
 
     /// Register Signatures
@@ -45,9 +47,11 @@ mod handlers {
         message: RegisterSignatureMessage,
         certifier_service: Arc<dyn CertifierService>,
         ticker_service: Arc<dyn TickerService>,
+        metrics_service: Arc<MetricsService>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: register_signatures/{:?}", message);
         trace!("⇄ HTTP SERVER: register_signatures"; "complete_message" => #?message );
+        metrics_service.signature_registration_received_since_startup_counter_increment();
 
         let signed_entity_type = match message.signed_entity_type.clone() {
             Some(signed_entity_type) => Ok(signed_entity_type),
@@ -63,6 +67,8 @@ mod handlers {
                     Ok(signature) => signature,
                     Err(err) => {
                         warn!("register_signatures::payload decoding error"; "error" => ?err);
+                        metrics_service
+                            .signature_registration_rejected_since_startup_counter_increment();
 
                         return Ok(reply::bad_request(
                             "Could not decode signature payload".to_string(),
@@ -75,25 +81,30 @@ mod handlers {
                     .register_single_signature(&signed_entity_type, &signatures)
                     .await
                 {
-                    Err(err) => match err.downcast_ref::<CertifierServiceError>() {
-                        Some(CertifierServiceError::AlreadyCertified(signed_entity_type)) => {
-                            debug!("register_signatures::open_message_already_certified"; "signed_entity_type" => ?signed_entity_type);
-                            Ok(reply::empty(StatusCode::GONE))
-                        }
-                        Some(CertifierServiceError::NotFound(signed_entity_type)) => {
-                            debug!("register_signatures::not_found"; "signed_entity_type" => ?signed_entity_type);
-                            Ok(reply::empty(StatusCode::NOT_FOUND))
-                        }
-                        Some(_) | None => {
-                            warn!("register_signatures::error"; "error" => ?err);
-                            Ok(reply::internal_server_error(err))
+                    Err(err) => {
+                        metrics_service
+                            .signature_registration_rejected_since_startup_counter_increment();
+                        match err.downcast_ref::<CertifierServiceError>() {
+                            Some(CertifierServiceError::AlreadyCertified(signed_entity_type)) => {
+                                debug!("register_signatures::open_message_already_certified"; "signed_entity_type" => ?signed_entity_type);
+                                Ok(reply::empty(StatusCode::GONE))
+                            }
+                            Some(CertifierServiceError::NotFound(signed_entity_type)) => {
+                                debug!("register_signatures::not_found"; "signed_entity_type" => ?signed_entity_type);
+                                Ok(reply::empty(StatusCode::NOT_FOUND))
+                            }
+                            Some(_) | None => {
+                                warn!("register_signatures::error"; "error" => ?err);
+                                Ok(reply::internal_server_error(err))
+                            }
                         }
-                    },
+                    }
                     Ok(()) => Ok(reply::empty(StatusCode::CREATED)),
                 }
             }
             Err(err) => {
                 warn!("register_signatures::cant_retrieve_signed_entity_type"; "error" => ?err);
+                metrics_service.signature_registration_rejected_since_startup_counter_increment();
                 Ok(reply::internal_server_error(err))
             }
         }
@@ -165,6 +176,42 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_register_signatures_post_increments_received_and_rejected_metrics() {
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_register_single_signature()
+            .return_once(move |_, _| Ok(()))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certifier_service = Arc::new(mock_certifier_service);
+        let dependency_manager = Arc::new(dependency_manager);
+        let valid_message = RegisterSignatureMessage::dummy();
+        let invalid_message = RegisterSignatureMessage {
+            signature: "invalid-signature".to_string(),
+            ..RegisterSignatureMessage::dummy()
+        };
+
+        for message in [valid_message, invalid_message] {
+            request()
+                .method(Method::POST.as_str())
+                .path(&format!("/{SERVER_BASE_PATH}/register-signatures"))
+                .json(&message)
+                .reply(&setup_router(dependency_manager.clone()))
+                .await;
+        }
+
+        let metrics_service = &dependency_manager.metrics_service;
+        assert_eq!(
+            2,
+            metrics_service.signature_registration_received_since_startup_counter_get()
+        );
+        assert_eq!(
+            1,
+            metrics_service.signature_registration_rejected_since_startup_counter_get()
+        );
+    }
+
     #[tokio::test]
     async fn test_register_signatures_post_ko_400() {
         let mut mock_certifier_service = MockCertifierService::new();
@@ -31,7 +31,10 @@ fn register_signer(
         .and(middlewares::with_event_transmitter(
             dependency_manager.clone(),
         ))
-        .and(middlewares::with_time_point_provider(dependency_manager))
+        .and(middlewares::with_time_point_provider(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_metrics_service(dependency_manager))
         .and_then(handlers::register_signer)
 }
 
@@ -63,7 +66,8 @@ mod handlers {
     };
     use crate::event_store::{EventMessage, TransmitterService};
     use crate::{
-        http_server::routes::reply, Configuration, SignerRegisterer, SignerRegistrationError,
+        http_server::routes::reply, Configuration, MetricsService, SignerRegisterer,
+        SignerRegistrationError,
     };
     use crate::{FromRegisterSignerAdapter, VerificationKeyStorer};
     use mithril_common::entities::Epoch;
@@ -81,6 +85,7 @@ mod handlers {
         signer_registerer: Arc<dyn SignerRegisterer>,
         event_transmitter: Arc<TransmitterService<EventMessage>>,
         time_point_provider: Arc<dyn TimePointProvider>,
+        metrics_service: Arc<MetricsService>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!(
             "⇄ HTTP SERVER: register_signer/{:?}",
@@ -90,6 +95,7 @@ mod handlers {
             "⇄ HTTP SERVER: register_signer";
             "complete_message" => #?register_signer_message
         );
+        metrics_service.signer_registration_total_since_startup_counter_increment();
 
         let registration_epoch = match register_signer_message.epoch {
             Some(epoch) => epoch,
@@ -135,6 +141,8 @@ mod handlers {
             .await
         {
             Ok(signer_with_stake) => {
+                metrics_service
+                    .signer_registration_success_per_epoch_counter_increment(registration_epoch);
                 let _ = event_transmitter.send_event_message(
                     "HTTP::signer_register",
                     "register_signer",
@@ -322,6 +330,40 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_register_signer_post_increments_registration_metrics() {
+        let signer_with_stake = fake_data::signers_with_stakes(1).pop().unwrap();
+        let mut mock_signer_registerer = MockSignerRegisterer::new();
+        mock_signer_registerer
+            .expect_register_signer()
+            .return_once(|_, _| Ok(signer_with_stake));
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signer_registerer = Arc::new(mock_signer_registerer);
+        let dependency_manager = Arc::new(dependency_manager);
+
+        let signer = RegisterSignerMessage {
+            epoch: Some(Epoch(7)),
+            ..RegisterSignerMessage::dummy()
+        };
+
+        request()
+            .method(Method::POST.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/register-signer"))
+            .json(&signer)
+            .reply(&setup_router(dependency_manager.clone()))
+            .await;
+
+        let metrics_service = &dependency_manager.metrics_service;
+        assert_eq!(
+            1,
+            metrics_service.signer_registration_total_since_startup_counter_get()
+        );
+        assert_eq!(
+            1,
+            metrics_service.signer_registration_success_per_epoch_counter_get(Epoch(7))
+        );
+    }
+
     #[tokio::test]
     async fn test_register_signer_post_ok_existing() {
         let signer_with_stake = fake_data::signers_with_stakes(1).pop().unwrap();
@@ -0,0 +1,291 @@
+use crate::http_server::routes::middlewares;
+use crate::DependencyContainer;
+use mithril_common::entities::{
+    SignedEntityType, SignedEntityTypeDiscriminants, SignerWithStake, Stake, TimePoint,
+};
+use mithril_common::era::SupportedEra;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use warp::Filter;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct AggregatorStatusMessage {
+    pub time_point: TimePoint,
+    pub runtime_state: String,
+    pub mithril_era: SupportedEra,
+    pub current_epoch_signers: SignersStatus,
+    pub next_epoch_signers: SignersStatus,
+    pub last_certificate_hashes: BTreeMap<SignedEntityTypeDiscriminants, String>,
+    pub pending_open_messages: Vec<SignedEntityType>,
+    pub signed_entity_types: BTreeSet<SignedEntityTypeDiscriminants>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SignersStatus {
+    pub total_signers: usize,
+    pub total_stake: Stake,
+}
+
+impl SignersStatus {
+    fn from_signers(signers: &[SignerWithStake]) -> Self {
+        Self {
+            total_signers: signers.len(),
+            total_stake: signers.iter().map(|signer| signer.stake).sum(),
+        }
+    }
+}
+
+pub fn routes(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    status(dependency_manager).or(health())
+}
+
+/// GET /status
+fn status(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("status")
+        .and(warp::get())
+        .and(middlewares::with_time_point_provider(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_runtime_state(dependency_manager.clone()))
+        .and(middlewares::with_era_checker(dependency_manager.clone()))
+        .and(middlewares::with_epoch_service(dependency_manager.clone()))
+        .and(middlewares::with_certificate_repository(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_open_message_repository(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_config(dependency_manager))
+        .and_then(handlers::status)
+}
+
+/// GET /health
+fn health() -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("health")
+        .and(warp::get())
+        .and_then(handlers::health)
+}
+
+mod handlers {
+    use mithril_common::entities::Certificate;
+    use mithril_common::era::EraChecker;
+    use mithril_common::TimePointProvider;
+    use slog_scope::{debug, warn};
+    use std::collections::BTreeMap;
+    use std::convert::Infallible;
+    use std::sync::Arc;
+    use warp::http::StatusCode;
+
+    use crate::database::repository::{CertificateRepository, OpenMessageRepository};
+    use crate::dependency_injection::{EpochServiceWrapper, RuntimeStateWrapper};
+    use crate::http_server::routes::reply;
+    use crate::http_server::routes::status_routes::{AggregatorStatusMessage, SignersStatus};
+    use crate::{unwrap_to_internal_server_error, Configuration};
+
+    /// Status
+    pub async fn status(
+        time_point_provider: Arc<dyn TimePointProvider>,
+        runtime_state: RuntimeStateWrapper,
+        era_checker: Arc<EraChecker>,
+        epoch_service: EpochServiceWrapper,
+        certificate_repository: Arc<CertificateRepository>,
+        open_message_repository: Arc<OpenMessageRepository>,
+        config: Configuration,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: status");
+
+        let time_point = unwrap_to_internal_server_error!(
+            time_point_provider.get_current_time_point().await,
+            "status::error"
+        );
+        let (current_epoch_signers, next_epoch_signers) = {
+            let epoch_service = epoch_service.read().await;
+            let current_signers = unwrap_to_internal_server_error!(
+                epoch_service.current_signers_with_stake(),
+                "status::error"
+            );
+            let next_signers = unwrap_to_internal_server_error!(
+                epoch_service.next_signers_with_stake(),
+                "status::error"
+            );
+
+            (
+                SignersStatus::from_signers(current_signers),
+                SignersStatus::from_signers(next_signers),
+            )
+        };
+        let signed_entity_types = unwrap_to_internal_server_error!(
+            config.list_allowed_signed_entity_types_discriminants(),
+            "status::error"
+        );
+
+        let mut last_certificate_hashes = BTreeMap::new();
+        for signed_entity_type in &signed_entity_types {
+            if let Some(certificate) = unwrap_to_internal_server_error!(
+                certificate_repository
+                    .get_latest_certificate_for_signed_entity_type::<Certificate>(
+                        signed_entity_type
+                    )
+                    .await,
+                "status::error"
+            ) {
+                last_certificate_hashes.insert(*signed_entity_type, certificate.hash);
+            }
+        }
+        let pending_open_messages = unwrap_to_internal_server_error!(
+            open_message_repository.get_pending_open_messages().await,
+            "status::error"
+        )
+        .into_iter()
+        .map(|open_message| open_message.signed_entity_type)
+        .collect();
+
+        Ok(reply::json(
+            &AggregatorStatusMessage {
+                time_point,
+                runtime_state: runtime_state.read().await.clone(),
+                mithril_era: era_checker.current_era(),
+                current_epoch_signers,
+                next_epoch_signers,
+                last_certificate_hashes,
+                pending_open_messages,
+                signed_entity_types,
+            },
+            StatusCode::OK,
+        ))
+    }
+
+    /// Health
+    pub async fn health() -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: health");
+
+        Ok(reply::empty(StatusCode::OK))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::{
+        entities::Epoch,
+        test_utils::{apispec::APISpec, fake_data, MithrilFixtureBuilder},
+    };
+    use serde_json::Value::Null;
+    use tokio::sync::RwLock;
+    use warp::http::{Method, StatusCode};
+    use warp::test::request;
+
+    use crate::http_server::SERVER_BASE_PATH;
+    use crate::initialize_dependencies;
+    use crate::services::FakeEpochService;
+
+    use super::*;
+
+    fn setup_router(
+        dependency_manager: Arc<DependencyContainer>,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let cors = warp::cors()
+            .allow_any_origin()
+            .allow_headers(vec!["content-type"])
+            .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+
+        warp::any()
+            .and(warp::path(SERVER_BASE_PATH))
+            .and(routes(dependency_manager).with(cors))
+    }
+
+    #[tokio::test]
+    async fn test_status_get_ok() {
+        let method = Method::GET.as_str();
+        let path = "/status";
+        let mut dependency_manager = initialize_dependencies().await;
+        let fixture = MithrilFixtureBuilder::default().with_signers(5).build();
+        let epoch_service = FakeEpochService::from_fixture(Epoch(5), &fixture);
+        dependency_manager.epoch_service = Arc::new(RwLock::new(epoch_service));
+        *dependency_manager.runtime_state.write().await = "signing".to_string();
+        let certificate = fake_data::genesis_certificate("certificate-hash");
+        dependency_manager
+            .certificate_repository
+            .create_certificate(certificate.clone())
+            .await
+            .unwrap();
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+
+        let status: AggregatorStatusMessage = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!("signing", status.runtime_state);
+        assert_eq!(
+            SignersStatus {
+                total_signers: 5,
+                total_stake: fixture.signers_with_stake().iter().map(|s| s.stake).sum(),
+            },
+            status.current_epoch_signers
+        );
+        assert_eq!(
+            Some(&certificate.hash),
+            status
+                .last_certificate_hashes
+                .get(&SignedEntityTypeDiscriminants::from(
+                    &certificate.signed_entity_type()
+                ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_status_get_ko_500() {
+        let method = Method::GET.as_str();
+        let path = "/status";
+        let dependency_manager = initialize_dependencies().await;
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_health_get_ok() {
+        let method = Method::GET.as_str();
+        let path = "/health";
+        let dependency_manager = initialize_dependencies().await;
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+    }
+}
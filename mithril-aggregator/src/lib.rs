@@ -20,6 +20,7 @@ pub mod entities;
 pub mod event_store;
 mod http_server;
 mod message_adapters;
+pub mod metrics;
 mod multi_signer;
 mod runtime;
 pub mod services;
@@ -31,7 +32,8 @@ mod tools;
 
 pub use crate::artifact_builder::ArtifactBuilder;
 pub use crate::configuration::{
-    Configuration, DefaultConfiguration, ExecutionEnvironment, SnapshotUploaderType,
+    Configuration, ConfigurationSecret, DefaultConfiguration, ExecutionEnvironment,
+    SignerImporterSourceType, SnapshotStorageSettings, SnapshotUploaderType,
     ZstandardCompressionParameters,
 };
 pub use crate::multi_signer::{MultiSigner, MultiSignerImpl};
@@ -40,6 +42,7 @@ pub use dependency_injection::DependencyContainer;
 pub use message_adapters::{
     FromRegisterSignerAdapter, ToCertificatePendingMessageAdapter, ToEpochSettingsMessageAdapter,
 };
+pub use metrics::*;
 pub use runtime::{
     AggregatorConfig, AggregatorRunner, AggregatorRunnerTrait, AggregatorRuntime, RuntimeError,
 };
@@ -48,7 +51,8 @@ pub use signer_registerer::{
     SignerRegistrationRound, SignerRegistrationRoundOpener,
 };
 pub use snapshot_uploaders::{
-    DumbSnapshotUploader, LocalSnapshotUploader, RemoteSnapshotUploader, SnapshotUploader,
+    DumbSnapshotUploader, LocalSnapshotUploader, RemoteSnapshotUploader, S3SnapshotUploader,
+    SnapshotUploader,
 };
 pub use snapshotter::{
     CompressedArchiveSnapshotter, DumbSnapshotter, SnapshotError, Snapshotter,
@@ -58,7 +62,8 @@ pub use store::{
     CertificatePendingStore, ProtocolParametersStorer, VerificationKeyStore, VerificationKeyStorer,
 };
 pub use tools::{
-    CExplorerSignerRetriever, SignersImporter, SignersImporterPersister, SignersImporterRetriever,
+    CExplorerSignerRetriever, JsonHttpSignerRetriever, JsonSignerMapping, LocalFileSignerRetriever,
+    SignersImporter, SignersImporterPersister, SignersImporterRetriever,
 };
 
 #[cfg(test)]
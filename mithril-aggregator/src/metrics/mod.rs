@@ -0,0 +1,92 @@
+//! metrics module.
+//! This module contains the aggregator metrics service and metrics server.
+
+mod server;
+mod service;
+
+pub use server::MetricsServer;
+pub use service::MetricsService;
+
+/// 'runtime_cycle_total_since_startup' metric name
+pub const RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_runtime_cycle_total_since_startup";
+/// 'runtime_cycle_total_since_startup' metric help
+pub const RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of runtime cycles per state since startup on a Mithril aggregator node";
+
+/// 'runtime_cycle_success_since_startup' metric name
+pub const RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_runtime_cycle_success_since_startup";
+/// 'runtime_cycle_success_since_startup' metric help
+pub const RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of successful runtime cycles per state since startup on a Mithril aggregator node";
+
+/// 'signer_registration_total_since_startup' metric name
+pub const SIGNER_REGISTRATION_TOTAL_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_signer_registration_total_since_startup";
+/// 'signer_registration_total_since_startup' metric help
+pub const SIGNER_REGISTRATION_TOTAL_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of signer registrations received since startup on a Mithril aggregator node";
+
+/// 'signer_registration_success_per_epoch' metric name
+pub const SIGNER_REGISTRATION_SUCCESS_PER_EPOCH_METRIC_NAME: &str =
+    "mithril_aggregator_signer_registration_success_per_epoch";
+/// 'signer_registration_success_per_epoch' metric help
+pub const SIGNER_REGISTRATION_SUCCESS_PER_EPOCH_METRIC_HELP: &str =
+    "Number of successful signer registrations per registration epoch on a Mithril aggregator node";
+
+/// 'signature_registration_received_since_startup' metric name
+pub const SIGNATURE_REGISTRATION_RECEIVED_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_signature_registration_received_since_startup";
+/// 'signature_registration_received_since_startup' metric help
+pub const SIGNATURE_REGISTRATION_RECEIVED_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of single signatures received since startup on a Mithril aggregator node";
+
+/// 'signature_registration_rejected_since_startup' metric name
+pub const SIGNATURE_REGISTRATION_REJECTED_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_signature_registration_rejected_since_startup";
+/// 'signature_registration_rejected_since_startup' metric help
+pub const SIGNATURE_REGISTRATION_REJECTED_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of single signatures rejected since startup on a Mithril aggregator node";
+
+/// 'certificate_created_since_startup' metric name
+pub const CERTIFICATE_CREATED_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_certificate_created_since_startup";
+/// 'certificate_created_since_startup' metric help
+pub const CERTIFICATE_CREATED_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of certificates created per signed entity type since startup on a Mithril aggregator node";
+
+/// 'open_message_expired_since_startup' metric name
+pub const OPEN_MESSAGE_EXPIRED_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_open_message_expired_since_startup";
+/// 'open_message_expired_since_startup' metric help
+pub const OPEN_MESSAGE_EXPIRED_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of expired open messages per signed entity type since startup on a Mithril aggregator node";
+
+/// 'artifact_build_duration_seconds' metric name
+pub const ARTIFACT_BUILD_DURATION_SECONDS_METRIC_NAME: &str =
+    "mithril_aggregator_artifact_build_duration_seconds";
+/// 'artifact_build_duration_seconds' metric help
+pub const ARTIFACT_BUILD_DURATION_SECONDS_METRIC_HELP: &str =
+    "Duration of the artifact builds per signed entity type on a Mithril aggregator node";
+
+/// 'snapshot_size_last_bytes' metric name
+pub const SNAPSHOT_SIZE_LAST_BYTES_METRIC_NAME: &str =
+    "mithril_aggregator_snapshot_size_last_bytes";
+/// 'snapshot_size_last_bytes' metric help
+pub const SNAPSHOT_SIZE_LAST_BYTES_METRIC_HELP: &str =
+    "Size in bytes of the latest snapshot archive produced by a Mithril aggregator node";
+
+/// 'http_request_duration_seconds' metric name
+pub const HTTP_REQUEST_DURATION_SECONDS_METRIC_NAME: &str =
+    "mithril_aggregator_http_request_duration_seconds";
+/// 'http_request_duration_seconds' metric help
+pub const HTTP_REQUEST_DURATION_SECONDS_METRIC_HELP: &str =
+    "Duration of the HTTP requests per method, route and status on a Mithril aggregator node";
+
+/// 'cardano_transactions_pruned_since_startup' metric name
+pub const CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_cardano_transactions_pruned_since_startup";
+/// 'cardano_transactions_pruned_since_startup' metric help
+pub const CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of Cardano transactions pruned from the database since startup on a Mithril aggregator node";
@@ -0,0 +1,651 @@
+use mithril_common::{
+    entities::{Epoch, SignedEntityTypeDiscriminants},
+    StdResult,
+};
+use prometheus::{
+    CounterVec, Encoder, Gauge, HistogramOpts, HistogramVec, IntCounter, Opts, Registry,
+    TextEncoder,
+};
+use slog_scope::debug;
+use std::time::Duration;
+
+use super::{
+    ARTIFACT_BUILD_DURATION_SECONDS_METRIC_HELP, ARTIFACT_BUILD_DURATION_SECONDS_METRIC_NAME,
+    CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_HELP,
+    CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_NAME,
+    CERTIFICATE_CREATED_SINCE_STARTUP_METRIC_HELP, CERTIFICATE_CREATED_SINCE_STARTUP_METRIC_NAME,
+    HTTP_REQUEST_DURATION_SECONDS_METRIC_HELP, HTTP_REQUEST_DURATION_SECONDS_METRIC_NAME,
+    OPEN_MESSAGE_EXPIRED_SINCE_STARTUP_METRIC_HELP, OPEN_MESSAGE_EXPIRED_SINCE_STARTUP_METRIC_NAME,
+    RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_HELP,
+    RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_NAME, RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_HELP,
+    RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME,
+    SIGNATURE_REGISTRATION_RECEIVED_SINCE_STARTUP_METRIC_HELP,
+    SIGNATURE_REGISTRATION_RECEIVED_SINCE_STARTUP_METRIC_NAME,
+    SIGNATURE_REGISTRATION_REJECTED_SINCE_STARTUP_METRIC_HELP,
+    SIGNATURE_REGISTRATION_REJECTED_SINCE_STARTUP_METRIC_NAME,
+    SIGNER_REGISTRATION_SUCCESS_PER_EPOCH_METRIC_HELP,
+    SIGNER_REGISTRATION_SUCCESS_PER_EPOCH_METRIC_NAME,
+    SIGNER_REGISTRATION_TOTAL_SINCE_STARTUP_METRIC_HELP,
+    SIGNER_REGISTRATION_TOTAL_SINCE_STARTUP_METRIC_NAME, SNAPSHOT_SIZE_LAST_BYTES_METRIC_HELP,
+    SNAPSHOT_SIZE_LAST_BYTES_METRIC_NAME,
+};
+
+/// Type alias for a metric name.
+pub type MetricName = str;
+
+/// Type alias for a counter value.
+type CounterValue = u32;
+
+/// Buckets (in seconds) of the artifact build durations histogram, artifacts such as the
+/// snapshots of the Cardano database can take several hours to build.
+const ARTIFACT_BUILD_DURATION_BUCKETS: [f64; 12] = [
+    1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1200.0, 1800.0, 3600.0, 7200.0,
+];
+
+/// Metrics service which is responsible for recording and exposing metrics.
+pub struct MetricsService {
+    registry: Registry,
+    runtime_cycle_total_since_startup_counter: Box<CounterVec>,
+    runtime_cycle_success_since_startup_counter: Box<CounterVec>,
+    signer_registration_total_since_startup_counter: Box<IntCounter>,
+    signer_registration_success_per_epoch_counter: Box<CounterVec>,
+    signature_registration_received_since_startup_counter: Box<IntCounter>,
+    signature_registration_rejected_since_startup_counter: Box<IntCounter>,
+    certificate_created_since_startup_counter: Box<CounterVec>,
+    open_message_expired_since_startup_counter: Box<CounterVec>,
+    artifact_build_duration_seconds_histogram: Box<HistogramVec>,
+    snapshot_size_last_bytes_gauge: Box<Gauge>,
+    http_request_duration_seconds_histogram: Box<HistogramVec>,
+    cardano_transactions_pruned_since_startup_counter: Box<IntCounter>,
+}
+
+impl MetricsService {
+    /// Create a new `MetricsService` instance.
+    pub fn new() -> StdResult<Self> {
+        let registry = Registry::new();
+
+        // Runtime cycle metrics
+        let runtime_cycle_total_since_startup_counter = Box::new(Self::create_metric_counter_vec(
+            RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME,
+            RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_HELP,
+            &["state"],
+        )?);
+        registry.register(runtime_cycle_total_since_startup_counter.clone())?;
+
+        let runtime_cycle_success_since_startup_counter =
+            Box::new(Self::create_metric_counter_vec(
+                RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_NAME,
+                RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_HELP,
+                &["state"],
+            )?);
+        registry.register(runtime_cycle_success_since_startup_counter.clone())?;
+
+        // Signer registration metrics
+        let signer_registration_total_since_startup_counter =
+            Box::new(Self::create_metric_int_counter(
+                SIGNER_REGISTRATION_TOTAL_SINCE_STARTUP_METRIC_NAME,
+                SIGNER_REGISTRATION_TOTAL_SINCE_STARTUP_METRIC_HELP,
+            )?);
+        registry.register(signer_registration_total_since_startup_counter.clone())?;
+
+        let signer_registration_success_per_epoch_counter =
+            Box::new(Self::create_metric_counter_vec(
+                SIGNER_REGISTRATION_SUCCESS_PER_EPOCH_METRIC_NAME,
+                SIGNER_REGISTRATION_SUCCESS_PER_EPOCH_METRIC_HELP,
+                &["epoch"],
+            )?);
+        registry.register(signer_registration_success_per_epoch_counter.clone())?;
+
+        // Signature registration metrics
+        let signature_registration_received_since_startup_counter =
+            Box::new(Self::create_metric_int_counter(
+                SIGNATURE_REGISTRATION_RECEIVED_SINCE_STARTUP_METRIC_NAME,
+                SIGNATURE_REGISTRATION_RECEIVED_SINCE_STARTUP_METRIC_HELP,
+            )?);
+        registry.register(signature_registration_received_since_startup_counter.clone())?;
+
+        let signature_registration_rejected_since_startup_counter =
+            Box::new(Self::create_metric_int_counter(
+                SIGNATURE_REGISTRATION_REJECTED_SINCE_STARTUP_METRIC_NAME,
+                SIGNATURE_REGISTRATION_REJECTED_SINCE_STARTUP_METRIC_HELP,
+            )?);
+        registry.register(signature_registration_rejected_since_startup_counter.clone())?;
+
+        // Certification metrics
+        let certificate_created_since_startup_counter = Box::new(Self::create_metric_counter_vec(
+            CERTIFICATE_CREATED_SINCE_STARTUP_METRIC_NAME,
+            CERTIFICATE_CREATED_SINCE_STARTUP_METRIC_HELP,
+            &["signed_entity_type"],
+        )?);
+        registry.register(certificate_created_since_startup_counter.clone())?;
+
+        let open_message_expired_since_startup_counter = Box::new(Self::create_metric_counter_vec(
+            OPEN_MESSAGE_EXPIRED_SINCE_STARTUP_METRIC_NAME,
+            OPEN_MESSAGE_EXPIRED_SINCE_STARTUP_METRIC_HELP,
+            &["signed_entity_type"],
+        )?);
+        registry.register(open_message_expired_since_startup_counter.clone())?;
+
+        // Artifact metrics
+        let artifact_build_duration_seconds_histogram =
+            Box::new(Self::create_metric_histogram_vec(
+                ARTIFACT_BUILD_DURATION_SECONDS_METRIC_NAME,
+                ARTIFACT_BUILD_DURATION_SECONDS_METRIC_HELP,
+                &["signed_entity_type"],
+                ARTIFACT_BUILD_DURATION_BUCKETS.to_vec(),
+            )?);
+        registry.register(artifact_build_duration_seconds_histogram.clone())?;
+
+        let snapshot_size_last_bytes_gauge = Box::new(Self::create_metric_gauge(
+            SNAPSHOT_SIZE_LAST_BYTES_METRIC_NAME,
+            SNAPSHOT_SIZE_LAST_BYTES_METRIC_HELP,
+        )?);
+        registry.register(snapshot_size_last_bytes_gauge.clone())?;
+
+        // HTTP server metrics
+        let http_request_duration_seconds_histogram = Box::new(Self::create_metric_histogram_vec(
+            HTTP_REQUEST_DURATION_SECONDS_METRIC_NAME,
+            HTTP_REQUEST_DURATION_SECONDS_METRIC_HELP,
+            &["method", "route", "status"],
+            prometheus::DEFAULT_BUCKETS.to_vec(),
+        )?);
+        registry.register(http_request_duration_seconds_histogram.clone())?;
+
+        // Cardano transactions metrics
+        let cardano_transactions_pruned_since_startup_counter =
+            Box::new(Self::create_metric_int_counter(
+                CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_NAME,
+                CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_HELP,
+            )?);
+        registry.register(cardano_transactions_pruned_since_startup_counter.clone())?;
+
+        Ok(Self {
+            registry,
+            runtime_cycle_total_since_startup_counter,
+            runtime_cycle_success_since_startup_counter,
+            signer_registration_total_since_startup_counter,
+            signer_registration_success_per_epoch_counter,
+            signature_registration_received_since_startup_counter,
+            signature_registration_rejected_since_startup_counter,
+            certificate_created_since_startup_counter,
+            open_message_expired_since_startup_counter,
+            artifact_build_duration_seconds_histogram,
+            snapshot_size_last_bytes_gauge,
+            http_request_duration_seconds_histogram,
+            cardano_transactions_pruned_since_startup_counter,
+        })
+    }
+
+    fn create_metric_int_counter(name: &MetricName, help: &str) -> StdResult<IntCounter> {
+        let counter_opts = Opts::new(name, help);
+        let counter = IntCounter::with_opts(counter_opts)?;
+
+        Ok(counter)
+    }
+
+    fn create_metric_counter_vec(
+        name: &MetricName,
+        help: &str,
+        label_names: &[&str],
+    ) -> StdResult<CounterVec> {
+        let counter_opts = Opts::new(name, help);
+        let counter = CounterVec::new(counter_opts, label_names)?;
+
+        Ok(counter)
+    }
+
+    fn create_metric_gauge(name: &MetricName, help: &str) -> StdResult<Gauge> {
+        let gauge_opts = Opts::new(name, help);
+        let gauge = Gauge::with_opts(gauge_opts)?;
+
+        Ok(gauge)
+    }
+
+    fn create_metric_histogram_vec(
+        name: &MetricName,
+        help: &str,
+        label_names: &[&str],
+        buckets: Vec<f64>,
+    ) -> StdResult<HistogramVec> {
+        let histogram_opts = HistogramOpts::new(name, help).buckets(buckets);
+        let histogram = HistogramVec::new(histogram_opts, label_names)?;
+
+        Ok(histogram)
+    }
+
+    /// Export the metrics as a string with the Open Metrics standard format.
+    /// These metrics can be exposed on a HTTP server.
+    pub fn export_metrics(&self) -> StdResult<String> {
+        let mut buffer = vec![];
+        let encoder = TextEncoder::new();
+        let metric_families = self.registry.gather();
+        encoder.encode(&metric_families, &mut buffer)?;
+
+        Ok(String::from_utf8(buffer)?)
+    }
+
+    /// Increment the `runtime_cycle_total_since_startup` counter for the given state.
+    pub fn runtime_cycle_total_since_startup_counter_increment(&self, state: &str) {
+        debug!("MetricsService: incrementing 'runtime_cycle_total_since_startup' counter for state '{state}'");
+        self.runtime_cycle_total_since_startup_counter
+            .with_label_values(&[state])
+            .inc();
+    }
+
+    /// Get the `runtime_cycle_total_since_startup` counter for the given state.
+    pub fn runtime_cycle_total_since_startup_counter_get(&self, state: &str) -> CounterValue {
+        self.runtime_cycle_total_since_startup_counter
+            .with_label_values(&[state])
+            .get()
+            .round() as CounterValue
+    }
+
+    /// Increment the `runtime_cycle_success_since_startup` counter for the given state.
+    pub fn runtime_cycle_success_since_startup_counter_increment(&self, state: &str) {
+        debug!("MetricsService: incrementing 'runtime_cycle_success_since_startup' counter for state '{state}'");
+        self.runtime_cycle_success_since_startup_counter
+            .with_label_values(&[state])
+            .inc();
+    }
+
+    /// Get the `runtime_cycle_success_since_startup` counter for the given state.
+    pub fn runtime_cycle_success_since_startup_counter_get(&self, state: &str) -> CounterValue {
+        self.runtime_cycle_success_since_startup_counter
+            .with_label_values(&[state])
+            .get()
+            .round() as CounterValue
+    }
+
+    /// Increment the `signer_registration_total_since_startup` counter.
+    pub fn signer_registration_total_since_startup_counter_increment(&self) {
+        debug!("MetricsService: incrementing 'signer_registration_total_since_startup' counter");
+        self.signer_registration_total_since_startup_counter.inc();
+    }
+
+    /// Get the `signer_registration_total_since_startup` counter.
+    pub fn signer_registration_total_since_startup_counter_get(&self) -> CounterValue {
+        self.signer_registration_total_since_startup_counter.get() as CounterValue
+    }
+
+    /// Increment the `signer_registration_success_per_epoch` counter for the given epoch.
+    pub fn signer_registration_success_per_epoch_counter_increment(&self, epoch: Epoch) {
+        debug!("MetricsService: incrementing 'signer_registration_success_per_epoch' counter for epoch {epoch}");
+        self.signer_registration_success_per_epoch_counter
+            .with_label_values(&[&epoch.to_string()])
+            .inc();
+    }
+
+    /// Get the `signer_registration_success_per_epoch` counter for the given epoch.
+    pub fn signer_registration_success_per_epoch_counter_get(&self, epoch: Epoch) -> CounterValue {
+        self.signer_registration_success_per_epoch_counter
+            .with_label_values(&[&epoch.to_string()])
+            .get()
+            .round() as CounterValue
+    }
+
+    /// Increment the `signature_registration_received_since_startup` counter.
+    pub fn signature_registration_received_since_startup_counter_increment(&self) {
+        debug!(
+            "MetricsService: incrementing 'signature_registration_received_since_startup' counter"
+        );
+        self.signature_registration_received_since_startup_counter
+            .inc();
+    }
+
+    /// Get the `signature_registration_received_since_startup` counter.
+    pub fn signature_registration_received_since_startup_counter_get(&self) -> CounterValue {
+        self.signature_registration_received_since_startup_counter
+            .get() as CounterValue
+    }
+
+    /// Increment the `signature_registration_rejected_since_startup` counter.
+    pub fn signature_registration_rejected_since_startup_counter_increment(&self) {
+        debug!(
+            "MetricsService: incrementing 'signature_registration_rejected_since_startup' counter"
+        );
+        self.signature_registration_rejected_since_startup_counter
+            .inc();
+    }
+
+    /// Get the `signature_registration_rejected_since_startup` counter.
+    pub fn signature_registration_rejected_since_startup_counter_get(&self) -> CounterValue {
+        self.signature_registration_rejected_since_startup_counter
+            .get() as CounterValue
+    }
+
+    /// Increment the `certificate_created_since_startup` counter for the given signed entity type.
+    pub fn certificate_created_since_startup_counter_increment(
+        &self,
+        signed_entity_type: SignedEntityTypeDiscriminants,
+    ) {
+        debug!("MetricsService: incrementing 'certificate_created_since_startup' counter for '{signed_entity_type:?}'");
+        self.certificate_created_since_startup_counter
+            .with_label_values(&[signed_entity_type.as_ref()])
+            .inc();
+    }
+
+    /// Get the `certificate_created_since_startup` counter for the given signed entity type.
+    pub fn certificate_created_since_startup_counter_get(
+        &self,
+        signed_entity_type: SignedEntityTypeDiscriminants,
+    ) -> CounterValue {
+        self.certificate_created_since_startup_counter
+            .with_label_values(&[signed_entity_type.as_ref()])
+            .get()
+            .round() as CounterValue
+    }
+
+    /// Increment the `open_message_expired_since_startup` counter for the given signed entity type.
+    pub fn open_message_expired_since_startup_counter_increment(
+        &self,
+        signed_entity_type: SignedEntityTypeDiscriminants,
+    ) {
+        debug!("MetricsService: incrementing 'open_message_expired_since_startup' counter for '{signed_entity_type:?}'");
+        self.open_message_expired_since_startup_counter
+            .with_label_values(&[signed_entity_type.as_ref()])
+            .inc();
+    }
+
+    /// Get the `open_message_expired_since_startup` counter for the given signed entity type.
+    pub fn open_message_expired_since_startup_counter_get(
+        &self,
+        signed_entity_type: SignedEntityTypeDiscriminants,
+    ) -> CounterValue {
+        self.open_message_expired_since_startup_counter
+            .with_label_values(&[signed_entity_type.as_ref()])
+            .get()
+            .round() as CounterValue
+    }
+
+    /// Record a duration in the `artifact_build_duration_seconds` histogram for the given
+    /// signed entity type.
+    pub fn artifact_build_duration_seconds_observe(
+        &self,
+        signed_entity_type: SignedEntityTypeDiscriminants,
+        duration: Duration,
+    ) {
+        debug!("MetricsService: record {duration:?} in 'artifact_build_duration_seconds' histogram for '{signed_entity_type:?}'");
+        self.artifact_build_duration_seconds_histogram
+            .with_label_values(&[signed_entity_type.as_ref()])
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Get the number of durations recorded in the `artifact_build_duration_seconds` histogram
+    /// for the given signed entity type.
+    pub fn artifact_build_duration_seconds_count_get(
+        &self,
+        signed_entity_type: SignedEntityTypeDiscriminants,
+    ) -> u64 {
+        self.artifact_build_duration_seconds_histogram
+            .with_label_values(&[signed_entity_type.as_ref()])
+            .get_sample_count()
+    }
+
+    /// Set the `snapshot_size_last_bytes` gauge value.
+    pub fn snapshot_size_last_bytes_gauge_set(&self, size: u64) {
+        debug!("MetricsService: set 'snapshot_size_last_bytes' gauge value to {size}");
+        self.snapshot_size_last_bytes_gauge.set(size as f64);
+    }
+
+    /// Get the `snapshot_size_last_bytes` gauge value.
+    pub fn snapshot_size_last_bytes_gauge_get(&self) -> u64 {
+        self.snapshot_size_last_bytes_gauge.get().round() as u64
+    }
+
+    /// Record a duration in the `http_request_duration_seconds` histogram.
+    pub fn http_request_duration_seconds_observe(
+        &self,
+        method: &str,
+        route: &str,
+        status: u16,
+        duration: Duration,
+    ) {
+        self.http_request_duration_seconds_histogram
+            .with_label_values(&[method, route, &status.to_string()])
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Get the number of durations recorded in the `http_request_duration_seconds` histogram.
+    pub fn http_request_duration_seconds_count_get(
+        &self,
+        method: &str,
+        route: &str,
+        status: u16,
+    ) -> u64 {
+        self.http_request_duration_seconds_histogram
+            .with_label_values(&[method, route, &status.to_string()])
+            .get_sample_count()
+    }
+
+    /// Increment the `cardano_transactions_pruned_since_startup` counter by the given value.
+    pub fn cardano_transactions_pruned_since_startup_counter_increment_by(&self, value: u64) {
+        debug!("MetricsService: incrementing 'cardano_transactions_pruned_since_startup' counter by {value}");
+        self.cardano_transactions_pruned_since_startup_counter
+            .inc_by(value);
+    }
+
+    /// Get the `cardano_transactions_pruned_since_startup` counter.
+    pub fn cardano_transactions_pruned_since_startup_counter_get(&self) -> u64 {
+        self.cardano_transactions_pruned_since_startup_counter.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus_parse::Value;
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn parse_metrics(raw_metrics: &str) -> StdResult<BTreeMap<String, Value>> {
+        Ok(
+            prometheus_parse::Scrape::parse(raw_metrics.lines().map(|s| Ok(s.to_owned())))?
+                .samples
+                .into_iter()
+                .map(|s| (s.metric, s.value))
+                .collect::<BTreeMap<_, _>>(),
+        )
+    }
+
+    #[test]
+    fn test_export_metrics() {
+        let metrics_service = MetricsService::new().unwrap();
+        metrics_service.runtime_cycle_total_since_startup_counter_increment("idle");
+        metrics_service.certificate_created_since_startup_counter_increment(
+            SignedEntityTypeDiscriminants::MithrilStakeDistribution,
+        );
+        let exported_metrics = metrics_service.export_metrics().unwrap();
+
+        let parsed_metrics = parse_metrics(&exported_metrics).unwrap();
+
+        assert_eq!(
+            Some(&Value::Counter(1.0)),
+            parsed_metrics.get(RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME)
+        );
+        assert_eq!(
+            Some(&Value::Counter(1.0)),
+            parsed_metrics.get(CERTIFICATE_CREATED_SINCE_STARTUP_METRIC_NAME)
+        );
+        assert_eq!(
+            Some(&Value::Counter(0.0)),
+            parsed_metrics.get(SIGNER_REGISTRATION_TOTAL_SINCE_STARTUP_METRIC_NAME)
+        );
+        assert_eq!(
+            Some(&Value::Counter(0.0)),
+            parsed_metrics.get(SIGNATURE_REGISTRATION_RECEIVED_SINCE_STARTUP_METRIC_NAME)
+        );
+        assert_eq!(
+            Some(&Value::Counter(0.0)),
+            parsed_metrics.get(SIGNATURE_REGISTRATION_REJECTED_SINCE_STARTUP_METRIC_NAME)
+        );
+        assert_eq!(
+            Some(&Value::Gauge(0.0)),
+            parsed_metrics.get(SNAPSHOT_SIZE_LAST_BYTES_METRIC_NAME)
+        );
+    }
+
+    #[test]
+    fn test_runtime_cycle_counters_increment_per_state() {
+        let metrics_service = MetricsService::new().unwrap();
+        assert_eq!(
+            0,
+            metrics_service.runtime_cycle_total_since_startup_counter_get("idle")
+        );
+
+        metrics_service.runtime_cycle_total_since_startup_counter_increment("idle");
+        metrics_service.runtime_cycle_total_since_startup_counter_increment("ready");
+        metrics_service.runtime_cycle_success_since_startup_counter_increment("ready");
+
+        assert_eq!(
+            1,
+            metrics_service.runtime_cycle_total_since_startup_counter_get("idle")
+        );
+        assert_eq!(
+            1,
+            metrics_service.runtime_cycle_total_since_startup_counter_get("ready")
+        );
+        assert_eq!(
+            0,
+            metrics_service.runtime_cycle_success_since_startup_counter_get("idle")
+        );
+        assert_eq!(
+            1,
+            metrics_service.runtime_cycle_success_since_startup_counter_get("ready")
+        );
+    }
+
+    #[test]
+    fn test_signer_registration_counters_increment() {
+        let metrics_service = MetricsService::new().unwrap();
+
+        metrics_service.signer_registration_total_since_startup_counter_increment();
+        metrics_service.signer_registration_total_since_startup_counter_increment();
+        metrics_service.signer_registration_success_per_epoch_counter_increment(Epoch(4));
+
+        assert_eq!(
+            2,
+            metrics_service.signer_registration_total_since_startup_counter_get()
+        );
+        assert_eq!(
+            1,
+            metrics_service.signer_registration_success_per_epoch_counter_get(Epoch(4))
+        );
+        assert_eq!(
+            0,
+            metrics_service.signer_registration_success_per_epoch_counter_get(Epoch(5))
+        );
+    }
+
+    #[test]
+    fn test_signature_registration_counters_increment() {
+        let metrics_service = MetricsService::new().unwrap();
+
+        metrics_service.signature_registration_received_since_startup_counter_increment();
+        metrics_service.signature_registration_received_since_startup_counter_increment();
+        metrics_service.signature_registration_rejected_since_startup_counter_increment();
+
+        assert_eq!(
+            2,
+            metrics_service.signature_registration_received_since_startup_counter_get()
+        );
+        assert_eq!(
+            1,
+            metrics_service.signature_registration_rejected_since_startup_counter_get()
+        );
+    }
+
+    #[test]
+    fn test_certification_counters_increment_per_signed_entity_type() {
+        let metrics_service = MetricsService::new().unwrap();
+
+        metrics_service.certificate_created_since_startup_counter_increment(
+            SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+        );
+        metrics_service.open_message_expired_since_startup_counter_increment(
+            SignedEntityTypeDiscriminants::CardanoTransactions,
+        );
+
+        assert_eq!(
+            1,
+            metrics_service.certificate_created_since_startup_counter_get(
+                SignedEntityTypeDiscriminants::CardanoImmutableFilesFull
+            )
+        );
+        assert_eq!(
+            0,
+            metrics_service.certificate_created_since_startup_counter_get(
+                SignedEntityTypeDiscriminants::CardanoTransactions
+            )
+        );
+        assert_eq!(
+            1,
+            metrics_service.open_message_expired_since_startup_counter_get(
+                SignedEntityTypeDiscriminants::CardanoTransactions
+            )
+        );
+    }
+
+    #[test]
+    fn test_artifact_metrics_record() {
+        let metrics_service = MetricsService::new().unwrap();
+
+        metrics_service.artifact_build_duration_seconds_observe(
+            SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+            Duration::from_secs(42),
+        );
+        metrics_service.snapshot_size_last_bytes_gauge_set(123_456);
+
+        assert_eq!(
+            1,
+            metrics_service.artifact_build_duration_seconds_count_get(
+                SignedEntityTypeDiscriminants::CardanoImmutableFilesFull
+            )
+        );
+        assert_eq!(
+            123_456,
+            metrics_service.snapshot_size_last_bytes_gauge_get()
+        );
+    }
+
+    #[test]
+    fn test_http_request_duration_record() {
+        let metrics_service = MetricsService::new().unwrap();
+
+        metrics_service.http_request_duration_seconds_observe(
+            "GET",
+            "/certificate/{hash}",
+            200,
+            Duration::from_millis(12),
+        );
+
+        assert_eq!(
+            1,
+            metrics_service.http_request_duration_seconds_count_get(
+                "GET",
+                "/certificate/{hash}",
+                200
+            )
+        );
+        assert_eq!(
+            0,
+            metrics_service.http_request_duration_seconds_count_get(
+                "GET",
+                "/certificate/{hash}",
+                404
+            )
+        );
+    }
+
+    #[test]
+    fn test_cardano_transactions_pruned_counter_increment_by() {
+        let metrics_service = MetricsService::new().unwrap();
+
+        metrics_service.cardano_transactions_pruned_since_startup_counter_increment_by(15);
+        metrics_service.cardano_transactions_pruned_since_startup_counter_increment_by(10);
+
+        assert_eq!(
+            25,
+            metrics_service.cardano_transactions_pruned_since_startup_counter_get()
+        );
+    }
+}
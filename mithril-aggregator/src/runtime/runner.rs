@@ -2,11 +2,11 @@ use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use slog_scope::{debug, warn};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use mithril_common::entities::{
     Certificate, CertificatePending, Epoch, ProtocolMessage, ProtocolMessagePartKey,
-    SignedEntityType, Signer, TimePoint,
+    SignedEntityType, SignedEntityTypeDiscriminants, Signer, TimePoint,
 };
 use mithril_common::{CardanoNetwork, StdResult};
 use mithril_persistence::store::StakeStorer;
@@ -301,6 +301,11 @@ impl AggregatorRunnerTrait for AggregatorRunner {
             "RUNNER: marked expired open messages: {:#?}",
             expired_open_message
         );
+        if expired_open_message.is_some() {
+            self.dependencies
+                .metrics_service
+                .open_message_expired_since_startup_counter_increment(signed_entity_type.into());
+        }
 
         Ok(expired_open_message)
     }
@@ -371,7 +376,7 @@ impl AggregatorRunnerTrait for AggregatorRunner {
     ) -> StdResult<Option<Certificate>> {
         debug!("RUNNER: create_certificate");
 
-        self.dependencies
+        let certificate = self.dependencies
             .certifier_service
             .create_certificate(signed_entity_type)
             .await
@@ -379,7 +384,14 @@ impl AggregatorRunnerTrait for AggregatorRunner {
                 format!(
                     "CertifierService can not create certificate for signed_entity_type: '{signed_entity_type}'"
                 )
-            })
+            })?;
+        if certificate.is_some() {
+            self.dependencies
+                .metrics_service
+                .certificate_created_since_startup_counter_increment(signed_entity_type.into());
+        }
+
+        Ok(certificate)
     }
 
     async fn create_artifact(
@@ -388,6 +400,7 @@ impl AggregatorRunnerTrait for AggregatorRunner {
         certificate: &Certificate,
     ) -> StdResult<()> {
         debug!("RUNNER: create artifact");
+        let started_at = Instant::now();
         self.dependencies
             .signed_entity_service
             .create_artifact(signed_entity_type.to_owned(), certificate)
@@ -399,6 +412,36 @@ impl AggregatorRunnerTrait for AggregatorRunner {
                 )
             })?;
 
+        let signed_entity_type_discriminant =
+            SignedEntityTypeDiscriminants::from(signed_entity_type);
+        self.dependencies
+            .metrics_service
+            .artifact_build_duration_seconds_observe(
+                signed_entity_type_discriminant,
+                started_at.elapsed(),
+            );
+        if signed_entity_type_discriminant
+            == SignedEntityTypeDiscriminants::CardanoImmutableFilesFull
+        {
+            match self
+                .dependencies
+                .signed_entity_service
+                .get_last_signed_snapshots(1)
+                .await
+            {
+                Ok(snapshots) => {
+                    if let Some(snapshot) = snapshots.first() {
+                        self.dependencies
+                            .metrics_service
+                            .snapshot_size_last_bytes_gauge_set(snapshot.artifact.size);
+                    }
+                }
+                Err(error) => {
+                    warn!("RUNNER: could not retrieve the last snapshot to record its size"; "error" => ?error);
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -481,7 +524,9 @@ pub mod tests {
         entities::OpenMessage,
         initialize_dependencies,
         runtime::{AggregatorRunner, AggregatorRunnerTrait},
-        services::{MithrilStakeDistributionService, MockCertifierService},
+        services::{
+            MithrilStakeDistributionService, MockCertifierService, MockSignedEntityService,
+        },
         DependencyContainer, MithrilSignerRegisterer, SignerRegistrationRound,
     };
     use async_trait::async_trait;
@@ -490,8 +535,8 @@ pub mod tests {
         chain_observer::FakeObserver,
         digesters::DumbImmutableFileObserver,
         entities::{
-            CertificatePending, ProtocolMessage, SignedEntityType, Signer, StakeDistribution,
-            TimePoint,
+            CertificatePending, Epoch, ProtocolMessage, SignedEntity, SignedEntityType,
+            SignedEntityTypeDiscriminants, Signer, Snapshot, StakeDistribution, TimePoint,
         },
         signable_builder::SignableBuilderService,
         test_utils::{fake_data, MithrilFixtureBuilder},
@@ -763,6 +808,79 @@ pub mod tests {
             .expect("mark_open_message_if_expired should not fail");
 
         assert_eq!(Some(open_message_expected), open_message_expired);
+        assert_eq!(
+            1,
+            runner
+                .dependencies
+                .metrics_service
+                .open_message_expired_since_startup_counter_get(
+                    pending_certificate.signed_entity_type.into()
+                )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_certificate_increments_certificate_created_metric() {
+        let signed_entity_type = SignedEntityType::MithrilStakeDistribution(Epoch(5));
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_create_certificate()
+            .return_once(|_| Ok(Some(fake_data::certificate("hash".to_string()))));
+        let mut deps = initialize_dependencies().await;
+        deps.certifier_service = Arc::new(mock_certifier_service);
+        let runner = AggregatorRunner::new(Arc::new(deps));
+
+        runner
+            .create_certificate(&signed_entity_type)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            1,
+            runner
+                .dependencies
+                .metrics_service
+                .certificate_created_since_startup_counter_get(
+                    SignedEntityTypeDiscriminants::MithrilStakeDistribution
+                )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_artifact_records_build_duration_and_snapshot_size() {
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(fake_data::beacon());
+        let mut snapshot = SignedEntity::<Snapshot>::dummy();
+        snapshot.artifact.size = 123_456;
+        let mut mock_signed_entity_service = MockSignedEntityService::new();
+        mock_signed_entity_service
+            .expect_create_artifact()
+            .return_once(|_, _| Ok(()));
+        mock_signed_entity_service
+            .expect_get_last_signed_snapshots()
+            .return_once(|_| Ok(vec![snapshot]));
+        let mut deps = initialize_dependencies().await;
+        deps.signed_entity_service = Arc::new(mock_signed_entity_service);
+        let runner = AggregatorRunner::new(Arc::new(deps));
+
+        runner
+            .create_artifact(
+                &signed_entity_type,
+                &fake_data::certificate("hash".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let metrics_service = &runner.dependencies.metrics_service;
+        assert_eq!(
+            1,
+            metrics_service.artifact_build_duration_seconds_count_get(
+                SignedEntityTypeDiscriminants::CardanoImmutableFilesFull
+            )
+        );
+        assert_eq!(
+            123_456,
+            metrics_service.snapshot_size_last_bytes_gauge_get()
+        );
     }
 
     #[tokio::test]
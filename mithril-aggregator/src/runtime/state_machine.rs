@@ -1,7 +1,8 @@
 use crate::{
+    dependency_injection::RuntimeStateWrapper,
     entities::OpenMessage,
     runtime::{AggregatorRunnerTrait, RuntimeError},
-    AggregatorConfig,
+    AggregatorConfig, MetricsService,
 };
 
 use anyhow::Context;
@@ -65,6 +66,12 @@ pub struct AggregatorRuntime {
 
     /// specific runner for this state machine
     runner: Arc<dyn AggregatorRunnerTrait>,
+
+    /// Metrics service
+    metrics_service: Arc<MetricsService>,
+
+    /// State shared with the other components of the aggregator
+    runtime_state: RuntimeStateWrapper,
 }
 
 impl AggregatorRuntime {
@@ -73,6 +80,8 @@ impl AggregatorRuntime {
         aggregator_config: AggregatorConfig,
         init_state: Option<AggregatorState>,
         runner: Arc<dyn AggregatorRunnerTrait>,
+        metrics_service: Arc<MetricsService>,
+        runtime_state: RuntimeStateWrapper,
     ) -> Result<Self, RuntimeError> {
         info!("initializing runtime");
 
@@ -86,11 +95,16 @@ impl AggregatorRuntime {
             })
         };
 
-        Ok(Self {
+        let runtime = Self {
             config: aggregator_config,
             state,
             runner,
-        })
+            metrics_service,
+            runtime_state,
+        };
+        runtime.share_state().await;
+
+        Ok(runtime)
     }
 
     /// Return the actual state of the state machine.
@@ -102,6 +116,11 @@ impl AggregatorRuntime {
         }
     }
 
+    /// Publish the actual state of the state machine to the other components of the aggregator.
+    async fn share_state(&self) {
+        *self.runtime_state.write().await = self.get_state();
+    }
+
     /// Launches an infinite loop ticking the state machine.
     pub async fn run(&mut self) -> Result<(), RuntimeError> {
         info!("STATE MACHINE: launching");
@@ -145,6 +164,7 @@ impl AggregatorRuntime {
                         self.state = AggregatorState::Idle(IdleState {
                             current_time_point: None,
                         });
+                        self.share_state().await;
                     }
                 }
             }
@@ -162,6 +182,10 @@ impl AggregatorRuntime {
         info!("================================================================================");
         info!("STATE MACHINE: new cycle: {}", self.state);
 
+        let cycle_state = self.get_state();
+        self.metrics_service
+            .runtime_cycle_total_since_startup_counter_increment(&cycle_state);
+
         match self.state.clone() {
             AggregatorState::Idle(state) => {
                 let last_time_point = self.runner.get_time_point_from_chain().await.with_context(
@@ -264,6 +288,11 @@ impl AggregatorRuntime {
                 }
             }
         }
+
+        self.share_state().await;
+        self.metrics_service
+            .runtime_cycle_success_since_startup_counter_increment(&cycle_state);
+
         Ok(())
     }
 
@@ -405,6 +434,7 @@ mod tests {
     use anyhow::anyhow;
     use mockall::predicate;
     use std::time::Duration;
+    use tokio::sync::RwLock;
 
     use mithril_common::entities::{Epoch, SignedEntityType};
     use mithril_common::test_utils::fake_data;
@@ -420,6 +450,8 @@ mod tests {
             AggregatorConfig::new(Duration::from_millis(20), fake_data::network()),
             init_state,
             Arc::new(runner),
+            Arc::new(MetricsService::new().unwrap()),
+            Arc::new(RwLock::new(String::new())),
         )
         .await
         .unwrap()
@@ -801,4 +833,90 @@ mod tests {
 
         assert_eq!("idle".to_string(), runtime.get_state());
     }
+
+    #[tokio::test]
+    async fn cycle_shares_the_new_state_of_the_state_machine() {
+        let mut runner = MockAggregatorRunner::new();
+        let time_point = TimePoint::dummy();
+        let new_time_point = TimePoint {
+            epoch: time_point.epoch + 1,
+            ..time_point.clone()
+        };
+        runner
+            .expect_get_time_point_from_chain()
+            .once()
+            .returning(move || Ok(new_time_point.clone()));
+        let mut runtime = init_runtime(
+            Some(AggregatorState::Ready(ReadyState {
+                current_time_point: time_point,
+            })),
+            runner,
+        )
+        .await;
+        assert_eq!("ready".to_string(), *runtime.runtime_state.read().await);
+
+        runtime.cycle().await.unwrap();
+
+        assert_eq!("idle".to_string(), *runtime.runtime_state.read().await);
+    }
+
+    #[tokio::test]
+    async fn cycle_increments_runtime_cycle_metrics_of_the_cycle_state() {
+        let mut runner = MockAggregatorRunner::new();
+        runner
+            .expect_get_time_point_from_chain()
+            .once()
+            .returning(|| Ok(TimePoint::dummy()));
+        runner
+            .expect_get_current_non_certified_open_message()
+            .once()
+            .returning(|_| Ok(None));
+        let mut runtime = init_runtime(
+            Some(AggregatorState::Ready(ReadyState {
+                current_time_point: TimePoint::dummy(),
+            })),
+            runner,
+        )
+        .await;
+
+        runtime.cycle().await.unwrap();
+
+        let metrics_service = &runtime.metrics_service;
+        assert_eq!(
+            1,
+            metrics_service.runtime_cycle_total_since_startup_counter_get("ready")
+        );
+        assert_eq!(
+            1,
+            metrics_service.runtime_cycle_success_since_startup_counter_get("ready")
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_cycle_does_not_increment_runtime_cycle_success_metric() {
+        let mut runner = MockAggregatorRunner::new();
+        runner
+            .expect_get_time_point_from_chain()
+            .once()
+            .returning(|| Err(anyhow!("ERROR")));
+        let mut runtime = init_runtime(
+            Some(AggregatorState::Ready(ReadyState {
+                current_time_point: TimePoint::dummy(),
+            })),
+            runner,
+        )
+        .await;
+
+        runtime.cycle().await.unwrap_err();
+
+        let metrics_service = &runtime.metrics_service;
+        assert_eq!(
+            1,
+            metrics_service.runtime_cycle_total_since_startup_counter_get("ready")
+        );
+        assert_eq!(
+            0,
+            metrics_service.runtime_cycle_success_since_startup_counter_get("ready")
+        );
+    }
 }
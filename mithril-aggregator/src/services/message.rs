@@ -8,9 +8,11 @@ use thiserror::Error;
 use mithril_common::{
     entities::SignedEntityTypeDiscriminants,
     messages::{
+        CardanoStakeDistributionListMessage, CardanoStakeDistributionMessage,
         CardanoTransactionSnapshotListMessage, CardanoTransactionSnapshotMessage,
-        CertificateListMessage, CertificateMessage, MithrilStakeDistributionListMessage,
-        MithrilStakeDistributionMessage, SnapshotListMessage, SnapshotMessage,
+        CertificateListMessage, CertificateMessage, ListQueryParameters,
+        MithrilStakeDistributionListMessage, MithrilStakeDistributionMessage, SnapshotListMessage,
+        SnapshotMessage,
     },
     StdResult,
 };
@@ -37,9 +39,11 @@ pub trait MessageService: Sync + Send {
         certificate_hash: &str,
     ) -> StdResult<Option<CertificateMessage>>;
 
-    /// Return the message representation of the last N certificates
-    async fn get_certificate_list_message(&self, limit: usize)
-        -> StdResult<CertificateListMessage>;
+    /// Return the message representation of the certificates matching the given list parameters
+    async fn get_certificate_list_message(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> StdResult<CertificateListMessage>;
 
     /// Return the information regarding the given snapshot
     async fn get_snapshot_message(
@@ -47,9 +51,11 @@ pub trait MessageService: Sync + Send {
         signed_entity_id: &str,
     ) -> StdResult<Option<SnapshotMessage>>;
 
-    /// Return the list of the last signed snapshots. The limit of the list is
-    /// passed as argument.
-    async fn get_snapshot_list_message(&self, limit: usize) -> StdResult<SnapshotListMessage>;
+    /// Return the list of the signed snapshots matching the given list parameters.
+    async fn get_snapshot_list_message(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> StdResult<SnapshotListMessage>;
 
     /// Return the information regarding the MSD for the given identifier.
     async fn get_mithril_stake_distribution_message(
@@ -57,10 +63,10 @@ pub trait MessageService: Sync + Send {
         signed_entity_id: &str,
     ) -> StdResult<Option<MithrilStakeDistributionMessage>>;
 
-    /// Return the list of the last Mithril stake distributions message
+    /// Return the list of the Mithril stake distributions message matching the given list parameters
     async fn get_mithril_stake_distribution_list_message(
         &self,
-        limit: usize,
+        parameters: &ListQueryParameters,
     ) -> StdResult<MithrilStakeDistributionListMessage>;
 
     /// Return the information regarding the Cardano transactions set for the given identifier.
@@ -69,11 +75,23 @@ pub trait MessageService: Sync + Send {
         signed_entity_id: &str,
     ) -> StdResult<Option<CardanoTransactionSnapshotMessage>>;
 
-    /// Return the list of the last Cardano transactions set message
+    /// Return the list of the Cardano transactions set message matching the given list parameters
     async fn get_cardano_transaction_list_message(
         &self,
-        limit: usize,
+        parameters: &ListQueryParameters,
     ) -> StdResult<CardanoTransactionSnapshotListMessage>;
+
+    /// Return the information regarding the Cardano stake distribution for the given identifier.
+    async fn get_cardano_stake_distribution_message(
+        &self,
+        signed_entity_id: &str,
+    ) -> StdResult<Option<CardanoStakeDistributionMessage>>;
+
+    /// Return the list of the Cardano stake distributions message matching the given list parameters
+    async fn get_cardano_stake_distribution_list_message(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> StdResult<CardanoStakeDistributionListMessage>;
 }
 
 /// Implementation of the [MessageService]
@@ -108,10 +126,10 @@ impl MessageService for MithrilMessageService {
 
     async fn get_certificate_list_message(
         &self,
-        limit: usize,
+        parameters: &ListQueryParameters,
     ) -> StdResult<CertificateListMessage> {
         self.certificate_repository
-            .get_latest_certificates(limit)
+            .get_certificates_by_list_parameters(parameters)
             .await
     }
 
@@ -127,11 +145,14 @@ impl MessageService for MithrilMessageService {
         signed_entity.map(|s| s.try_into()).transpose()
     }
 
-    async fn get_snapshot_list_message(&self, limit: usize) -> StdResult<SnapshotListMessage> {
+    async fn get_snapshot_list_message(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> StdResult<SnapshotListMessage> {
         let signed_entity_type_id = SignedEntityTypeDiscriminants::CardanoImmutableFilesFull;
         let entities = self
             .signed_entity_storer
-            .get_last_signed_entities_by_type(&signed_entity_type_id, limit)
+            .get_signed_entities_by_type_and_list_parameters(&signed_entity_type_id, parameters)
             .await?;
 
         entities.into_iter().map(|i| i.try_into()).collect()
@@ -151,12 +172,12 @@ impl MessageService for MithrilMessageService {
 
     async fn get_mithril_stake_distribution_list_message(
         &self,
-        limit: usize,
+        parameters: &ListQueryParameters,
     ) -> StdResult<MithrilStakeDistributionListMessage> {
         let signed_entity_type_id = SignedEntityTypeDiscriminants::MithrilStakeDistribution;
         let entities = self
             .signed_entity_storer
-            .get_last_signed_entities_by_type(&signed_entity_type_id, limit)
+            .get_signed_entities_by_type_and_list_parameters(&signed_entity_type_id, parameters)
             .await?;
 
         entities.into_iter().map(|i| i.try_into()).collect()
@@ -176,12 +197,37 @@ impl MessageService for MithrilMessageService {
 
     async fn get_cardano_transaction_list_message(
         &self,
-        limit: usize,
+        parameters: &ListQueryParameters,
     ) -> StdResult<CardanoTransactionSnapshotListMessage> {
         let signed_entity_type_id = SignedEntityTypeDiscriminants::CardanoTransactions;
         let entities = self
             .signed_entity_storer
-            .get_last_signed_entities_by_type(&signed_entity_type_id, limit)
+            .get_signed_entities_by_type_and_list_parameters(&signed_entity_type_id, parameters)
+            .await?;
+
+        entities.into_iter().map(|i| i.try_into()).collect()
+    }
+
+    async fn get_cardano_stake_distribution_message(
+        &self,
+        signed_entity_id: &str,
+    ) -> StdResult<Option<CardanoStakeDistributionMessage>> {
+        let signed_entity = self
+            .signed_entity_storer
+            .get_signed_entity(signed_entity_id)
+            .await?;
+
+        signed_entity.map(|v| v.try_into()).transpose()
+    }
+
+    async fn get_cardano_stake_distribution_list_message(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> StdResult<CardanoStakeDistributionListMessage> {
+        let signed_entity_type_id = SignedEntityTypeDiscriminants::CardanoStakeDistribution;
+        let entities = self
+            .signed_entity_storer
+            .get_signed_entities_by_type_and_list_parameters(&signed_entity_type_id, parameters)
             .await?;
 
         entities.into_iter().map(|i| i.try_into()).collect()
@@ -193,16 +239,17 @@ mod tests {
     use std::sync::Arc;
 
     use mithril_common::entities::{
-        CardanoTransactionsSnapshot, Certificate, Epoch, MithrilStakeDistribution, SignedEntity,
-        SignedEntityType, Snapshot,
+        CardanoStakeDistribution, CardanoTransactionsSnapshot, Certificate, Epoch,
+        MithrilStakeDistribution, SignedEntity, SignedEntityType, Snapshot,
     };
-    use mithril_common::messages::ToMessageAdapter;
+    use mithril_common::messages::{ListQueryParameters, ToMessageAdapter};
     use mithril_common::test_utils::MithrilFixtureBuilder;
 
     use crate::database::record::SignedEntityRecord;
     use crate::database::repository::MockSignedEntityStorer;
     use crate::dependency_injection::DependenciesBuilder;
     use crate::message_adapters::{
+        ToCardanoStakeDistributionListMessageAdapter, ToCardanoStakeDistributionMessageAdapter,
         ToCardanoTransactionListMessageAdapter, ToCardanoTransactionMessageAdapter,
         ToMithrilStakeDistributionListMessageAdapter, ToMithrilStakeDistributionMessageAdapter,
         ToSnapshotListMessageAdapter, ToSnapshotMessageAdapter,
@@ -267,7 +314,10 @@ mod tests {
             .unwrap();
 
         // test
-        let certificate_messages = service.get_certificate_list_message(5).await.unwrap();
+        let certificate_messages = service
+            .get_certificate_list_message(&ListQueryParameters::default().with_limit(5))
+            .await
+            .unwrap();
 
         assert_eq!(2, certificate_messages.len());
         assert_eq!(last_certificate_hash, certificate_messages[0].hash);
@@ -332,12 +382,15 @@ mod tests {
         let mut dep_builder = DependenciesBuilder::new(configuration);
         let mut storer = MockSignedEntityStorer::new();
         storer
-            .expect_get_last_signed_entities_by_type()
+            .expect_get_signed_entities_by_type_and_list_parameters()
             .return_once(|_, _| Ok(records))
             .once();
         dep_builder.signed_entity_storer = Some(Arc::new(storer));
         let service = dep_builder.get_message_service().await.unwrap();
-        let response = service.get_snapshot_list_message(3).await.unwrap();
+        let response = service
+            .get_snapshot_list_message(&ListQueryParameters::default().with_limit(3))
+            .await
+            .unwrap();
 
         assert_eq!(message, response);
     }
@@ -405,13 +458,15 @@ mod tests {
         let mut dep_builder = DependenciesBuilder::new(configuration);
         let mut storer = MockSignedEntityStorer::new();
         storer
-            .expect_get_last_signed_entities_by_type()
+            .expect_get_signed_entities_by_type_and_list_parameters()
             .return_once(|_, _| Ok(records))
             .once();
         dep_builder.signed_entity_storer = Some(Arc::new(storer));
         let service = dep_builder.get_message_service().await.unwrap();
         let response = service
-            .get_mithril_stake_distribution_list_message(10)
+            .get_mithril_stake_distribution_list_message(
+                &ListQueryParameters::default().with_limit(10),
+            )
             .await
             .unwrap();
 
@@ -485,13 +540,91 @@ mod tests {
         let mut dep_builder = DependenciesBuilder::new(configuration);
         let mut storer = MockSignedEntityStorer::new();
         storer
-            .expect_get_last_signed_entities_by_type()
+            .expect_get_signed_entities_by_type_and_list_parameters()
+            .return_once(|_, _| Ok(records))
+            .once();
+        dep_builder.signed_entity_storer = Some(Arc::new(storer));
+        let service = dep_builder.get_message_service().await.unwrap();
+        let response = service
+            .get_cardano_transaction_list_message(&ListQueryParameters::default().with_limit(10))
+            .await
+            .unwrap();
+
+        assert_eq!(message, response);
+    }
+
+    #[tokio::test]
+    async fn get_cardano_stake_distribution() {
+        let entity = SignedEntity::<CardanoStakeDistribution>::dummy();
+        let record = SignedEntityRecord {
+            signed_entity_id: entity.signed_entity_id.clone(),
+            signed_entity_type: SignedEntityType::CardanoStakeDistribution(entity.artifact.epoch),
+            certificate_id: entity.certificate_id.clone(),
+            artifact: serde_json::to_string(&entity.artifact).unwrap(),
+            created_at: entity.created_at,
+        };
+        let message = ToCardanoStakeDistributionMessageAdapter::adapt(entity);
+        let configuration = Configuration::new_sample();
+        let mut dep_builder = DependenciesBuilder::new(configuration);
+        let mut storer = MockSignedEntityStorer::new();
+        storer
+            .expect_get_signed_entity()
+            .return_once(|_| Ok(Some(record)))
+            .once();
+        dep_builder.signed_entity_storer = Some(Arc::new(storer));
+        let service = dep_builder.get_message_service().await.unwrap();
+        let response = service
+            .get_cardano_stake_distribution_message("whatever")
+            .await
+            .unwrap()
+            .expect("A CardanoStakeDistributionMessage was expected.");
+
+        assert_eq!(message, response);
+    }
+
+    #[tokio::test]
+    async fn get_cardano_stake_distribution_not_exist() {
+        let configuration = Configuration::new_sample();
+        let mut dep_builder = DependenciesBuilder::new(configuration);
+        let mut storer = MockSignedEntityStorer::new();
+        storer
+            .expect_get_signed_entity()
+            .return_once(|_| Ok(None))
+            .once();
+        dep_builder.signed_entity_storer = Some(Arc::new(storer));
+        let service = dep_builder.get_message_service().await.unwrap();
+        let response = service
+            .get_cardano_stake_distribution_message("whatever")
+            .await
+            .unwrap();
+
+        assert!(response.is_none());
+    }
+
+    #[tokio::test]
+    async fn get_cardano_stake_distribution_list_message() {
+        let entity = SignedEntity::<CardanoStakeDistribution>::dummy();
+        let records = vec![SignedEntityRecord {
+            signed_entity_id: entity.signed_entity_id.clone(),
+            signed_entity_type: SignedEntityType::CardanoStakeDistribution(entity.artifact.epoch),
+            certificate_id: entity.certificate_id.clone(),
+            artifact: serde_json::to_string(&entity.artifact).unwrap(),
+            created_at: entity.created_at,
+        }];
+        let message = ToCardanoStakeDistributionListMessageAdapter::adapt(vec![entity]);
+        let configuration = Configuration::new_sample();
+        let mut dep_builder = DependenciesBuilder::new(configuration);
+        let mut storer = MockSignedEntityStorer::new();
+        storer
+            .expect_get_signed_entities_by_type_and_list_parameters()
             .return_once(|_, _| Ok(records))
             .once();
         dep_builder.signed_entity_storer = Some(Arc::new(storer));
         let service = dep_builder.get_message_service().await.unwrap();
         let response = service
-            .get_cardano_transaction_list_message(10)
+            .get_cardano_stake_distribution_list_message(
+                &ListQueryParameters::default().with_limit(10),
+            )
             .await
             .unwrap();
 
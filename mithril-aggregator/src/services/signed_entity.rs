@@ -10,8 +10,9 @@ use std::sync::Arc;
 
 use mithril_common::{
     entities::{
-        CardanoDbBeacon, CardanoTransactionsSnapshot, Certificate, Epoch, MithrilStakeDistribution,
-        SignedEntity, SignedEntityType, SignedEntityTypeDiscriminants, Snapshot,
+        CardanoDbBeacon, CardanoStakeDistribution, CardanoTransactionsSnapshot, Certificate, Epoch,
+        MithrilStakeDistribution, SignedEntity, SignedEntityType, SignedEntityTypeDiscriminants,
+        Snapshot,
     },
     signable_builder::Artifact,
     StdResult,
@@ -65,6 +66,19 @@ pub trait SignedEntityService: Send + Sync {
         &self,
         signed_entity_id: &str,
     ) -> StdResult<Option<SignedEntity<MithrilStakeDistribution>>>;
+
+    /// Return a list of signed Cardano stake distribution order by creation
+    /// date descending.
+    async fn get_last_signed_cardano_stake_distributions(
+        &self,
+        total: usize,
+    ) -> StdResult<Vec<SignedEntity<CardanoStakeDistribution>>>;
+
+    /// Return a signed Cardano stake distribution
+    async fn get_signed_cardano_stake_distribution_by_id(
+        &self,
+        signed_entity_id: &str,
+    ) -> StdResult<Option<SignedEntity<CardanoStakeDistribution>>>;
 }
 
 /// Mithril ArtifactBuilder Service
@@ -76,6 +90,8 @@ pub struct MithrilSignedEntityService {
         Arc<dyn ArtifactBuilder<CardanoDbBeacon, Snapshot>>,
     cardano_transactions_artifact_builder:
         Arc<dyn ArtifactBuilder<CardanoDbBeacon, CardanoTransactionsSnapshot>>,
+    cardano_stake_distribution_artifact_builder:
+        Arc<dyn ArtifactBuilder<Epoch, CardanoStakeDistribution>>,
 }
 
 impl MithrilSignedEntityService {
@@ -91,12 +107,16 @@ impl MithrilSignedEntityService {
         cardano_transactions_artifact_builder: Arc<
             dyn ArtifactBuilder<CardanoDbBeacon, CardanoTransactionsSnapshot>,
         >,
+        cardano_stake_distribution_artifact_builder: Arc<
+            dyn ArtifactBuilder<Epoch, CardanoStakeDistribution>,
+        >,
     ) -> Self {
         Self {
             signed_entity_storer,
             mithril_stake_distribution_artifact_builder,
             cardano_immutable_files_full_artifact_builder,
             cardano_transactions_artifact_builder,
+            cardano_stake_distribution_artifact_builder,
         }
     }
 
@@ -127,7 +147,16 @@ impl MithrilSignedEntityService {
                         )
                     })?,
             )),
-            SignedEntityType::CardanoStakeDistribution(_) => todo!(),
+            SignedEntityType::CardanoStakeDistribution(epoch) => Ok(Arc::new(
+                self.cardano_stake_distribution_artifact_builder
+                    .compute_artifact(epoch, certificate)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Signed Entity Service can not compute artifact for entity type: '{signed_entity_type}'"
+                        )
+                    })?,
+            )),
             SignedEntityType::CardanoTransactions(beacon) => Ok(Arc::new(
                 self.cardano_transactions_artifact_builder
                     .compute_artifact(beacon.clone(), certificate)
@@ -294,6 +323,45 @@ impl SignedEntityService for MithrilSignedEntityService {
 
         Ok(entity)
     }
+
+    async fn get_last_signed_cardano_stake_distributions(
+        &self,
+        total: usize,
+    ) -> StdResult<Vec<SignedEntity<CardanoStakeDistribution>>> {
+        let signed_entities_records = self
+            .get_last_signed_entities(
+                total,
+                &SignedEntityTypeDiscriminants::CardanoStakeDistribution,
+            )
+            .await?;
+        let mut signed_entities: Vec<SignedEntity<CardanoStakeDistribution>> = Vec::new();
+
+        for record in signed_entities_records {
+            signed_entities.push(record.try_into()?);
+        }
+
+        Ok(signed_entities)
+    }
+
+    async fn get_signed_cardano_stake_distribution_by_id(
+        &self,
+        signed_entity_id: &str,
+    ) -> StdResult<Option<SignedEntity<CardanoStakeDistribution>>> {
+        let entity: Option<SignedEntity<CardanoStakeDistribution>> = match self
+            .signed_entity_storer
+            .get_signed_entity(signed_entity_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Signed Entity Service can not get signed entity with id: '{signed_entity_id}'"
+                )
+            })? {
+            Some(entity) => Some(entity.try_into()?),
+            None => None,
+        };
+
+        Ok(entity)
+    }
 }
 
 #[cfg(test)]
@@ -338,6 +406,8 @@ mod tests {
             MockArtifactBuilder<CardanoDbBeacon, Snapshot>,
         mock_cardano_transactions_artifact_builder:
             MockArtifactBuilder<CardanoDbBeacon, CardanoTransactionsSnapshot>,
+        mock_cardano_stake_distribution_artifact_builder:
+            MockArtifactBuilder<Epoch, CardanoStakeDistribution>,
     }
 
     impl MockDependencyInjector {
@@ -356,6 +426,10 @@ mod tests {
                     CardanoDbBeacon,
                     CardanoTransactionsSnapshot,
                 >::new(),
+                mock_cardano_stake_distribution_artifact_builder: MockArtifactBuilder::<
+                    Epoch,
+                    CardanoStakeDistribution,
+                >::new(),
             }
         }
 
@@ -365,6 +439,7 @@ mod tests {
                 Arc::new(self.mock_mithril_stake_distribution_artifact_builder),
                 Arc::new(self.mock_cardano_immutable_files_full_artifact_builder),
                 Arc::new(self.mock_cardano_transactions_artifact_builder),
+                Arc::new(self.mock_cardano_stake_distribution_artifact_builder),
             )
         }
     }
@@ -479,6 +554,41 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn build_cardano_stake_distribution_artifact_when_given_cardano_stake_distribution_entity_type(
+    ) {
+        let mut mock_container = MockDependencyInjector::new();
+
+        let cardano_stake_distribution_expected = fake_data::cardano_stake_distribution(Epoch(1));
+
+        mock_container
+            .mock_cardano_stake_distribution_artifact_builder
+            .expect_compute_artifact()
+            .times(1)
+            .returning(|_, _| Ok(fake_data::cardano_stake_distribution(Epoch(1))));
+
+        let artifact_builder_service = mock_container.build_artifact_builder_service();
+
+        let certificate = fake_data::certificate("hash".to_string());
+        let signed_entity_type = SignedEntityType::CardanoStakeDistribution(Epoch(1));
+        let artifact = artifact_builder_service
+            .compute_artifact(signed_entity_type.clone(), &certificate)
+            .await
+            .unwrap();
+
+        assert_expected(&cardano_stake_distribution_expected, &artifact);
+    }
+
+    #[tokio::test]
+    async fn should_store_the_artifact_when_creating_artifact_for_a_cardano_stake_distribution() {
+        generic_test_that_the_artifact_is_stored(
+            SignedEntityType::CardanoStakeDistribution(Epoch(1)),
+            fake_data::cardano_stake_distribution(Epoch(1)),
+            &|mock_injector| &mut mock_injector.mock_cardano_stake_distribution_artifact_builder,
+        )
+        .await;
+    }
+
     async fn generic_test_that_the_artifact_is_stored<
         T: Artifact + Clone + Serialize + 'static,
         U: signable_builder::Beacon,
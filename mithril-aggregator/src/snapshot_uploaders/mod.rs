@@ -1,11 +1,13 @@
 mod dumb_snapshot_uploader;
 mod local_snapshot_uploader;
 mod remote_snapshot_uploader;
+mod s3_snapshot_uploader;
 mod snapshot_uploader;
 
 pub use dumb_snapshot_uploader::*;
 pub use local_snapshot_uploader::LocalSnapshotUploader;
 pub use remote_snapshot_uploader::RemoteSnapshotUploader;
+pub use s3_snapshot_uploader::S3SnapshotUploader;
 pub use snapshot_uploader::SnapshotLocation;
 pub use snapshot_uploader::SnapshotUploader;
 
@@ -0,0 +1,167 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use mithril_common::StdResult;
+use slog_scope::debug;
+use std::path::Path;
+
+use crate::snapshot_uploaders::{SnapshotLocation, SnapshotUploader};
+use crate::tools::{RemoteFileUploader, S3FileUploader};
+
+/// S3SnapshotUploader is a snapshot uploader working using S3 compatible storages
+pub struct S3SnapshotUploader {
+    file_uploader: Box<dyn RemoteFileUploader>,
+    public_url: String,
+    path_prefix: Option<String>,
+}
+
+impl S3SnapshotUploader {
+    /// S3SnapshotUploader factory
+    ///
+    /// The `public_url` is the base url under which the objects of the bucket are served.
+    pub fn new(
+        file_uploader: Box<dyn RemoteFileUploader>,
+        public_url: String,
+        path_prefix: Option<String>,
+    ) -> Self {
+        debug!("New S3SnapshotUploader created");
+        Self {
+            file_uploader,
+            public_url,
+            path_prefix,
+        }
+    }
+
+    /// Compute the default public url of a bucket, used when no public url is configured.
+    ///
+    /// If a custom endpoint is given (ie: a MinIO server) a path style url is used, else the
+    /// url of the AWS S3 bucket.
+    pub fn default_public_url(bucket: &str, region: &str, endpoint: Option<&str>) -> String {
+        match endpoint {
+            Some(endpoint) => format!("{}/{}", endpoint.trim_end_matches('/'), bucket),
+            None => format!("https://{bucket}.s3.{region}.amazonaws.com"),
+        }
+    }
+}
+
+#[async_trait]
+impl SnapshotUploader for S3SnapshotUploader {
+    async fn upload_snapshot(&self, snapshot_filepath: &Path) -> StdResult<SnapshotLocation> {
+        let archive_name = snapshot_filepath
+            .file_name()
+            .and_then(|name| name.to_str())
+            .with_context(|| format!("invalid archive name: '{}'", snapshot_filepath.display()))?;
+        let location = format!(
+            "{}/{}",
+            self.public_url.trim_end_matches('/'),
+            S3FileUploader::object_key(self.path_prefix.as_deref(), archive_name)
+        );
+
+        self.file_uploader.upload_file(snapshot_filepath).await?;
+
+        Ok(location)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use std::path::Path;
+
+    use crate::tools::MockRemoteFileUploader;
+
+    use super::*;
+
+    #[test]
+    fn default_public_url_use_endpoint_with_path_style_if_given() {
+        assert_eq!(
+            "http://localhost:9000/snapshots",
+            S3SnapshotUploader::default_public_url(
+                "snapshots",
+                "us-east-1",
+                Some("http://localhost:9000/")
+            )
+        );
+    }
+
+    #[test]
+    fn default_public_url_use_aws_bucket_url_without_endpoint() {
+        assert_eq!(
+            "https://snapshots.s3.eu-west-3.amazonaws.com",
+            S3SnapshotUploader::default_public_url("snapshots", "eu-west-3", None)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_snapshot_ok() {
+        let mut file_uploader = MockRemoteFileUploader::new();
+        file_uploader.expect_upload_file().returning(|_| Ok(()));
+        let snapshot_uploader = S3SnapshotUploader::new(
+            Box::new(file_uploader),
+            "https://cdn.mithril.network/".to_string(),
+            None,
+        );
+        let snapshot_filepath = Path::new("test/snapshot.xxx.tar.gz");
+
+        let location = snapshot_uploader
+            .upload_snapshot(snapshot_filepath)
+            .await
+            .expect("s3 upload should not fail");
+
+        assert_eq!(
+            "https://cdn.mithril.network/snapshot.xxx.tar.gz".to_string(),
+            location
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_snapshot_with_path_prefix_ok() {
+        let mut file_uploader = MockRemoteFileUploader::new();
+        file_uploader.expect_upload_file().returning(|_| Ok(()));
+        let snapshot_uploader = S3SnapshotUploader::new(
+            Box::new(file_uploader),
+            "http://localhost:9000/snapshots".to_string(),
+            Some("testnet".to_string()),
+        );
+        let snapshot_filepath = Path::new("test/snapshot.xxx.tar.gz");
+
+        let location = snapshot_uploader
+            .upload_snapshot(snapshot_filepath)
+            .await
+            .expect("s3 upload should not fail");
+
+        assert_eq!(
+            "http://localhost:9000/snapshots/testnet/snapshot.xxx.tar.gz".to_string(),
+            location
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_snapshot_ko() {
+        let mut file_uploader = MockRemoteFileUploader::new();
+        file_uploader
+            .expect_upload_file()
+            .returning(|_| Err(anyhow!("unexpected error")));
+        let snapshot_uploader =
+            S3SnapshotUploader::new(Box::new(file_uploader), "".to_string(), None);
+        let snapshot_filepath = Path::new("test/snapshot.xxx.tar.gz");
+
+        let result = snapshot_uploader
+            .upload_snapshot(snapshot_filepath)
+            .await
+            .expect_err("s3 upload should fail");
+        assert_eq!("unexpected error".to_string(), result.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_upload_snapshot_without_file_name_fails() {
+        let mut file_uploader = MockRemoteFileUploader::new();
+        file_uploader.expect_upload_file().never();
+        let snapshot_uploader =
+            S3SnapshotUploader::new(Box::new(file_uploader), "".to_string(), None);
+
+        snapshot_uploader
+            .upload_snapshot(Path::new("test/.."))
+            .await
+            .expect_err("s3 upload of a path without file name should fail");
+    }
+}
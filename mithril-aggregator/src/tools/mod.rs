@@ -11,9 +11,10 @@ pub use certificates_hash_migrator::CertificatesHashMigrator;
 pub use digest_helpers::extract_digest_from_path;
 pub use era::EraTools;
 pub use genesis::{GenesisTools, GenesisToolsDependency};
-pub use remote_file_uploader::{GcpFileUploader, RemoteFileUploader};
+pub use remote_file_uploader::{GcpFileUploader, RemoteFileUploader, S3FileUploader};
 pub use signer_importer::{
-    CExplorerSignerRetriever, SignersImporter, SignersImporterPersister, SignersImporterRetriever,
+    CExplorerSignerRetriever, JsonHttpSignerRetriever, JsonSignerMapping, LocalFileSignerRetriever,
+    SignersImporter, SignersImporterPersister, SignersImporterRetriever,
 };
 
 #[cfg(test)]
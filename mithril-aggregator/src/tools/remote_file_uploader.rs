@@ -5,8 +5,14 @@ use cloud_storage::{
     Client,
 };
 use mithril_common::StdResult;
+use reqwest::{
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    Body,
+};
+use s3::{serde_types::Part, Bucket};
 use slog_scope::info;
 use std::{env, path::Path};
+use tokio::io::AsyncReadExt;
 use tokio_util::{codec::BytesCodec, codec::FramedRead};
 
 #[cfg(test)]
@@ -41,7 +47,10 @@ impl RemoteFileUploader for GcpFileUploader {
             ));
         };
 
-        let filename = filepath.file_name().unwrap().to_str().unwrap();
+        let filename = filepath
+            .file_name()
+            .and_then(|name| name.to_str())
+            .with_context(|| format!("invalid file name: '{}'", filepath.display()))?;
 
         info!("uploading {}", filename);
         let client = Client::default();
@@ -85,3 +94,365 @@ impl RemoteFileUploader for GcpFileUploader {
         Ok(())
     }
 }
+
+/// S3FileUploader represents a file uploader interactor for S3 compatible storages
+/// (AWS S3, MinIO, ...)
+pub struct S3FileUploader {
+    bucket: Bucket,
+    path_prefix: Option<String>,
+    multipart_part_size: u64,
+    http_client: reqwest::Client,
+}
+
+impl S3FileUploader {
+    /// Minimum size of a part of a multipart upload (except the last one) allowed by S3
+    pub const MIN_MULTIPART_PART_SIZE: u64 = 5 * 1024 * 1024;
+
+    /// Validity of the presigned url used to upload a file in a single request
+    const PRESIGNED_URL_EXPIRY_SECS: u32 = 3600;
+
+    /// S3FileUploader factory
+    ///
+    /// Files bigger than `multipart_part_size` are sent using a multipart upload with parts
+    /// of this size.
+    pub fn new(bucket: Bucket, path_prefix: Option<String>, multipart_part_size: u64) -> Self {
+        Self {
+            bucket,
+            path_prefix,
+            multipart_part_size,
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Compute the key of the object stored for the given filename.
+    pub fn object_key(path_prefix: Option<&str>, filename: &str) -> String {
+        match path_prefix.map(|p| p.trim_matches('/')) {
+            Some(prefix) if !prefix.is_empty() => format!("{prefix}/{filename}"),
+            _ => filename.to_string(),
+        }
+    }
+
+    async fn upload_parts(
+        &self,
+        file: &mut tokio::fs::File,
+        key: &str,
+        upload_id: &str,
+        content_type: &str,
+    ) -> StdResult<Vec<Part>> {
+        let mut parts: Vec<Part> = vec![];
+        loop {
+            let mut chunk = Vec::with_capacity(self.multipart_part_size as usize);
+            (&mut *file)
+                .take(self.multipart_part_size)
+                .read_to_end(&mut chunk)
+                .await
+                .with_context(|| format!("reading file failure while uploading '{key}'"))?;
+            if chunk.is_empty() {
+                break;
+            }
+
+            let part_number = parts.len() as u32 + 1;
+            let part = self
+                .bucket
+                .put_multipart_chunk(chunk, key, part_number, upload_id, content_type)
+                .await
+                .with_context(|| format!("uploading part {part_number} failure for '{key}'"))?;
+            info!("uploaded part {} of {}", part_number, key);
+            parts.push(part);
+        }
+
+        Ok(parts)
+    }
+
+    /// Upload a file in a single request to a presigned url, its content is streamed from the disk.
+    async fn upload_single_part(
+        &self,
+        file: tokio::fs::File,
+        file_size: u64,
+        key: &str,
+    ) -> StdResult<()> {
+        let url = self
+            .bucket
+            .presign_put(key, Self::PRESIGNED_URL_EXPIRY_SECS, None)
+            .await
+            .with_context(|| format!("presigning upload url failure for '{key}'"))?;
+
+        self.http_client
+            .put(url)
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .header(CONTENT_LENGTH, file_size)
+            .body(Body::wrap_stream(FramedRead::new(file, BytesCodec::new())))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("remote uploading failure for '{key}'"))?;
+
+        Ok(())
+    }
+
+    async fn upload_multipart(&self, file: &mut tokio::fs::File, key: &str) -> StdResult<()> {
+        let content_type = "application/octet-stream";
+        let upload_id = self
+            .bucket
+            .initiate_multipart_upload(key, content_type)
+            .await
+            .with_context(|| format!("initiating multipart upload failure for '{key}'"))?
+            .upload_id;
+
+        let parts = match self.upload_parts(file, key, &upload_id, content_type).await {
+            Ok(parts) => parts,
+            Err(error) => {
+                // Abort the upload so the already uploaded parts are not kept (and billed)
+                self.bucket.abort_upload(key, &upload_id).await.ok();
+                return Err(error);
+            }
+        };
+
+        if let Err(error) = self
+            .bucket
+            .complete_multipart_upload(key, &upload_id, parts)
+            .await
+        {
+            self.bucket.abort_upload(key, &upload_id).await.ok();
+            return Err(anyhow!(error))
+                .with_context(|| format!("completing multipart upload failure for '{key}'"));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RemoteFileUploader for S3FileUploader {
+    async fn upload_file(&self, filepath: &Path) -> StdResult<()> {
+        let filename = filepath
+            .file_name()
+            .and_then(|name| name.to_str())
+            .with_context(|| format!("invalid file name: '{}'", filepath.display()))?;
+        let key = Self::object_key(self.path_prefix.as_deref(), filename);
+
+        info!("uploading {} to {}", filename, key);
+        let mut file = tokio::fs::File::open(filepath)
+            .await
+            .with_context(|| format!("opening file failure: '{}'", filepath.display()))?;
+        let file_size = file.metadata().await?.len();
+
+        if file_size > self.multipart_part_size {
+            self.upload_multipart(&mut file, &key).await?;
+        } else {
+            self.upload_single_part(file, file_size, &key).await?;
+        }
+
+        info!("uploaded {}", filename);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::{Method, MockServer};
+    use s3::{creds::Credentials, Region};
+    use std::path::PathBuf;
+
+    use mithril_common::test_utils::TempDir;
+
+    use super::*;
+
+    fn test_bucket(server: &MockServer) -> Bucket {
+        let region = Region::Custom {
+            region: "us-east-1".to_string(),
+            endpoint: server.base_url(),
+        };
+        let credentials = Credentials {
+            access_key: Some("access_key".to_string()),
+            secret_key: Some("secret_key".to_string()),
+            security_token: None,
+            session_token: None,
+            expiration: None,
+        };
+
+        Bucket::new("snapshots", region, credentials)
+            .unwrap()
+            .with_path_style()
+    }
+
+    fn write_file(test_name: &str, filename: &str, content: &[u8]) -> PathBuf {
+        let dir = TempDir::create("s3_file_uploader", test_name);
+        let filepath = dir.join(filename);
+        std::fs::write(&filepath, content).unwrap();
+        filepath
+    }
+
+    #[test]
+    fn object_key_is_prefixed_with_path_prefix_when_given() {
+        assert_eq!(
+            "file.tar.gz",
+            S3FileUploader::object_key(None, "file.tar.gz")
+        );
+        assert_eq!(
+            "file.tar.gz",
+            S3FileUploader::object_key(Some(""), "file.tar.gz")
+        );
+        assert_eq!(
+            "testnet/snapshots/file.tar.gz",
+            S3FileUploader::object_key(Some("testnet/snapshots"), "file.tar.gz")
+        );
+        assert_eq!(
+            "testnet/file.tar.gz",
+            S3FileUploader::object_key(Some("/testnet/"), "file.tar.gz")
+        );
+    }
+
+    #[tokio::test]
+    async fn upload_file_smaller_than_part_size_in_a_single_request() {
+        let server = MockServer::start();
+        let put_mock = server.mock(|when, then| {
+            when.method(Method::PUT)
+                .path("/snapshots/prefix/snapshot.tar.gz")
+                .header("content-length", "16")
+                .body("snapshot content");
+            then.status(200).header("ETag", "\"etag\"");
+        });
+        let filepath = write_file(
+            "upload_file_smaller_than_part_size_in_a_single_request",
+            "snapshot.tar.gz",
+            b"snapshot content",
+        );
+        let uploader = S3FileUploader::new(test_bucket(&server), Some("prefix".to_string()), 100);
+
+        uploader.upload_file(&filepath).await.unwrap();
+
+        put_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn upload_file_bigger_than_part_size_using_a_multipart_upload() {
+        let server = MockServer::start();
+        let initiate_mock = server.mock(|when, then| {
+            when.method(Method::POST)
+                .path("/snapshots/snapshot.tar.gz")
+                .query_param_exists("uploads");
+            then.status(200).body(
+                "<InitiateMultipartUploadResult>\
+                    <Bucket>snapshots</Bucket>\
+                    <Key>snapshot.tar.gz</Key>\
+                    <UploadId>upload-123</UploadId>\
+                </InitiateMultipartUploadResult>",
+            );
+        });
+        let parts_mocks: Vec<_> = ["1", "2", "3"]
+            .into_iter()
+            .zip(["0123", "4567", "89"])
+            .map(|(part_number, part_content)| {
+                server.mock(|when, then| {
+                    when.method(Method::PUT)
+                        .path("/snapshots/snapshot.tar.gz")
+                        .query_param("partNumber", part_number)
+                        .query_param("uploadId", "upload-123")
+                        .body(part_content);
+                    then.status(200)
+                        .header("ETag", format!("\"etag-{part_number}\""));
+                })
+            })
+            .collect();
+        let complete_mock = server.mock(|when, then| {
+            when.method(Method::POST)
+                .path("/snapshots/snapshot.tar.gz")
+                .query_param("uploadId", "upload-123")
+                .body_contains("<PartNumber>3</PartNumber>");
+            then.status(200);
+        });
+        let filepath = write_file(
+            "upload_file_bigger_than_part_size_using_a_multipart_upload",
+            "snapshot.tar.gz",
+            b"0123456789",
+        );
+        let uploader = S3FileUploader::new(test_bucket(&server), None, 4);
+
+        uploader.upload_file(&filepath).await.unwrap();
+
+        initiate_mock.assert();
+        for part_mock in parts_mocks {
+            part_mock.assert();
+        }
+        complete_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn upload_file_aborts_the_multipart_upload_when_a_part_fails() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(Method::POST)
+                .path("/snapshots/snapshot.tar.gz")
+                .query_param_exists("uploads");
+            then.status(200).body(
+                "<InitiateMultipartUploadResult>\
+                    <Bucket>snapshots</Bucket>\
+                    <Key>snapshot.tar.gz</Key>\
+                    <UploadId>upload-123</UploadId>\
+                </InitiateMultipartUploadResult>",
+            );
+        });
+        server.mock(|when, then| {
+            when.method(Method::PUT)
+                .path("/snapshots/snapshot.tar.gz")
+                .query_param("partNumber", "1");
+            then.status(200).header("ETag", "\"etag-1\"");
+        });
+        server.mock(|when, then| {
+            when.method(Method::PUT)
+                .path("/snapshots/snapshot.tar.gz")
+                .query_param("partNumber", "2");
+            then.status(500)
+                .body("<Error><Code>InternalError</Code></Error>");
+        });
+        let complete_mock = server.mock(|when, then| {
+            when.method(Method::POST)
+                .path("/snapshots/snapshot.tar.gz")
+                .query_param("uploadId", "upload-123");
+            then.status(200);
+        });
+        let abort_mock = server.mock(|when, then| {
+            when.method(Method::DELETE)
+                .path("/snapshots/snapshot.tar.gz")
+                .query_param("uploadId", "upload-123");
+            then.status(204);
+        });
+        let filepath = write_file(
+            "upload_file_aborts_the_multipart_upload_when_a_part_fails",
+            "snapshot.tar.gz",
+            b"0123456789",
+        );
+        let uploader = S3FileUploader::new(test_bucket(&server), None, 4);
+
+        uploader
+            .upload_file(&filepath)
+            .await
+            .expect_err("upload should fail");
+
+        assert!(abort_mock.hits() >= 1, "multipart upload should be aborted");
+        complete_mock.assert_hits(0);
+    }
+
+    #[tokio::test]
+    async fn upload_file_fails_when_the_storage_returns_an_error() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(Method::PUT);
+            then.status(403)
+                .body("<Error><Code>AccessDenied</Code></Error>");
+        });
+        let filepath = write_file(
+            "upload_file_fails_when_the_storage_returns_an_error",
+            "snapshot.tar.gz",
+            b"snapshot content",
+        );
+        let uploader = S3FileUploader::new(test_bucket(&server), None, 100);
+
+        uploader
+            .upload_file(&filepath)
+            .await
+            .expect_err("upload should fail");
+    }
+}
@@ -1,10 +1,11 @@
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use mithril_common::{entities::PartyId, StdResult};
 use reqwest::{IntoUrl, Url};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::ops::Not;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -178,6 +179,263 @@ impl SPOItem {
     }
 }
 
+/// A [SignersImporterRetriever] reading signers data from a local file.
+///
+/// The format of the file depends on its extension:
+/// - `.json`: an object mapping each pool id to its ticker, ie: `{"pool1...": "TICKER", "pool2...": null}`
+/// - `.csv`: one `pool_id,ticker` line per pool, with an optional `pool_id,ticker` header line,
+///   an empty ticker means no ticker.
+pub struct LocalFileSignerRetriever {
+    path: PathBuf,
+}
+
+impl LocalFileSignerRetriever {
+    /// Create a new [LocalFileSignerRetriever] that will read data from the given file.
+    pub(crate) fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    fn parse_json(content: &str) -> StdResult<HashMap<PartyId, Option<PoolTicker>>> {
+        let items: HashMap<PartyId, Option<PoolTicker>> =
+            serde_json::from_str(content).with_context(|| "Invalid JSON pools file")?;
+
+        Ok(items
+            .into_iter()
+            .map(|(pool_id, ticker)| (pool_id, ticker.filter(|t| !t.trim().is_empty())))
+            .collect())
+    }
+
+    fn parse_csv(content: &str) -> StdResult<HashMap<PartyId, Option<PoolTicker>>> {
+        let mut signers = HashMap::new();
+
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (line_number == 0 && line.eq_ignore_ascii_case("pool_id,ticker"))
+            {
+                continue;
+            }
+
+            let (pool_id, ticker) = line.split_once(',').ok_or_else(|| {
+                anyhow!(
+                    "Invalid CSV pools file, line {} is not a 'pool_id,ticker' pair: '{line}'",
+                    line_number + 1
+                )
+            })?;
+            let ticker = ticker.trim();
+            signers.insert(
+                pool_id.trim().to_string(),
+                ticker.is_empty().not().then(|| ticker.to_string()),
+            );
+        }
+
+        Ok(signers)
+    }
+}
+
+#[async_trait]
+impl SignersImporterRetriever for LocalFileSignerRetriever {
+    async fn retrieve(&self) -> StdResult<HashMap<PartyId, Option<PoolTicker>>> {
+        info!(
+            "🔧 Signer Importer: retrieving data from file";
+            "path" => self.path.display()
+        );
+        let content = tokio::fs::read_to_string(&self.path)
+            .await
+            .with_context(|| format!("Could not read pools file: '{}'", self.path.display()))?;
+
+        match self.path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::parse_json(&content),
+            Some("csv") => Self::parse_csv(&content),
+            _ => Err(anyhow!(
+                "Unsupported pools file format, expected a '.json' or '.csv' file: '{}'",
+                self.path.display()
+            )),
+        }
+    }
+}
+
+/// Mapping used by a [JsonHttpSignerRetriever] to extract the signers from a JSON document.
+///
+/// The paths are JSONPath-like: keys separated by dots, with numbers used to index arrays, and
+/// an optional leading `$` for the root of the document (ie: `$.data`, `meta_json.ticker`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonSignerMapping {
+    /// Path to the list of items in the document, default to the root of the document.
+    #[serde(default = "JsonSignerMapping::default_items_path")]
+    pub items_path: String,
+
+    /// Path to the pool id, relative to an item.
+    pub pool_id_path: String,
+
+    /// Path to the ticker, relative to an item.
+    pub ticker_path: String,
+}
+
+impl JsonSignerMapping {
+    fn default_items_path() -> String {
+        "$".to_string()
+    }
+
+    fn select<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+        let path = path.strip_prefix('$').unwrap_or(path);
+        path.split('.')
+            .filter(|segment| !segment.is_empty())
+            .try_fold(value, |value, segment| match value {
+                serde_json::Value::Array(items) => {
+                    segment.parse::<usize>().ok().and_then(|i| items.get(i))
+                }
+                _ => value.get(segment),
+            })
+    }
+
+    /// Extract the signers from the given document.
+    ///
+    /// Items without a pool id are ignored, items with a missing, null or empty ticker yield
+    /// an empty ticker.
+    fn extract(
+        &self,
+        document: &serde_json::Value,
+    ) -> StdResult<HashMap<PartyId, Option<PoolTicker>>> {
+        Ok(self.extract_items(self.select_items(document)?))
+    }
+
+    fn select_items<'a>(
+        &self,
+        document: &'a serde_json::Value,
+    ) -> StdResult<&'a Vec<serde_json::Value>> {
+        Self::select(document, &self.items_path)
+            .and_then(|items| items.as_array())
+            .ok_or_else(|| {
+                anyhow!(
+                    "No list of items found at path '{}' in the retrieved document",
+                    self.items_path
+                )
+            })
+    }
+
+    fn extract_items(&self, items: &[serde_json::Value]) -> HashMap<PartyId, Option<PoolTicker>> {
+        items
+            .iter()
+            .filter_map(|item| {
+                let pool_id = Self::select(item, &self.pool_id_path)?.as_str()?;
+                let ticker = Self::select(item, &self.ticker_path)
+                    .and_then(|ticker| ticker.as_str())
+                    .map(|ticker| ticker.trim())
+                    .filter(|ticker| !ticker.is_empty());
+
+                Some((pool_id.to_string(), ticker.map(|t| t.to_string())))
+            })
+            .collect()
+    }
+}
+
+/// A [SignersImporterRetriever] fetching signers data from any HTTP source returning a JSON
+/// document (ie: a Koios `pool_list` endpoint), using a [JsonSignerMapping] to extract them.
+///
+/// If a page size is given, the document is fetched by pages using `offset` and `limit` query
+/// parameters (as Koios and other PostgREST APIs, which cap the number of rows of a response),
+/// until a page holds less items than the page size.
+pub struct JsonHttpSignerRetriever {
+    source_url: Url,
+    mapping: JsonSignerMapping,
+    page_size: Option<usize>,
+    client: reqwest::Client,
+}
+
+impl JsonHttpSignerRetriever {
+    /// Default number of items fetched by page, the maximum number of rows returned by Koios.
+    pub const DEFAULT_PAGE_SIZE: usize = 1000;
+
+    /// Create a new [JsonHttpSignerRetriever] that will fetch data from the given url.
+    pub(crate) fn new<T: IntoUrl>(
+        source_url: T,
+        mapping: JsonSignerMapping,
+        page_size: Option<usize>,
+        timeout: Option<Duration>,
+    ) -> StdResult<Self> {
+        let source_url = source_url
+            .into_url()
+            .with_context(|| "Given `source_url` is not a valid Url")?;
+        let client_builder = reqwest::Client::builder();
+        let client = match timeout {
+            None => client_builder,
+            Some(timeout) => client_builder.timeout(timeout),
+        }
+        .build()
+        .with_context(|| "Http Client build failed")?;
+
+        Ok(Self {
+            source_url,
+            mapping,
+            page_size: page_size.filter(|size| *size > 0),
+            client,
+        })
+    }
+
+    fn page_url(&self, offset: usize, limit: usize) -> Url {
+        let mut url = self.source_url.clone();
+        url.query_pairs_mut()
+            .append_pair("offset", &offset.to_string())
+            .append_pair("limit", &limit.to_string());
+
+        url
+    }
+
+    async fn fetch_document(&self, url: Url) -> StdResult<serde_json::Value> {
+        self.client
+            .get(url)
+            .send()
+            .await
+            .with_context(|| "Retrieving of JSON SPO list failed")?
+            .error_for_status()
+            .with_context(|| "Data fetching failed")?
+            .json::<serde_json::Value>()
+            .await
+            .with_context(|| "Failed to deserialize retrieved JSON SPO list")
+    }
+}
+
+#[async_trait]
+impl SignersImporterRetriever for JsonHttpSignerRetriever {
+    async fn retrieve(&self) -> StdResult<HashMap<PartyId, Option<PoolTicker>>> {
+        info!(
+            "🔧 Signer Importer: retrieving data from source";
+            "source_url" => &self.source_url.as_str()
+        );
+        let Some(page_size) = self.page_size else {
+            let document = self.fetch_document(self.source_url.clone()).await?;
+            return self.mapping.extract(&document);
+        };
+
+        let mut signers = HashMap::new();
+        let mut offset = 0;
+        loop {
+            let document = self
+                .fetch_document(self.page_url(offset, page_size))
+                .await?;
+            let items = self.mapping.select_items(&document)?;
+            let nb_known_signers = signers.len();
+            signers.extend(self.mapping.extract_items(items));
+
+            if items.len() < page_size {
+                break;
+            }
+            if signers.len() == nb_known_signers {
+                warn!(
+                    "🔧 Signer Importer: a page of the source holds no new signer, it probably does not support the `offset` and `limit` parameters";
+                    "offset" => offset
+                );
+                break;
+            }
+            offset += items.len();
+        }
+
+        Ok(signers)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::{BTreeMap, BTreeSet};
@@ -186,6 +444,7 @@ mod tests {
     use warp::Filter;
 
     use mithril_common::test_utils::test_http_server::test_http_server;
+    use mithril_common::test_utils::TempDir;
     use mithril_common::StdResult;
     use mithril_persistence::sqlite::SqliteConnection;
 
@@ -332,6 +591,283 @@ mod tests {
             .expect_err("An error should have been raised");
     }
 
+    fn write_pools_file(test_name: &str, file_name: &str, content: &str) -> PathBuf {
+        let path = TempDir::create("signer_importer", test_name).join(file_name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn local_file_retriever_read_json_file() {
+        let path = write_pools_file(
+            "local_file_retriever_read_json_file",
+            "pools.json",
+            r#"{ "pool1": "[Pool1]", "pool2": null, "pool3": " " }"#,
+        );
+
+        let retriever = LocalFileSignerRetriever::new(&path);
+        let result = retriever
+            .retrieve()
+            .await
+            .expect("Retriever should not fail");
+
+        assert_eq!(
+            result.into_iter().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([
+                ("pool1".to_string(), Some("[Pool1]".to_string())),
+                ("pool2".to_string(), None),
+                ("pool3".to_string(), None),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn local_file_retriever_read_csv_file() {
+        let path = write_pools_file(
+            "local_file_retriever_read_csv_file",
+            "pools.csv",
+            "pool_id,ticker\npool1,[Pool1]\n\npool2,\n pool3 , [Pool, 3] \n",
+        );
+
+        let retriever = LocalFileSignerRetriever::new(&path);
+        let result = retriever
+            .retrieve()
+            .await
+            .expect("Retriever should not fail");
+
+        assert_eq!(
+            result.into_iter().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([
+                ("pool1".to_string(), Some("[Pool1]".to_string())),
+                ("pool2".to_string(), None),
+                ("pool3".to_string(), Some("[Pool, 3]".to_string())),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn local_file_retriever_yield_error_when_csv_line_is_malformed() {
+        let path = write_pools_file(
+            "local_file_retriever_yield_error_when_csv_line_is_malformed",
+            "pools.csv",
+            "pool1,[Pool1]\npool2\n",
+        );
+
+        LocalFileSignerRetriever::new(&path)
+            .retrieve()
+            .await
+            .expect_err("An error should have been raised");
+    }
+
+    #[tokio::test]
+    async fn local_file_retriever_yield_error_when_file_format_is_not_supported() {
+        let path = write_pools_file(
+            "local_file_retriever_yield_error_when_file_format_is_not_supported",
+            "pools.txt",
+            "pool1 [Pool1]",
+        );
+
+        LocalFileSignerRetriever::new(&path)
+            .retrieve()
+            .await
+            .expect_err("An error should have been raised");
+    }
+
+    #[test]
+    fn json_mapping_extract_signers_from_root_list() {
+        let mapping = JsonSignerMapping {
+            items_path: "$".to_string(),
+            pool_id_path: "pool_id_bech32".to_string(),
+            ticker_path: "ticker".to_string(),
+        };
+        let document = serde_json::json!([
+            {"pool_id_bech32": "pool1", "ticker": "[Pool1]"},
+            {"pool_id_bech32": "pool2", "ticker": null},
+            {"pool_id_bech32": "pool3"},
+            {"ticker": "[No pool id]"}
+        ]);
+
+        let result = mapping.extract(&document).unwrap();
+
+        assert_eq!(
+            result.into_iter().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([
+                ("pool1".to_string(), Some("[Pool1]".to_string())),
+                ("pool2".to_string(), None),
+                ("pool3".to_string(), None),
+            ])
+        );
+    }
+
+    #[test]
+    fn json_mapping_extract_signers_with_nested_paths() {
+        let mapping = JsonSignerMapping {
+            items_path: "$.result.pools".to_string(),
+            pool_id_path: "ids.0".to_string(),
+            ticker_path: "meta_json.ticker".to_string(),
+        };
+        let document = serde_json::json!({
+            "result": {
+                "pools": [
+                    {"ids": ["pool1", "other"], "meta_json": {"ticker": "[Pool1]"}},
+                    {"ids": ["pool2"], "meta_json": null}
+                ]
+            }
+        });
+
+        let result = mapping.extract(&document).unwrap();
+
+        assert_eq!(
+            result.into_iter().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([
+                ("pool1".to_string(), Some("[Pool1]".to_string())),
+                ("pool2".to_string(), None),
+            ])
+        );
+    }
+
+    #[test]
+    fn json_mapping_yield_error_when_items_path_is_not_a_list() {
+        let mapping = JsonSignerMapping {
+            items_path: "data".to_string(),
+            pool_id_path: "pool_id".to_string(),
+            ticker_path: "ticker".to_string(),
+        };
+
+        mapping
+            .extract(&serde_json::json!({"data": {"pool_id": "pool1"}}))
+            .expect_err("An error should have been raised");
+    }
+
+    #[test]
+    fn json_mapping_items_path_default_to_root_when_deserialized() {
+        let mapping: JsonSignerMapping =
+            serde_json::from_str(r#"{"pool_id_path": "pool_id", "ticker_path": "ticker"}"#)
+                .unwrap();
+
+        assert_eq!("$", mapping.items_path);
+    }
+
+    #[tokio::test]
+    async fn json_http_retriever_fetch_and_map_data() {
+        let server = test_http_server(warp::path("pool_list").map(|| {
+            r#"[
+                {"pool_id_bech32": "pool1", "ticker": "[Pool1]"},
+                {"pool_id_bech32": "pool2", "ticker": null}
+            ]"#
+        }));
+
+        let retriever = JsonHttpSignerRetriever::new(
+            format!("{}/pool_list", server.url()),
+            JsonSignerMapping {
+                items_path: "$".to_string(),
+                pool_id_path: "pool_id_bech32".to_string(),
+                ticker_path: "ticker".to_string(),
+            },
+            Some(JsonHttpSignerRetriever::DEFAULT_PAGE_SIZE),
+            None,
+        )
+        .unwrap();
+        let result = retriever
+            .retrieve()
+            .await
+            .expect("Retriever should not fail");
+
+        assert_eq!(
+            result.into_iter().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([
+                ("pool1".to_string(), Some("[Pool1]".to_string())),
+                ("pool2".to_string(), None),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn json_http_retriever_fetch_all_pages() {
+        let server = test_http_server(warp::path("pool_list").and(warp::query()).map(
+            |query: HashMap<String, usize>| {
+                let pools: Vec<_> = (1..=5)
+                    .map(|i| serde_json::json!({"pool_id_bech32": format!("pool{i}")}))
+                    .skip(query["offset"])
+                    .take(query["limit"])
+                    .collect();
+                warp::reply::json(&pools)
+            },
+        ));
+
+        let retriever = JsonHttpSignerRetriever::new(
+            format!("{}/pool_list", server.url()),
+            JsonSignerMapping {
+                items_path: "$".to_string(),
+                pool_id_path: "pool_id_bech32".to_string(),
+                ticker_path: "ticker".to_string(),
+            },
+            Some(2),
+            None,
+        )
+        .unwrap();
+        let result = retriever
+            .retrieve()
+            .await
+            .expect("Retriever should not fail");
+
+        assert_eq!(
+            result.into_keys().collect::<BTreeSet<_>>(),
+            BTreeSet::from(["pool1", "pool2", "pool3", "pool4", "pool5"].map(String::from))
+        );
+    }
+
+    #[tokio::test]
+    async fn json_http_retriever_stops_if_the_source_does_not_support_pagination() {
+        let server = test_http_server(warp::path("pool_list").map(|| {
+            r#"[
+                {"pool_id_bech32": "pool1", "ticker": "[Pool1]"},
+                {"pool_id_bech32": "pool2", "ticker": null}
+            ]"#
+        }));
+
+        let retriever = JsonHttpSignerRetriever::new(
+            format!("{}/pool_list", server.url()),
+            JsonSignerMapping {
+                items_path: "$".to_string(),
+                pool_id_path: "pool_id_bech32".to_string(),
+                ticker_path: "ticker".to_string(),
+            },
+            Some(2),
+            None,
+        )
+        .unwrap();
+        let result = retriever
+            .retrieve()
+            .await
+            .expect("Retriever should not fail");
+
+        assert_eq!(2, result.len());
+    }
+
+    #[tokio::test]
+    async fn json_http_retriever_handle_http_data_fetching_error() {
+        let server = test_http_server(
+            warp::path("pool_list").map(|| reply::internal_server_error("whatever")),
+        );
+
+        let retriever = JsonHttpSignerRetriever::new(
+            format!("{}/pool_list", server.url()),
+            JsonSignerMapping {
+                items_path: "$".to_string(),
+                pool_id_path: "pool_id_bech32".to_string(),
+                ticker_path: "ticker".to_string(),
+            },
+            Some(JsonHttpSignerRetriever::DEFAULT_PAGE_SIZE),
+            None,
+        )
+        .unwrap();
+        retriever
+            .retrieve()
+            .await
+            .expect_err("An error should have been raised");
+    }
+
     #[tokio::test]
     async fn persist_list_of_two_signers_one_with_ticker_the_other_without() {
         let connection = Arc::new(main_db_connection().unwrap());
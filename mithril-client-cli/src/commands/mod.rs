@@ -4,6 +4,7 @@
 //!
 
 pub mod cardano_db;
+pub mod cardano_stake_distribution;
 pub mod cardano_transaction;
 pub mod mithril_stake_distribution;
 
@@ -16,6 +16,7 @@ use mithril_doc::{Documenter, GenerateDocCommands, StructDoc};
 
 use mithril_client_cli::commands::{
     cardano_db::{deprecated::SnapshotCommands, CardanoDbCommands},
+    cardano_stake_distribution::CardanoStakeDistributionCommands,
     cardano_transaction::CardanoTransactionCommands,
     mithril_stake_distribution::MithrilStakeDistributionCommands,
 };
@@ -177,6 +178,9 @@ enum ArtifactCommands {
     #[clap(subcommand, alias("ctx"))]
     CardanoTransaction(CardanoTransactionCommands),
 
+    #[clap(subcommand, alias("csd"))]
+    CardanoStakeDistribution(CardanoStakeDistributionCommands),
+
     #[clap(alias("doc"), hide(true))]
     GenerateDoc(GenerateDocCommands),
 }
@@ -212,6 +216,18 @@ impl ArtifactCommands {
                     ctx.execute(config_builder).await
                 }
             }
+            Self::CardanoStakeDistribution(csd) => {
+                if !unstable_enabled {
+                    Err(anyhow::anyhow!(
+                        "The \"cardano-stake-distribution\" subcommand is only accepted using the \
+                        --unstable flag.\n \
+                    \n \
+                    ie: \"mithril-client --unstable cardano-stake-distribution list\""
+                    ))
+                } else {
+                    csd.execute(config_builder).await
+                }
+            }
             Self::GenerateDoc(cmd) => cmd
                 .execute(&mut Args::command())
                 .map_err(|message| anyhow!(message)),
@@ -245,4 +261,14 @@ mod tests {
             .await
             .expect_err("Should fail if unstable flag missing");
     }
+
+    #[tokio::test]
+    async fn fail_if_cardano_stake_distribution_command_is_used_without_unstable_flag() {
+        let args =
+            Args::try_parse_from(["mithril-client", "cardano-stake-distribution", "list"]).unwrap();
+
+        args.execute()
+            .await
+            .expect_err("Should fail if unstable flag missing");
+    }
 }
@@ -20,8 +20,12 @@ use tokio::sync::RwLock;
 #[cfg(test)]
 use mockall::automock;
 
+#[cfg(feature = "unstable")]
+use mithril_common::messages::CardanoTransactionsProofsRequestMessage;
+use mithril_common::messages::ListQueryParameters;
 use mithril_common::MITHRIL_API_VERSION_HEADER;
 
+use crate::http_client_options::HttpClientOptions;
 use crate::{MithrilError, MithrilResult};
 
 /// Error tied with the Aggregator client
@@ -54,6 +58,11 @@ pub enum AggregatorRequest {
     },
     /// Lists the aggregator [certificates][crate::MithrilCertificate]
     ListCertificates,
+    /// Lists the aggregator [certificates][crate::MithrilCertificate] matching the given parameters
+    ListCertificatesWithParameters {
+        /// Pagination and filters of the list
+        parameters: ListQueryParameters,
+    },
     /// Get a specific [Mithril stake distribution][crate::MithrilStakeDistribution] from the aggregator
     GetMithrilStakeDistribution {
         /// Hash of the Mithril stake distribution to retrieve
@@ -61,6 +70,11 @@ pub enum AggregatorRequest {
     },
     /// Lists the aggregator [Mithril stake distribution][crate::MithrilStakeDistribution]
     ListMithrilStakeDistributions,
+    /// Lists the aggregator [Mithril stake distribution][crate::MithrilStakeDistribution] matching the given parameters
+    ListMithrilStakeDistributionsWithParameters {
+        /// Pagination and filters of the list
+        parameters: ListQueryParameters,
+    },
     /// Get a specific [snapshot][crate::Snapshot] from the aggregator
     GetSnapshot {
         /// Digest of the snapshot to retrieve
@@ -68,6 +82,11 @@ pub enum AggregatorRequest {
     },
     /// Lists the aggregator [snapshots][crate::Snapshot]
     ListSnapshots,
+    /// Lists the aggregator [snapshots][crate::Snapshot] matching the given parameters
+    ListSnapshotsWithParameters {
+        /// Pagination and filters of the list
+        parameters: ListQueryParameters,
+    },
 
     /// Increments the aggregator snapshot download statistics
     IncrementSnapshotStatistic {
@@ -82,6 +101,14 @@ pub enum AggregatorRequest {
         transactions_hashes: Vec<String>,
     },
 
+    /// Post a request for proofs that the given set of Cardano transactions is included in the
+    /// global Cardano transactions set, allowing more hashes than what fits in an URL
+    #[cfg(feature = "unstable")]
+    PostTransactionsProofs {
+        /// Hashes of the transactions to get proofs for
+        request: CardanoTransactionsProofsRequestMessage,
+    },
+
     /// Get a specific [Cardano transaction snapshot][crate::CardanoTransactionSnapshot]
     #[cfg(feature = "unstable")]
     GetCardanoTransactionSnapshot {
@@ -92,6 +119,31 @@ pub enum AggregatorRequest {
     /// Lists the aggregator [Cardano transaction snapshot][crate::CardanoTransactionSnapshot]
     #[cfg(feature = "unstable")]
     ListCardanoTransactionSnapshots,
+
+    /// Lists the aggregator [Cardano transaction snapshot][crate::CardanoTransactionSnapshot] matching the given parameters
+    #[cfg(feature = "unstable")]
+    ListCardanoTransactionSnapshotsWithParameters {
+        /// Pagination and filters of the list
+        parameters: ListQueryParameters,
+    },
+
+    /// Get a specific [Cardano stake distribution][crate::CardanoStakeDistribution] from the aggregator
+    #[cfg(feature = "unstable")]
+    GetCardanoStakeDistribution {
+        /// Hash of the Cardano stake distribution to retrieve
+        hash: String,
+    },
+
+    /// Lists the aggregator [Cardano stake distribution][crate::CardanoStakeDistribution]
+    #[cfg(feature = "unstable")]
+    ListCardanoStakeDistributions,
+
+    /// Lists the aggregator [Cardano stake distribution][crate::CardanoStakeDistribution] matching the given parameters
+    #[cfg(feature = "unstable")]
+    ListCardanoStakeDistributionsWithParameters {
+        /// Pagination and filters of the list
+        parameters: ListQueryParameters,
+    },
 }
 
 impl AggregatorRequest {
@@ -102,16 +154,25 @@ impl AggregatorRequest {
                 format!("certificate/{hash}")
             }
             AggregatorRequest::ListCertificates => "certificates".to_string(),
+            AggregatorRequest::ListCertificatesWithParameters { parameters } => {
+                Self::route_with_query("certificates", parameters)
+            }
             AggregatorRequest::GetMithrilStakeDistribution { hash } => {
                 format!("artifact/mithril-stake-distribution/{hash}")
             }
             AggregatorRequest::ListMithrilStakeDistributions => {
                 "artifact/mithril-stake-distributions".to_string()
             }
+            AggregatorRequest::ListMithrilStakeDistributionsWithParameters { parameters } => {
+                Self::route_with_query("artifact/mithril-stake-distributions", parameters)
+            }
             AggregatorRequest::GetSnapshot { digest } => {
                 format!("artifact/snapshot/{}", digest)
             }
             AggregatorRequest::ListSnapshots => "artifact/snapshots".to_string(),
+            AggregatorRequest::ListSnapshotsWithParameters { parameters } => {
+                Self::route_with_query("artifact/snapshots", parameters)
+            }
             AggregatorRequest::IncrementSnapshotStatistic { snapshot: _ } => {
                 "statistics/snapshot".to_string()
             }
@@ -123,6 +184,10 @@ impl AggregatorRequest {
                 transactions_hashes.join(",")
             ),
             #[cfg(feature = "unstable")]
+            AggregatorRequest::PostTransactionsProofs { request: _ } => {
+                "proof/cardano-transaction".to_string()
+            }
+            #[cfg(feature = "unstable")]
             AggregatorRequest::GetCardanoTransactionSnapshot { hash } => {
                 format!("artifact/cardano-transaction/{hash}")
             }
@@ -130,6 +195,29 @@ impl AggregatorRequest {
             AggregatorRequest::ListCardanoTransactionSnapshots => {
                 "artifact/cardano-transactions".to_string()
             }
+            #[cfg(feature = "unstable")]
+            AggregatorRequest::ListCardanoTransactionSnapshotsWithParameters { parameters } => {
+                Self::route_with_query("artifact/cardano-transactions", parameters)
+            }
+            #[cfg(feature = "unstable")]
+            AggregatorRequest::GetCardanoStakeDistribution { hash } => {
+                format!("artifact/cardano-stake-distribution/{hash}")
+            }
+            #[cfg(feature = "unstable")]
+            AggregatorRequest::ListCardanoStakeDistributions => {
+                "artifact/cardano-stake-distributions".to_string()
+            }
+            #[cfg(feature = "unstable")]
+            AggregatorRequest::ListCardanoStakeDistributionsWithParameters { parameters } => {
+                Self::route_with_query("artifact/cardano-stake-distributions", parameters)
+            }
+        }
+    }
+
+    fn route_with_query(route: &str, parameters: &ListQueryParameters) -> String {
+        match parameters.to_query_string() {
+            query if query.is_empty() => route.to_string(),
+            query => format!("{route}?{query}"),
         }
     }
 
@@ -139,6 +227,10 @@ impl AggregatorRequest {
             AggregatorRequest::IncrementSnapshotStatistic { snapshot } => {
                 Some(snapshot.to_string())
             }
+            #[cfg(feature = "unstable")]
+            AggregatorRequest::PostTransactionsProofs { request } => {
+                serde_json::to_string(request).ok()
+            }
             _ => None,
         }
     }
@@ -164,6 +256,7 @@ pub trait AggregatorClient: Sync + Send {
 /// Responsible for HTTP transport and API version check.
 pub struct AggregatorHTTPClient {
     http_client: reqwest::Client,
+    http_client_options: HttpClientOptions,
     aggregator_endpoint: Url,
     api_versions: Arc<RwLock<Vec<Version>>>,
     logger: Logger,
@@ -176,8 +269,24 @@ impl AggregatorHTTPClient {
         api_versions: Vec<Version>,
         logger: Logger,
     ) -> MithrilResult<Self> {
-        let http_client = reqwest::ClientBuilder::new()
-            .build()
+        Self::new_with_options(
+            aggregator_endpoint,
+            api_versions,
+            logger,
+            HttpClientOptions::default(),
+        )
+    }
+
+    /// Constructs a new `AggregatorHTTPClient` which requests are sent using the given
+    /// [HttpClientOptions].
+    pub fn new_with_options(
+        aggregator_endpoint: Url,
+        api_versions: Vec<Version>,
+        logger: Logger,
+        http_client_options: HttpClientOptions,
+    ) -> MithrilResult<Self> {
+        let http_client = http_client_options
+            .build_http_client()
             .with_context(|| "Building http client for Aggregator client failed")?;
 
         // Trailing slash is significant because url::join
@@ -193,6 +302,7 @@ impl AggregatorHTTPClient {
 
         Ok(Self {
             http_client,
+            http_client_options,
             aggregator_endpoint,
             api_versions: Arc::new(RwLock::new(api_versions)),
             logger,
@@ -228,7 +338,6 @@ impl AggregatorHTTPClient {
     #[cfg_attr(not(target_family = "wasm"), async_recursion)]
     async fn get(&self, url: Url) -> Result<Response, AggregatorClientError> {
         debug!(self.logger, "GET url='{url}'.");
-        let request_builder = self.http_client.get(url.clone());
         let current_api_version = self
             .compute_current_api_version()
             .await
@@ -238,13 +347,20 @@ impl AggregatorHTTPClient {
             self.logger,
             "Prepare request with version: {current_api_version}"
         );
-        let request_builder =
-            request_builder.header(MITHRIL_API_VERSION_HEADER, current_api_version);
-        let response = request_builder.send().await.map_err(|e| {
-            AggregatorClientError::SubsystemError(anyhow!(e).context(format!(
-                "Cannot perform a GET against the Aggregator HTTP server (url='{url}')"
-            )))
-        })?;
+        let build_request = || {
+            self.http_client
+                .get(url.clone())
+                .header(MITHRIL_API_VERSION_HEADER, &current_api_version)
+        };
+        let response = self
+            .http_client_options
+            .send_with_retry(build_request, &self.logger)
+            .await
+            .map_err(|e| {
+                AggregatorClientError::SubsystemError(anyhow!(e).context(format!(
+                    "Cannot perform a GET against the Aggregator HTTP server (url='{url}')"
+                )))
+            })?;
 
         match response.status() {
             StatusCode::OK => Ok(response),
@@ -270,7 +386,6 @@ impl AggregatorHTTPClient {
     #[cfg_attr(not(target_family = "wasm"), async_recursion)]
     async fn post(&self, url: Url, json: &str) -> Result<Response, AggregatorClientError> {
         debug!(self.logger, "POST url='{url}' json='{json}'.");
-        let request_builder = self.http_client.post(url.to_owned()).body(json.to_owned());
         let current_api_version = self
             .compute_current_api_version()
             .await
@@ -280,14 +395,22 @@ impl AggregatorHTTPClient {
             self.logger,
             "Prepare request with version: {current_api_version}"
         );
-        let request_builder =
-            request_builder.header(MITHRIL_API_VERSION_HEADER, current_api_version);
+        let build_request = || {
+            self.http_client
+                .post(url.to_owned())
+                .body(json.to_owned())
+                .header(MITHRIL_API_VERSION_HEADER, &current_api_version)
+        };
 
-        let response = request_builder.send().await.map_err(|e| {
-            AggregatorClientError::SubsystemError(
-                anyhow!(e).context("Error while POSTing data '{json}' to URL='{url}'."),
-            )
-        })?;
+        let response = self
+            .http_client_options
+            .send_with_retry(build_request, &self.logger)
+            .await
+            .map_err(|e| {
+                AggregatorClientError::SubsystemError(
+                    anyhow!(e).context("Error while POSTing data '{json}' to URL='{url}'."),
+                )
+            })?;
 
         match response.status() {
             StatusCode::OK | StatusCode::CREATED => Ok(response),
@@ -377,6 +500,8 @@ impl AggregatorClient for AggregatorHTTPClient {
 
 #[cfg(test)]
 mod tests {
+    use mithril_common::entities::Epoch;
+
     use super::*;
 
     #[test]
@@ -445,6 +570,40 @@ mod tests {
             AggregatorRequest::ListSnapshots.route()
         );
 
+        assert_eq!(
+            "artifact/snapshots".to_string(),
+            AggregatorRequest::ListSnapshotsWithParameters {
+                parameters: ListQueryParameters::default()
+            }
+            .route()
+        );
+
+        assert_eq!(
+            "artifact/snapshots?limit=5&offset=10".to_string(),
+            AggregatorRequest::ListSnapshotsWithParameters {
+                parameters: ListQueryParameters::default().with_limit(5).with_offset(10)
+            }
+            .route()
+        );
+
+        assert_eq!(
+            "certificates?from_epoch=3&to_epoch=5".to_string(),
+            AggregatorRequest::ListCertificatesWithParameters {
+                parameters: ListQueryParameters::default()
+                    .with_from_epoch(Epoch(3))
+                    .with_to_epoch(Epoch(5))
+            }
+            .route()
+        );
+
+        assert_eq!(
+            "artifact/mithril-stake-distributions?limit=2".to_string(),
+            AggregatorRequest::ListMithrilStakeDistributionsWithParameters {
+                parameters: ListQueryParameters::default().with_limit(2)
+            }
+            .route()
+        );
+
         assert_eq!(
             "statistics/snapshot".to_string(),
             AggregatorRequest::IncrementSnapshotStatistic {
@@ -468,6 +627,14 @@ mod tests {
                 .route()
             );
 
+            assert_eq!(
+                "proof/cardano-transaction".to_string(),
+                AggregatorRequest::PostTransactionsProofs {
+                    request: CardanoTransactionsProofsRequestMessage::new(&["abc", "def"])
+                }
+                .route()
+            );
+
             assert_eq!(
                 "artifact/cardano-transaction/abc".to_string(),
                 AggregatorRequest::GetCardanoTransactionSnapshot {
@@ -480,6 +647,46 @@ mod tests {
                 "artifact/cardano-transactions".to_string(),
                 AggregatorRequest::ListCardanoTransactionSnapshots.route()
             );
+
+            assert_eq!(
+                "artifact/cardano-stake-distribution/abc".to_string(),
+                AggregatorRequest::GetCardanoStakeDistribution {
+                    hash: "abc".to_string()
+                }
+                .route()
+            );
+
+            assert_eq!(
+                "artifact/cardano-stake-distributions".to_string(),
+                AggregatorRequest::ListCardanoStakeDistributions.route()
+            );
+
+            assert_eq!(
+                "artifact/cardano-transactions?offset=4".to_string(),
+                AggregatorRequest::ListCardanoTransactionSnapshotsWithParameters {
+                    parameters: ListQueryParameters::default().with_offset(4)
+                }
+                .route()
+            );
+
+            assert_eq!(
+                "artifact/cardano-stake-distributions?to_epoch=8".to_string(),
+                AggregatorRequest::ListCardanoStakeDistributionsWithParameters {
+                    parameters: ListQueryParameters::default().with_to_epoch(Epoch(8))
+                }
+                .route()
+            );
         }
     }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn post_transactions_proofs_body_is_the_serialized_request_message() {
+        let request = CardanoTransactionsProofsRequestMessage::new(&["abc", "def"]);
+
+        assert_eq!(
+            Some(serde_json::to_string(&request).unwrap()),
+            AggregatorRequest::PostTransactionsProofs { request }.get_body()
+        );
+    }
 }
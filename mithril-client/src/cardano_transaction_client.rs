@@ -77,11 +77,18 @@
 use crate::aggregator_client::{AggregatorClient, AggregatorClientError, AggregatorRequest};
 use crate::{
     CardanoTransactionSnapshot, CardanoTransactionSnapshotListItem, CardanoTransactionsProofs,
-    MithrilResult,
+    ListQueryParameters, MithrilResult,
 };
-use anyhow::Context;
+use anyhow::{anyhow, Context};
+use mithril_common::messages::CardanoTransactionsProofsRequestMessage;
 use std::sync::Arc;
 
+/// Maximum number of transactions hashes sent to the aggregator in a single proof request,
+/// bigger subsets are split in several requests.
+///
+/// It is the default maximum allowed by an aggregator, shared with it through `mithril-common`.
+pub use mithril_common::messages::MAX_TRANSACTIONS_HASHES_BY_REQUEST;
+
 /// HTTP client for CardanoTransactionsAPI from the Aggregator
 pub struct CardanoTransactionClient {
     aggregator_client: Arc<dyn AggregatorClient>,
@@ -94,27 +101,62 @@ impl CardanoTransactionClient {
     }
 
     /// Get proofs that the given subset of transactions is included in the Cardano transactions set.
+    ///
+    /// The hashes are sent to the aggregator in batches of at most
+    /// [MAX_TRANSACTIONS_HASHES_BY_REQUEST] hashes, the proofs of all batches are then merged.
     pub async fn get_proofs<T: ToString>(
         &self,
         transactions_hashes: &[T],
     ) -> MithrilResult<CardanoTransactionsProofs> {
-        match self
+        let transactions_hashes: Vec<String> =
+            transactions_hashes.iter().map(|h| h.to_string()).collect();
+        let mut merged_proofs: Option<CardanoTransactionsProofs> = None;
+
+        for chunk in transactions_hashes.chunks(MAX_TRANSACTIONS_HASHES_BY_REQUEST) {
+            let proofs = self.post_proofs_request(chunk).await?;
+
+            merged_proofs = match merged_proofs {
+                None => Some(proofs),
+                Some(mut merged_proofs) => {
+                    if merged_proofs.certificate_hash != proofs.certificate_hash {
+                        return Err(anyhow!(
+                            "CardanoTransactionProof Client received proofs certified by different certificates ('{}' and '{}'), a new Cardano transactions snapshot was probably certified while requesting the proofs, please retry",
+                            merged_proofs.certificate_hash,
+                            proofs.certificate_hash
+                        ));
+                    }
+                    merged_proofs
+                        .certified_transactions
+                        .extend(proofs.certified_transactions);
+                    merged_proofs
+                        .non_certified_transactions
+                        .extend(proofs.non_certified_transactions);
+                    Some(merged_proofs)
+                }
+            };
+        }
+
+        merged_proofs.ok_or_else(|| {
+            anyhow!("CardanoTransactionProof Client needs at least one transaction hash")
+        })
+    }
+
+    async fn post_proofs_request(
+        &self,
+        transactions_hashes: &[String],
+    ) -> MithrilResult<CardanoTransactionsProofs> {
+        let content = self
             .aggregator_client
-            .get_content(AggregatorRequest::GetTransactionsProofs {
-                transactions_hashes: transactions_hashes.iter().map(|h| h.to_string()).collect(),
+            .post_content(AggregatorRequest::PostTransactionsProofs {
+                request: CardanoTransactionsProofsRequestMessage::new(transactions_hashes),
             })
-            .await
-        {
-            Ok(content) => {
-                let transactions_proofs: CardanoTransactionsProofs = serde_json::from_str(&content)
-                    .with_context(|| {
-                        "CardanoTransactionProof Client can not deserialize transactions proofs"
-                    })?;
+            .await?;
+        let transactions_proofs: CardanoTransactionsProofs = serde_json::from_str(&content)
+            .with_context(|| {
+                "CardanoTransactionProof Client can not deserialize transactions proofs"
+            })?;
 
-                Ok(transactions_proofs)
-            }
-            Err(e) => Err(e.into()),
-        }
+        Ok(transactions_proofs)
     }
 
     /// Fetch a list of signed Cardano transaction snapshots.
@@ -130,6 +172,26 @@ impl CardanoTransactionClient {
         Ok(items)
     }
 
+    /// Fetch a list of signed Cardano transaction snapshots matching the given pagination and filters [parameters][ListQueryParameters]
+    pub async fn list_snapshots_with_parameters(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> MithrilResult<Vec<CardanoTransactionSnapshotListItem>> {
+        let response = self
+            .aggregator_client
+            .get_content(
+                AggregatorRequest::ListCardanoTransactionSnapshotsWithParameters {
+                    parameters: parameters.clone(),
+                },
+            )
+            .await
+            .with_context(|| "CardanoTransactionClient Client can not get the artifact list")?;
+        let items = serde_json::from_str::<Vec<CardanoTransactionSnapshotListItem>>(&response)
+            .with_context(|| "CardanoTransactionClient Client can not deserialize artifact list")?;
+
+        Ok(items)
+    }
+
     /// Get the given Cardano transaction snapshot data. If it cannot be found, a None is returned.
     pub async fn get_snapshot(
         &self,
@@ -159,7 +221,7 @@ impl CardanoTransactionClient {
 #[cfg(test)]
 mod tests {
     use crate::aggregator_client::{AggregatorClientError, MockAggregatorHTTPClient};
-    use crate::common::CardanoDbBeacon;
+    use crate::common::{CardanoDbBeacon, Epoch};
     use crate::{
         CardanoTransactionSnapshot, CardanoTransactionSnapshotListItem, CardanoTransactionsProofs,
         CardanoTransactionsSetProof,
@@ -247,7 +309,7 @@ mod tests {
         );
         let expected_transactions_proofs = transactions_proofs.clone();
         aggregator_client
-            .expect_get_content()
+            .expect_post_content()
             .return_once(move |_| Ok(serde_json::to_string(&transactions_proofs).unwrap()))
             .times(1);
 
@@ -270,7 +332,7 @@ mod tests {
     async fn test_get_proof_ko() {
         let mut aggregator_client = MockAggregatorHTTPClient::new();
         aggregator_client
-            .expect_get_content()
+            .expect_post_content()
             .return_once(move |_| {
                 Err(AggregatorClientError::RemoteServerTechnical(anyhow!(
                     "an error"
@@ -284,4 +346,105 @@ mod tests {
             .await
             .expect_err("The certificate client should fail here.");
     }
+
+    #[tokio::test]
+    async fn get_proofs_split_large_requests_and_merge_their_proofs() {
+        let transactions_hashes: Vec<String> = (0..MAX_TRANSACTIONS_HASHES_BY_REQUEST + 1)
+            .map(|i| format!("tx-{i}"))
+            .collect();
+        let mut aggregator_client = MockAggregatorHTTPClient::new();
+        aggregator_client
+            .expect_post_content()
+            .returning(|request| match request {
+                AggregatorRequest::PostTransactionsProofs { request } => {
+                    let set_proof = CardanoTransactionsSetProof {
+                        transactions_hashes: request.transaction_hashes.clone(),
+                        ..CardanoTransactionsSetProof::dummy()
+                    };
+                    Ok(serde_json::to_string(&CardanoTransactionsProofs::new(
+                        "cert-hash-123",
+                        vec![set_proof],
+                        vec![format!(
+                            "not-certified-{}",
+                            request.transaction_hashes.len()
+                        )],
+                        99999,
+                    ))
+                    .unwrap())
+                }
+                _ => panic!("Unexpected request: {request:?}"),
+            })
+            .times(2);
+
+        let cardano_tx_client = CardanoTransactionClient::new(Arc::new(aggregator_client));
+        let transactions_proofs = cardano_tx_client
+            .get_proofs(&transactions_hashes)
+            .await
+            .unwrap();
+
+        assert_eq!("cert-hash-123", transactions_proofs.certificate_hash);
+        assert_eq!(
+            transactions_hashes,
+            transactions_proofs.transactions_hashes()
+        );
+        assert_eq!(
+            vec![
+                format!("not-certified-{MAX_TRANSACTIONS_HASHES_BY_REQUEST}"),
+                "not-certified-1".to_string()
+            ],
+            transactions_proofs.non_certified_transactions
+        );
+    }
+
+    #[tokio::test]
+    async fn get_proofs_fails_if_the_proofs_of_split_requests_have_different_certificates() {
+        let transactions_hashes: Vec<String> = (0..MAX_TRANSACTIONS_HASHES_BY_REQUEST + 1)
+            .map(|i| format!("tx-{i}"))
+            .collect();
+        let mut aggregator_client = MockAggregatorHTTPClient::new();
+        let mut certificate_index = 0;
+        aggregator_client
+            .expect_post_content()
+            .returning(move |_| {
+                certificate_index += 1;
+                Ok(serde_json::to_string(&CardanoTransactionsProofs::new(
+                    &format!("cert-hash-{certificate_index}"),
+                    vec![],
+                    vec![],
+                    99999,
+                ))
+                .unwrap())
+            })
+            .times(2);
+
+        let cardano_tx_client = CardanoTransactionClient::new(Arc::new(aggregator_client));
+        cardano_tx_client
+            .get_proofs(&transactions_hashes)
+            .await
+            .expect_err("Proofs certified by different certificates should not be merged");
+    }
+
+    #[tokio::test]
+    async fn list_snapshots_with_parameters_sends_them_to_the_aggregator() {
+        let parameters = ListQueryParameters::default()
+            .with_limit(5)
+            .with_from_epoch(Epoch(2));
+        let mut http_client = MockAggregatorHTTPClient::new();
+        http_client
+            .expect_get_content()
+            .with(mockall::predicate::eq(
+                AggregatorRequest::ListCardanoTransactionSnapshotsWithParameters {
+                    parameters: parameters.clone(),
+                },
+            ))
+            .return_once(|_| Ok("[]".to_string()))
+            .once();
+        let client = CardanoTransactionClient::new(Arc::new(http_client));
+        let items = client
+            .list_snapshots_with_parameters(&parameters)
+            .await
+            .unwrap();
+
+        assert!(items.is_empty());
+    }
 }
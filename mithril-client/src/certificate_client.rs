@@ -3,6 +3,7 @@
 //! In order to do so it defines a [CertificateClient] exposes the following features:
 //!  - [get][CertificateClient::get]: get a certificate data from its hash
 //!  - [list][CertificateClient::list]: get the list of available certificates
+//!  - [list_with_parameters][CertificateClient::list_with_parameters]: get a page of the available certificates matching some filters
 //!  - [verify_chain][CertificateClient::verify_chain]: verify a certificate chain
 //!
 //! # Get a certificate
@@ -62,13 +63,13 @@ use async_trait::async_trait;
 use slog::{crit, debug, Logger};
 
 use crate::aggregator_client::{AggregatorClient, AggregatorClientError, AggregatorRequest};
+use crate::certificate_verifier_cache::CertificateVerifierCache;
 use crate::feedback::{FeedbackSender, MithrilEvent};
-use crate::{MithrilCertificate, MithrilCertificateListItem, MithrilResult};
+use crate::{ListQueryParameters, MithrilCertificate, MithrilCertificateListItem, MithrilResult};
 use mithril_common::crypto_helper::ProtocolGenesisVerificationKey;
 use mithril_common::{
     certificate_chain::{
         CertificateRetriever, CertificateRetrieverError,
-        CertificateVerifier as CommonCertificateVerifier,
         MithrilCertificateVerifier as CommonMithrilCertificateVerifier,
     },
     entities::Certificate,
@@ -126,6 +127,24 @@ impl CertificateClient {
         Ok(items)
     }
 
+    /// Fetch a list of certificates matching the given pagination and filters [parameters][ListQueryParameters]
+    pub async fn list_with_parameters(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> MithrilResult<Vec<MithrilCertificateListItem>> {
+        let response = self
+            .aggregator_client
+            .get_content(AggregatorRequest::ListCertificatesWithParameters {
+                parameters: parameters.clone(),
+            })
+            .await
+            .with_context(|| "CertificateClient can not get the certificate list")?;
+        let items = serde_json::from_str::<Vec<MithrilCertificateListItem>>(&response)
+            .with_context(|| "CertificateClient can not deserialize certificate list")?;
+
+        Ok(items)
+    }
+
     /// Get a single certificate full information from the aggregator.
     pub async fn get(&self, certificate_hash: &str) -> MithrilResult<Option<MithrilCertificate>> {
         self.retriever.get(certificate_hash).await
@@ -191,9 +210,11 @@ impl InternalCertificateRetriever {
 /// Implementation of a [CertificateVerifier] that can send feedbacks using
 /// the [feedback][crate::feedback] mechanism.
 pub struct MithrilCertificateVerifier {
-    internal_verifier: Arc<dyn CommonCertificateVerifier>,
+    internal_verifier: Arc<CommonMithrilCertificateVerifier>,
     genesis_verification_key: ProtocolGenesisVerificationKey,
     feedback_sender: FeedbackSender,
+    verifier_cache: Option<Arc<dyn CertificateVerifierCache>>,
+    logger: Logger,
 }
 
 impl MithrilCertificateVerifier {
@@ -209,7 +230,7 @@ impl MithrilCertificateVerifier {
             logger: logger.clone(),
         });
         let internal_verifier = Arc::new(CommonMithrilCertificateVerifier::new(
-            logger,
+            logger.clone(),
             retriever.clone(),
         ));
         let genesis_verification_key =
@@ -220,17 +241,40 @@ impl MithrilCertificateVerifier {
             internal_verifier,
             genesis_verification_key,
             feedback_sender,
+            verifier_cache: None,
+            logger,
         })
     }
+
+    /// Set a [CertificateVerifierCache] so the validation of a chain stops as soon as it reaches a
+    /// certificate which chain has already been verified with the same genesis verification key.
+    pub fn with_verifier_cache(
+        mut self,
+        verifier_cache: Arc<dyn CertificateVerifierCache>,
+    ) -> Self {
+        self.verifier_cache = Some(verifier_cache);
+        self
+    }
+
+    async fn is_verified_in_cache(
+        &self,
+        genesis_verification_key: &str,
+        certificate_hash: &str,
+    ) -> MithrilResult<bool> {
+        match &self.verifier_cache {
+            Some(cache) => cache
+                .is_certificate_verified(genesis_verification_key, certificate_hash)
+                .await
+                .with_context(|| "Could not read the certificate verifier cache"),
+            None => Ok(false),
+        }
+    }
 }
 
 #[cfg_attr(target_family = "wasm", async_trait(?Send))]
 #[cfg_attr(not(target_family = "wasm"), async_trait)]
 impl CertificateVerifier for MithrilCertificateVerifier {
     async fn verify_chain(&self, certificate: &MithrilCertificate) -> MithrilResult<()> {
-        // Todo: move most of this code in the `mithril_common` verifier by defining
-        // a new `verify_chain` method that take a callback called when a certificate is
-        // validated.
         let certificate_chain_validation_id = MithrilEvent::new_certificate_chain_validation_id();
         self.feedback_sender
             .send_event(MithrilEvent::CertificateChainValidationStarted {
@@ -238,29 +282,47 @@ impl CertificateVerifier for MithrilCertificateVerifier {
             })
             .await;
 
-        let mut current_certificate = certificate.clone().try_into()?;
-        loop {
-            let previous_or_none = self
-                .internal_verifier
-                .verify_certificate(&current_certificate, &self.genesis_verification_key)
-                .await?;
-
-            self.feedback_sender
-                .send_event(MithrilEvent::CertificateValidated {
-                    certificate_hash: current_certificate.hash.clone(),
-                    certificate_chain_validation_id: certificate_chain_validation_id.clone(),
-                })
-                .await;
+        let genesis_verification_key = self.genesis_verification_key.to_json_hex()?;
+        // The multi signatures are verified by batches, the verification of the chain stops at
+        // the first certificate whose chain is already verified in the cache.
+        let certificate_chain_validation_id = &certificate_chain_validation_id;
+        let verified_certificate_hashes = self
+            .internal_verifier
+            .verify_certificate_chain_until(
+                certificate.clone().try_into()?,
+                &self.genesis_verification_key,
+                |certificate_hash| {
+                    let genesis_verification_key = &genesis_verification_key;
+                    async move {
+                        self.is_verified_in_cache(genesis_verification_key, &certificate_hash)
+                            .await
+                    }
+                },
+                |certificate_hash| async move {
+                    self.feedback_sender
+                        .send_event(MithrilEvent::CertificateValidated {
+                            certificate_hash,
+                            certificate_chain_validation_id: certificate_chain_validation_id
+                                .clone(),
+                        })
+                        .await;
+                },
+            )
+            .await?;
 
-            match previous_or_none {
-                Some(previous_certificate) => current_certificate = previous_certificate,
-                None => break,
-            }
+        if let Some(cache) = &self.verifier_cache {
+            cache
+                .store_verified_certificates(
+                    &genesis_verification_key,
+                    &verified_certificate_hashes,
+                )
+                .await
+                .with_context(|| "Could not update the certificate verifier cache")?;
         }
 
         self.feedback_sender
             .send_event(MithrilEvent::CertificateChainValidated {
-                certificate_chain_validation_id,
+                certificate_chain_validation_id: certificate_chain_validation_id.clone(),
             })
             .await;
 
@@ -290,11 +352,15 @@ impl CertificateRetriever for InternalCertificateRetriever {
 
 #[cfg(test)]
 mod tests {
-    use mithril_common::crypto_helper::tests_setup::setup_certificate_chain;
+    use mithril_common::certificate_chain::CertificateVerifierError;
+    use mithril_common::crypto_helper::tests_setup::{
+        setup_certificate_chain, tamper_certificate_chain_multi_signature,
+    };
     use mithril_common::test_utils::fake_data;
     use mockall::predicate::eq;
 
     use crate::aggregator_client::MockAggregatorHTTPClient;
+    use crate::common::Epoch;
     use crate::feedback::StackFeedbackReceiver;
     use crate::test_utils;
 
@@ -516,4 +582,267 @@ mod tests {
 
         assert_eq!(certificate.hash, last_certificate_hash);
     }
+
+    async fn assert_verify_chain_reports_the_certificate_with_an_invalid_multi_signature(
+        chain_length: u64,
+        index_certificate_fail: usize,
+    ) {
+        let (mut chain, verifier) = setup_certificate_chain(chain_length, 1);
+        let verification_key: String = verifier.to_verification_key().try_into().unwrap();
+        tamper_certificate_chain_multi_signature(&mut chain, index_certificate_fail);
+        let mut aggregator_client = MockAggregatorHTTPClient::new();
+
+        for certificate in chain.clone() {
+            let hash = certificate.hash.clone();
+            let message = serde_json::to_string(
+                &TryInto::<CertificateMessage>::try_into(certificate).unwrap(),
+            )
+            .unwrap();
+            aggregator_client
+                .expect_get_content()
+                .with(eq(AggregatorRequest::GetCertificate { hash }))
+                .returning(move |_| Ok(message.to_owned()));
+        }
+
+        let aggregator_client = Arc::new(aggregator_client);
+        let certificate_client = build_client(
+            aggregator_client.clone(),
+            Some(Arc::new(
+                MithrilCertificateVerifier::new(
+                    aggregator_client,
+                    &verification_key,
+                    FeedbackSender::new(&[]),
+                    test_utils::test_logger(),
+                )
+                .unwrap(),
+            )),
+        );
+
+        let error = certificate_client
+            .verify_chain(&chain[0].hash)
+            .await
+            .expect_err("Chain validation should fail for an invalid multi signature");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<CertificateVerifierError>(),
+                Some(CertificateVerifierError::VerifyCertificateChainMultiSignature { certificate_hash, .. })
+                if certificate_hash == &chain[index_certificate_fail].hash
+            ),
+            "unexpected error type for the certificate {index_certificate_fail}: {error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn verify_chain_reports_the_first_certificate_with_an_invalid_multi_signature() {
+        for index_certificate_fail in [0, 2, 3] {
+            assert_verify_chain_reports_the_certificate_with_an_invalid_multi_signature(
+                5,
+                index_certificate_fail,
+            )
+            .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn list_with_parameters_sends_them_to_the_aggregator() {
+        let parameters = ListQueryParameters::default()
+            .with_limit(5)
+            .with_from_epoch(Epoch(2));
+        let mut http_client = MockAggregatorHTTPClient::new();
+        http_client
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListCertificatesWithParameters {
+                parameters: parameters.clone(),
+            }))
+            .return_once(|_| Ok("[]".to_string()))
+            .once();
+        let client = build_client(Arc::new(http_client), None);
+        let items = client.list_with_parameters(&parameters).await.unwrap();
+
+        assert!(items.is_empty());
+    }
+
+    mod verifier_cache {
+        use mithril_common::entities::Certificate;
+
+        use crate::certificate_verifier_cache::MemoryCertificateVerifierCache;
+
+        use super::*;
+
+        fn aggregator_client_serving(chain: &[Certificate]) -> MockAggregatorHTTPClient {
+            let mut aggregator_client = MockAggregatorHTTPClient::new();
+            for certificate in chain.iter().cloned() {
+                let hash = certificate.hash.clone();
+                let message = serde_json::to_string(
+                    &TryInto::<CertificateMessage>::try_into(certificate).unwrap(),
+                )
+                .unwrap();
+                aggregator_client
+                    .expect_get_content()
+                    .with(eq(AggregatorRequest::GetCertificate { hash }))
+                    .returning(move |_| Ok(message.to_owned()));
+            }
+
+            aggregator_client
+        }
+
+        fn build_client_with_cache(
+            aggregator_client: MockAggregatorHTTPClient,
+            verification_key: &str,
+            feedback_receiver: Arc<StackFeedbackReceiver>,
+            cache: Arc<dyn CertificateVerifierCache>,
+        ) -> CertificateClient {
+            let aggregator_client = Arc::new(aggregator_client);
+            build_client(
+                aggregator_client.clone(),
+                Some(Arc::new(
+                    MithrilCertificateVerifier::new(
+                        aggregator_client,
+                        verification_key,
+                        FeedbackSender::new(&[feedback_receiver]),
+                        test_utils::test_logger(),
+                    )
+                    .unwrap()
+                    .with_verifier_cache(cache),
+                )),
+            )
+        }
+
+        fn validated_hashes(feedback_receiver: &StackFeedbackReceiver) -> Vec<String> {
+            feedback_receiver
+                .stacked_events()
+                .into_iter()
+                .filter_map(|event| match event {
+                    MithrilEvent::CertificateValidated {
+                        certificate_hash, ..
+                    } => Some(certificate_hash),
+                    _ => None,
+                })
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn verify_chain_store_verified_certificates_in_cache() {
+            let (chain, verifier) = setup_certificate_chain(3, 1);
+            let verification_key: String = verifier.to_verification_key().try_into().unwrap();
+            let cache = Arc::new(MemoryCertificateVerifierCache::new());
+            let certificate_client = build_client_with_cache(
+                aggregator_client_serving(&chain),
+                &verification_key,
+                Arc::new(StackFeedbackReceiver::new()),
+                cache.clone(),
+            );
+
+            certificate_client
+                .verify_chain(&chain[0].hash)
+                .await
+                .expect("Chain validation should succeed");
+
+            let genesis_verification_key = verifier.to_verification_key().to_json_hex().unwrap();
+            for certificate in &chain {
+                assert!(
+                    cache
+                        .is_certificate_verified(&genesis_verification_key, &certificate.hash)
+                        .await
+                        .unwrap(),
+                    "Certificate '{}' should be in the cache",
+                    certificate.hash
+                );
+            }
+        }
+
+        #[tokio::test]
+        async fn verify_chain_stops_at_a_certificate_already_verified() {
+            let (chain, verifier) = setup_certificate_chain(5, 1);
+            let verification_key: String = verifier.to_verification_key().try_into().unwrap();
+            let cache = Arc::new(MemoryCertificateVerifierCache::new());
+            cache
+                .store_verified_certificates(
+                    &verifier.to_verification_key().to_json_hex().unwrap(),
+                    &[chain[1].hash.clone()],
+                )
+                .await
+                .unwrap();
+            let feedback_receiver = Arc::new(StackFeedbackReceiver::new());
+            let certificate_client = build_client_with_cache(
+                aggregator_client_serving(&chain),
+                &verification_key,
+                feedback_receiver.clone(),
+                cache,
+            );
+
+            certificate_client
+                .verify_chain(&chain[0].hash)
+                .await
+                .expect("Chain validation should succeed");
+
+            assert_eq!(
+                vec![chain[0].hash.clone()],
+                validated_hashes(&feedback_receiver)
+            );
+        }
+
+        #[tokio::test]
+        async fn verify_chain_ignores_certificates_verified_with_another_genesis_key() {
+            let (chain, verifier) = setup_certificate_chain(3, 1);
+            let verification_key: String = verifier.to_verification_key().try_into().unwrap();
+            let cache = Arc::new(MemoryCertificateVerifierCache::new());
+            cache
+                .store_verified_certificates("another-genesis-key", &[chain[1].hash.clone()])
+                .await
+                .unwrap();
+            let feedback_receiver = Arc::new(StackFeedbackReceiver::new());
+            let certificate_client = build_client_with_cache(
+                aggregator_client_serving(&chain),
+                &verification_key,
+                feedback_receiver.clone(),
+                cache,
+            );
+
+            certificate_client
+                .verify_chain(&chain[0].hash)
+                .await
+                .expect("Chain validation should succeed");
+
+            assert_eq!(
+                chain.iter().map(|c| c.hash.clone()).collect::<Vec<_>>(),
+                validated_hashes(&feedback_receiver)
+            );
+        }
+
+        #[tokio::test]
+        async fn verify_chain_fails_for_a_tampered_certificate_whose_hash_is_cached() {
+            let (mut chain, verifier) = setup_certificate_chain(3, 1);
+            let verification_key: String = verifier.to_verification_key().try_into().unwrap();
+            let cache = Arc::new(MemoryCertificateVerifierCache::new());
+            cache
+                .store_verified_certificates(
+                    &verifier.to_verification_key().to_json_hex().unwrap(),
+                    &[chain[0].hash.clone()],
+                )
+                .await
+                .unwrap();
+            chain[0].signed_message = "tampered signed message".to_string();
+            let certificate_client = build_client_with_cache(
+                aggregator_client_serving(&chain),
+                &verification_key,
+                Arc::new(StackFeedbackReceiver::new()),
+                cache,
+            );
+
+            let error = certificate_client
+                .verify_chain(&chain[0].hash)
+                .await
+                .expect_err("Chain validation should fail for a tampered certificate");
+
+            assert!(
+                matches!(
+                    error.downcast_ref::<CertificateVerifierError>(),
+                    Some(CertificateVerifierError::CertificateHashUnmatch)
+                ),
+                "unexpected error type: {error:?}"
+            );
+        }
+    }
 }
@@ -0,0 +1,300 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use slog::{warn, Logger};
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+use crate::certificate_verifier_cache::{CertificateVerifierCache, VerifiedCertificates};
+use crate::MithrilResult;
+
+/// A [CertificateVerifierCache] that keeps the verified certificates in a JSON file so they
+/// survive restarts of the client.
+///
+/// The file is read once, on the first access, and is replaced atomically on each store. A
+/// missing, unreadable or corrupted file is read as an empty cache.
+pub struct JsonFileCertificateVerifierCache {
+    file_path: PathBuf,
+    verified_certificates: Mutex<Option<VerifiedCertificates>>,
+    logger: Logger,
+}
+
+impl JsonFileCertificateVerifierCache {
+    /// Constructs a new `JsonFileCertificateVerifierCache` that reads and writes the given file.
+    pub fn new(file_path: &Path, logger: Logger) -> Self {
+        Self {
+            file_path: file_path.to_path_buf(),
+            verified_certificates: Mutex::new(None),
+            logger,
+        }
+    }
+
+    /// Read the file, falling back to an empty cache if it can not be read
+    async fn load(&self) -> VerifiedCertificates {
+        match self.read().await {
+            Ok(verified_certificates) => verified_certificates,
+            Err(error) => {
+                warn!(
+                    self.logger,
+                    "Could not load the certificate verifier cache, it is considered empty";
+                    "error" => ?error
+                );
+                VerifiedCertificates::default()
+            }
+        }
+    }
+
+    async fn read(&self) -> MithrilResult<VerifiedCertificates> {
+        if !self.file_path.exists() {
+            return Ok(VerifiedCertificates::default());
+        }
+
+        let content = tokio::fs::read_to_string(&self.file_path)
+            .await
+            .with_context(|| {
+                format!(
+                    "Could not read certificate verifier cache file: '{}'",
+                    self.file_path.display()
+                )
+            })?;
+        serde_json::from_str(&content).with_context(|| {
+            format!(
+                "Could not deserialize certificate verifier cache file: '{}'",
+                self.file_path.display()
+            )
+        })
+    }
+
+    /// Write the cache in a temporary file then rename it over the cache file, so the cache
+    /// file is never left partially written
+    async fn write(&self, verified_certificates: &VerifiedCertificates) -> MithrilResult<()> {
+        if let Some(parent) = self.file_path.parent() {
+            tokio::fs::create_dir_all(parent).await.with_context(|| {
+                format!(
+                    "Could not create certificate verifier cache directory: '{}'",
+                    parent.display()
+                )
+            })?;
+        }
+
+        let mut temp_file_path = self.file_path.clone().into_os_string();
+        temp_file_path.push(".tmp");
+        let temp_file_path = PathBuf::from(temp_file_path);
+        tokio::fs::write(
+            &temp_file_path,
+            serde_json::to_string(verified_certificates)?,
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "Could not write certificate verifier cache temporary file: '{}'",
+                temp_file_path.display()
+            )
+        })?;
+        tokio::fs::rename(&temp_file_path, &self.file_path)
+            .await
+            .with_context(|| {
+                format!(
+                    "Could not write certificate verifier cache file: '{}'",
+                    self.file_path.display()
+                )
+            })
+    }
+}
+
+#[async_trait]
+impl CertificateVerifierCache for JsonFileCertificateVerifierCache {
+    async fn store_verified_certificates(
+        &self,
+        genesis_verification_key: &str,
+        certificate_hashes: &[String],
+    ) -> MithrilResult<()> {
+        let mut cached_verified_certificates = self.verified_certificates.lock().await;
+        let mut verified_certificates = match cached_verified_certificates.as_ref() {
+            Some(verified_certificates) => verified_certificates.clone(),
+            None => self.load().await,
+        };
+        verified_certificates.insert(genesis_verification_key, certificate_hashes);
+        self.write(&verified_certificates).await?;
+        *cached_verified_certificates = Some(verified_certificates);
+
+        Ok(())
+    }
+
+    async fn is_certificate_verified(
+        &self,
+        genesis_verification_key: &str,
+        certificate_hash: &str,
+    ) -> MithrilResult<bool> {
+        let mut cached_verified_certificates = self.verified_certificates.lock().await;
+        if cached_verified_certificates.is_none() {
+            *cached_verified_certificates = Some(self.load().await);
+        }
+
+        Ok(cached_verified_certificates
+            .as_ref()
+            .is_some_and(|verified_certificates| {
+                verified_certificates.contains(genesis_verification_key, certificate_hash)
+            }))
+    }
+
+    async fn reset(&self) -> MithrilResult<()> {
+        let mut cached_verified_certificates = self.verified_certificates.lock().await;
+        let verified_certificates = VerifiedCertificates::default();
+        self.write(&verified_certificates).await?;
+        *cached_verified_certificates = Some(verified_certificates);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::TempDir;
+
+    use crate::test_utils;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn missing_file_is_read_as_an_empty_cache() {
+        let file_path =
+            TempDir::create("json_file_cache", "missing_file_is_read_as_an_empty_cache")
+                .join("cache.json");
+        let cache = JsonFileCertificateVerifierCache::new(&file_path, test_utils::test_logger());
+
+        assert!(!cache
+            .is_certificate_verified("genesis-key", "hash-1")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn verified_certificates_are_persisted_between_instances() {
+        let file_path = TempDir::create(
+            "json_file_cache",
+            "verified_certificates_are_persisted_between_instances",
+        )
+        .join("cache.json");
+        JsonFileCertificateVerifierCache::new(&file_path, test_utils::test_logger())
+            .store_verified_certificates("genesis-key", &["hash-1".to_string()])
+            .await
+            .unwrap();
+
+        let cache = JsonFileCertificateVerifierCache::new(&file_path, test_utils::test_logger());
+        assert!(cache
+            .is_certificate_verified("genesis-key", "hash-1")
+            .await
+            .unwrap());
+        assert!(!cache
+            .is_certificate_verified("another-genesis-key", "hash-1")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn storing_with_another_genesis_verification_key_invalidates_the_cache() {
+        let file_path = TempDir::create(
+            "json_file_cache",
+            "storing_with_another_genesis_verification_key_invalidates_the_cache",
+        )
+        .join("cache.json");
+        let cache = JsonFileCertificateVerifierCache::new(&file_path, test_utils::test_logger());
+        cache
+            .store_verified_certificates("genesis-key", &["hash-1".to_string()])
+            .await
+            .unwrap();
+        cache
+            .store_verified_certificates("another-genesis-key", &["hash-2".to_string()])
+            .await
+            .unwrap();
+
+        assert!(!cache
+            .is_certificate_verified("another-genesis-key", "hash-1")
+            .await
+            .unwrap());
+        assert!(cache
+            .is_certificate_verified("another-genesis-key", "hash-2")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn reset_remove_all_verified_certificates() {
+        let file_path =
+            TempDir::create("json_file_cache", "reset_remove_all_verified_certificates")
+                .join("cache.json");
+        let cache = JsonFileCertificateVerifierCache::new(&file_path, test_utils::test_logger());
+        cache
+            .store_verified_certificates("genesis-key", &["hash-1".to_string()])
+            .await
+            .unwrap();
+        cache.reset().await.unwrap();
+
+        assert!(!cache
+            .is_certificate_verified("genesis-key", "hash-1")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn corrupted_file_is_read_as_an_empty_cache() {
+        let file_path = TempDir::create(
+            "json_file_cache",
+            "corrupted_file_is_read_as_an_empty_cache",
+        )
+        .join("cache.json");
+        std::fs::write(&file_path, "{ not json").unwrap();
+        let cache = JsonFileCertificateVerifierCache::new(&file_path, test_utils::test_logger());
+
+        assert!(!cache
+            .is_certificate_verified("genesis-key", "hash-1")
+            .await
+            .unwrap());
+
+        cache
+            .store_verified_certificates("genesis-key", &["hash-1".to_string()])
+            .await
+            .unwrap();
+        assert!(
+            JsonFileCertificateVerifierCache::new(&file_path, test_utils::test_logger())
+                .is_certificate_verified("genesis-key", "hash-1")
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn file_is_read_once() {
+        let file_path = TempDir::create("json_file_cache", "file_is_read_once").join("cache.json");
+        let cache = JsonFileCertificateVerifierCache::new(&file_path, test_utils::test_logger());
+        cache
+            .store_verified_certificates("genesis-key", &["hash-1".to_string()])
+            .await
+            .unwrap();
+        std::fs::remove_file(&file_path).unwrap();
+
+        assert!(cache
+            .is_certificate_verified("genesis-key", "hash-1")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn no_temporary_file_is_left_after_a_store() {
+        let dir = TempDir::create("json_file_cache", "no_temporary_file_is_left_after_a_store");
+        let cache = JsonFileCertificateVerifierCache::new(
+            &dir.join("cache.json"),
+            test_utils::test_logger(),
+        );
+        cache
+            .store_verified_certificates("genesis-key", &["hash-1".to_string()])
+            .await
+            .unwrap();
+
+        let files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(vec!["cache.json"], files);
+    }
+}
@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::certificate_verifier_cache::{CertificateVerifierCache, VerifiedCertificates};
+use crate::MithrilResult;
+
+/// A [CertificateVerifierCache] that keeps the verified certificates in memory, its content is
+/// lost when it's dropped.
+#[derive(Default)]
+pub struct MemoryCertificateVerifierCache {
+    verified_certificates: RwLock<VerifiedCertificates>,
+}
+
+impl MemoryCertificateVerifierCache {
+    /// Constructs a new empty `MemoryCertificateVerifierCache`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl CertificateVerifierCache for MemoryCertificateVerifierCache {
+    async fn store_verified_certificates(
+        &self,
+        genesis_verification_key: &str,
+        certificate_hashes: &[String],
+    ) -> MithrilResult<()> {
+        self.verified_certificates
+            .write()
+            .await
+            .insert(genesis_verification_key, certificate_hashes);
+
+        Ok(())
+    }
+
+    async fn is_certificate_verified(
+        &self,
+        genesis_verification_key: &str,
+        certificate_hash: &str,
+    ) -> MithrilResult<bool> {
+        Ok(self
+            .verified_certificates
+            .read()
+            .await
+            .contains(genesis_verification_key, certificate_hash))
+    }
+
+    async fn reset(&self) -> MithrilResult<()> {
+        *self.verified_certificates.write().await = VerifiedCertificates::default();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn store_and_check_verified_certificates() {
+        let cache = MemoryCertificateVerifierCache::new();
+        cache
+            .store_verified_certificates("genesis-key", &["hash-1".to_string()])
+            .await
+            .unwrap();
+
+        assert!(cache
+            .is_certificate_verified("genesis-key", "hash-1")
+            .await
+            .unwrap());
+        assert!(!cache
+            .is_certificate_verified("genesis-key", "hash-2")
+            .await
+            .unwrap());
+        assert!(!cache
+            .is_certificate_verified("another-genesis-key", "hash-1")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn reset_remove_all_verified_certificates() {
+        let cache = MemoryCertificateVerifierCache::new();
+        cache
+            .store_verified_certificates("genesis-key", &["hash-1".to_string()])
+            .await
+            .unwrap();
+        cache.reset().await.unwrap();
+
+        assert!(!cache
+            .is_certificate_verified("genesis-key", "hash-1")
+            .await
+            .unwrap());
+    }
+}
@@ -0,0 +1,123 @@
+//! Caches of the certificates whose chain has already been verified.
+//!
+//! A [CertificateVerifierCache] allows the [MithrilCertificateVerifier][crate::certificate_client::MithrilCertificateVerifier]
+//! to stop the validation of a certificate chain as soon as it reaches a certificate which chain
+//! has already been verified against the same genesis verification key.
+//!
+//! The following implementations are available:
+//!  - [MemoryCertificateVerifierCache]: keeps the verified certificates in memory.
+//!  - `JsonFileCertificateVerifierCache`: keeps the verified certificates in a JSON file on disk
+//!    (requires the **fs** feature).
+//!
+//! # Use a cache when verifying certificate chains
+//!
+//! ```no_run
+//! # async fn run() -> mithril_client::MithrilResult<()> {
+//! use std::sync::Arc;
+//! use mithril_client::ClientBuilder;
+//! use mithril_client::certificate_verifier_cache::MemoryCertificateVerifierCache;
+//!
+//! let client = ClientBuilder::aggregator("YOUR_AGGREGATOR_ENDPOINT", "YOUR_GENESIS_VERIFICATION_KEY")
+//!     .with_certificate_verifier_cache(Arc::new(MemoryCertificateVerifierCache::new()))
+//!     .build()?;
+//! let certificate = client.certificate().verify_chain("CERTIFICATE_HASH").await?;
+//!
+//! println!("Chain of Certificate (hash: {}) is valid", certificate.hash);
+//! #    Ok(())
+//! # }
+//! ```
+
+mod memory_cache;
+pub use memory_cache::*;
+
+cfg_fs! {
+    mod json_file_cache;
+    pub use json_file_cache::*;
+}
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+#[cfg(test)]
+use mockall::automock;
+
+use crate::MithrilResult;
+
+/// API that defines a cache of the certificates whose chain has been verified.
+#[cfg_attr(test, automock)]
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+pub trait CertificateVerifierCache: Sync + Send {
+    /// Store the hashes of certificates whose chain has been verified against the given
+    /// genesis verification key.
+    ///
+    /// All the previously stored hashes must be discarded if they were verified against another
+    /// genesis verification key.
+    async fn store_verified_certificates(
+        &self,
+        genesis_verification_key: &str,
+        certificate_hashes: &[String],
+    ) -> MithrilResult<()>;
+
+    /// Check if the chain of the given certificate has already been verified against the given
+    /// genesis verification key.
+    async fn is_certificate_verified(
+        &self,
+        genesis_verification_key: &str,
+        certificate_hash: &str,
+    ) -> MithrilResult<bool>;
+
+    /// Remove all the stored hashes.
+    async fn reset(&self) -> MithrilResult<()>;
+}
+
+/// Hashes of the certificates verified against a genesis verification key, shared by the
+/// cache implementations.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct VerifiedCertificates {
+    genesis_verification_key: String,
+    certificate_hashes: BTreeSet<String>,
+}
+
+impl VerifiedCertificates {
+    fn contains(&self, genesis_verification_key: &str, certificate_hash: &str) -> bool {
+        self.genesis_verification_key == genesis_verification_key
+            && self.certificate_hashes.contains(certificate_hash)
+    }
+
+    fn insert(&mut self, genesis_verification_key: &str, certificate_hashes: &[String]) {
+        if self.genesis_verification_key != genesis_verification_key {
+            self.genesis_verification_key = genesis_verification_key.to_string();
+            self.certificate_hashes.clear();
+        }
+        self.certificate_hashes
+            .extend(certificate_hashes.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_only_hashes_verified_with_the_same_genesis_verification_key() {
+        let mut verified_certificates = VerifiedCertificates::default();
+        verified_certificates.insert("genesis-key", &["hash-1".to_string()]);
+
+        assert!(verified_certificates.contains("genesis-key", "hash-1"));
+        assert!(!verified_certificates.contains("genesis-key", "hash-2"));
+        assert!(!verified_certificates.contains("another-genesis-key", "hash-1"));
+    }
+
+    #[test]
+    fn inserting_with_another_genesis_verification_key_discards_previous_hashes() {
+        let mut verified_certificates = VerifiedCertificates::default();
+        verified_certificates.insert("genesis-key", &["hash-1".to_string()]);
+        verified_certificates.insert("another-genesis-key", &["hash-2".to_string()]);
+
+        assert!(!verified_certificates.contains("genesis-key", "hash-1"));
+        assert!(!verified_certificates.contains("another-genesis-key", "hash-1"));
+        assert!(verified_certificates.contains("another-genesis-key", "hash-2"));
+    }
+}
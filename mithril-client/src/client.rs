@@ -2,15 +2,25 @@ use anyhow::{anyhow, Context};
 use mithril_common::api_version::APIVersionProvider;
 use reqwest::Url;
 use slog::{o, Logger};
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+#[cfg(not(target_family = "wasm"))]
+use std::time::Duration;
 
 use crate::aggregator_client::{AggregatorClient, AggregatorHTTPClient};
 #[cfg(feature = "unstable")]
+use crate::cardano_stake_distribution_client::CardanoStakeDistributionClient;
+#[cfg(feature = "unstable")]
 use crate::cardano_transaction_client::CardanoTransactionClient;
 use crate::certificate_client::{
     CertificateClient, CertificateVerifier, MithrilCertificateVerifier,
 };
+use crate::certificate_verifier_cache::CertificateVerifierCache;
 use crate::feedback::{FeedbackReceiver, FeedbackSender};
+use crate::http_client_options::HttpClientOptions;
+#[cfg(not(target_family = "wasm"))]
+use crate::http_client_options::RetryPolicy;
 use crate::mithril_stake_distribution_client::MithrilStakeDistributionClient;
 use crate::snapshot_client::SnapshotClient;
 #[cfg(feature = "fs")]
@@ -22,6 +32,8 @@ use crate::MithrilResult;
 /// Use the [ClientBuilder] to instantiate it easily.
 #[derive(Clone)]
 pub struct Client {
+    #[cfg(feature = "unstable")]
+    cardano_stake_distribution_client: Arc<CardanoStakeDistributionClient>,
     #[cfg(feature = "unstable")]
     cardano_transaction_client: Arc<CardanoTransactionClient>,
     certificate_client: Arc<CertificateClient>,
@@ -30,6 +42,12 @@ pub struct Client {
 }
 
 impl Client {
+    /// Get the client that fetches Cardano stake distributions.
+    #[cfg(feature = "unstable")]
+    pub fn cardano_stake_distribution(&self) -> Arc<CardanoStakeDistributionClient> {
+        self.cardano_stake_distribution_client.clone()
+    }
+
     /// Get the client that fetches and verifies Mithril Cardano transaction proof.
     #[cfg(feature = "unstable")]
     pub fn cardano_transaction(&self) -> Arc<CardanoTransactionClient> {
@@ -58,8 +76,12 @@ pub struct ClientBuilder {
     genesis_verification_key: String,
     aggregator_client: Option<Arc<dyn AggregatorClient>>,
     certificate_verifier: Option<Arc<dyn CertificateVerifier>>,
+    certificate_verifier_cache: Option<Arc<dyn CertificateVerifierCache>>,
     #[cfg(feature = "fs")]
     snapshot_downloader: Option<Arc<dyn SnapshotDownloader>>,
+    #[cfg(feature = "fs")]
+    resumable_snapshot_download: Option<(PathBuf, u32)>,
+    http_client_options: HttpClientOptions,
     logger: Option<Logger>,
     feedback_receivers: Vec<Arc<dyn FeedbackReceiver>>,
 }
@@ -73,8 +95,12 @@ impl ClientBuilder {
             genesis_verification_key: genesis_verification_key.to_string(),
             aggregator_client: None,
             certificate_verifier: None,
+            certificate_verifier_cache: None,
             #[cfg(feature = "fs")]
             snapshot_downloader: None,
+            #[cfg(feature = "fs")]
+            resumable_snapshot_download: None,
+            http_client_options: HttpClientOptions::default(),
             logger: None,
             feedback_receivers: vec![],
         }
@@ -90,8 +116,12 @@ impl ClientBuilder {
             genesis_verification_key: genesis_verification_key.to_string(),
             aggregator_client: None,
             certificate_verifier: None,
+            certificate_verifier_cache: None,
             #[cfg(feature = "fs")]
             snapshot_downloader: None,
+            #[cfg(feature = "fs")]
+            resumable_snapshot_download: None,
+            http_client_options: HttpClientOptions::default(),
             logger: None,
             feedback_receivers: vec![],
         }
@@ -118,11 +148,12 @@ impl ClientBuilder {
                     .with_context(|| format!("Invalid aggregator endpoint, it must be a correctly formed url: '{endpoint}'"))?;
 
                 Arc::new(
-                    AggregatorHTTPClient::new(
+                    AggregatorHTTPClient::new_with_options(
                         endpoint_url,
                         APIVersionProvider::compute_all_versions_sorted()
                             .with_context(|| "Could not compute aggregator api versions")?,
                         logger.clone(),
+                        self.http_client_options.clone(),
                     )
                     .with_context(|| "Building aggregator client failed")?,
                 )
@@ -132,10 +163,20 @@ impl ClientBuilder {
 
         #[cfg(feature = "fs")]
         let snapshot_downloader = match self.snapshot_downloader {
-            None => Arc::new(
-                HttpSnapshotDownloader::new(feedback_sender.clone(), logger.clone())
-                    .with_context(|| "Building snapshot downloader failed")?,
-            ),
+            None => {
+                let mut snapshot_downloader = HttpSnapshotDownloader::new_with_options(
+                    feedback_sender.clone(),
+                    logger.clone(),
+                    self.http_client_options.clone(),
+                )
+                .with_context(|| "Building snapshot downloader failed")?;
+                if let Some((temp_directory, max_attempts)) = &self.resumable_snapshot_download {
+                    snapshot_downloader =
+                        snapshot_downloader.with_resumable_download(temp_directory, *max_attempts);
+                }
+
+                Arc::new(snapshot_downloader)
+            }
             Some(snapshot_downloader) => snapshot_downloader,
         };
 
@@ -143,16 +184,26 @@ impl ClientBuilder {
         let cardano_transaction_client =
             Arc::new(CardanoTransactionClient::new(aggregator_client.clone()));
 
+        #[cfg(feature = "unstable")]
+        let cardano_stake_distribution_client = Arc::new(CardanoStakeDistributionClient::new(
+            aggregator_client.clone(),
+        ));
+
         let certificate_verifier = match self.certificate_verifier {
-            None => Arc::new(
-                MithrilCertificateVerifier::new(
+            None => {
+                let verifier = MithrilCertificateVerifier::new(
                     aggregator_client.clone(),
                     &self.genesis_verification_key,
                     feedback_sender.clone(),
                     logger.clone(),
                 )
-                .with_context(|| "Building certificate verifier failed")?,
-            ),
+                .with_context(|| "Building certificate verifier failed")?;
+
+                match self.certificate_verifier_cache {
+                    Some(cache) => Arc::new(verifier.with_verifier_cache(cache)),
+                    None => Arc::new(verifier),
+                }
+            }
             Some(verifier) => verifier,
         };
         let certificate_client = Arc::new(CertificateClient::new(
@@ -175,6 +226,8 @@ impl ClientBuilder {
         ));
 
         Ok(Client {
+            #[cfg(feature = "unstable")]
+            cardano_stake_distribution_client,
             #[cfg(feature = "unstable")]
             cardano_transaction_client,
             certificate_client,
@@ -201,6 +254,18 @@ impl ClientBuilder {
         self
     }
 
+    /// Set the [CertificateVerifierCache] that the default certificate verifier will use to stop
+    /// the validation of a certificate chain as soon as it reaches an already verified certificate.
+    ///
+    /// This has no effect if a custom [CertificateVerifier] is set.
+    pub fn with_certificate_verifier_cache(
+        mut self,
+        certificate_verifier_cache: Arc<dyn CertificateVerifierCache>,
+    ) -> ClientBuilder {
+        self.certificate_verifier_cache = Some(certificate_verifier_cache);
+        self
+    }
+
     cfg_fs! {
     /// Set the [SnapshotDownloader] that will be used to download snapshots.
     pub fn with_snapshot_downloader(
@@ -210,6 +275,60 @@ impl ClientBuilder {
         self.snapshot_downloader = Some(snapshot_downloader);
         self
     }
+
+    /// Make the default snapshot downloader download archives to a temporary file in the given
+    /// directory so interrupted downloads can be resumed, see
+    /// [HttpSnapshotDownloader::with_resumable_download].
+    ///
+    /// This has no effect if a custom [SnapshotDownloader] is set.
+    pub fn with_resumable_snapshot_download(
+        mut self,
+        temp_directory: &Path,
+        max_attempts: u32,
+    ) -> ClientBuilder {
+        self.resumable_snapshot_download = Some((temp_directory.to_path_buf(), max_attempts));
+        self
+    }
+    }
+
+    /// Set the [HttpClientOptions] used by the default aggregator client and snapshot downloader.
+    ///
+    /// This has no effect on a custom [AggregatorClient] or [SnapshotDownloader][crate::snapshot_downloader::SnapshotDownloader].
+    pub fn with_http_client_options(mut self, http_client_options: HttpClientOptions) -> Self {
+        self.http_client_options = http_client_options;
+        self
+    }
+
+    /// Add a header to every HTTP request, ie: an API key expected by a gateway in front of the
+    /// aggregator.
+    pub fn with_http_header(mut self, name: &str, value: &str) -> Self {
+        self.http_client_options
+            .headers
+            .insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Set the [RetryPolicy] used to retry the HTTP requests that failed because of a connection
+    /// error or a server error (5xx).
+    #[cfg(not(target_family = "wasm"))]
+    pub fn with_http_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.http_client_options.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the timeout of the HTTP requests to the aggregator, for snapshot downloads it is
+    /// the maximum duration to connect or between two reads of the archive.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn with_http_timeout(mut self, timeout: Duration) -> Self {
+        self.http_client_options.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the URL of a proxy that all HTTP requests go through.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn with_http_proxy(mut self, proxy_url: &str) -> Self {
+        self.http_client_options.proxy = Some(proxy_url.to_string());
+        self
     }
 
     /// Set the [Logger] to use.
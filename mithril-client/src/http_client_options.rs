@@ -0,0 +1,323 @@
+//! Options of the HTTP clients used to request the aggregator and to download snapshots.
+//!
+//! [HttpClientOptions] allow to add custom headers to every request (ie: an API key expected
+//! by a gateway in front of the aggregator) and, except when targeting WebAssembly, to set a
+//! [retry policy][RetryPolicy], a request timeout and a proxy.
+//!
+//! # Configure the HTTP clients of a Client
+//!
+//! ```no_run
+//! # async fn run() -> mithril_client::MithrilResult<()> {
+//! use std::time::Duration;
+//! use mithril_client::ClientBuilder;
+//! use mithril_client::http_client_options::RetryPolicy;
+//!
+//! let client = ClientBuilder::aggregator("YOUR_AGGREGATOR_ENDPOINT", "YOUR_GENESIS_VERIFICATION_KEY")
+//!     .with_http_header("x-api-key", "YOUR_API_KEY")
+//!     .with_http_retry_policy(RetryPolicy::new(3, Duration::from_millis(500), Duration::from_secs(10)))
+//!     .with_http_timeout(Duration::from_secs(30))
+//!     .with_http_proxy("http://proxy.local:3128")
+//!     .build()?;
+//! #    Ok(())
+//! # }
+//! ```
+
+use anyhow::{anyhow, Context};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+#[cfg(not(target_family = "wasm"))]
+use reqwest::{RequestBuilder, Response};
+#[cfg(not(target_family = "wasm"))]
+use slog::{warn, Logger};
+use std::collections::BTreeMap;
+#[cfg(not(target_family = "wasm"))]
+use std::time::Duration;
+
+use crate::MithrilResult;
+
+/// Options applied to the HTTP clients used to request the aggregator and to download snapshots.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HttpClientOptions {
+    /// Headers added to every request
+    pub headers: BTreeMap<String, String>,
+
+    /// Policy used to retry the requests that failed because of a connection error or a server
+    /// error (5xx)
+    #[cfg(not(target_family = "wasm"))]
+    pub retry_policy: RetryPolicy,
+
+    /// Timeout of the requests to the aggregator.
+    ///
+    /// For snapshot downloads it's used as the maximum duration between two reads of the archive
+    /// instead, since downloading a whole archive can take much longer.
+    #[cfg(not(target_family = "wasm"))]
+    pub timeout: Option<Duration>,
+
+    /// URL of the proxy that all requests go through
+    #[cfg(not(target_family = "wasm"))]
+    pub proxy: Option<String>,
+}
+
+impl HttpClientOptions {
+    fn header_map(&self) -> MithrilResult<HeaderMap> {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in &self.headers {
+            header_map.insert(
+                HeaderName::from_bytes(name.as_bytes())
+                    .with_context(|| format!("Invalid HTTP header name: '{name}'"))?,
+                HeaderValue::from_str(value)
+                    .with_context(|| format!("Invalid value for HTTP header '{name}'"))?,
+            );
+        }
+
+        Ok(header_map)
+    }
+
+    fn client_builder(&self) -> MithrilResult<reqwest::ClientBuilder> {
+        let builder = reqwest::ClientBuilder::new().default_headers(self.header_map()?);
+
+        #[cfg(not(target_family = "wasm"))]
+        let builder = match &self.proxy {
+            Some(proxy) => builder.proxy(
+                reqwest::Proxy::all(proxy)
+                    .with_context(|| format!("Invalid HTTP proxy url: '{proxy}'"))?,
+            ),
+            None => builder,
+        };
+
+        Ok(builder)
+    }
+
+    /// Build a http client for requests to the aggregator.
+    pub(crate) fn build_http_client(&self) -> MithrilResult<reqwest::Client> {
+        let builder = self.client_builder()?;
+
+        #[cfg(not(target_family = "wasm"))]
+        let builder = match self.timeout {
+            Some(timeout) => builder.timeout(timeout),
+            None => builder,
+        };
+
+        builder.build().map_err(|e| anyhow!(e))
+    }
+
+    /// Build a http client for snapshot downloads.
+    #[cfg(feature = "fs")]
+    pub(crate) fn build_download_http_client(&self) -> MithrilResult<reqwest::Client> {
+        let builder = self.client_builder()?;
+        let builder = match self.timeout {
+            Some(timeout) => builder.connect_timeout(timeout).read_timeout(timeout),
+            None => builder,
+        };
+
+        builder.build().map_err(|e| anyhow!(e))
+    }
+
+    /// Send the request built by `build_request`, building and sending it again as long as
+    /// it fails with a retryable error and the [RetryPolicy] allows it.
+    #[cfg(not(target_family = "wasm"))]
+    pub(crate) async fn send_with_retry<F>(
+        &self,
+        build_request: F,
+        logger: &Logger,
+    ) -> reqwest::Result<Response>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let mut retry = 0;
+        loop {
+            let result = build_request().send().await;
+            let is_retryable = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(error) => error.is_connect() || error.is_timeout(),
+            };
+            if !is_retryable || retry >= self.retry_policy.max_retries {
+                return result;
+            }
+
+            let delay = self.retry_policy.backoff_delay(retry);
+            warn!(
+                logger, "HTTP request failed, retrying";
+                "retry" => retry + 1, "delay_ms" => delay.as_millis(),
+                "result" => ?result.map(|response| response.status())
+            );
+            tokio::time::sleep(delay).await;
+            retry += 1;
+        }
+    }
+
+    /// Send the request built by `build_request`.
+    #[cfg(target_family = "wasm")]
+    pub(crate) async fn send_with_retry<F>(
+        &self,
+        build_request: F,
+        _logger: &slog::Logger,
+    ) -> reqwest::Result<reqwest::Response>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        build_request().send().await
+    }
+}
+
+/// Policy used to retry failed HTTP requests with an exponential backoff.
+///
+/// The delay before the retry `n` (starting at 0) is a random duration between half and all
+/// of `min(initial_backoff * 2^n, max_backoff)`, the randomness (jitter) spreads the retries
+/// of concurrent clients over time.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt, `0` disables the retries
+    pub max_retries: u32,
+
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+
+    /// Upper bound of the delay between two retries
+    pub max_backoff: Duration,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl RetryPolicy {
+    /// Constructs a new `RetryPolicy`
+    pub fn new(max_retries: u32, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+            max_backoff,
+        }
+    }
+
+    /// A policy that never retries
+    pub fn no_retry() -> Self {
+        Self::new(0, Duration::ZERO, Duration::ZERO)
+    }
+
+    /// Compute the delay to wait before the given retry (starting at 0).
+    pub fn backoff_delay(&self, retry: u32) -> Duration {
+        let exponential_backoff = self
+            .initial_backoff
+            .saturating_mul(2_u32.saturating_pow(retry))
+            .min(self.max_backoff);
+        let half_backoff = exponential_backoff / 2;
+        let jitter_nanos = random_u64() % (half_backoff.as_nanos() as u64).saturating_add(1);
+
+        half_backoff + Duration::from_nanos(jitter_nanos)
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::no_retry()
+    }
+}
+
+/// Random number good enough for a jitter, each `RandomState` is built with new random keys.
+#[cfg(not(target_family = "wasm"))]
+fn random_u64() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::MockServer;
+
+    use crate::test_utils::test_logger;
+
+    use super::*;
+
+    #[test]
+    fn backoff_delay_grows_exponentially_with_jitter_and_is_bounded() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_millis(350));
+
+        for (retry, max_expected_delay) in [(0, 100), (1, 200), (2, 350), (4, 350)] {
+            let max_expected_delay = Duration::from_millis(max_expected_delay);
+            let delay = policy.backoff_delay(retry);
+
+            assert!(
+                delay >= max_expected_delay / 2 && delay <= max_expected_delay,
+                "retry {retry}: delay {delay:?} should be between {:?} and {max_expected_delay:?}",
+                max_expected_delay / 2
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_header_fails_to_build_http_client() {
+        let options = HttpClientOptions {
+            headers: BTreeMap::from([("invalid header".to_string(), "value".to_string())]),
+            ..HttpClientOptions::default()
+        };
+
+        options
+            .build_http_client()
+            .expect_err("Building a client with an invalid header name should fail");
+    }
+
+    #[tokio::test]
+    async fn custom_headers_are_sent_with_requests() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.path("/").header("x-api-key", "secret");
+            then.status(200);
+        });
+        let options = HttpClientOptions {
+            headers: BTreeMap::from([("x-api-key".to_string(), "secret".to_string())]),
+            ..HttpClientOptions::default()
+        };
+        let client = options.build_http_client().unwrap();
+
+        let response = client.get(server.url("/")).send().await.unwrap();
+
+        assert_eq!(200, response.status().as_u16());
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_retried_until_the_policy_gives_up() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.path("/");
+            then.status(503);
+        });
+        let options = HttpClientOptions {
+            retry_policy: RetryPolicy::new(2, Duration::from_millis(1), Duration::from_millis(2)),
+            ..HttpClientOptions::default()
+        };
+        let client = options.build_http_client().unwrap();
+
+        let response = options
+            .send_with_retry(|| client.get(server.url("/")), &test_logger())
+            .await
+            .unwrap();
+
+        assert_eq!(503, response.status().as_u16());
+        mock.assert_hits(3);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.path("/");
+            then.status(404);
+        });
+        let options = HttpClientOptions {
+            retry_policy: RetryPolicy::new(2, Duration::from_millis(1), Duration::from_millis(2)),
+            ..HttpClientOptions::default()
+        };
+        let client = options.build_http_client().unwrap();
+
+        options
+            .send_with_retry(|| client.get(server.url("/")), &test_logger())
+            .await
+            .unwrap();
+
+        mock.assert_hits(1);
+    }
+}
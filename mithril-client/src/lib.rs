@@ -8,11 +8,15 @@
 //!
 //! - [Snapshot][snapshot_client] list, get, download tarball and record statistics.
 //! - [Mithril stake distribution][mithril_stake_distribution_client] list and get.
+//! - [Cardano stake distribution][cardano_stake_distribution_client] list and get
+//! _(available using crate feature_ **unstable**_)_.
 //! - [Cardano transactions][cardano_transaction_client] list & get snapshot, get proofs
 //! _(available using crate feature_ **unstable**_)_.
-//! - [Certificates][certificate_client] list, get, and chain validation.
+//! - [Certificates][certificate_client] list, get, and chain validation (optionally speed up by a
+//! [cache][certificate_verifier_cache] of the already verified certificates).
 //!
-//! The [Client] aggregates the queries of all of those types.
+//! The [Client] aggregates the queries of all of those types, the HTTP requests it sends can be
+//! customized using [http client options][http_client_options] (headers, retries, timeout, proxy).
 //!
 //! **NOTE:** Snapshot download and Certificate chain validation can take quite some time even with a fast
 //! computer and network.
@@ -84,11 +88,14 @@ macro_rules! cfg_unstable {
 
 pub mod aggregator_client;
 cfg_unstable! {
+    pub mod cardano_stake_distribution_client;
     pub mod cardano_transaction_client;
 }
 pub mod certificate_client;
+pub mod certificate_verifier_cache;
 mod client;
 pub mod feedback;
+pub mod http_client_options;
 mod message;
 pub mod mithril_stake_distribution_client;
 pub mod snapshot_client;
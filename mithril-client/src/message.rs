@@ -15,8 +15,10 @@ use crate::common::{ProtocolMessage, ProtocolMessagePartKey};
 #[cfg(any(feature = "fs", feature = "unstable"))]
 use crate::MithrilCertificate;
 #[cfg(feature = "unstable")]
-use crate::VerifiedCardanoTransactions;
+use crate::{CardanoStakeDistribution, VerifiedCardanoTransactions};
 use crate::{MithrilResult, MithrilSigner, MithrilStakeDistribution};
+#[cfg(feature = "unstable")]
+use mithril_common::signable_builder::CardanoStakeDistributionSignableBuilder;
 
 /// A [MessageBuilder] can be used to compute the message of Mithril artifacts.
 pub struct MessageBuilder {
@@ -138,6 +140,30 @@ impl MessageBuilder {
             verified_transactions.fill_protocol_message(&mut message);
             message
         }
+
+        /// Compute message for a Cardano stake distribution.
+        pub fn compute_cardano_stake_distribution_message(
+            &self,
+            certificate: &MithrilCertificate,
+            cardano_stake_distribution: &CardanoStakeDistribution,
+        ) -> MithrilResult<ProtocolMessage> {
+            let mk_tree =
+                CardanoStakeDistributionSignableBuilder::compute_merkle_tree_from_stake_distribution(
+                    &cardano_stake_distribution.stake_distribution,
+                )?;
+
+            let mut message = certificate.protocol_message.clone();
+            message.set_message_part(
+                ProtocolMessagePartKey::CardanoStakeDistributionEpoch,
+                cardano_stake_distribution.epoch.to_string(),
+            );
+            message.set_message_part(
+                ProtocolMessagePartKey::CardanoStakeDistributionMerkleRoot,
+                mk_tree.compute_root()?.to_hex(),
+            );
+
+            Ok(message)
+        }
     }
 }
 
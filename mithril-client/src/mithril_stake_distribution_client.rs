@@ -43,7 +43,9 @@ use std::sync::Arc;
 use crate::aggregator_client::{AggregatorClient, AggregatorClientError, AggregatorRequest};
 use anyhow::Context;
 
-use crate::{MithrilResult, MithrilStakeDistribution, MithrilStakeDistributionListItem};
+use crate::{
+    ListQueryParameters, MithrilResult, MithrilStakeDistribution, MithrilStakeDistributionListItem,
+};
 
 /// HTTP client for MithrilStakeDistribution API from the Aggregator
 pub struct MithrilStakeDistributionClient {
@@ -69,6 +71,26 @@ impl MithrilStakeDistributionClient {
         Ok(items)
     }
 
+    /// Fetch a list of signed MithrilStakeDistribution matching the given pagination and filters [parameters][ListQueryParameters]
+    pub async fn list_with_parameters(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> MithrilResult<Vec<MithrilStakeDistributionListItem>> {
+        let response = self
+            .aggregator_client
+            .get_content(
+                AggregatorRequest::ListMithrilStakeDistributionsWithParameters {
+                    parameters: parameters.clone(),
+                },
+            )
+            .await
+            .with_context(|| "MithrilStakeDistribution Client can not get the artifact list")?;
+        let items = serde_json::from_str::<Vec<MithrilStakeDistributionListItem>>(&response)
+            .with_context(|| "MithrilStakeDistribution Client can not deserialize artifact list")?;
+
+        Ok(items)
+    }
+
     /// Get the given stake distribution data. If it cannot be found, a None is returned.
     pub async fn get(&self, hash: &str) -> MithrilResult<Option<MithrilStakeDistribution>> {
         match self
@@ -163,4 +185,25 @@ mod tests {
         assert_eq!("hash".to_string(), stake_distribution_entity.hash);
         assert_eq!(2, stake_distribution_entity.signers_with_stake.len(),);
     }
+
+    #[tokio::test]
+    async fn list_with_parameters_sends_them_to_the_aggregator() {
+        let parameters = ListQueryParameters::default()
+            .with_limit(5)
+            .with_from_epoch(Epoch(2));
+        let mut http_client = MockAggregatorHTTPClient::new();
+        http_client
+            .expect_get_content()
+            .with(mockall::predicate::eq(
+                AggregatorRequest::ListMithrilStakeDistributionsWithParameters {
+                    parameters: parameters.clone(),
+                },
+            ))
+            .return_once(|_| Ok("[]".to_string()))
+            .once();
+        let client = MithrilStakeDistributionClient::new(Arc::new(http_client));
+        let items = client.list_with_parameters(&parameters).await.unwrap();
+
+        assert!(items.is_empty());
+    }
 }
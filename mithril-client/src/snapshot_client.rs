@@ -102,7 +102,7 @@ use crate::aggregator_client::{AggregatorClient, AggregatorClientError, Aggregat
 use crate::feedback::FeedbackSender;
 #[cfg(feature = "fs")]
 use crate::snapshot_downloader::SnapshotDownloader;
-use crate::{MithrilResult, Snapshot, SnapshotListItem};
+use crate::{ListQueryParameters, MithrilResult, Snapshot, SnapshotListItem};
 
 /// Error for the Snapshot client
 #[derive(Error, Debug)]
@@ -161,6 +161,24 @@ impl SnapshotClient {
         Ok(items)
     }
 
+    /// Fetch a list of available snapshots matching the given pagination and filters [parameters][ListQueryParameters]
+    pub async fn list_with_parameters(
+        &self,
+        parameters: &ListQueryParameters,
+    ) -> MithrilResult<Vec<SnapshotListItem>> {
+        let response = self
+            .aggregator_client
+            .get_content(AggregatorRequest::ListSnapshotsWithParameters {
+                parameters: parameters.clone(),
+            })
+            .await
+            .with_context(|| "Snapshot Client can not get the artifact list")?;
+        let items = serde_json::from_str::<Vec<SnapshotListItem>>(&response)
+            .with_context(|| "Snapshot Client can not deserialize artifact list")?;
+
+        Ok(items)
+    }
+
     /// Get the given snapshot data. If it cannot be found, a None is returned.
     pub async fn get(&self, digest: &str) -> MithrilResult<Option<Snapshot>> {
         match self
@@ -184,6 +202,10 @@ impl SnapshotClient {
     cfg_fs! {
         /// Download and unpack the given snapshot to the given directory
         ///
+        /// The snapshot locations are tried in order: a location is skipped if it can't be
+        /// probed, and if a download fails the files it partially unpacked are removed before
+        /// the next location is used.
+        ///
         /// **NOTE**: The directory should already exist, and the user running the binary
         /// must have read/write access to it.
         pub async fn download_unpack(
@@ -193,51 +215,109 @@ impl SnapshotClient {
         ) -> MithrilResult<()> {
             use crate::feedback::MithrilEvent;
 
+            let initial_entries = Self::list_directory_entries(target_dir)?;
+            let mut last_error = None;
             for location in snapshot.locations.as_slice() {
-                if self.snapshot_downloader.probe(location).await.is_ok() {
-                    let download_id = MithrilEvent::new_snapshot_download_id();
-                    self.feedback_sender
-                        .send_event(MithrilEvent::SnapshotDownloadStarted {
-                            digest: snapshot.digest.clone(),
-                            download_id: download_id.clone(),
-                            size: snapshot.size,
-                        })
-                        .await;
-                    return match self
-                        .snapshot_downloader
-                        .download_unpack(
-                            location,
-                            target_dir,
-                            snapshot.compression_algorithm.unwrap_or_default(),
-                            &download_id,
-                            snapshot.size,
-                        )
-                        .await
-                    {
-                        Ok(()) => {
-                            self.feedback_sender
-                                .send_event(MithrilEvent::SnapshotDownloadCompleted { download_id })
-                                .await;
-                            Ok(())
-                        }
-                        Err(e) => {
-                            slog::warn!(
-                                self.logger,
-                                "Failed downloading snapshot from '{location}' Error: {e}."
-                            );
-                            Err(e)
-                        }
-                    };
+                if let Err(e) = self.snapshot_downloader.probe(location).await {
+                    slog::warn!(
+                        self.logger,
+                        "Skipping snapshot location '{location}' that could not be probed. Error: {e}."
+                    );
+                    continue;
                 }
-            }
 
-            let locations = snapshot.locations.join(", ");
+                let download_id = MithrilEvent::new_snapshot_download_id();
+                self.feedback_sender
+                    .send_event(MithrilEvent::SnapshotDownloadStarted {
+                        digest: snapshot.digest.clone(),
+                        download_id: download_id.clone(),
+                        size: snapshot.size,
+                    })
+                    .await;
+                match self
+                    .snapshot_downloader
+                    .download_unpack(
+                        location,
+                        target_dir,
+                        snapshot.compression_algorithm.unwrap_or_default(),
+                        &download_id,
+                        snapshot.size,
+                    )
+                    .await
+                {
+                    Ok(()) => {
+                        self.feedback_sender
+                            .send_event(MithrilEvent::SnapshotDownloadCompleted { download_id })
+                            .await;
+                        return Ok(());
+                    }
+                    Err(e) => {
+                        slog::warn!(
+                            self.logger,
+                            "Failed downloading snapshot from '{location}' Error: {e}."
+                        );
+                        last_error = Some(e);
+                        self.remove_new_directory_entries(target_dir, &initial_entries)?;
+                    }
+                };
+            }
 
-            Err(SnapshotClientError::NoWorkingLocation {
+            let error = SnapshotClientError::NoWorkingLocation {
                 digest: snapshot.digest.clone(),
-                locations,
+                locations: snapshot.locations.join(", "),
+            };
+
+            match last_error {
+                Some(e) => Err(e.context(error)),
+                None => Err(error.into()),
             }
-            .into())
+        }
+
+        fn list_directory_entries(
+            directory: &std::path::Path,
+        ) -> MithrilResult<std::collections::HashSet<std::path::PathBuf>> {
+            if !directory.exists() {
+                return Ok(std::collections::HashSet::new());
+            }
+
+            let entries = std::fs::read_dir(directory)
+                .with_context(|| format!("Could not list directory '{}'", directory.display()))?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<Result<_, _>>()
+                .with_context(|| format!("Could not list directory '{}'", directory.display()))?;
+
+            Ok(entries)
+        }
+
+        /// Remove the entries of the directory that are not in the given initial entries,
+        /// ie: the files unpacked by a failed download.
+        fn remove_new_directory_entries(
+            &self,
+            directory: &std::path::Path,
+            initial_entries: &std::collections::HashSet<std::path::PathBuf>,
+        ) -> MithrilResult<()> {
+            for entry in Self::list_directory_entries(directory)?
+                .difference(initial_entries)
+            {
+                slog::debug!(
+                    self.logger,
+                    "Removing '{}' left by a failed snapshot download",
+                    entry.display()
+                );
+                let result = if entry.is_dir() {
+                    std::fs::remove_dir_all(entry)
+                } else {
+                    std::fs::remove_file(entry)
+                };
+                result.with_context(|| {
+                    format!(
+                        "Could not remove '{}' left by a failed snapshot download",
+                        entry.display()
+                    )
+                })?;
+            }
+
+            Ok(())
         }
     }
 
@@ -254,6 +334,42 @@ impl SnapshotClient {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::{aggregator_client::MockAggregatorHTTPClient, common::Epoch};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn list_with_parameters_sends_them_to_the_aggregator() {
+        let parameters = ListQueryParameters::default()
+            .with_limit(5)
+            .with_from_epoch(Epoch(2));
+        let mut http_client = MockAggregatorHTTPClient::new();
+        http_client
+            .expect_get_content()
+            .with(mockall::predicate::eq(
+                AggregatorRequest::ListSnapshotsWithParameters {
+                    parameters: parameters.clone(),
+                },
+            ))
+            .return_once(|_| Ok("[]".to_string()))
+            .once();
+        let client = SnapshotClient::new(
+            Arc::new(http_client),
+            #[cfg(feature = "fs")]
+            Arc::new(crate::snapshot_downloader::MockHttpSnapshotDownloader::new()),
+            #[cfg(feature = "fs")]
+            FeedbackSender::new(&[]),
+            #[cfg(feature = "fs")]
+            crate::test_utils::test_logger(),
+        );
+        let items = client.list_with_parameters(&parameters).await.unwrap();
+
+        assert!(items.is_empty());
+    }
+}
+
 #[cfg(all(test, feature = "fs"))]
 mod tests_download {
     use crate::{
@@ -262,6 +378,9 @@ mod tests_download {
         snapshot_downloader::MockHttpSnapshotDownloader,
         test_utils,
     };
+    use anyhow::anyhow;
+    use mithril_common::test_utils::TempDir;
+    use mockall::predicate::{always, eq};
     use std::path::Path;
 
     use super::*;
@@ -302,4 +421,134 @@ mod tests_download {
 
         assert_eq!(actual, expected);
     }
+
+    fn snapshot_with_locations(locations: &[&str]) -> Snapshot {
+        Snapshot {
+            locations: locations.iter().map(|l| l.to_string()).collect(),
+            ..Snapshot::dummy()
+        }
+    }
+
+    fn build_client(snapshot_downloader: MockHttpSnapshotDownloader) -> SnapshotClient {
+        SnapshotClient::new(
+            Arc::new(MockAggregatorHTTPClient::new()),
+            Arc::new(snapshot_downloader),
+            FeedbackSender::new(&[]),
+            test_utils::test_logger(),
+        )
+    }
+
+    #[tokio::test]
+    async fn download_unpack_skip_locations_that_can_not_be_probed() {
+        let mut snapshot_downloader = MockHttpSnapshotDownloader::new();
+        snapshot_downloader
+            .expect_probe()
+            .with(eq("http://unreachable"))
+            .returning(|_| Err(anyhow!("unreachable")));
+        snapshot_downloader
+            .expect_probe()
+            .with(eq("http://mirror"))
+            .returning(|_| Ok(()));
+        snapshot_downloader
+            .expect_download_unpack()
+            .with(eq("http://mirror"), always(), always(), always(), always())
+            .returning(|_, _, _, _, _| Ok(()))
+            .once();
+        let client = build_client(snapshot_downloader);
+        let snapshot = snapshot_with_locations(&["http://unreachable", "http://mirror"]);
+
+        client
+            .download_unpack(&snapshot, Path::new(""))
+            .await
+            .expect("download should succeed");
+    }
+
+    #[tokio::test]
+    async fn download_unpack_fallback_to_next_location_when_a_download_fails() {
+        let mut snapshot_downloader = MockHttpSnapshotDownloader::new();
+        snapshot_downloader.expect_probe().returning(|_| Ok(()));
+        snapshot_downloader
+            .expect_download_unpack()
+            .with(eq("http://main"), always(), always(), always(), always())
+            .returning(|_, _, _, _, _| Err(anyhow!("connection reset")))
+            .once();
+        snapshot_downloader
+            .expect_download_unpack()
+            .with(eq("http://mirror"), always(), always(), always(), always())
+            .returning(|_, _, _, _, _| Ok(()))
+            .once();
+        let client = build_client(snapshot_downloader);
+        let snapshot = snapshot_with_locations(&["http://main", "http://mirror"]);
+
+        client
+            .download_unpack(&snapshot, Path::new(""))
+            .await
+            .expect("download should succeed using the mirror");
+    }
+
+    #[tokio::test]
+    async fn download_unpack_removes_files_unpacked_by_a_failed_download_before_fallback() {
+        let target_dir = TempDir::create(
+            "snapshot_client",
+            "download_unpack_removes_files_unpacked_by_a_failed_download_before_fallback",
+        );
+        std::fs::write(target_dir.join("existing_file"), "existing").unwrap();
+        let mut snapshot_downloader = MockHttpSnapshotDownloader::new();
+        snapshot_downloader.expect_probe().returning(|_| Ok(()));
+        snapshot_downloader
+            .expect_download_unpack()
+            .with(eq("http://main"), always(), always(), always(), always())
+            .returning(|_, target_dir, _, _, _| {
+                std::fs::create_dir_all(target_dir.join("immutable")).unwrap();
+                std::fs::write(target_dir.join("immutable").join("00001.chunk"), "partial")
+                    .unwrap();
+                Err(anyhow!("connection reset"))
+            })
+            .once();
+        snapshot_downloader
+            .expect_download_unpack()
+            .with(eq("http://mirror"), always(), always(), always(), always())
+            .returning(|_, target_dir, _, _, _| {
+                assert!(
+                    !target_dir.join("immutable").exists(),
+                    "files unpacked by the failed download should have been removed"
+                );
+                Ok(())
+            })
+            .once();
+        let client = build_client(snapshot_downloader);
+        let snapshot = snapshot_with_locations(&["http://main", "http://mirror"]);
+
+        client
+            .download_unpack(&snapshot, &target_dir)
+            .await
+            .expect("download should succeed using the mirror");
+
+        assert!(target_dir.join("existing_file").exists());
+    }
+
+    #[tokio::test]
+    async fn download_unpack_fails_when_all_locations_fail() {
+        let mut snapshot_downloader = MockHttpSnapshotDownloader::new();
+        snapshot_downloader.expect_probe().returning(|_| Ok(()));
+        snapshot_downloader
+            .expect_download_unpack()
+            .returning(|_, _, _, _, _| Err(anyhow!("connection reset")))
+            .times(2);
+        let client = build_client(snapshot_downloader);
+        let snapshot = snapshot_with_locations(&["http://main", "http://mirror"]);
+
+        let error = client
+            .download_unpack(&snapshot, Path::new(""))
+            .await
+            .expect_err("download should fail");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<SnapshotClientError>(),
+                Some(SnapshotClientError::NoWorkingLocation { .. })
+            ),
+            "Expected a NoWorkingLocation error, got: {error:?}"
+        );
+    }
 }
@@ -6,19 +6,29 @@
 //! Snapshots locations can be of various kinds, right now we only support HTTP
 //! download (using the [HttpSnapshotDownloader]) but other types may be added in
 //! the future.
+//!
+//! By default the [HttpSnapshotDownloader] streams the archive directly to the unpacker.
+//! When [resumable downloads][HttpSnapshotDownloader::with_resumable_download] are enabled
+//! the archive is first downloaded to a temporary file using HTTP Range requests, so an
+//! interrupted download resumes from the last received byte instead of starting over, as long as
+//! the `ETag` of the archive is unchanged.
 
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use futures::StreamExt;
-use reqwest::{Response, StatusCode};
-use slog::{debug, Logger};
-use std::path::Path;
+use reqwest::{header, Response, StatusCode};
+use sha2::{Digest, Sha256};
+use slog::{debug, warn, Logger};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
 
 #[cfg(test)]
 use mockall::automock;
 
 use crate::common::CompressionAlgorithm;
 use crate::feedback::{FeedbackSender, MithrilEvent};
+use crate::http_client_options::{HttpClientOptions, RetryPolicy};
 use crate::utils::SnapshotUnpacker;
 use crate::MithrilResult;
 
@@ -47,30 +57,135 @@ pub trait SnapshotDownloader: Sync + Send {
 /// A snapshot downloader that only handles download through HTTP.
 pub struct HttpSnapshotDownloader {
     http_client: reqwest::Client,
+    http_client_options: HttpClientOptions,
     feedback_sender: FeedbackSender,
     logger: Logger,
+    resumable_download: Option<ResumableDownloadConfiguration>,
+}
+
+struct ResumableDownloadConfiguration {
+    temp_directory: PathBuf,
+    max_attempts: u32,
+    backoff: RetryPolicy,
 }
 
 impl HttpSnapshotDownloader {
+    /// Default delay before resuming an interrupted download for the first time
+    pub const DEFAULT_RESUME_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+    /// Default upper bound of the delay before resuming an interrupted download
+    pub const DEFAULT_RESUME_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
     /// Constructs a new `HttpSnapshotDownloader`.
     pub fn new(feedback_sender: FeedbackSender, logger: Logger) -> MithrilResult<Self> {
-        let http_client = reqwest::ClientBuilder::new()
-            .build()
+        Self::new_with_options(feedback_sender, logger, HttpClientOptions::default())
+    }
+
+    /// Constructs a new `HttpSnapshotDownloader` which requests are sent using the given
+    /// [HttpClientOptions].
+    pub fn new_with_options(
+        feedback_sender: FeedbackSender,
+        logger: Logger,
+        http_client_options: HttpClientOptions,
+    ) -> MithrilResult<Self> {
+        let http_client = http_client_options
+            .build_download_http_client()
             .with_context(|| "Building http client for HttpSnapshotDownloader failed")?;
 
         Ok(Self {
             http_client,
+            http_client_options,
             feedback_sender,
             logger,
+            resumable_download: None,
         })
     }
 
+    /// Download the snapshots archives to a temporary file in the given directory before
+    /// unpacking them.
+    ///
+    /// If the download is interrupted it is resumed, using an HTTP Range request, from the last
+    /// byte written to the temporary file, at most `max_attempts` times. A partial file left by
+    /// a previous run is resumed as well.
+    ///
+    /// A download is only resumed if the archive still has the `ETag` it had when the download
+    /// started (using an `If-Range` header), it is restarted from scratch otherwise.
+    ///
+    /// The attempts are spaced by an exponential backoff, see
+    /// [with_resume_backoff][Self::with_resume_backoff].
+    pub fn with_resumable_download(mut self, temp_directory: &Path, max_attempts: u32) -> Self {
+        let max_attempts = max_attempts.max(1);
+        self.resumable_download = Some(ResumableDownloadConfiguration {
+            temp_directory: temp_directory.to_path_buf(),
+            max_attempts,
+            backoff: RetryPolicy::new(
+                max_attempts - 1,
+                Self::DEFAULT_RESUME_INITIAL_BACKOFF,
+                Self::DEFAULT_RESUME_MAX_BACKOFF,
+            ),
+        });
+        self
+    }
+
+    /// Set the exponential backoff between two attempts of a resumable download, computed
+    /// like the delays of a [RetryPolicy].
+    ///
+    /// Has no effect if [resumable downloads][Self::with_resumable_download] are not enabled.
+    pub fn with_resume_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        if let Some(configuration) = self.resumable_download.as_mut() {
+            configuration.backoff =
+                RetryPolicy::new(configuration.max_attempts - 1, initial_backoff, max_backoff);
+        }
+        self
+    }
+
+    /// Path of the temporary file used to download the archive at the given location.
+    fn temp_archive_path(temp_directory: &Path, location: &str) -> PathBuf {
+        let location_hash = hex::encode(Sha256::digest(location.as_bytes()));
+
+        temp_directory.join(format!("{location_hash}.part"))
+    }
+
+    /// Path of the file holding the `ETag` of the archive downloaded to the given temporary file.
+    fn etag_path(archive_path: &Path) -> PathBuf {
+        archive_path.with_extension("etag")
+    }
+
+    /// Start of the range sent in a `206 Partial Content` response, read from its
+    /// `Content-Range` header (ie: `bytes 100-999/1000`).
+    fn content_range_start(response: &Response) -> Option<u64> {
+        response
+            .headers()
+            .get(header::CONTENT_RANGE)?
+            .to_str()
+            .ok()?
+            .strip_prefix("bytes ")?
+            .split_once('-')?
+            .0
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// Strong `ETag` of a response, weak ones can not be used to resume a download.
+    fn strong_etag(response: &Response) -> Option<String> {
+        response
+            .headers()
+            .get(header::ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .filter(|etag| !etag.starts_with("W/"))
+            .map(|etag| etag.to_string())
+    }
+
     async fn get(&self, location: &str) -> MithrilResult<Response> {
         debug!(self.logger, "GET Snapshot location='{location}'.");
-        let request_builder = self.http_client.get(location);
-        let response = request_builder.send().await.with_context(|| {
-            format!("Cannot perform a GET for the snapshot (location='{location}')")
-        })?;
+        let response = self
+            .http_client_options
+            .send_with_retry(|| self.http_client.get(location), &self.logger)
+            .await
+            .with_context(|| {
+                format!("Cannot perform a GET for the snapshot (location='{location}')")
+            })?;
 
         match response.status() {
             StatusCode::OK => Ok(response),
@@ -78,12 +193,60 @@ impl HttpSnapshotDownloader {
             status_code => Err(anyhow!("Unhandled error {status_code}")),
         }
     }
-}
 
-#[cfg_attr(test, automock)]
-#[async_trait]
-impl SnapshotDownloader for HttpSnapshotDownloader {
-    async fn download_unpack(
+    /// Get the archive from the given offset, only if it still matches the given `ETag`.
+    ///
+    /// The server answers with a `206 Partial Content` if the archive is unchanged, or with the
+    /// whole archive (`200 OK`) if it changed or if it does not support ranges.
+    async fn get_from_offset(
+        &self,
+        location: &str,
+        offset: u64,
+        etag: &str,
+    ) -> MithrilResult<Response> {
+        debug!(
+            self.logger,
+            "GET Snapshot location='{location}' from offset={offset}."
+        );
+        let build_request = || {
+            self.http_client
+                .get(location)
+                .header(header::RANGE, format!("bytes={offset}-"))
+                .header(header::IF_RANGE, etag)
+        };
+        let response = self
+            .http_client_options
+            .send_with_retry(build_request, &self.logger)
+            .await
+            .with_context(|| {
+                format!("Cannot perform a GET for the snapshot (location='{location}')")
+            })?;
+
+        match response.status() {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT | StatusCode::RANGE_NOT_SATISFIABLE => {
+                Ok(response)
+            }
+            StatusCode::NOT_FOUND => Err(anyhow!("Location='{location} not found")),
+            status_code => Err(anyhow!("Unhandled error {status_code}")),
+        }
+    }
+
+    async fn send_download_progress(
+        &self,
+        download_id: &str,
+        downloaded_bytes: u64,
+        snapshot_size: u64,
+    ) {
+        self.feedback_sender
+            .send_event(MithrilEvent::SnapshotDownloadProgress {
+                download_id: download_id.to_owned(),
+                downloaded_bytes,
+                size: snapshot_size,
+            })
+            .await
+    }
+
+    async fn stream_download_unpack(
         &self,
         location: &str,
         target_dir: &Path,
@@ -91,12 +254,6 @@ impl SnapshotDownloader for HttpSnapshotDownloader {
         download_id: &str,
         snapshot_size: u64,
     ) -> MithrilResult<()> {
-        if !target_dir.is_dir() {
-            Err(
-                anyhow!("target path is not a directory or does not exist: `{target_dir:?}`")
-                    .context("Download-Unpack: prerequisite error"),
-            )?;
-        }
         let mut downloaded_bytes: u64 = 0;
         let mut remote_stream = self.get(location).await?.bytes_stream();
         let (sender, receiver) = flume::bounded(5);
@@ -115,12 +272,7 @@ impl SnapshotDownloader for HttpSnapshotDownloader {
             })?;
 
             downloaded_bytes += chunk.len() as u64;
-            self.feedback_sender
-                .send_event(MithrilEvent::SnapshotDownloadProgress {
-                    download_id: download_id.to_owned(),
-                    downloaded_bytes,
-                    size: snapshot_size,
-                })
+            self.send_download_progress(download_id, downloaded_bytes, snapshot_size)
                 .await
         }
 
@@ -140,6 +292,241 @@ impl SnapshotDownloader for HttpSnapshotDownloader {
         Ok(())
     }
 
+    async fn resumable_download_unpack(
+        &self,
+        configuration: &ResumableDownloadConfiguration,
+        location: &str,
+        target_dir: &Path,
+        compression_algorithm: CompressionAlgorithm,
+        download_id: &str,
+        snapshot_size: u64,
+    ) -> MithrilResult<()> {
+        tokio::fs::create_dir_all(&configuration.temp_directory)
+            .await
+            .with_context(|| {
+                format!(
+                    "Download: could not create temporary directory '{}'",
+                    configuration.temp_directory.display()
+                )
+            })?;
+        let archive_path = Self::temp_archive_path(&configuration.temp_directory, location);
+
+        let mut attempt = 1;
+        while let Err(error) = self
+            .download_to_file(location, &archive_path, download_id, snapshot_size)
+            .await
+        {
+            if attempt >= configuration.max_attempts {
+                return Err(error.context(format!(
+                    "Download: giving up after {attempt} attempt(s) (location='{location}')"
+                )));
+            }
+            let delay = configuration.backoff.backoff_delay(attempt - 1);
+            warn!(
+                self.logger,
+                "Download of snapshot interrupted, resuming it";
+                "location" => location, "attempt" => attempt,
+                "delay_ms" => delay.as_millis(), "error" => ?error
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+
+        let downloaded_size = tokio::fs::metadata(&archive_path)
+            .await
+            .with_context(|| {
+                format!(
+                    "Download: could not read size of file '{}'",
+                    archive_path.display()
+                )
+            })?
+            .len();
+        if downloaded_size != snapshot_size {
+            let _ = tokio::fs::remove_file(&archive_path).await;
+            let _ = tokio::fs::remove_file(Self::etag_path(&archive_path)).await;
+            return Err(anyhow!(
+                "Download: downloaded archive size ({downloaded_size} bytes) does not match the snapshot size ({snapshot_size} bytes)"
+            ));
+        }
+
+        let dest_dir = target_dir.to_path_buf();
+        let unpacked_archive_path = archive_path.clone();
+        tokio::task::spawn_blocking(move || -> MithrilResult<()> {
+            let unpacker = SnapshotUnpacker;
+            unpacker.unpack_snapshot_file(&unpacked_archive_path, compression_algorithm, &dest_dir)
+        })
+        .await
+        .with_context(|| {
+            format!(
+                "Unpack: panic while unpacking to dir '{}'",
+                target_dir.display()
+            )
+        })?
+        .with_context(|| format!("Unpack: could not unpack to dir '{}'", target_dir.display()))?;
+
+        tokio::fs::remove_file(&archive_path)
+            .await
+            .with_context(|| {
+                format!(
+                    "Unpack: could not remove downloaded archive '{}'",
+                    archive_path.display()
+                )
+            })?;
+        let _ = tokio::fs::remove_file(Self::etag_path(&archive_path)).await;
+
+        Ok(())
+    }
+
+    /// Download the archive at the given location to the given file, resuming from the end of
+    /// the file if it already exists and if the `ETag` of the archive did not change.
+    async fn download_to_file(
+        &self,
+        location: &str,
+        archive_path: &Path,
+        download_id: &str,
+        snapshot_size: u64,
+    ) -> MithrilResult<()> {
+        let etag_path = Self::etag_path(archive_path);
+        let mut downloaded_bytes = match tokio::fs::metadata(archive_path).await {
+            Ok(metadata) if metadata.len() <= snapshot_size => metadata.len(),
+            _ => 0,
+        };
+        if downloaded_bytes == snapshot_size {
+            self.send_download_progress(download_id, downloaded_bytes, snapshot_size)
+                .await;
+            return Ok(());
+        }
+
+        let stored_etag = match downloaded_bytes {
+            0 => None,
+            _ => tokio::fs::read_to_string(&etag_path).await.ok(),
+        };
+        let resumed_response = match &stored_etag {
+            Some(etag) => Some(
+                self.get_from_offset(location, downloaded_bytes, etag)
+                    .await?,
+            ),
+            None => None,
+        };
+        let response = match resumed_response {
+            Some(response)
+                if response.status() == StatusCode::PARTIAL_CONTENT
+                    && Self::content_range_start(&response) == Some(downloaded_bytes) =>
+            {
+                response
+            }
+            Some(response) if response.status() == StatusCode::OK => {
+                // The archive changed, or the server ignored the Range header: the whole
+                // archive is sent
+                downloaded_bytes = 0;
+                response
+            }
+            resumed_response => {
+                if let Some(response) = resumed_response {
+                    warn!(
+                        self.logger,
+                        "Download of snapshot can not be resumed, restarting it";
+                        "location" => location, "offset" => downloaded_bytes,
+                        "status" => %response.status(),
+                        "content_range" => ?response.headers().get(header::CONTENT_RANGE)
+                    );
+                }
+                downloaded_bytes = 0;
+                self.get(location).await?
+            }
+        };
+        if downloaded_bytes == 0 {
+            match Self::strong_etag(&response) {
+                Some(etag) => tokio::fs::write(&etag_path, etag).await.with_context(|| {
+                    format!("Download: could not write file '{}'", etag_path.display())
+                })?,
+                None => {
+                    let _ = tokio::fs::remove_file(&etag_path).await;
+                }
+            }
+        }
+        let mut archive_file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(downloaded_bytes > 0)
+            .truncate(downloaded_bytes == 0)
+            .open(archive_path)
+            .await
+            .with_context(|| {
+                format!("Download: could not open file '{}'", archive_path.display())
+            })?;
+
+        self.send_download_progress(download_id, downloaded_bytes, snapshot_size)
+            .await;
+        let mut remote_stream = response.bytes_stream();
+        while let Some(item) = remote_stream.next().await {
+            let chunk = item.with_context(|| "Download: Could not read from byte stream")?;
+
+            archive_file.write_all(&chunk).await.with_context(|| {
+                format!(
+                    "Download: could not write {} bytes to file '{}'",
+                    chunk.len(),
+                    archive_path.display()
+                )
+            })?;
+
+            downloaded_bytes += chunk.len() as u64;
+            self.send_download_progress(download_id, downloaded_bytes, snapshot_size)
+                .await
+        }
+        archive_file.flush().await.with_context(|| {
+            format!(
+                "Download: could not flush file '{}'",
+                archive_path.display()
+            )
+        })?;
+
+        Ok(())
+    }
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+impl SnapshotDownloader for HttpSnapshotDownloader {
+    async fn download_unpack(
+        &self,
+        location: &str,
+        target_dir: &Path,
+        compression_algorithm: CompressionAlgorithm,
+        download_id: &str,
+        snapshot_size: u64,
+    ) -> MithrilResult<()> {
+        if !target_dir.is_dir() {
+            Err(
+                anyhow!("target path is not a directory or does not exist: `{target_dir:?}`")
+                    .context("Download-Unpack: prerequisite error"),
+            )?;
+        }
+        match &self.resumable_download {
+            Some(configuration) => {
+                self.resumable_download_unpack(
+                    configuration,
+                    location,
+                    target_dir,
+                    compression_algorithm,
+                    download_id,
+                    snapshot_size,
+                )
+                .await
+            }
+            None => {
+                self.stream_download_unpack(
+                    location,
+                    target_dir,
+                    compression_algorithm,
+                    download_id,
+                    snapshot_size,
+                )
+                .await
+            }
+        }
+    }
+
     async fn probe(&self, location: &str) -> MithrilResult<()> {
         debug!(self.logger, "HEAD Snapshot location='{location}'.");
 
@@ -155,3 +542,437 @@ impl SnapshotDownloader for HttpSnapshotDownloader {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use flate2::{write::GzEncoder, Compression};
+    use httpmock::MockServer;
+    use mithril_common::test_utils::TempDir;
+    use std::sync::Arc;
+
+    use crate::feedback::{FeedbackReceiver, StackFeedbackReceiver};
+
+    use super::*;
+
+    const UNPACKED_FILE_NAME: &str = "immutable/00001.chunk";
+
+    fn build_gzip_archive() -> Vec<u8> {
+        let content = "immutable file content".repeat(100);
+        let encoder = GzEncoder::new(Vec::new(), Compression::default());
+        let mut tar_builder = tar::Builder::new(encoder);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar_builder
+            .append_data(&mut header, UNPACKED_FILE_NAME, content.as_bytes())
+            .unwrap();
+
+        tar_builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn build_downloader() -> (HttpSnapshotDownloader, Arc<StackFeedbackReceiver>) {
+        let feedback_receiver = Arc::new(StackFeedbackReceiver::new());
+        let feedback_receivers: Vec<Arc<dyn FeedbackReceiver>> = vec![feedback_receiver.clone()];
+        let downloader = HttpSnapshotDownloader::new(
+            FeedbackSender::new(&feedback_receivers),
+            Logger::root(slog::Discard, slog::o!()),
+        )
+        .unwrap();
+
+        (downloader, feedback_receiver)
+    }
+
+    fn downloaded_bytes_progress(feedback_receiver: &StackFeedbackReceiver) -> Vec<u64> {
+        feedback_receiver
+            .stacked_events()
+            .into_iter()
+            .filter_map(|event| match event {
+                MithrilEvent::SnapshotDownloadProgress {
+                    downloaded_bytes, ..
+                } => Some(downloaded_bytes),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn stream_download_unpack_archive() {
+        let test_dir = TempDir::create("snapshot_downloader", "stream_download_unpack_archive");
+        let archive = build_gzip_archive();
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/snapshot");
+            then.status(200).body(&archive);
+        });
+        let (downloader, _) = build_downloader();
+
+        downloader
+            .download_unpack(
+                &server.url("/snapshot"),
+                &test_dir,
+                CompressionAlgorithm::Gzip,
+                "download_id",
+                archive.len() as u64,
+            )
+            .await
+            .unwrap();
+
+        assert!(test_dir.join(UNPACKED_FILE_NAME).exists());
+    }
+
+    #[tokio::test]
+    async fn resumable_download_resumes_from_existing_partial_file() {
+        let test_dir = TempDir::create(
+            "snapshot_downloader",
+            "resumable_download_resumes_from_existing_partial_file",
+        );
+        let (target_dir, temp_dir) = (test_dir.join("target"), test_dir.join("temp"));
+        std::fs::create_dir_all(&target_dir).unwrap();
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let archive = build_gzip_archive();
+        let server = MockServer::start();
+        let location = server.url("/snapshot");
+        let archive_path = HttpSnapshotDownloader::temp_archive_path(&temp_dir, &location);
+        std::fs::write(&archive_path, &archive[..100]).unwrap();
+        std::fs::write(
+            HttpSnapshotDownloader::etag_path(&archive_path),
+            "\"archive-etag\"",
+        )
+        .unwrap();
+        let mock = server.mock(|when, then| {
+            when.path("/snapshot")
+                .header("range", "bytes=100-")
+                .header("if-range", "\"archive-etag\"");
+            then.status(206)
+                .header(
+                    "content-range",
+                    format!("bytes 100-{}/{}", archive.len() - 1, archive.len()),
+                )
+                .body(&archive[100..]);
+        });
+        let (downloader, feedback_receiver) = build_downloader();
+        let downloader = downloader.with_resumable_download(&temp_dir, 3);
+
+        downloader
+            .download_unpack(
+                &location,
+                &target_dir,
+                CompressionAlgorithm::Gzip,
+                "download_id",
+                archive.len() as u64,
+            )
+            .await
+            .unwrap();
+
+        mock.assert();
+        assert!(target_dir.join(UNPACKED_FILE_NAME).exists());
+        assert!(!archive_path.exists());
+        assert!(!HttpSnapshotDownloader::etag_path(&archive_path).exists());
+        let progress = downloaded_bytes_progress(&feedback_receiver);
+        assert_eq!(Some(&100), progress.first());
+        assert_eq!(Some(&(archive.len() as u64)), progress.last());
+    }
+
+    #[tokio::test]
+    async fn resumable_download_restarts_when_server_ignores_range() {
+        let test_dir = TempDir::create(
+            "snapshot_downloader",
+            "resumable_download_restarts_when_server_ignores_range",
+        );
+        let temp_dir = test_dir.join("temp");
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let archive = build_gzip_archive();
+        let server = MockServer::start();
+        let location = server.url("/snapshot");
+        let archive_path = HttpSnapshotDownloader::temp_archive_path(&temp_dir, &location);
+        std::fs::write(&archive_path, "stale partial content").unwrap();
+        server.mock(|when, then| {
+            when.path("/snapshot");
+            then.status(200).body(&archive);
+        });
+        let (downloader, _) = build_downloader();
+        let downloader = downloader.with_resumable_download(&temp_dir, 3);
+
+        downloader
+            .download_unpack(
+                &location,
+                &test_dir,
+                CompressionAlgorithm::Gzip,
+                "download_id",
+                archive.len() as u64,
+            )
+            .await
+            .unwrap();
+
+        assert!(test_dir.join(UNPACKED_FILE_NAME).exists());
+        assert!(!archive_path.exists());
+    }
+
+    #[tokio::test]
+    async fn resumable_download_stores_the_etag_of_the_archive() {
+        let test_dir = TempDir::create(
+            "snapshot_downloader",
+            "resumable_download_stores_the_etag_of_the_archive",
+        );
+        let archive = build_gzip_archive();
+        let server = MockServer::start();
+        let location = server.url("/snapshot");
+        let archive_path = HttpSnapshotDownloader::temp_archive_path(&test_dir, &location);
+        server.mock(|when, then| {
+            when.path("/snapshot");
+            then.status(200)
+                .header("etag", "\"archive-etag\"")
+                .body(&archive);
+        });
+        let (downloader, _) = build_downloader();
+
+        downloader
+            .download_to_file(
+                &location,
+                &archive_path,
+                "download_id",
+                archive.len() as u64,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(archive, std::fs::read(&archive_path).unwrap());
+        assert_eq!(
+            "\"archive-etag\"",
+            std::fs::read_to_string(HttpSnapshotDownloader::etag_path(&archive_path)).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn resumable_download_restarts_when_the_archive_etag_changed() {
+        let test_dir = TempDir::create(
+            "snapshot_downloader",
+            "resumable_download_restarts_when_the_archive_etag_changed",
+        );
+        let archive = build_gzip_archive();
+        let server = MockServer::start();
+        let location = server.url("/snapshot");
+        let archive_path = HttpSnapshotDownloader::temp_archive_path(&test_dir, &location);
+        std::fs::write(&archive_path, "content of the previous archive").unwrap();
+        std::fs::write(
+            HttpSnapshotDownloader::etag_path(&archive_path),
+            "\"previous-etag\"",
+        )
+        .unwrap();
+        // The If-Range condition does not match: the whole new archive is sent
+        let mock = server.mock(|when, then| {
+            when.path("/snapshot")
+                .header("if-range", "\"previous-etag\"");
+            then.status(200)
+                .header("etag", "\"new-etag\"")
+                .body(&archive);
+        });
+        let (downloader, _) = build_downloader();
+
+        downloader
+            .download_to_file(
+                &location,
+                &archive_path,
+                "download_id",
+                archive.len() as u64,
+            )
+            .await
+            .unwrap();
+
+        mock.assert();
+        assert_eq!(archive, std::fs::read(&archive_path).unwrap());
+        assert_eq!(
+            "\"new-etag\"",
+            std::fs::read_to_string(HttpSnapshotDownloader::etag_path(&archive_path)).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn resumable_download_restarts_when_the_content_range_does_not_start_at_the_offset() {
+        let test_dir = TempDir::create(
+            "snapshot_downloader",
+            "resumable_download_restarts_when_the_content_range_does_not_start_at_the_offset",
+        );
+        let archive = build_gzip_archive();
+        let server = MockServer::start();
+        let location = server.url("/snapshot");
+        let archive_path = HttpSnapshotDownloader::temp_archive_path(&test_dir, &location);
+        std::fs::write(&archive_path, &archive[..100]).unwrap();
+        std::fs::write(
+            HttpSnapshotDownloader::etag_path(&archive_path),
+            "\"archive-etag\"",
+        )
+        .unwrap();
+        let range_mock = server.mock(|when, then| {
+            when.path("/snapshot").header("range", "bytes=100-");
+            then.status(206)
+                .header(
+                    "content-range",
+                    format!("bytes 50-{}/{}", archive.len() - 1, archive.len()),
+                )
+                .body(&archive[50..]);
+        });
+        let full_mock = server.mock(|when, then| {
+            when.path("/snapshot").matches(|req| {
+                !req.headers
+                    .iter()
+                    .flatten()
+                    .any(|(name, _)| name.eq_ignore_ascii_case("range"))
+            });
+            then.status(200)
+                .header("etag", "\"archive-etag\"")
+                .body(&archive);
+        });
+        let (downloader, _) = build_downloader();
+
+        downloader
+            .download_to_file(
+                &location,
+                &archive_path,
+                "download_id",
+                archive.len() as u64,
+            )
+            .await
+            .unwrap();
+
+        range_mock.assert();
+        full_mock.assert();
+        assert_eq!(archive, std::fs::read(&archive_path).unwrap());
+    }
+
+    #[tokio::test]
+    async fn resumable_download_restarts_a_partial_file_without_etag() {
+        let test_dir = TempDir::create(
+            "snapshot_downloader",
+            "resumable_download_restarts_a_partial_file_without_etag",
+        );
+        let archive = build_gzip_archive();
+        let server = MockServer::start();
+        let location = server.url("/snapshot");
+        let archive_path = HttpSnapshotDownloader::temp_archive_path(&test_dir, &location);
+        std::fs::write(&archive_path, &archive[..100]).unwrap();
+        let mock = server.mock(|when, then| {
+            when.path("/snapshot").matches(|req| {
+                !req.headers
+                    .iter()
+                    .flatten()
+                    .any(|(name, _)| name.eq_ignore_ascii_case("range"))
+            });
+            then.status(200).body(&archive);
+        });
+        let (downloader, _) = build_downloader();
+
+        downloader
+            .download_to_file(
+                &location,
+                &archive_path,
+                "download_id",
+                archive.len() as u64,
+            )
+            .await
+            .unwrap();
+
+        mock.assert();
+        assert_eq!(archive, std::fs::read(&archive_path).unwrap());
+        assert!(!HttpSnapshotDownloader::etag_path(&archive_path).exists());
+    }
+
+    #[tokio::test]
+    async fn resumable_download_fails_and_removes_file_if_size_does_not_match_snapshot_size() {
+        let test_dir = TempDir::create(
+            "snapshot_downloader",
+            "resumable_download_fails_if_size_does_not_match_snapshot_size",
+        );
+        let temp_dir = test_dir.join("temp");
+        let archive = build_gzip_archive();
+        let server = MockServer::start();
+        let location = server.url("/snapshot");
+        server.mock(|when, then| {
+            when.path("/snapshot");
+            then.status(200).body(&archive);
+        });
+        let (downloader, _) = build_downloader();
+        let downloader = downloader.with_resumable_download(&temp_dir, 3);
+
+        downloader
+            .download_unpack(
+                &location,
+                &test_dir,
+                CompressionAlgorithm::Gzip,
+                "download_id",
+                archive.len() as u64 + 1,
+            )
+            .await
+            .expect_err("download_unpack should fail when the downloaded size is wrong");
+
+        assert!(!HttpSnapshotDownloader::temp_archive_path(&temp_dir, &location).exists());
+        assert!(!test_dir.join(UNPACKED_FILE_NAME).exists());
+    }
+
+    #[tokio::test]
+    async fn resumable_download_gives_up_after_max_attempts() {
+        let test_dir = TempDir::create(
+            "snapshot_downloader",
+            "resumable_download_gives_up_after_max_attempts",
+        );
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.path("/snapshot");
+            then.status(500);
+        });
+        let (downloader, _) = build_downloader();
+        let downloader = downloader
+            .with_resumable_download(&test_dir.join("temp"), 3)
+            .with_resume_backoff(Duration::from_millis(1), Duration::from_millis(5));
+
+        downloader
+            .download_unpack(
+                &server.url("/snapshot"),
+                &test_dir,
+                CompressionAlgorithm::Gzip,
+                "download_id",
+                1000,
+            )
+            .await
+            .expect_err("download_unpack should fail when the server always fails");
+
+        mock.assert_hits(3);
+    }
+
+    #[tokio::test]
+    async fn resumable_download_waits_with_a_backoff_between_attempts() {
+        let test_dir = TempDir::create(
+            "snapshot_downloader",
+            "resumable_download_waits_with_a_backoff_between_attempts",
+        );
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/snapshot");
+            then.status(500);
+        });
+        let (downloader, _) = build_downloader();
+        let downloader = downloader
+            .with_resumable_download(&test_dir.join("temp"), 3)
+            .with_resume_backoff(Duration::from_millis(100), Duration::from_secs(1));
+
+        let start = std::time::Instant::now();
+        downloader
+            .download_unpack(
+                &server.url("/snapshot"),
+                &test_dir,
+                CompressionAlgorithm::Gzip,
+                "download_id",
+                1000,
+            )
+            .await
+            .expect_err("download_unpack should fail when the server always fails");
+
+        // Two backoffs of at least 50ms and 100ms (half of 100ms and 200ms with the jitter)
+        assert!(
+            start.elapsed() >= Duration::from_millis(150),
+            "attempts should be spaced by a backoff, elapsed: {:?}",
+            start.elapsed()
+        );
+    }
+}
@@ -32,6 +32,10 @@ pub use mithril_common::messages::CertificateListItemMessage as MithrilCertifica
 
 pub use mithril_common::messages::CertificateListItemMessageMetadata as MithrilCertificateListItemMetadata;
 
+/// Pagination and filters of the certificate and artifact list requests.
+///
+pub use mithril_common::messages::ListQueryParameters;
+
 /// An individual signer of a [Mithril certificate][MithrilCertificate]
 ///
 pub use mithril_common::messages::SignerWithStakeMessagePart as MithrilSigner;
@@ -52,15 +56,23 @@ cfg_unstable! {
     /// List item of a Cardano transaction snapshot.
     ///
     pub use mithril_common::messages::CardanoTransactionSnapshotListItemMessage as CardanoTransactionSnapshotListItem;
+
+    /// A Cardano stake distribution.
+    ///
+    pub use mithril_common::messages::CardanoStakeDistributionMessage as CardanoStakeDistribution;
+
+    /// List item of Cardano stake distributions.
+    ///
+    pub use mithril_common::messages::CardanoStakeDistributionListItemMessage as CardanoStakeDistributionListItem;
 }
 
 /// `mithril-common` re-exports
 pub mod common {
     pub use mithril_common::entities::{
         CardanoDbBeacon, CompressionAlgorithm, Epoch, ProtocolMessage, ProtocolMessagePartKey,
-        ProtocolParameters,
+        ProtocolParameters, SignedEntityTypeDiscriminants,
     };
     cfg_unstable! {
-        pub use mithril_common::entities::TransactionHash;
+        pub use mithril_common::entities::{StakeDistribution, TransactionHash};
     }
 }
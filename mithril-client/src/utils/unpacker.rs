@@ -1,6 +1,8 @@
 use anyhow::Context;
 use flate2::read::GzDecoder;
 use flume::Receiver;
+use std::fs::File;
+use std::io::{BufReader, Read};
 use std::path::Path;
 use tar::Archive;
 
@@ -22,27 +24,57 @@ impl SnapshotUnpacker {
     ) -> MithrilResult<()> {
         let input = StreamReader::new(stream);
 
+        self.unpack(input, compression_algorithm, unpack_dir)
+            .with_context(|| {
+                format!(
+                    "Could not unpack from streamed data snapshot to directory '{}'",
+                    unpack_dir.display()
+                )
+            })
+    }
+
+    /// Unpack the snapshot archive file at the given path into the given directory.
+    pub fn unpack_snapshot_file(
+        &self,
+        archive_path: &Path,
+        compression_algorithm: CompressionAlgorithm,
+        unpack_dir: &Path,
+    ) -> MithrilResult<()> {
+        let archive_file = File::open(archive_path).with_context(|| {
+            format!(
+                "Could not open snapshot archive file '{}'",
+                archive_path.display()
+            )
+        })?;
+        let input = BufReader::new(archive_file);
+
+        self.unpack(input, compression_algorithm, unpack_dir)
+            .with_context(|| {
+                format!(
+                    "Could not unpack snapshot archive file '{}' to directory '{}'",
+                    archive_path.display(),
+                    unpack_dir.display()
+                )
+            })
+    }
+
+    fn unpack<R: Read>(
+        &self,
+        input: R,
+        compression_algorithm: CompressionAlgorithm,
+        unpack_dir: &Path,
+    ) -> MithrilResult<()> {
         match compression_algorithm {
             CompressionAlgorithm::Gzip => {
                 let gzip_decoder = GzDecoder::new(input);
                 let mut snapshot_archive = Archive::new(gzip_decoder);
-                snapshot_archive.unpack(unpack_dir).with_context(|| {
-                    format!(
-                        "Could not unpack from streamed data snapshot to directory '{}'",
-                        unpack_dir.display()
-                    )
-                })?;
+                snapshot_archive.unpack(unpack_dir)?;
             }
             CompressionAlgorithm::Zstandard => {
                 let zstandard_decoder = zstd::Decoder::new(input)
                     .with_context(|| "Unpack failed: Create Zstandard decoder error")?;
                 let mut snapshot_archive = Archive::new(zstandard_decoder);
-                snapshot_archive.unpack(unpack_dir).with_context(|| {
-                    format!(
-                        "Could not unpack from streamed data snapshot to directory '{}'",
-                        unpack_dir.display()
-                    )
-                })?;
+                snapshot_archive.unpack(unpack_dir)?;
             }
         };
 
@@ -2,6 +2,7 @@ mod extensions;
 
 use crate::extensions::fake::{FakeAggregator, FakeCertificateVerifier};
 use mithril_client::{aggregator_client::AggregatorRequest, ClientBuilder, MessageBuilder};
+use mithril_common::messages::CardanoTransactionsProofsRequestMessage;
 
 #[tokio::test]
 async fn cardano_transaction_proof_get_validate() {
@@ -28,8 +29,8 @@ async fn cardano_transaction_proof_get_validate() {
         fake_aggregator.get_last_call().await,
         Some(format!(
             "/{}",
-            AggregatorRequest::GetTransactionsProofs {
-                transactions_hashes: transactions_hashes.iter().map(|h| h.to_string()).collect(),
+            AggregatorRequest::PostTransactionsProofs {
+                request: CardanoTransactionsProofsRequestMessage::new(&transactions_hashes),
             }
             .route()
         ))
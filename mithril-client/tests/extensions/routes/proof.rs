@@ -15,16 +15,10 @@ fn proof_cardano_transaction(
     returned_value: String,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("proof" / "cardano-transaction")
-        .and(warp::get())
+        .and(warp::post())
         .and(warp::path::full().map(move |p| p))
-        .and(warp::query::raw())
         .and(with_calls_middleware(calls.clone()))
-        .and_then(move |fullpath, query, calls| {
-            FakeAggregator::store_call_with_query_and_return_value(
-                fullpath,
-                query,
-                calls,
-                returned_value.clone(),
-            )
+        .and_then(move |fullpath, calls| {
+            FakeAggregator::store_call_and_return_value(fullpath, calls, returned_value.clone())
         })
 }
@@ -0,0 +1,674 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use pallas_network::facades::NodeClient;
+use pallas_network::miniprotocols::chainsync::NextResponse;
+use pallas_network::miniprotocols::Point;
+use pallas_traverse::MultiEraBlock;
+use slog::{debug, error, warn, Logger};
+
+use crate::cardano_block_scanner::{
+    BlockScanner, BlockStreamer, ChainScannedBlocks, ImmutableBlockStreamer, ScannedBlock,
+};
+use crate::digesters::ImmutableFile;
+use crate::entities::{BlockNumber, ImmutableFileNumber, SlotNumber};
+use crate::{CardanoNetwork, StdResult};
+
+/// Maximum number of blocks returned by a single poll of a [ChainSyncBlockStreamer]
+const MAX_ROLL_FORWARDS_PER_POLL: usize = 100;
+
+/// Maximum number of streamed blocks kept to resolve the rollbacks, a rollback can't be
+/// deeper than the security parameter of the Cardano network (`k = 2160` on the mainnet).
+const MAX_ROLLBACK_DEPTH: usize = 2160;
+
+/// Cardano block scanner that follows the chain of a Cardano node using the chain-sync
+/// mini-protocol over the node socket.
+///
+/// Like the [CardanoBlockScanner][crate::cardano_block_scanner::CardanoBlockScanner] the scan
+/// stops at the last block of the `until_immutable` immutable file: the volatile blocks of the
+/// node are never streamed, so the imported transactions never have to be removed once their
+/// immutable file is completed. The streamer yields a [ChainScannedBlocks::RollBackward] when
+/// the node switches to another fork.
+///
+/// The streamed blocks are labelled with the `until_immutable` immutable file number.
+pub struct ChainSyncBlockScanner {
+    socket: PathBuf,
+    network: CardanoNetwork,
+    /// When set to true, no error is returned in case of unparsable block, and an error log is written instead.
+    /// This can occur when the crate 'pallas-traverse' doesn't support some non final encoding for a Cardano era.
+    /// This situation should only happen on the test networks and not on the mainnet.
+    allow_unparsable_block: bool,
+    logger: Logger,
+}
+
+impl ChainSyncBlockScanner {
+    /// Factory
+    pub fn new(
+        socket: &Path,
+        network: CardanoNetwork,
+        allow_unparsable_block: bool,
+        logger: Logger,
+    ) -> Self {
+        if allow_unparsable_block {
+            warn!(
+                logger,
+                "The 'allow_unparsable_block' option is activated. This option should only be used on test networks.")
+        }
+
+        Self {
+            socket: socket.to_owned(),
+            network,
+            allow_unparsable_block,
+            logger,
+        }
+    }
+
+    /// Compute the point where the chain-sync starts: the last block of the immutable file
+    /// preceding `from_immutable`, or the origin of the chain if there's no such file.
+    async fn get_intersection(
+        &self,
+        dirpath: &Path,
+        from_immutable: Option<ImmutableFileNumber>,
+    ) -> StdResult<Option<ScannedBlock>> {
+        let previous_immutable = match from_immutable {
+            None | Some(0) => return Ok(None),
+            Some(from) => from - 1,
+        };
+        let last_block = self.get_last_block_of(dirpath, previous_immutable).await?;
+        if last_block.is_none() {
+            warn!(
+                self.logger,
+                "ChainSyncBlockScanner could not find the immutable file preceding the scan lower bound, scanning from the origin of the chain";
+                "immutable_file_number" => previous_immutable
+            );
+        }
+
+        Ok(last_block)
+    }
+
+    /// Read the last block of the given completed immutable file, `None` if there's no such file.
+    async fn get_last_block_of(
+        &self,
+        dirpath: &Path,
+        immutable_file_number: ImmutableFileNumber,
+    ) -> StdResult<Option<ScannedBlock>> {
+        let Some(chunk) = ImmutableFile::list_completed_in_dir(dirpath)?
+            .into_iter()
+            .find(|f| f.number == immutable_file_number && f.filename.contains("chunk"))
+        else {
+            return Ok(None);
+        };
+
+        let mut streamer = ImmutableBlockStreamer::new(
+            vec![chunk],
+            self.allow_unparsable_block,
+            self.logger.clone(),
+        );
+        let blocks = streamer.poll_all().await?;
+
+        Ok(blocks.into_iter().last())
+    }
+}
+
+#[async_trait]
+impl BlockScanner for ChainSyncBlockScanner {
+    async fn scan(
+        &self,
+        dirpath: &Path,
+        from_immutable: Option<ImmutableFileNumber>,
+        until_immutable: ImmutableFileNumber,
+    ) -> StdResult<Box<dyn BlockStreamer>> {
+        let intersection_block = self.get_intersection(dirpath, from_immutable).await?;
+        let last_immutable_block = self
+            .get_last_block_of(dirpath, until_immutable)
+            .await?
+            .ok_or(anyhow!(
+                "ChainSyncBlockScanner could not find the immutable file of the scan upper bound: '{until_immutable}'"
+            ))?;
+        let intersection = match &intersection_block {
+            Some(block) => Point::Specific(
+                block.slot_number,
+                hex::decode(&block.block_hash)
+                    .with_context(|| format!("Invalid block hash: '{}'", block.block_hash))?,
+            ),
+            None => Point::Origin,
+        };
+
+        let mut client = NodeClient::connect(&self.socket, self.network.code())
+            .await
+            .map_err(|err| anyhow!(err))
+            .with_context(|| "ChainSyncBlockScanner failed to connect to the Cardano node")?;
+        let (found_intersection, _tip) = client
+            .chainsync()
+            .find_intersect(vec![intersection.clone()])
+            .await
+            .map_err(|err| anyhow!(err))
+            .with_context(|| "ChainSyncBlockScanner failed to find the chain intersection")?;
+        if found_intersection.is_none() {
+            client.abort().await;
+            return Err(anyhow!(
+                "ChainSyncBlockScanner could not find the intersection point '{intersection:?}' on the chain of the Cardano node"
+            ));
+        }
+
+        debug!(self.logger, "ChainSyncBlockScanner found intersection"; "point" => ?intersection);
+
+        Ok(Box::new(ChainSyncBlockStreamer::new(
+            client,
+            intersection,
+            intersection_block.map(|b| (b.slot_number, b.block_number)),
+            until_immutable,
+            last_immutable_block.slot_number,
+            self.allow_unparsable_block,
+            self.logger.clone(),
+        )))
+    }
+}
+
+/// [Block streamer][BlockStreamer] that streams blocks from a Cardano node using the chain-sync
+/// mini-protocol, until it reaches the last block of an immutable file or the tip of the node.
+pub struct ChainSyncBlockStreamer {
+    client: Option<NodeClient>,
+    intersection: Point,
+    /// Slot and block numbers of the last streamed blocks, starting with the intersection block
+    streamed_points: VecDeque<(SlotNumber, BlockNumber)>,
+    has_streamed_blocks: bool,
+    immutable_file_number: ImmutableFileNumber,
+    /// Slot number of the last block of the `immutable_file_number` immutable file, the stream
+    /// stops once it is reached
+    last_immutable_slot_number: SlotNumber,
+    max_roll_forwards_per_poll: usize,
+    allow_unparsable_block: bool,
+    logger: Logger,
+}
+
+#[async_trait]
+impl BlockStreamer for ChainSyncBlockStreamer {
+    async fn poll_next(&mut self) -> StdResult<Option<ChainScannedBlocks>> {
+        let mut blocks = Vec::new();
+
+        while blocks.len() < self.max_roll_forwards_per_poll {
+            let Some(client) = self.client.as_mut() else {
+                break;
+            };
+            let next = client
+                .chainsync()
+                .request_next()
+                .await
+                .map_err(|err| anyhow!(err))
+                .with_context(|| "ChainSyncBlockStreamer failed to request the next block")?;
+
+            match next {
+                NextResponse::RollForward(content, _tip) => {
+                    let Some(block) = self.convert_to_block(&content.0)? else {
+                        continue;
+                    };
+                    if block.slot_number > self.last_immutable_slot_number {
+                        self.stop(
+                            "ChainSyncBlockStreamer went past the upper bound immutable file",
+                        )
+                        .await;
+                        continue;
+                    }
+
+                    let is_last_immutable_block =
+                        block.slot_number == self.last_immutable_slot_number;
+                    self.record_streamed_point(&block);
+                    blocks.push(block);
+                    if is_last_immutable_block {
+                        self.stop("ChainSyncBlockStreamer reached the last block of the upper bound immutable file")
+                            .await;
+                    }
+                }
+                NextResponse::RollBackward(point, _tip) => {
+                    if let Some(block_number) = self.resolve_rollback(&point)? {
+                        debug!(
+                            self.logger, "ChainSyncBlockStreamer received a rollback";
+                            "point" => ?point, "block_number" => block_number
+                        );
+
+                        if blocks.iter().any(|b| b.block_number <= block_number) {
+                            blocks.retain(|b| b.block_number <= block_number);
+                        } else {
+                            return Ok(Some(ChainScannedBlocks::RollBackward(block_number)));
+                        }
+                    }
+                }
+                NextResponse::Await => {
+                    self.stop("ChainSyncBlockStreamer reached the tip of the chain")
+                        .await;
+                }
+            }
+        }
+
+        match (blocks.is_empty(), &self.client) {
+            (true, None) => Ok(None),
+            _ => Ok(Some(ChainScannedBlocks::RollForwards(blocks))),
+        }
+    }
+}
+
+impl ChainSyncBlockStreamer {
+    /// Factory
+    fn new(
+        client: NodeClient,
+        intersection: Point,
+        intersection_block: Option<(SlotNumber, BlockNumber)>,
+        immutable_file_number: ImmutableFileNumber,
+        last_immutable_slot_number: SlotNumber,
+        allow_unparsable_block: bool,
+        logger: Logger,
+    ) -> Self {
+        Self {
+            client: Some(client),
+            intersection,
+            streamed_points: intersection_block.into_iter().collect(),
+            has_streamed_blocks: false,
+            immutable_file_number,
+            last_immutable_slot_number,
+            max_roll_forwards_per_poll: MAX_ROLL_FORWARDS_PER_POLL,
+            allow_unparsable_block,
+            logger,
+        }
+    }
+
+    /// Stop the chain-sync, the next polls return no more blocks
+    async fn stop(&mut self, reason: &str) {
+        debug!(self.logger, "{reason}");
+        if let Some(client) = self.client.take() {
+            client.abort().await;
+        }
+    }
+
+    fn convert_to_block(&self, block: &[u8]) -> StdResult<Option<ScannedBlock>> {
+        match MultiEraBlock::decode(block) {
+            Ok(multi_era_block) => Ok(Some(ScannedBlock::convert(
+                multi_era_block,
+                self.immutable_file_number,
+            ))),
+            Err(err) if self.allow_unparsable_block => {
+                error!(
+                    self.logger,
+                    "The cbor encoded block could not be parsed";
+                    "error" => ?err, "immutable_file_number" => self.immutable_file_number
+                );
+                Ok(None)
+            }
+            Err(err) => {
+                Err(anyhow!(err)).with_context(|| "Error while decoding block from the chain-sync")
+            }
+        }
+    }
+
+    fn record_streamed_point(&mut self, block: &ScannedBlock) {
+        self.has_streamed_blocks = true;
+        self.streamed_points
+            .push_back((block.slot_number, block.block_number));
+        if self.streamed_points.len() > MAX_ROLLBACK_DEPTH {
+            self.streamed_points.pop_front();
+        }
+    }
+
+    /// Compute the block number to roll back to, or `None` if there's nothing to roll back.
+    fn resolve_rollback(&mut self, point: &Point) -> StdResult<Option<BlockNumber>> {
+        if !self.has_streamed_blocks && point == &self.intersection {
+            // The node always starts by rolling back to the intersection
+            return Ok(None);
+        }
+
+        match point {
+            Point::Origin => Err(anyhow!(
+                "ChainSyncBlockStreamer can't roll back to the origin of the chain"
+            )),
+            Point::Specific(slot_number, _) => {
+                let position = self
+                    .streamed_points
+                    .iter()
+                    .rposition(|(slot, _)| slot == slot_number)
+                    .ok_or(anyhow!(
+                        "ChainSyncBlockStreamer can't roll back to '{point:?}': point is unknown or too deep"
+                    ))?;
+                self.streamed_points.truncate(position + 1);
+
+                Ok(Some(self.streamed_points[position].1))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pallas_network::facades::NodeServer;
+    use pallas_network::miniprotocols::chainsync::{BlockContent, ClientRequest, Tip};
+    use tokio::net::UnixListener;
+
+    use crate::test_utils::{TempDir, TestLogger};
+
+    use super::*;
+
+    const DB_PATH: &str = "../mithril-test-lab/test_data/immutable/";
+
+    enum ServerAction {
+        RollForward(Vec<u8>),
+        RollBackward(Point),
+    }
+
+    fn create_temp_dir(folder_name: &str) -> PathBuf {
+        TempDir::create_with_short_path("chain_sync_block_scanner_test", folder_name)
+    }
+
+    fn read_raw_blocks(immutable_file_number: ImmutableFileNumber) -> Vec<Vec<u8>> {
+        pallas_hardano::storage::immutable::chunk::read_blocks(
+            Path::new(DB_PATH),
+            &format!("{immutable_file_number:05}"),
+        )
+        .unwrap()
+        .map(|block| block.unwrap())
+        .collect()
+    }
+
+    fn point_of(raw_block: &[u8]) -> Point {
+        let block = MultiEraBlock::decode(raw_block).unwrap();
+        Point::Specific(block.slot(), block.hash().to_vec())
+    }
+
+    fn scanned_block(raw_block: &[u8], immutable_file_number: ImmutableFileNumber) -> ScannedBlock {
+        ScannedBlock::convert(
+            MultiEraBlock::decode(raw_block).unwrap(),
+            immutable_file_number,
+        )
+    }
+
+    /// Sets up a mock node server that answers the chain-sync intersection with the first point
+    /// received, plays the given actions and then tells the client to wait for new blocks.
+    ///
+    /// The server task returns the points received in the intersection request.
+    async fn setup_server(
+        socket_path: PathBuf,
+        actions: Vec<ServerAction>,
+    ) -> tokio::task::JoinHandle<Vec<Point>> {
+        tokio::spawn(async move {
+            let unix_listener = UnixListener::bind(socket_path.as_path()).unwrap();
+            let mut server = NodeServer::accept(&unix_listener, 10).await.unwrap();
+            let tip = Tip(Point::Origin, 0);
+
+            let points = match server.chainsync().recv_while_idle().await.unwrap() {
+                Some(ClientRequest::Intersect(points)) => points,
+                request => panic!("Expected an intersect request, got: {request:?}"),
+            };
+            let intersection = points[0].clone();
+            server
+                .chainsync()
+                .send_intersect_found(intersection.clone(), tip.clone())
+                .await
+                .unwrap();
+
+            let mut responses = vec![ServerAction::RollBackward(intersection)];
+            responses.extend(actions);
+            for response in responses {
+                match server.chainsync().recv_while_idle().await {
+                    Ok(Some(ClientRequest::RequestNext)) => {}
+                    // The client stopped the chain-sync before the end of the actions
+                    Ok(None) | Err(_) => return points,
+                    Ok(Some(request)) => panic!("Expected a request next, got: {request:?}"),
+                };
+                match response {
+                    ServerAction::RollForward(block) => server
+                        .chainsync()
+                        .send_roll_forward(BlockContent(block), tip.clone())
+                        .await
+                        .unwrap(),
+                    ServerAction::RollBackward(point) => server
+                        .chainsync()
+                        .send_roll_backward(point, tip.clone())
+                        .await
+                        .unwrap(),
+                }
+            }
+
+            if let Ok(Some(ClientRequest::RequestNext)) = server.chainsync().recv_while_idle().await
+            {
+                server.chainsync().send_await_reply().await.unwrap();
+            }
+
+            points
+        })
+    }
+
+    #[tokio::test]
+    async fn scan_from_origin_streams_all_blocks_until_the_upper_bound_immutable() {
+        let socket_path = create_temp_dir("scan_from_origin").join("node.socket");
+        let raw_blocks = [read_raw_blocks(1), read_raw_blocks(2)].concat();
+        let server = setup_server(
+            socket_path.clone(),
+            raw_blocks
+                .iter()
+                .cloned()
+                .map(ServerAction::RollForward)
+                .collect(),
+        )
+        .await;
+
+        let client = tokio::spawn(async move {
+            let scanner = ChainSyncBlockScanner::new(
+                &socket_path,
+                CardanoNetwork::TestNet(10),
+                false,
+                TestLogger::stdout(),
+            );
+            let mut streamer = scanner.scan(Path::new(DB_PATH), None, 2).await.unwrap();
+            streamer.poll_all().await.unwrap()
+        });
+
+        let (server_res, client_res) = tokio::join!(server, client);
+        let intersection_points = server_res.unwrap();
+        let blocks = client_res.unwrap();
+
+        assert_eq!(vec![Point::Origin], intersection_points);
+        assert_eq!(
+            raw_blocks
+                .iter()
+                .map(|b| scanned_block(b, 2))
+                .collect::<Vec<_>>(),
+            blocks
+        );
+    }
+
+    #[tokio::test]
+    async fn scan_stops_at_the_last_block_of_the_upper_bound_immutable() {
+        let socket_path = create_temp_dir("scan_stops_at_upper_bound").join("node.socket");
+        let immutable_raw_blocks = read_raw_blocks(1);
+        let server = setup_server(
+            socket_path.clone(),
+            [immutable_raw_blocks.clone(), read_raw_blocks(2)]
+                .concat()
+                .into_iter()
+                .map(ServerAction::RollForward)
+                .collect(),
+        )
+        .await;
+
+        let client = tokio::spawn(async move {
+            let scanner = ChainSyncBlockScanner::new(
+                &socket_path,
+                CardanoNetwork::TestNet(10),
+                false,
+                TestLogger::stdout(),
+            );
+            let mut streamer = scanner.scan(Path::new(DB_PATH), None, 1).await.unwrap();
+            streamer.poll_all().await.unwrap()
+        });
+
+        let (server_res, client_res) = tokio::join!(server, client);
+        server_res.unwrap();
+        let blocks = client_res.unwrap();
+
+        assert_eq!(
+            immutable_raw_blocks
+                .iter()
+                .map(|b| scanned_block(b, 1))
+                .collect::<Vec<_>>(),
+            blocks
+        );
+    }
+
+    #[tokio::test]
+    async fn scan_fails_if_the_upper_bound_immutable_is_not_completed() {
+        let scanner = ChainSyncBlockScanner::new(
+            &create_temp_dir("scan_fails_without_upper_bound").join("node.socket"),
+            CardanoNetwork::TestNet(10),
+            false,
+            TestLogger::stdout(),
+        );
+
+        scanner
+            .scan(Path::new(DB_PATH), None, 100)
+            .await
+            .expect_err("Scan should fail if the upper bound immutable file is not completed");
+    }
+
+    #[tokio::test]
+    async fn scan_starts_at_the_last_block_of_the_immutable_preceding_the_lower_bound() {
+        let socket_path = create_temp_dir("scan_from_immutable").join("node.socket");
+        let raw_blocks = read_raw_blocks(2);
+        let server = setup_server(
+            socket_path.clone(),
+            raw_blocks
+                .iter()
+                .cloned()
+                .map(ServerAction::RollForward)
+                .collect(),
+        )
+        .await;
+
+        let client = tokio::spawn(async move {
+            let scanner = ChainSyncBlockScanner::new(
+                &socket_path,
+                CardanoNetwork::TestNet(10),
+                false,
+                TestLogger::stdout(),
+            );
+            let mut streamer = scanner.scan(Path::new(DB_PATH), Some(2), 2).await.unwrap();
+            streamer.poll_all().await.unwrap()
+        });
+
+        let (server_res, client_res) = tokio::join!(server, client);
+        let intersection_points = server_res.unwrap();
+        let blocks = client_res.unwrap();
+
+        let last_block_of_previous_immutable = read_raw_blocks(1).pop().unwrap();
+        assert_eq!(
+            vec![point_of(&last_block_of_previous_immutable)],
+            intersection_points
+        );
+        assert_eq!(
+            raw_blocks
+                .iter()
+                .map(|b| scanned_block(b, 2))
+                .collect::<Vec<_>>(),
+            blocks
+        );
+    }
+
+    #[tokio::test]
+    async fn rollback_within_a_poll_discards_the_rolled_back_blocks() {
+        let socket_path = create_temp_dir("rollback_within_a_poll").join("node.socket");
+        let raw_blocks = read_raw_blocks(2);
+        assert!(raw_blocks.len() >= 3);
+        let server = setup_server(
+            socket_path.clone(),
+            vec![
+                ServerAction::RollForward(raw_blocks[0].clone()),
+                ServerAction::RollForward(raw_blocks[1].clone()),
+                ServerAction::RollForward(raw_blocks[2].clone()),
+                ServerAction::RollBackward(point_of(&raw_blocks[0])),
+            ],
+        )
+        .await;
+
+        let client = tokio::spawn(async move {
+            let scanner = ChainSyncBlockScanner::new(
+                &socket_path,
+                CardanoNetwork::TestNet(10),
+                false,
+                TestLogger::stdout(),
+            );
+            let mut streamer = scanner.scan(Path::new(DB_PATH), None, 2).await.unwrap();
+            let first_poll = streamer.poll_next().await.unwrap();
+            let second_poll = streamer.poll_next().await.unwrap();
+            (first_poll, second_poll)
+        });
+
+        let (server_res, client_res) = tokio::join!(server, client);
+        server_res.unwrap();
+        let (first_poll, second_poll) = client_res.unwrap();
+
+        assert_eq!(
+            Some(ChainScannedBlocks::RollForwards(vec![scanned_block(
+                &raw_blocks[0],
+                2
+            )])),
+            first_poll
+        );
+        assert_eq!(None, second_poll);
+    }
+
+    #[tokio::test]
+    async fn rollback_to_a_block_streamed_in_a_previous_poll_is_returned_as_an_event() {
+        let socket_path = create_temp_dir("rollback_previous_poll").join("node.socket");
+        let raw_blocks = read_raw_blocks(2);
+        let server = setup_server(
+            socket_path.clone(),
+            vec![
+                ServerAction::RollForward(raw_blocks[0].clone()),
+                ServerAction::RollForward(raw_blocks[1].clone()),
+                ServerAction::RollBackward(point_of(&raw_blocks[0])),
+                ServerAction::RollForward(raw_blocks[1].clone()),
+            ],
+        )
+        .await;
+
+        let client = tokio::spawn(async move {
+            let mut client = NodeClient::connect(&socket_path, 10).await.unwrap();
+            client
+                .chainsync()
+                .find_intersect(vec![Point::Origin])
+                .await
+                .unwrap();
+            let mut streamer = ChainSyncBlockStreamer::new(
+                client,
+                Point::Origin,
+                None,
+                2,
+                u64::MAX,
+                false,
+                TestLogger::stdout(),
+            );
+            // Limit the number of blocks per poll so the rollback happens after a poll
+            streamer.max_roll_forwards_per_poll = 2;
+
+            let mut polls = vec![];
+            while let Some(poll) = streamer.poll_next().await.unwrap() {
+                polls.push(poll);
+            }
+            polls
+        });
+
+        let (server_res, client_res) = tokio::join!(server, client);
+        server_res.unwrap();
+        let polls = client_res.unwrap();
+
+        let block_0 = scanned_block(&raw_blocks[0], 2);
+        let block_1 = scanned_block(&raw_blocks[1], 2);
+        assert_eq!(
+            vec![
+                ChainScannedBlocks::RollForwards(vec![block_0.clone(), block_1.clone()]),
+                ChainScannedBlocks::RollBackward(block_0.block_number),
+                ChainScannedBlocks::RollForwards(vec![block_1]),
+            ],
+            polls
+        );
+    }
+}
@@ -4,8 +4,8 @@ use std::path::Path;
 use async_trait::async_trait;
 use tokio::sync::RwLock;
 
-use crate::cardano_block_scanner::{BlockScanner, BlockStreamer, ScannedBlock};
-use crate::entities::ImmutableFileNumber;
+use crate::cardano_block_scanner::{BlockScanner, BlockStreamer, ChainScannedBlocks, ScannedBlock};
+use crate::entities::{BlockNumber, ImmutableFileNumber};
 use crate::StdResult;
 
 /// Dumb block scanner
@@ -43,22 +43,39 @@ impl BlockScanner for DumbBlockScanner {
 
 /// Dumb block streamer
 pub struct DumbBlockStreamer {
-    blocks: VecDeque<Vec<ScannedBlock>>,
+    streamer_responses: VecDeque<ChainScannedBlocks>,
 }
 
 impl DumbBlockStreamer {
     /// Factory - the resulting streamer can be polled one time for each list of blocks given
     pub fn new(blocks: Vec<Vec<ScannedBlock>>) -> Self {
         Self {
-            blocks: VecDeque::from(blocks),
+            streamer_responses: blocks
+                .into_iter()
+                .map(ChainScannedBlocks::RollForwards)
+                .collect(),
         }
     }
+
+    /// Add to the inner streamer a roll forward with the given list of blocks
+    pub fn forwards(mut self, blocks: Vec<ScannedBlock>) -> Self {
+        self.streamer_responses
+            .push_back(ChainScannedBlocks::RollForwards(blocks));
+        self
+    }
+
+    /// Add to the inner streamer a roll backward to the given block number
+    pub fn rollback(mut self, block_number: BlockNumber) -> Self {
+        self.streamer_responses
+            .push_back(ChainScannedBlocks::RollBackward(block_number));
+        self
+    }
 }
 
 #[async_trait]
 impl BlockStreamer for DumbBlockStreamer {
-    async fn poll_next(&mut self) -> StdResult<Option<Vec<ScannedBlock>>> {
-        Ok(self.blocks.pop_front())
+    async fn poll_next(&mut self) -> StdResult<Option<ChainScannedBlocks>> {
+        Ok(self.streamer_responses.pop_front())
     }
 }
 
@@ -79,7 +96,10 @@ mod tests {
         let mut streamer = DumbBlockStreamer::new(vec![expected_blocks.clone()]);
 
         let blocks = streamer.poll_next().await.unwrap();
-        assert_eq!(blocks, Some(expected_blocks));
+        assert_eq!(
+            blocks,
+            Some(ChainScannedBlocks::RollForwards(expected_blocks))
+        );
 
         let blocks = streamer.poll_next().await.unwrap();
         assert_eq!(blocks, None);
@@ -98,18 +118,90 @@ mod tests {
         let mut streamer = DumbBlockStreamer::new(expected_blocks.clone());
 
         let blocks = streamer.poll_next().await.unwrap();
-        assert_eq!(blocks, Some(expected_blocks[0].clone()));
+        assert_eq!(
+            blocks,
+            Some(ChainScannedBlocks::RollForwards(expected_blocks[0].clone()))
+        );
 
         let blocks = streamer.poll_next().await.unwrap();
-        assert_eq!(blocks, Some(expected_blocks[1].clone()));
+        assert_eq!(
+            blocks,
+            Some(ChainScannedBlocks::RollForwards(expected_blocks[1].clone()))
+        );
 
         let blocks = streamer.poll_next().await.unwrap();
-        assert_eq!(blocks, Some(expected_blocks[2].clone()));
+        assert_eq!(
+            blocks,
+            Some(ChainScannedBlocks::RollForwards(expected_blocks[2].clone()))
+        );
 
         let blocks = streamer.poll_next().await.unwrap();
         assert_eq!(blocks, None);
     }
 
+    #[tokio::test]
+    async fn polling_with_rollbacks_returns_them_in_order() {
+        let blocks = vec![
+            ScannedBlock::new("hash-1", 1, 10, 20, Vec::<&str>::new()),
+            ScannedBlock::new("hash-2", 2, 11, 21, Vec::<&str>::new()),
+        ];
+        let mut streamer = DumbBlockStreamer::new(vec![blocks.clone()])
+            .rollback(1)
+            .forwards(vec![ScannedBlock::new(
+                "hash-3",
+                2,
+                12,
+                22,
+                Vec::<&str>::new(),
+            )]);
+
+        let response = streamer.poll_next().await.unwrap();
+        assert_eq!(response, Some(ChainScannedBlocks::RollForwards(blocks)));
+
+        let response = streamer.poll_next().await.unwrap();
+        assert_eq!(response, Some(ChainScannedBlocks::RollBackward(1)));
+
+        let response = streamer.poll_next().await.unwrap();
+        assert_eq!(
+            response,
+            Some(ChainScannedBlocks::RollForwards(vec![ScannedBlock::new(
+                "hash-3",
+                2,
+                12,
+                22,
+                Vec::<&str>::new()
+            )]))
+        );
+
+        let response = streamer.poll_next().await.unwrap();
+        assert_eq!(response, None);
+    }
+
+    #[tokio::test]
+    async fn poll_all_applies_rollbacks() {
+        let mut streamer = DumbBlockStreamer::new(vec![vec![
+            ScannedBlock::new("hash-1", 1, 10, 20, Vec::<&str>::new()),
+            ScannedBlock::new("hash-2", 2, 11, 21, Vec::<&str>::new()),
+        ]])
+        .rollback(1)
+        .forwards(vec![ScannedBlock::new(
+            "hash-3",
+            2,
+            12,
+            22,
+            Vec::<&str>::new(),
+        )]);
+
+        let blocks = streamer.poll_all().await.unwrap();
+        assert_eq!(
+            blocks,
+            vec![
+                ScannedBlock::new("hash-1", 1, 10, 20, Vec::<&str>::new()),
+                ScannedBlock::new("hash-3", 2, 12, 22, Vec::<&str>::new()),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn dumb_scanned_construct_a_streamer_based_on_its_stored_blocks() {
         let expected_blocks = vec![ScannedBlock::new("hash-1", 1, 10, 20, Vec::<&str>::new())];
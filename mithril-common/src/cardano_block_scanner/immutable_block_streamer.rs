@@ -7,7 +7,7 @@ use pallas_hardano::storage::immutable::chunk::{read_blocks, Reader};
 use pallas_traverse::MultiEraBlock;
 use slog::{debug, error, Logger};
 
-use crate::cardano_block_scanner::{BlockStreamer, ScannedBlock};
+use crate::cardano_block_scanner::{BlockStreamer, ChainScannedBlocks, ScannedBlock};
 use crate::digesters::ImmutableFile;
 use crate::StdResult;
 
@@ -20,7 +20,7 @@ pub struct ImmutableBlockStreamer {
 
 #[async_trait]
 impl BlockStreamer for ImmutableBlockStreamer {
-    async fn poll_next(&mut self) -> StdResult<Option<Vec<ScannedBlock>>> {
+    async fn poll_next(&mut self) -> StdResult<Option<ChainScannedBlocks>> {
         match &self.remaining_immutable_files.pop_front() {
             Some(immutable_file) => {
                 debug!(
@@ -37,7 +37,7 @@ impl BlockStreamer for ImmutableBlockStreamer {
                             immutable_file.path.display()
                         )
                     })?;
-                Ok(Some(blocks))
+                Ok(Some(ChainScannedBlocks::RollForwards(blocks)))
             }
             None => Ok(None),
         }
@@ -147,21 +147,39 @@ mod tests {
         );
 
         let immutable_blocks = streamer.poll_next().await.unwrap();
+        let Some(ChainScannedBlocks::RollForwards(immutable_blocks)) = immutable_blocks else {
+            panic!("Expected a roll forward, got: {immutable_blocks:?}");
+        };
         assert_eq!(
-            immutable_blocks.map(|b| b.into_iter().map(|b| b.transactions_len()).sum()),
-            Some(immutable_files[0].1)
+            immutable_blocks
+                .into_iter()
+                .map(|b| b.transactions_len())
+                .sum::<usize>(),
+            immutable_files[0].1
         );
 
         let immutable_blocks = streamer.poll_next().await.unwrap();
+        let Some(ChainScannedBlocks::RollForwards(immutable_blocks)) = immutable_blocks else {
+            panic!("Expected a roll forward, got: {immutable_blocks:?}");
+        };
         assert_eq!(
-            immutable_blocks.map(|b| b.into_iter().map(|b| b.transactions_len()).sum()),
-            Some(immutable_files[1].1)
+            immutable_blocks
+                .into_iter()
+                .map(|b| b.transactions_len())
+                .sum::<usize>(),
+            immutable_files[1].1
         );
 
         let immutable_blocks = streamer.poll_next().await.unwrap();
+        let Some(ChainScannedBlocks::RollForwards(immutable_blocks)) = immutable_blocks else {
+            panic!("Expected a roll forward, got: {immutable_blocks:?}");
+        };
         assert_eq!(
-            immutable_blocks.map(|b| b.into_iter().map(|b| b.transactions_len()).sum()),
-            Some(immutable_files[2].1)
+            immutable_blocks
+                .into_iter()
+                .map(|b| b.transactions_len())
+                .sum::<usize>(),
+            immutable_files[2].1
         );
 
         let immutable_blocks = streamer.poll_next().await.unwrap();
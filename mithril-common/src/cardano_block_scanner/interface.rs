@@ -3,7 +3,7 @@ use std::path::Path;
 use async_trait::async_trait;
 
 use crate::cardano_block_scanner::ScannedBlock;
-use crate::entities::ImmutableFileNumber;
+use crate::entities::{BlockNumber, ImmutableFileNumber};
 use crate::StdResult;
 
 /// A scanner that can read cardano transactions in a cardano database
@@ -55,17 +55,36 @@ pub trait BlockScanner: Sync + Send {
     ) -> StdResult<Box<dyn BlockStreamer>>;
 }
 
+/// The action that indicates what to do next when scanning the chain
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChainScannedBlocks {
+    /// Roll forward on the chain to the next list of [ScannedBlock]
+    RollForwards(Vec<ScannedBlock>),
+    /// Roll backward on the chain to the given block number, the blocks after it must be
+    /// discarded
+    RollBackward(BlockNumber),
+}
+
 /// Trait that define how blocks are streamed from a Cardano database
 #[async_trait]
 pub trait BlockStreamer: Sync + Send {
     /// Stream the next available blocks
-    async fn poll_next(&mut self) -> StdResult<Option<Vec<ScannedBlock>>>;
+    async fn poll_next(&mut self) -> StdResult<Option<ChainScannedBlocks>>;
 
     /// Stream all the available blocks, may be very memory intensive
+    ///
+    /// The rollbacks are applied to the returned blocks.
     async fn poll_all(&mut self) -> StdResult<Vec<ScannedBlock>> {
         let mut blocks = Vec::new();
-        while let Some(mut next_blocks) = self.poll_next().await? {
-            blocks.append(&mut next_blocks);
+        while let Some(scanned_blocks) = self.poll_next().await? {
+            match scanned_blocks {
+                ChainScannedBlocks::RollForwards(mut forward_blocks) => {
+                    blocks.append(&mut forward_blocks);
+                }
+                ChainScannedBlocks::RollBackward(block_number) => {
+                    blocks.retain(|block| block.block_number <= block_number);
+                }
+            }
         }
         Ok(blocks)
     }
@@ -3,7 +3,14 @@
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use hex::ToHex;
+#[cfg(not(target_family = "wasm"))]
+use rayon::prelude::*;
+#[cfg(not(target_family = "wasm"))]
+use slog::warn;
 use slog::{debug, Logger};
+#[cfg(not(target_family = "wasm"))]
+use std::collections::BTreeMap;
+use std::future::Future;
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -16,6 +23,8 @@ use crate::entities::{
     Certificate, CertificateSignature, ProtocolMessage, ProtocolMessagePartKey, ProtocolParameters,
 };
 use crate::StdResult;
+#[cfg(not(target_family = "wasm"))]
+use mithril_stm::stm::StmAggrSig;
 
 #[cfg(test)]
 use mockall::automock;
@@ -27,6 +36,16 @@ pub enum CertificateVerifierError {
     #[error("multi signature verification failed: '{0}'")]
     VerifyMultiSignature(String),
 
+    /// Error raised when the multi signature of a [Certificate] of a certificate chain fails
+    /// its verification.
+    #[error("multi signature verification failed for certificate '{certificate_hash}': '{error}'")]
+    VerifyCertificateChainMultiSignature {
+        /// Hash of the certificate with the invalid multi signature
+        certificate_hash: String,
+        /// Multi signature verification error
+        error: String,
+    },
+
     /// Error raised when the Genesis Signature stored in a [Certificate] is invalid.
     #[error("certificate genesis error")]
     CertificateGenesis(#[from] ProtocolGenesisError),
@@ -110,11 +129,16 @@ pub trait CertificateVerifier: Send + Sync {
     }
 }
 
+/// Default number of certificates of a certificate chain whose multi signatures are verified
+/// together
+pub const DEFAULT_CERTIFICATE_CHAIN_BATCH_SIZE: usize = 100;
+
 /// MithrilCertificateVerifier is an implementation of the CertificateVerifier
 pub struct MithrilCertificateVerifier {
     /// The logger where the logs should be written
     logger: Logger,
     certificate_retriever: Arc<dyn CertificateRetriever>,
+    batch_size: usize,
 }
 
 impl MithrilCertificateVerifier {
@@ -124,9 +148,17 @@ impl MithrilCertificateVerifier {
         Self {
             logger,
             certificate_retriever,
+            batch_size: DEFAULT_CERTIFICATE_CHAIN_BATCH_SIZE,
         }
     }
 
+    /// Set the number of certificates of a certificate chain whose multi signatures are verified
+    /// together (at least 1)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
     /// Verify a multi signature
     fn verify_multi_signature(
         &self,
@@ -162,6 +194,16 @@ impl MithrilCertificateVerifier {
             &certificate.aggregate_verification_key,
             &certificate.metadata.protocol_parameters,
         )?;
+
+        self.verify_previous_certificate(certificate).await
+    }
+
+    /// Retrieve the previous certificate of a standard certificate and check that they are
+    /// chained, returns the previous certificate if the chain continues
+    async fn verify_previous_certificate(
+        &self,
+        certificate: &Certificate,
+    ) -> StdResult<Option<Certificate>> {
         let previous_certificate = self
             .certificate_retriever
             .get_certificate_details(&certificate.previous_hash)
@@ -231,6 +273,233 @@ impl MithrilCertificateVerifier {
             }
         }
     }
+
+    /// Verify the certificate chain starting with the given certificate, the walk stops at the
+    /// genesis certificate or at the first certificate for which `is_verified` returns `true`.
+    ///
+    /// The multi signatures of the standard certificates are verified by batches of
+    /// certificates while walking the chain. When a certificate breaks the chain, the multi
+    /// signatures of the certificates that precede it in the walk are verified first, so that
+    /// the first failing certificate of the chain is reported.
+    ///
+    /// `on_certificate_verified` is called with the hash of each certificate once it is fully
+    /// verified, in the order of the walk.
+    ///
+    /// Returns the hashes of the verified certificates in the order of the walk.
+    pub async fn verify_certificate_chain_until<F, Fut, V, VFut>(
+        &self,
+        certificate: Certificate,
+        genesis_verification_key: &ProtocolGenesisVerificationKey,
+        is_verified: F,
+        on_certificate_verified: V,
+    ) -> StdResult<Vec<String>>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = StdResult<bool>>,
+        V: Fn(String) -> VFut,
+        VFut: Future<Output = ()>,
+    {
+        let mut verified_certificate_hashes = vec![];
+        let mut pending_certificate_hashes = vec![];
+        let mut pending_multi_signatures = Vec::with_capacity(self.batch_size);
+        let mut certificate = Some(certificate);
+
+        while let Some(current_certificate) = certificate.take() {
+            if let Err(error) = self.verify_certificate_hash(&current_certificate) {
+                self.verify_multi_signatures_batch(pending_multi_signatures)
+                    .await?;
+                return Err(error);
+            }
+
+            if is_verified(current_certificate.hash.clone()).await? {
+                debug!(
+                    self.logger,
+                    "Certificate '{}' chain already verified, stopping the chain validation",
+                    current_certificate.hash
+                );
+                break;
+            }
+            pending_certificate_hashes.push(current_certificate.hash.clone());
+
+            let next_certificate = match &current_certificate.signature {
+                CertificateSignature::GenesisSignature(_signature) => self
+                    .verify_genesis_certificate(&current_certificate, genesis_verification_key)
+                    .await
+                    .map(|_| None),
+                CertificateSignature::MultiSignature(_, signature) => {
+                    let signature = signature.clone();
+                    let next_certificate =
+                        self.verify_previous_certificate(&current_certificate).await;
+                    pending_multi_signatures.push((current_certificate, signature));
+                    next_certificate
+                }
+            };
+
+            match next_certificate {
+                Ok(Some(next_certificate)) if pending_multi_signatures.len() < self.batch_size => {
+                    certificate = Some(next_certificate);
+                }
+                Ok(next_certificate) => {
+                    self.verify_multi_signatures_batch(std::mem::take(
+                        &mut pending_multi_signatures,
+                    ))
+                    .await?;
+                    for certificate_hash in pending_certificate_hashes.drain(..) {
+                        on_certificate_verified(certificate_hash.clone()).await;
+                        verified_certificate_hashes.push(certificate_hash);
+                    }
+                    certificate = next_certificate;
+                }
+                Err(error) => {
+                    self.verify_multi_signatures_batch(pending_multi_signatures)
+                        .await?;
+                    return Err(error);
+                }
+            }
+        }
+        self.verify_multi_signatures_batch(pending_multi_signatures)
+            .await?;
+        for certificate_hash in pending_certificate_hashes {
+            on_certificate_verified(certificate_hash.clone()).await;
+            verified_certificate_hashes.push(certificate_hash);
+        }
+
+        Ok(verified_certificate_hashes)
+    }
+
+    /// Check that a certificate has not been tampered and does not chain to itself
+    fn verify_certificate_hash(&self, certificate: &Certificate) -> StdResult<()> {
+        debug!(
+            self.logger,
+            "Verifying certificate";
+            "certificate_hash" => &certificate.hash,
+            "certificate_previous_hash" => &certificate.previous_hash,
+            "certificate_epoch" => ?certificate.epoch,
+            "certificate_signed_entity_type" => ?certificate.signed_entity_type(),
+        );
+
+        certificate
+            .hash
+            .eq(&certificate.compute_hash())
+            .then(|| certificate.hash.clone())
+            .ok_or(CertificateVerifierError::CertificateHashUnmatch)?;
+
+        if certificate.is_chaining_to_itself() {
+            return Err(anyhow!(
+                CertificateVerifierError::CertificateChainInfiniteLoop
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Verify the multi signatures of a batch of standard certificates of a certificate chain.
+    ///
+    /// The multi signatures are grouped by aggregate verification key, each group is batch
+    /// verified (in parallel on a blocking thread on native targets). If the batch verification
+    /// of a group fails, its multi signatures are verified one at a time to find the first
+    /// failing certificate of the chain.
+    async fn verify_multi_signatures_batch(
+        &self,
+        certificates: Vec<(Certificate, ProtocolMultiSignature)>,
+    ) -> StdResult<()> {
+        if certificates.is_empty() {
+            return Ok(());
+        }
+        debug!(
+            self.logger,
+            "Verifying a batch of {} multi signatures",
+            certificates.len()
+        );
+
+        #[cfg(not(target_family = "wasm"))]
+        let (certificates, indexes_to_verify) = {
+            let logger = self.logger.clone();
+            tokio::task::spawn_blocking(move || {
+                let indexes_to_verify =
+                    Self::find_multi_signatures_failing_batch_verification(&logger, &certificates);
+                (certificates, indexes_to_verify)
+            })
+            .await
+            .with_context(|| "Batch verification of the multi signatures failed to complete")?
+        };
+        // Batch verification of aggregate signatures is not available on wasm targets
+        #[cfg(target_family = "wasm")]
+        let indexes_to_verify: Vec<usize> = (0..certificates.len()).collect();
+
+        for index in indexes_to_verify {
+            let (certificate, signature) = &certificates[index];
+            self.verify_multi_signature(
+                certificate.signed_message.as_bytes(),
+                signature,
+                &certificate.aggregate_verification_key,
+                &certificate.metadata.protocol_parameters,
+            )
+            .map_err(|error| {
+                CertificateVerifierError::VerifyCertificateChainMultiSignature {
+                    certificate_hash: certificate.hash.clone(),
+                    error: error.to_string(),
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Group the multi signatures by aggregate verification key and batch verify each group in
+    /// parallel, returns the sorted indexes of the certificates of the groups that failed
+    #[cfg(not(target_family = "wasm"))]
+    fn find_multi_signatures_failing_batch_verification(
+        logger: &Logger,
+        certificates: &[(Certificate, ProtocolMultiSignature)],
+    ) -> Vec<usize> {
+        let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (index, (certificate, _)) in certificates.iter().enumerate() {
+            let avk = certificate
+                .aggregate_verification_key
+                .to_json_hex()
+                .unwrap_or_else(|_| certificate.hash.clone());
+            groups.entry(avk).or_default().push(index);
+        }
+
+        let mut failed_indexes: Vec<usize> = groups
+            .into_par_iter()
+            .filter(|(_, group)| !Self::batch_verify_multi_signatures(certificates, group))
+            .flat_map(|(_, group)| group)
+            .collect();
+        if !failed_indexes.is_empty() {
+            warn!(
+                logger,
+                "Batch verification of {} multi signatures failed, verifying them one at a time",
+                failed_indexes.len()
+            );
+        }
+        failed_indexes.sort_unstable();
+
+        failed_indexes
+    }
+
+    /// Batch verify the multi signatures of the given certificates, returns `true` if they are
+    /// all valid
+    #[cfg(not(target_family = "wasm"))]
+    fn batch_verify_multi_signatures(
+        certificates: &[(Certificate, ProtocolMultiSignature)],
+        indexes: &[usize],
+    ) -> bool {
+        let (signatures, messages, avks, parameters) = indexes.iter().fold(
+            (vec![], vec![], vec![], vec![]),
+            |(mut signatures, mut messages, mut avks, mut parameters), &index| {
+                let (certificate, signature) = &certificates[index];
+                signatures.push((**signature).clone());
+                messages.push(certificate.signed_message.as_bytes().to_vec());
+                avks.push((*certificate.aggregate_verification_key).clone());
+                parameters.push(certificate.metadata.protocol_parameters.clone().into());
+                (signatures, messages, avks, parameters)
+            },
+        );
+
+        StmAggrSig::batch_verify(&signatures, &messages, &avks, &parameters).is_ok()
+    }
 }
 
 #[cfg_attr(target_family = "wasm", async_trait(?Send))]
@@ -263,39 +532,38 @@ impl CertificateVerifier for MithrilCertificateVerifier {
         certificate: &Certificate,
         genesis_verification_key: &ProtocolGenesisVerificationKey,
     ) -> StdResult<Option<Certificate>> {
-        debug!(
-            self.logger,
-            "Verifying certificate";
-            "certificate_hash" => &certificate.hash,
-            "certificate_previous_hash" => &certificate.previous_hash,
-            "certificate_epoch" => ?certificate.epoch,
-            "certificate_signed_entity_type" => ?certificate.signed_entity_type(),
-        );
+        self.verify_certificate_hash(certificate)?;
 
-        certificate
-            .hash
-            .eq(&certificate.compute_hash())
-            .then(|| certificate.hash.clone())
-            .ok_or(CertificateVerifierError::CertificateHashUnmatch)?;
-
-        if certificate.is_chaining_to_itself() {
-            Err(anyhow!(
-                CertificateVerifierError::CertificateChainInfiniteLoop
-            ))
-        } else {
-            match &certificate.signature {
-                CertificateSignature::GenesisSignature(_signature) => {
-                    self.verify_genesis_certificate(certificate, genesis_verification_key)
-                        .await?;
-                    Ok(None)
-                }
-                CertificateSignature::MultiSignature(_, signature) => {
-                    self.verify_standard_certificate(certificate, signature)
-                        .await
-                }
+        match &certificate.signature {
+            CertificateSignature::GenesisSignature(_signature) => {
+                self.verify_genesis_certificate(certificate, genesis_verification_key)
+                    .await?;
+                Ok(None)
+            }
+            CertificateSignature::MultiSignature(_, signature) => {
+                self.verify_standard_certificate(certificate, signature)
+                    .await
             }
         }
     }
+
+    /// Verify the certificate chain, the multi signatures of the standard certificates are
+    /// verified by batches of certificates while walking the chain.
+    async fn verify_certificate_chain(
+        &self,
+        certificate: Certificate,
+        genesis_verification_key: &ProtocolGenesisVerificationKey,
+    ) -> StdResult<()> {
+        self.verify_certificate_chain_until(
+            certificate,
+            genesis_verification_key,
+            |_| async { Ok(false) },
+            |_| async {},
+        )
+        .await?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -310,6 +578,7 @@ mod tests {
     use crate::certificate_chain::CertificateRetrieverError;
     use crate::crypto_helper::{tests_setup::*, ProtocolClerk};
     use crate::test_utils::MithrilFixtureBuilder;
+    use std::collections::HashMap;
 
     mock! {
         pub CertificateRetrieverImpl { }
@@ -592,4 +861,120 @@ mod tests {
             "unexpected error type: {error:?}"
         );
     }
+
+    fn verifier_for_certificate_chain(
+        certificates: &[Certificate],
+        batch_size: usize,
+    ) -> MithrilCertificateVerifier {
+        let certificates_by_hash: HashMap<String, Certificate> = certificates
+            .iter()
+            .map(|certificate| (certificate.hash.clone(), certificate.clone()))
+            .collect();
+        let mut mock_certificate_retriever = MockCertificateRetrieverImpl::new();
+        mock_certificate_retriever
+            .expect_get_certificate_details()
+            .returning(move |hash| {
+                Ok(certificates_by_hash
+                    .get(hash)
+                    .cloned()
+                    .expect("certificate should be in the chain"))
+            });
+
+        MithrilCertificateVerifier::new(slog_scope::logger(), Arc::new(mock_certificate_retriever))
+            .with_batch_size(batch_size)
+    }
+
+    #[tokio::test]
+    async fn test_verify_certificate_chain_ok_with_several_batches() {
+        let (fake_certificates, genesis_verifier) = setup_certificate_chain(15, 2);
+        let certificate_to_verify = fake_certificates[0].clone();
+
+        for batch_size in [1, 4, 100] {
+            let verifier = verifier_for_certificate_chain(&fake_certificates, batch_size);
+            verifier
+                .verify_certificate_chain(
+                    certificate_to_verify.clone(),
+                    &genesis_verifier.to_verification_key(),
+                )
+                .await
+                .unwrap_or_else(|e| panic!("unexpected error with batch size {batch_size}: {e:?}"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_certificate_chain_until_stops_at_a_verified_certificate() {
+        let (fake_certificates, genesis_verifier) = setup_certificate_chain(15, 2);
+        let certificate_to_verify = fake_certificates[0].clone();
+        let verified_certificate_hash = fake_certificates[6].hash.clone();
+
+        let expected_verified_certificate_hashes = fake_certificates[..6]
+            .iter()
+            .map(|certificate| certificate.hash.clone())
+            .collect::<Vec<_>>();
+
+        for batch_size in [1, 4, 100] {
+            let verifier = verifier_for_certificate_chain(&fake_certificates, batch_size);
+            let reported_certificate_hashes = std::sync::Mutex::new(vec![]);
+            let verified_certificate_hashes = verifier
+                .verify_certificate_chain_until(
+                    certificate_to_verify.clone(),
+                    &genesis_verifier.to_verification_key(),
+                    |certificate_hash| {
+                        let is_verified = certificate_hash == verified_certificate_hash;
+                        async move { Ok(is_verified) }
+                    },
+                    |certificate_hash| {
+                        reported_certificate_hashes
+                            .lock()
+                            .unwrap()
+                            .push(certificate_hash);
+                        async {}
+                    },
+                )
+                .await
+                .unwrap_or_else(|e| panic!("unexpected error with batch size {batch_size}: {e:?}"));
+
+            assert_eq!(
+                expected_verified_certificate_hashes, verified_certificate_hashes,
+                "unexpected verified certificates with batch size {batch_size}"
+            );
+            assert_eq!(
+                expected_verified_certificate_hashes,
+                reported_certificate_hashes.into_inner().unwrap(),
+                "unexpected reported certificates with batch size {batch_size}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_certificate_chain_ko_multi_signature_pinpoints_the_failing_certificate() {
+        let (mut fake_certificates, genesis_verifier) = setup_certificate_chain(15, 2);
+        let index_certificate_fail = 5;
+        tamper_certificate_chain_multi_signature(&mut fake_certificates, index_certificate_fail);
+        let certificate_to_verify = fake_certificates[0].clone();
+        let expected_certificate_hash = fake_certificates[index_certificate_fail].hash.clone();
+
+        for batch_size in [1, 4, 100] {
+            let verifier = verifier_for_certificate_chain(&fake_certificates, batch_size);
+            let error = verifier
+                .verify_certificate_chain(
+                    certificate_to_verify.clone(),
+                    &genesis_verifier.to_verification_key(),
+                )
+                .await
+                .expect_err("verify_certificate_chain should fail");
+            let error = error
+                .downcast_ref::<CertificateVerifierError>()
+                .expect("Can not downcast to `CertificateVerifierError`.");
+
+            assert!(
+                matches!(
+                    error,
+                    CertificateVerifierError::VerifyCertificateChainMultiSignature { certificate_hash, .. }
+                    if certificate_hash == &expected_certificate_hash
+                ),
+                "unexpected error type with batch size {batch_size}: {error:?}"
+            );
+        }
+    }
 }
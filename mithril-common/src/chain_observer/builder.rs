@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use slog::Logger;
 use std::{fmt::Display, path::PathBuf, sync::Arc};
 use thiserror::Error;
 
@@ -6,7 +7,9 @@ use crate::{chain_observer::ChainObserver, CardanoNetwork, StdResult};
 
 #[cfg(any(test, feature = "test_tools"))]
 use super::FakeObserver;
-use super::{CardanoCliChainObserver, CardanoCliRunner, PallasChainObserver};
+use super::{
+    CardanoCliChainObserver, CardanoCliRunner, FallbackChainObserver, PallasChainObserver,
+};
 
 /// Type of chain observers available
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -17,6 +20,9 @@ pub enum ChainObserverType {
     CardanoCli,
     /// Pallas chain observer.
     Pallas,
+    /// Pallas chain observer, falling back to the Cardano Cli chain observer on error.
+    #[serde(rename = "pallas-with-cli-fallback")]
+    PallasWithCliFallback,
     /// Fake chain observer.
     #[cfg(any(test, feature = "test_tools"))]
     Fake,
@@ -27,6 +33,7 @@ impl Display for ChainObserverType {
         match self {
             Self::CardanoCli => write!(f, "cardano-cli"),
             Self::Pallas => write!(f, "pallas"),
+            Self::PallasWithCliFallback => write!(f, "pallas-with-cli-fallback"),
             #[cfg(any(test, feature = "test_tools"))]
             Self::Fake => write!(f, "fake"),
         }
@@ -47,6 +54,7 @@ pub struct ChainObserverBuilder {
     cardano_node_socket_path: PathBuf,
     cardano_network: CardanoNetwork,
     cardano_cli_runner: Option<Box<CardanoCliRunner>>,
+    logger: Logger,
 }
 
 impl ChainObserverBuilder {
@@ -62,25 +70,47 @@ impl ChainObserverBuilder {
             cardano_node_socket_path: cardano_node_socket_path.to_owned(),
             cardano_network: cardano_node_network.to_owned(),
             cardano_cli_runner: cardano_cli_runner.map(|c| c.to_owned().into()),
+            logger: Logger::root(slog::Discard, slog::o!()),
         }
     }
 
+    /// Set the logger used by the built chain observer
+    pub fn with_logger(mut self, logger: Logger) -> Self {
+        self.logger = logger;
+        self
+    }
+
     /// Create chain observer
     pub fn build(&self) -> StdResult<Arc<dyn ChainObserver>> {
         match self.chain_observer_type {
-            ChainObserverType::CardanoCli => Ok(Arc::new(CardanoCliChainObserver::new(
-                self.cardano_cli_runner
-                    .as_ref()
-                    .ok_or(ChainObserverBuilderError::MissingCardanoCliRunner)?
-                    .to_owned(),
-            ))),
+            ChainObserverType::CardanoCli => Ok(Arc::new(self.build_cardano_cli_observer()?)),
             ChainObserverType::Pallas => {
                 let observer =
                     PallasChainObserver::new(&self.cardano_node_socket_path, self.cardano_network);
                 Ok(Arc::new(observer))
             }
+            ChainObserverType::PallasWithCliFallback => {
+                let observer = FallbackChainObserver::new(
+                    Arc::new(PallasChainObserver::new(
+                        &self.cardano_node_socket_path,
+                        self.cardano_network,
+                    )),
+                    Arc::new(self.build_cardano_cli_observer()?),
+                    self.logger.clone(),
+                );
+                Ok(Arc::new(observer))
+            }
             #[cfg(any(test, feature = "test_tools"))]
             ChainObserverType::Fake => Ok(Arc::new(FakeObserver::default())),
         }
     }
+
+    fn build_cardano_cli_observer(&self) -> StdResult<CardanoCliChainObserver> {
+        Ok(CardanoCliChainObserver::new(
+            self.cardano_cli_runner
+                .as_ref()
+                .ok_or(ChainObserverBuilderError::MissingCardanoCliRunner)?
+                .to_owned(),
+        ))
+    }
 }
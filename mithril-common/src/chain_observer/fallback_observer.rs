@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use slog::{warn, Logger};
+
+use crate::chain_observer::interface::*;
+use crate::chain_observer::{ChainAddress, TxDatum};
+use crate::crypto_helper::{KESPeriod, OpCert};
+use crate::entities::{ChainPoint, Epoch, StakeDistribution};
+
+/// A [ChainObserver] that requests a primary observer first and falls back to a secondary
+/// observer when the primary one fails.
+///
+/// Mainly used to query the node with Pallas and fall back to the `cardano-cli`, for example
+/// when a node returns a response that the Pallas decoders don't support yet.
+pub struct FallbackChainObserver {
+    primary: Arc<dyn ChainObserver>,
+    fallback: Arc<dyn ChainObserver>,
+    logger: Logger,
+}
+
+impl FallbackChainObserver {
+    /// FallbackChainObserver factory
+    pub fn new(
+        primary: Arc<dyn ChainObserver>,
+        fallback: Arc<dyn ChainObserver>,
+        logger: Logger,
+    ) -> Self {
+        Self {
+            primary,
+            fallback,
+            logger,
+        }
+    }
+
+    fn log_fallback(&self, query: &str, error: &ChainObserverError) {
+        warn!(
+            self.logger,
+            "FallbackChainObserver: primary chain observer failed, falling back to the secondary one";
+            "query" => query, "error" => ?error
+        );
+    }
+}
+
+#[async_trait]
+impl ChainObserver for FallbackChainObserver {
+    async fn get_current_datums(
+        &self,
+        address: &ChainAddress,
+    ) -> Result<Vec<TxDatum>, ChainObserverError> {
+        match self.primary.get_current_datums(address).await {
+            Err(error) => {
+                self.log_fallback("get_current_datums", &error);
+                self.fallback.get_current_datums(address).await
+            }
+            result => result,
+        }
+    }
+
+    async fn get_current_epoch(&self) -> Result<Option<Epoch>, ChainObserverError> {
+        match self.primary.get_current_epoch().await {
+            Err(error) => {
+                self.log_fallback("get_current_epoch", &error);
+                self.fallback.get_current_epoch().await
+            }
+            result => result,
+        }
+    }
+
+    async fn get_current_chain_point(&self) -> Result<Option<ChainPoint>, ChainObserverError> {
+        match self.primary.get_current_chain_point().await {
+            Err(error) => {
+                self.log_fallback("get_current_chain_point", &error);
+                self.fallback.get_current_chain_point().await
+            }
+            result => result,
+        }
+    }
+
+    async fn get_current_stake_distribution(
+        &self,
+    ) -> Result<Option<StakeDistribution>, ChainObserverError> {
+        match self.primary.get_current_stake_distribution().await {
+            Err(error) => {
+                self.log_fallback("get_current_stake_distribution", &error);
+                self.fallback.get_current_stake_distribution().await
+            }
+            result => result,
+        }
+    }
+
+    async fn get_current_kes_period(
+        &self,
+        opcert: &OpCert,
+    ) -> Result<Option<KESPeriod>, ChainObserverError> {
+        match self.primary.get_current_kes_period(opcert).await {
+            Err(error) => {
+                self.log_fallback("get_current_kes_period", &error);
+                self.fallback.get_current_kes_period(opcert).await
+            }
+            result => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use crate::chain_observer::MockChainObserver;
+    use crate::test_utils::TestLogger;
+
+    use super::*;
+
+    fn observer_returning_epoch(epoch: u64) -> MockChainObserver {
+        let mut observer = MockChainObserver::new();
+        observer
+            .expect_get_current_epoch()
+            .returning(move || Ok(Some(Epoch(epoch))));
+        observer
+    }
+
+    fn failing_observer() -> MockChainObserver {
+        let mut observer = MockChainObserver::new();
+        observer
+            .expect_get_current_epoch()
+            .returning(|| Err(ChainObserverError::General(anyhow!("primary failure"))));
+        observer
+    }
+
+    #[tokio::test]
+    async fn use_primary_observer_when_it_succeeds() {
+        let mut fallback = MockChainObserver::new();
+        fallback.expect_get_current_epoch().never();
+        let observer = FallbackChainObserver::new(
+            Arc::new(observer_returning_epoch(10)),
+            Arc::new(fallback),
+            TestLogger::stdout(),
+        );
+
+        let epoch = observer.get_current_epoch().await.unwrap();
+
+        assert_eq!(Some(Epoch(10)), epoch);
+    }
+
+    #[tokio::test]
+    async fn use_fallback_observer_when_primary_fails() {
+        let observer = FallbackChainObserver::new(
+            Arc::new(failing_observer()),
+            Arc::new(observer_returning_epoch(20)),
+            TestLogger::stdout(),
+        );
+
+        let epoch = observer.get_current_epoch().await.unwrap();
+
+        assert_eq!(Some(Epoch(20)), epoch);
+    }
+
+    #[tokio::test]
+    async fn fail_when_both_observers_fail() {
+        let observer = FallbackChainObserver::new(
+            Arc::new(failing_observer()),
+            Arc::new(failing_observer()),
+            TestLogger::stdout(),
+        );
+
+        observer
+            .get_current_epoch()
+            .await
+            .expect_err("Should fail when both observers fail");
+    }
+}
@@ -13,14 +13,18 @@ pub use model::{
 cfg_fs_random! {
     mod builder;
     mod cli_observer;
+    mod fallback_observer;
     mod pallas_observer;
 
     #[cfg(test)]
     mod test_cli_runner;
+    #[cfg(test)]
+    mod test_conformance;
 
     pub use builder::{ChainObserverBuilder, ChainObserverType};
     pub use cli_observer::CliRunner;
     pub use cli_observer::{CardanoCliChainObserver, CardanoCliRunner};
+    pub use fallback_observer::FallbackChainObserver;
     pub use pallas_observer::PallasChainObserver;
 }
 
@@ -5,6 +5,7 @@ use pallas_codec::utils::{Bytes, CborWrap, TagWrap};
 use pallas_network::{
     facades::NodeClient,
     miniprotocols::{
+        chainsync::{self, Tip},
         localstate::{
             queries_v16::{
                 self, Addr, Addrs, Genesis, PostAlonsoTransactionOutput, StakeSnapshot, Stakes,
@@ -279,6 +280,40 @@ impl PallasChainObserver {
         Ok(chain_point)
     }
 
+    /// Fetches the tip of the chain using the provided `chainsync` client.
+    ///
+    /// The intersection with the origin of the chain always exists and its response contains
+    /// the tip of the node.
+    async fn do_get_chain_tip_chainsync(
+        &self,
+        chainsync: &mut chainsync::N2CClient,
+    ) -> StdResult<Tip> {
+        let (_, tip) = chainsync
+            .find_intersect(vec![Point::Origin])
+            .await
+            .map_err(|err| anyhow!(err))
+            .with_context(|| "PallasChainObserver failed to find the chain tip")?;
+
+        Ok(tip)
+    }
+
+    /// Fetches the current chain point, `None` if the node is at the origin of the chain.
+    async fn get_chain_point(
+        &self,
+        client: &mut NodeClient,
+    ) -> Result<Option<ChainPoint>, ChainObserverError> {
+        let Tip(point, block_number) = self.do_get_chain_tip_chainsync(client.chainsync()).await?;
+
+        match point {
+            Point::Origin => Ok(None),
+            Point::Specific(slot_number, block_hash) => Ok(Some(ChainPoint {
+                slot_number,
+                block_number,
+                block_hash: hex::encode(block_hash),
+            })),
+        }
+    }
+
     /// Fetches the current era using the provided `statequery` client.
     async fn do_get_current_era_state_query(&self, statequery: &mut Client) -> StdResult<u16> {
         let era = queries_v16::get_current_era(statequery)
@@ -385,8 +420,15 @@ impl ChainObserver for PallasChainObserver {
     }
 
     async fn get_current_chain_point(&self) -> Result<Option<ChainPoint>, ChainObserverError> {
-        // TODO: Implement get_current_chain_point with pallas
-        todo!("Implement get_current_chain_point")
+        let mut client = self.get_client().await?;
+
+        let chain_point = self.get_chain_point(&mut client).await?;
+
+        self.sync(&mut client).await?;
+
+        client.abort().await;
+
+        Ok(chain_point)
     }
 
     async fn get_current_datums(
@@ -769,6 +811,46 @@ mod tests {
         assert_eq!(chain_point, Point::Specific(52851885, vec![1, 2, 3]));
     }
 
+    #[tokio::test]
+    async fn get_current_chain_point() {
+        let socket_path = create_temp_dir("get_current_chain_point").join("node.socket");
+        let server = tokio::spawn({
+            let socket_path = socket_path.clone();
+            async move {
+                let unix_listener = UnixListener::bind(socket_path.as_path()).unwrap();
+                let mut server = pallas_network::facades::NodeServer::accept(&unix_listener, 10)
+                    .await
+                    .unwrap();
+
+                server.chainsync().recv_while_idle().await.unwrap();
+                server
+                    .chainsync()
+                    .send_intersect_found(
+                        Point::Origin,
+                        Tip(Point::Specific(52851885, vec![1, 2, 3]), 1234),
+                    )
+                    .await
+                    .unwrap();
+            }
+        });
+        let client = tokio::spawn(async move {
+            let observer =
+                PallasChainObserver::new(socket_path.as_path(), CardanoNetwork::TestNet(10));
+            observer.get_current_chain_point().await.unwrap()
+        });
+
+        let (_, client_res) = tokio::join!(server, client);
+        let chain_point = client_res.expect("Client failed");
+        assert_eq!(
+            Some(ChainPoint {
+                slot_number: 52851885,
+                block_number: 1234,
+                block_hash: "010203".to_string(),
+            }),
+            chain_point
+        );
+    }
+
     #[tokio::test]
     async fn get_genesis_config() {
         let socket_path = create_temp_dir("get_genesis_config").join("node.socket");
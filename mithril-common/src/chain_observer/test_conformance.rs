@@ -0,0 +1,384 @@
+//! Conformance tests of the [ChainObserver] implementations
+//!
+//! The same checks are run against the [CardanoCliChainObserver] using the [TestCliRunner] and
+//! the [PallasChainObserver] connected to a node server that replays a recorded node-to-client
+//! exchange holding the same data as the [TestCliRunner] outputs.
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use kes_summed_ed25519::{kes::Sum6Kes, traits::KesSk};
+use pallas_addresses::Address;
+use pallas_codec::utils::{AnyCbor, AnyUInt, Bytes, KeyValuePairs, TagWrap};
+use pallas_crypto::hash::Hash;
+use pallas_network::facades::NodeServer;
+use pallas_network::miniprotocols::chainsync::{ClientRequest, Tip};
+use pallas_network::miniprotocols::localstate::queries_v16::{
+    self, Addr, BlockQuery, Fraction, Genesis, HardForkQuery, LedgerQuery,
+    PostAlonsoTransactionOutput, Request, Snapshots, StakeSnapshot, Stakes, SystemStart,
+    TransactionOutput, UTxOByAddress, Value,
+};
+use pallas_network::miniprotocols::localstate::ClientQueryRequest;
+use pallas_network::miniprotocols::Point;
+use tokio::net::UnixListener;
+
+use crate::chain_observer::test_cli_runner::TestCliRunner;
+use crate::chain_observer::{CardanoCliChainObserver, ChainObserver, PallasChainObserver};
+use crate::crypto_helper::{ColdKeyGenerator, OpCert};
+use crate::entities::{ChainPoint, Epoch, StakeDistribution};
+use crate::test_utils::TempDir;
+use crate::CardanoNetwork;
+
+const SLOTS_PER_KES_PERIOD: u64 = 129600;
+
+/// Mini-protocol exchange replayed by the recorded node server
+enum RecordedExchange {
+    /// Acquire the ledger state then answer the given number of local state queries
+    StateQueries(usize),
+    /// Answer a chain-sync intersection request with the tip of the node
+    ChainSyncTip,
+}
+
+fn recorded_chain_tip() -> Tip {
+    Tip(
+        Point::Specific(
+            25886617,
+            hex::decode("7383b17d7b05b0953cf0649abff60173995eb9febe556889333e20e1e5b7ca84")
+                .unwrap(),
+        ),
+        1270276,
+    )
+}
+
+fn recorded_utxo_by_address() -> UTxOByAddress {
+    let tx_hex = "1fd4d3e131afe3c8b212772a3f3083d2fbc6b2a7b20e54e4ff08e001598818d8";
+    let tx_bytes: [u8; 32] = hex::decode(tx_hex).unwrap().try_into().unwrap();
+    let datum_bytes = hex::decode("5b0a20207b0a20202020226e616d65223a20227468616c6573222c0a202020202265706f6368223a203132330a20207d2c0a20207b0a20202020226e616d65223a20227079746861676f726173222c0a202020202265706f6368223a206e756c6c0a20207d0a5d0a").unwrap();
+    // Plutus data `Constr 0 [B datum_bytes]`
+    let datum = [
+        hex::decode("d8799f5868").unwrap(),
+        datum_bytes,
+        hex::decode("ff").unwrap(),
+    ]
+    .concat();
+    let address: Addr =
+        Address::from_bech32("addr_test1vpcr3he05gemue6eyy0c9clajqnnww8aa2l3jszjdlszjhq093qrn")
+            .unwrap()
+            .to_vec()
+            .into();
+    let output_with_datum = TransactionOutput::Current(PostAlonsoTransactionOutput {
+        address: address.clone(),
+        amount: Value::Coin(AnyUInt::U32(10000000)),
+        inline_datum: Some((1_u16, TagWrap::<_, 24>::new(Bytes::from(datum)))),
+        script_ref: None,
+    });
+    let output_without_datum = TransactionOutput::Current(PostAlonsoTransactionOutput {
+        address,
+        amount: Value::Coin(AnyUInt::U64(9989656678)),
+        inline_datum: None,
+        script_ref: None,
+    });
+
+    UTxOByAddress {
+        utxo: KeyValuePairs::from(vec![
+            (
+                queries_v16::UTxO {
+                    transaction_id: Hash::from(tx_bytes),
+                    index: AnyUInt::MajorByte(0),
+                },
+                output_with_datum,
+            ),
+            (
+                queries_v16::UTxO {
+                    transaction_id: Hash::from(tx_bytes),
+                    index: AnyUInt::MajorByte(1),
+                },
+                output_without_datum,
+            ),
+        ]),
+    }
+}
+
+fn recorded_stake_snapshot() -> StakeSnapshot {
+    let pool_stakes = |pool_id_hex: &str, go: u64, mark: u64, set: u64| {
+        (
+            Bytes::from(hex::decode(pool_id_hex).unwrap()),
+            Stakes {
+                snapshot_mark_pool: mark,
+                snapshot_set_pool: set,
+                snapshot_go_pool: go,
+            },
+        )
+    };
+
+    StakeSnapshot {
+        snapshots: Snapshots {
+            stake_snapshots: KeyValuePairs::from(vec![
+                pool_stakes(
+                    "00000036d515e12e18cd3c88c74f09a67984c2c279a5296aa96efe89",
+                    300000000000,
+                    300000000001,
+                    300000000002,
+                ),
+                pool_stakes(
+                    "000000f66e28b0f18aef20555f4c4954234e3270dfbbdcc13f54e799",
+                    600000000000,
+                    600000000001,
+                    600000000002,
+                ),
+                pool_stakes(
+                    "00000110093effbf3ce788aebd3e7506b80322bd3995ad432e61fad5",
+                    1200000000000,
+                    1200000000001,
+                    1200000000002,
+                ),
+                pool_stakes(
+                    "00000ffff93effbf3ce788aebd3e7506b80322bd3995ad432e61fad5",
+                    0,
+                    0,
+                    1300000000002,
+                ),
+            ]),
+            snapshot_stake_mark_total: 2100000000003,
+            snapshot_stake_set_total: 2100000000006,
+            snapshot_stake_go_total: 2100000000000,
+        },
+    }
+}
+
+fn recorded_genesis_config() -> Vec<Genesis> {
+    vec![Genesis {
+        system_start: SystemStart {
+            year: 2021,
+            day_of_year: 150,
+            picoseconds_of_day: 0,
+        },
+        network_magic: 42,
+        network_id: 42,
+        active_slots_coefficient: Fraction { num: 6, dem: 10 },
+        security_param: 2160,
+        epoch_length: 432000,
+        slots_per_kes_period: SLOTS_PER_KES_PERIOD as u32,
+        max_kes_evolutions: 62,
+        slot_length: 1,
+        update_quorum: 5,
+        max_lovelace_supply: AnyUInt::MajorByte(2),
+    }]
+}
+
+/// Answer of the recorded node to a local state query
+fn recorded_query_result(query: Request) -> AnyCbor {
+    match query {
+        // Slot of the acquired ledger state, in the 404th KES period
+        Request::GetChainPoint => AnyCbor::from_encode(Point::Specific(
+            404 * SLOTS_PER_KES_PERIOD + 1000,
+            vec![1, 2, 3],
+        )),
+        Request::LedgerQuery(LedgerQuery::HardForkQuery(HardForkQuery::GetCurrentEra)) => {
+            AnyCbor::from_encode(4)
+        }
+        Request::LedgerQuery(LedgerQuery::BlockQuery(_, BlockQuery::GetEpochNo)) => {
+            AnyCbor::from_encode([120])
+        }
+        Request::LedgerQuery(LedgerQuery::BlockQuery(_, BlockQuery::GetGenesisConfig)) => {
+            AnyCbor::from_encode(recorded_genesis_config())
+        }
+        Request::LedgerQuery(LedgerQuery::BlockQuery(_, BlockQuery::GetUTxOByAddress(_))) => {
+            AnyCbor::from_encode(recorded_utxo_by_address())
+        }
+        Request::LedgerQuery(LedgerQuery::BlockQuery(_, BlockQuery::GetStakeSnapshots(_))) => {
+            AnyCbor::from_encode(recorded_stake_snapshot())
+        }
+        _ => panic!("unexpected query from client: {query:?}"),
+    }
+}
+
+/// Start a node server on the given socket that replays the given exchange
+fn setup_recorded_node_server(
+    socket_path: PathBuf,
+    exchange: RecordedExchange,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let unix_listener = UnixListener::bind(socket_path.as_path()).unwrap();
+        let mut server = NodeServer::accept(&unix_listener, 10).await.unwrap();
+
+        match exchange {
+            RecordedExchange::StateQueries(number_of_queries) => {
+                server.statequery().recv_while_idle().await.unwrap();
+                server.statequery().send_acquired().await.unwrap();
+
+                for _ in 0..number_of_queries {
+                    let query = match server.statequery().recv_while_acquired().await.unwrap() {
+                        ClientQueryRequest::Query(query) => query.into_decode().unwrap(),
+                        request => panic!("unexpected message from client: {request:?}"),
+                    };
+                    server
+                        .statequery()
+                        .send_result(recorded_query_result(query))
+                        .await
+                        .unwrap();
+                }
+            }
+            RecordedExchange::ChainSyncTip => {
+                match server.chainsync().recv_while_idle().await.unwrap() {
+                    Some(ClientRequest::Intersect(_)) => {}
+                    request => panic!("unexpected message from client: {request:?}"),
+                };
+                server
+                    .chainsync()
+                    .send_intersect_found(Point::Origin, recorded_chain_tip())
+                    .await
+                    .unwrap();
+            }
+        }
+    })
+}
+
+/// Run the given observation against both the cardano-cli and the Pallas chain observers.
+async fn observe_with_all_observers<T, F, Fut>(
+    test_name: &str,
+    exchange: RecordedExchange,
+    observe: F,
+) -> Vec<(&'static str, T)>
+where
+    T: Send + 'static,
+    F: Fn(Arc<dyn ChainObserver>) -> Fut,
+    Fut: Future<Output = T> + Send + 'static,
+{
+    let cli_observer: Arc<dyn ChainObserver> =
+        Arc::new(CardanoCliChainObserver::new(Box::<TestCliRunner>::default()));
+    let cli_result = observe(cli_observer).await;
+
+    let socket_path = TempDir::create_with_short_path("chain_observer_conformance", test_name)
+        .join("node.socket");
+    let server = setup_recorded_node_server(socket_path.clone(), exchange);
+    let pallas_observer: Arc<dyn ChainObserver> = Arc::new(PallasChainObserver::new(
+        &socket_path,
+        CardanoNetwork::TestNet(10),
+    ));
+    let client = tokio::spawn(observe(pallas_observer));
+    let (server_res, pallas_result) = tokio::join!(server, client);
+    server_res.expect("Recorded node server failed");
+
+    vec![
+        ("cardano-cli", cli_result),
+        (
+            "pallas",
+            pallas_result.expect("Pallas observer task failed"),
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn all_observers_return_the_same_current_epoch() {
+    let results = observe_with_all_observers(
+        "current_epoch",
+        RecordedExchange::StateQueries(2),
+        |observer| async move { observer.get_current_epoch().await.unwrap() },
+    )
+    .await;
+
+    for (observer_name, epoch) in results {
+        assert_eq!(Some(Epoch(120)), epoch, "observer: {observer_name}");
+    }
+}
+
+#[tokio::test]
+async fn all_observers_return_the_same_current_chain_point() {
+    let results = observe_with_all_observers(
+        "current_chain_point",
+        RecordedExchange::ChainSyncTip,
+        |observer| async move { observer.get_current_chain_point().await.unwrap() },
+    )
+    .await;
+
+    for (observer_name, chain_point) in results {
+        assert_eq!(
+            Some(ChainPoint {
+                slot_number: 25886617,
+                block_number: 1270276,
+                block_hash: "7383b17d7b05b0953cf0649abff60173995eb9febe556889333e20e1e5b7ca84"
+                    .to_string(),
+            }),
+            chain_point,
+            "observer: {observer_name}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn all_observers_return_the_same_current_datums() {
+    let results = observe_with_all_observers(
+        "current_datums",
+        RecordedExchange::StateQueries(2),
+        |observer| async move {
+            let address =
+                "addr_test1vpcr3he05gemue6eyy0c9clajqnnww8aa2l3jszjdlszjhq093qrn".to_string();
+            observer.get_current_datums(&address).await.unwrap()
+        },
+    )
+    .await;
+
+    let (_, cli_datums) = &results[0];
+    assert_eq!(1, cli_datums.len());
+    for (observer_name, datums) in &results {
+        assert_eq!(cli_datums, datums, "observer: {observer_name}");
+    }
+}
+
+#[tokio::test]
+async fn all_observers_return_the_same_current_stake_distribution() {
+    let results = observe_with_all_observers(
+        "current_stake_distribution",
+        RecordedExchange::StateQueries(2),
+        |observer| async move { observer.get_current_stake_distribution().await.unwrap() },
+    )
+    .await;
+
+    let expected_stake_distribution = StakeDistribution::from([
+        (
+            "pool1qqqqqdk4zhsjuxxd8jyvwncf5eucfskz0xjjj64fdmlgj735lr9".to_string(),
+            300000000001,
+        ),
+        (
+            "pool1qqqqpanw9zc0rzh0yp247nzf2s35uvnsm7aaesfl2nnejaev0uc".to_string(),
+            600000000001,
+        ),
+        (
+            "pool1qqqqzyqf8mlm70883zht60n4q6uqxg4a8x266sewv8ad2grkztl".to_string(),
+            1200000000001,
+        ),
+    ]);
+    for (observer_name, stake_distribution) in results {
+        assert_eq!(
+            Some(expected_stake_distribution.clone()),
+            stake_distribution,
+            "observer: {observer_name}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn all_observers_return_the_same_current_kes_period() {
+    let results = observe_with_all_observers(
+        "current_kes_period",
+        RecordedExchange::StateQueries(3),
+        |observer| async move {
+            let keypair = ColdKeyGenerator::create_deterministic_keypair([0u8; 32]);
+            let mut dummy_key_buffer = [0u8; Sum6Kes::SIZE + 4];
+            let mut dummy_seed = [0u8; 32];
+            let (_, kes_verification_key) = Sum6Kes::keygen(&mut dummy_key_buffer, &mut dummy_seed);
+            let operational_certificate = OpCert::new(kes_verification_key, 0, 0, keypair);
+            observer
+                .get_current_kes_period(&operational_certificate)
+                .await
+                .unwrap()
+        },
+    )
+    .await;
+
+    for (observer_name, kes_period) in results {
+        assert_eq!(Some(404), kes_period, "observer: {observer_name}");
+    }
+}
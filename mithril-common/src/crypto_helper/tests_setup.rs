@@ -303,3 +303,14 @@ pub fn setup_certificate_chain(
     certificates_new.reverse();
     (certificates_new, genesis_verifier)
 }
+
+/// Make the multi signature of a certificate of a chain invalid by changing its signed message,
+/// then fix the hashes of the certificates that follow it in the chain, use this for tests only.
+pub fn tamper_certificate_chain_multi_signature(certificates: &mut [Certificate], index: usize) {
+    certificates[index].signed_message = "tampered-signed-message".to_string();
+    certificates[index].hash = certificates[index].compute_hash();
+    for i in (0..index).rev() {
+        certificates[i].previous_hash = certificates[i + 1].hash.clone();
+        certificates[i].hash = certificates[i].compute_hash();
+    }
+}
@@ -5,8 +5,9 @@ use crate::crypto_helper::cardano::{
 
 use mithril_stm::{
     key_reg::ClosedKeyReg,
+    security::{SecurityRequirements, StmParametersSecurity},
     stm::{Index, Stake, StmClerk, StmParameters, StmSigner},
-    AggregationError,
+    AggregationError, StmParametersSecurityError,
 };
 
 use blake2::{digest::consts::U32, Blake2b};
@@ -29,6 +30,12 @@ pub type ProtocolStakeDistribution = Vec<(ProtocolPartyId, ProtocolStake)>;
 /// Alias of [MithrilStm::StmParameters](struct@mithril_stm::stm::StmParameters).
 pub type ProtocolParameters = StmParameters;
 
+/// Alias of [MithrilStm::StmParametersSecurity](struct@mithril_stm::security::StmParametersSecurity).
+pub type ProtocolParametersSecurity = StmParametersSecurity;
+
+/// Alias of [MithrilStm::SecurityRequirements](struct@mithril_stm::security::SecurityRequirements).
+pub type ProtocolSecurityRequirements = SecurityRequirements;
+
 /// Alias of [MithrilStm::Index](type@mithril_stm::stm::Index).
 pub type ProtocolLotteryIndex = Index;
 
@@ -56,3 +63,6 @@ pub type ProtocolInitializerError = ProtocolInitializerErrorWrapper;
 
 /// Alias of [MithrilStm:AggregationError](enum@mithril_stm::AggregationError).
 pub type ProtocolAggregationError = AggregationError;
+
+/// Alias of [MithrilStm:StmParametersSecurityError](enum@mithril_stm::StmParametersSecurityError).
+pub type ProtocolParametersSecurityError = StmParametersSecurityError;
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::signable_builder::Artifact;
+
+use super::{Epoch, StakeDistribution};
+
+/// Cardano Stake Distribution
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CardanoStakeDistribution {
+    /// Unique hash of the Cardano Stake Distribution
+    pub hash: String,
+
+    /// Epoch at which the Cardano Stake Distribution is computed
+    pub epoch: Epoch,
+
+    /// Stake distribution of the Cardano stake pools, indexed by pool id
+    pub stake_distribution: StakeDistribution,
+}
+
+impl CardanoStakeDistribution {
+    /// Constructor
+    pub fn new(epoch: Epoch, stake_distribution: StakeDistribution) -> CardanoStakeDistribution {
+        let mut cardano_stake_distribution = CardanoStakeDistribution {
+            hash: "".to_string(),
+            epoch,
+            stake_distribution,
+        };
+        cardano_stake_distribution.hash = cardano_stake_distribution.compute_hash();
+        cardano_stake_distribution
+    }
+
+    /// Cardano stake distribution hash computation
+    fn compute_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.epoch.to_be_bytes());
+        for (pool_id, stake) in &self.stake_distribution {
+            hasher.update(pool_id.as_bytes());
+            hasher.update(stake.to_be_bytes());
+        }
+
+        hex::encode(hasher.finalize())
+    }
+}
+
+#[typetag::serde]
+impl Artifact for CardanoStakeDistribution {
+    fn get_id(&self) -> String {
+        self.hash.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPECTED_HASH: &str = "6df01d2c98f81406566d1e6069cdb5ff08171958beed6dd4bf0a762f919069b5";
+
+    fn stake_distribution() -> StakeDistribution {
+        StakeDistribution::from([("pool-123".to_string(), 100), ("pool-456".to_string(), 150)])
+    }
+
+    #[test]
+    fn test_compute_hash() {
+        let cardano_stake_distribution =
+            CardanoStakeDistribution::new(Epoch(1), stake_distribution());
+
+        assert_eq!(EXPECTED_HASH, cardano_stake_distribution.compute_hash());
+    }
+
+    #[test]
+    fn test_hash_fail_for_different_epoch() {
+        let cardano_stake_distribution =
+            CardanoStakeDistribution::new(Epoch(2), stake_distribution());
+
+        assert_ne!(EXPECTED_HASH, cardano_stake_distribution.compute_hash());
+    }
+
+    #[test]
+    fn test_hash_fail_for_different_stake_distribution() {
+        let mut stake_distribution = stake_distribution();
+        stake_distribution.insert("pool-789".to_string(), 200);
+        let cardano_stake_distribution =
+            CardanoStakeDistribution::new(Epoch(1), stake_distribution);
+
+        assert_ne!(EXPECTED_HASH, cardano_stake_distribution.compute_hash());
+    }
+}
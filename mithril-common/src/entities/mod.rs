@@ -4,6 +4,7 @@ mod block_range;
 mod cardano_chain_point;
 mod cardano_db_beacon;
 mod cardano_network;
+mod cardano_stake_distribution;
 mod cardano_transaction;
 mod cardano_transactions_set_proof;
 mod cardano_transactions_snapshot;
@@ -28,6 +29,7 @@ pub use block_range::{BlockRange, BlockRangeLength, BlockRangesSequence};
 pub use cardano_chain_point::{BlockHash, BlockNumber, ChainPoint, SlotNumber};
 pub use cardano_db_beacon::CardanoDbBeacon;
 pub use cardano_network::CardanoNetwork;
+pub use cardano_stake_distribution::CardanoStakeDistribution;
 pub use cardano_transaction::{CardanoTransaction, TransactionHash};
 pub use cardano_transactions_set_proof::CardanoTransactionsSetProof;
 pub use cardano_transactions_snapshot::CardanoTransactionsSnapshot;
@@ -1,6 +1,9 @@
 #[cfg(any(test, feature = "test_tools"))]
 use super::{CardanoDbBeacon, Epoch};
-use super::{CardanoTransactionsSnapshot, MithrilStakeDistribution, SignedEntityType, Snapshot};
+use super::{
+    CardanoStakeDistribution, CardanoTransactionsSnapshot, MithrilStakeDistribution,
+    SignedEntityType, Snapshot,
+};
 use crate::signable_builder::Artifact;
 #[cfg(any(test, feature = "test_tools"))]
 use crate::test_utils::fake_data;
@@ -62,6 +65,23 @@ impl SignedEntity<MithrilStakeDistribution> {
     }
 }
 
+impl SignedEntity<CardanoStakeDistribution> {
+    cfg_test_tools! {
+        /// Create a dummy [SignedEntity] for [CardanoStakeDistribution] entity
+        pub fn dummy() -> Self {
+            SignedEntity {
+                signed_entity_id: "cardano-stake-distribution-id-123".to_string(),
+                signed_entity_type: SignedEntityType::CardanoStakeDistribution(Epoch(1)),
+                certificate_id: "certificate-hash-123".to_string(),
+                artifact: fake_data::cardano_stake_distributions(1)[0].to_owned(),
+                created_at: DateTime::parse_from_rfc3339("2024-07-29T16:15:05.618857482Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+            }
+        }
+    }
+}
+
 impl SignedEntity<CardanoTransactionsSnapshot> {
     cfg_test_tools! {
         /// Create a dummy [SignedEntity] for [CardanoTransactionsSnapshot] entity
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::Epoch;
+
+/// Message structure of a Cardano Stake Distribution list
+pub type CardanoStakeDistributionListMessage = Vec<CardanoStakeDistributionListItemMessage>;
+
+/// Message structure of a Cardano Stake Distribution list item
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CardanoStakeDistributionListItemMessage {
+    /// Epoch at which the Cardano Stake Distribution is computed
+    pub epoch: Epoch,
+
+    /// Hash of the Cardano Stake Distribution
+    pub hash: String,
+
+    /// Hash of the associated certificate
+    pub certificate_hash: String,
+
+    /// DateTime of creation
+    pub created_at: DateTime<Utc>,
+}
+
+impl CardanoStakeDistributionListItemMessage {
+    cfg_test_tools! {
+        /// Return a dummy test entity (test-only).
+        pub fn dummy() -> Self {
+            Self {
+                epoch: Epoch(1),
+                hash: "hash-123".to_string(),
+                certificate_hash: "cert-hash-123".to_string(),
+                created_at: DateTime::parse_from_rfc3339("2024-07-29T16:15:05.618857482Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn golden_message() -> CardanoStakeDistributionListMessage {
+        vec![CardanoStakeDistributionListItemMessage {
+            epoch: Epoch(1),
+            hash: "hash-123".to_string(),
+            certificate_hash: "cert-hash-123".to_string(),
+            created_at: DateTime::parse_from_rfc3339("2024-07-29T16:15:05.618857482Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        }]
+    }
+
+    // Test the retro compatibility with possible future upgrades.
+    #[test]
+    fn test_v1() {
+        let json = r#"[{
+            "epoch": 1,
+            "hash": "hash-123",
+            "certificate_hash": "cert-hash-123",
+            "created_at": "2024-07-29T16:15:05.618857482Z"
+        }]"#;
+        let message: CardanoStakeDistributionListMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be successfully parsed into a CardanoStakeDistributionListMessage instance.",
+        );
+
+        assert_eq!(golden_message(), message);
+    }
+}
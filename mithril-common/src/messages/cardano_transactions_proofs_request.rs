@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+use crate::entities::TransactionHash;
+
+/// Default maximum number of transaction hashes allowed in a single request of proofs.
+pub const MAX_TRANSACTIONS_HASHES_BY_REQUEST: usize = 100;
+
+/// Message structure of a request of proofs for a set of Cardano transactions
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CardanoTransactionsProofsRequestMessage {
+    /// Hashes of the transactions to get proofs for
+    pub transaction_hashes: Vec<TransactionHash>,
+}
+
+impl CardanoTransactionsProofsRequestMessage {
+    /// Create a new `CardanoTransactionsProofsRequestMessage` for the given hashes
+    pub fn new<T: ToString>(transaction_hashes: &[T]) -> Self {
+        Self {
+            transaction_hashes: transaction_hashes.iter().map(|h| h.to_string()).collect(),
+        }
+    }
+
+    /// Maximum size, in bytes, of the JSON body of a request holding up to `max_hashes`
+    /// transaction hashes.
+    ///
+    /// A hash takes 67 bytes once quoted and separated from the next one, the bound doubles that
+    /// and adds a kilobyte so that pretty printed bodies are not refused.
+    pub fn max_json_body_size(max_hashes: usize) -> u64 {
+        1024 + 2 * 67 * max_hashes as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn golden_message() -> CardanoTransactionsProofsRequestMessage {
+        CardanoTransactionsProofsRequestMessage {
+            transaction_hashes: vec!["tx-1".to_string(), "tx-2".to_string()],
+        }
+    }
+
+    #[test]
+    fn max_json_body_size_allows_a_pretty_printed_body_of_max_hashes() {
+        let max_hashes = MAX_TRANSACTIONS_HASHES_BY_REQUEST;
+        let message =
+            CardanoTransactionsProofsRequestMessage::new(&vec!["a".repeat(64); max_hashes]);
+        let body = serde_json::to_vec_pretty(&message).unwrap();
+
+        assert!(
+            (body.len() as u64)
+                <= CardanoTransactionsProofsRequestMessage::max_json_body_size(max_hashes)
+        );
+    }
+
+    // Test the retro compatibility with possible future upgrades.
+    #[test]
+    fn test_v1() {
+        let json = r#"{
+"transaction_hashes": ["tx-1", "tx-2"]
+}"#;
+        let message: CardanoTransactionsProofsRequestMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a CardanoTransactionsProofsRequestMessage instance.",
+        );
+
+        assert_eq!(golden_message(), message);
+    }
+}
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{Epoch, ImmutableFileNumber, SignedEntityTypeDiscriminants};
+
+/// Pagination and filters of the certificate and artifact list routes of an aggregator.
+///
+/// Items are listed from the most recent to the oldest, every filter left to `None` is ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListQueryParameters {
+    /// Maximum number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+
+    /// Number of items to skip before starting to return items
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+
+    /// Cursor: only return items older than the item with this identifier (the hash of a
+    /// certificate or the identifier of an artifact), usually the last item of the previous page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+
+    /// Only return items signed at this epoch or after
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_epoch: Option<Epoch>,
+
+    /// Only return items signed at this epoch or before
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_epoch: Option<Epoch>,
+
+    /// Only return items of this signed entity type (certificates only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_entity_type: Option<SignedEntityTypeDiscriminants>,
+
+    /// Only return items whose beacon has this immutable file number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub immutable_file_number: Option<ImmutableFileNumber>,
+}
+
+impl ListQueryParameters {
+    /// Set the maximum number of items to return
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set the number of items to skip
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Set the identifier of the item after which items are listed
+    pub fn with_before<T: Into<String>>(mut self, before: T) -> Self {
+        self.before = Some(before.into());
+        self
+    }
+
+    /// Set the lower bound (inclusive) of the epoch range
+    pub fn with_from_epoch(mut self, epoch: Epoch) -> Self {
+        self.from_epoch = Some(epoch);
+        self
+    }
+
+    /// Set the upper bound (inclusive) of the epoch range
+    pub fn with_to_epoch(mut self, epoch: Epoch) -> Self {
+        self.to_epoch = Some(epoch);
+        self
+    }
+
+    /// Set the signed entity type
+    pub fn with_signed_entity_type(
+        mut self,
+        signed_entity_type: SignedEntityTypeDiscriminants,
+    ) -> Self {
+        self.signed_entity_type = Some(signed_entity_type);
+        self
+    }
+
+    /// Set the immutable file number of the beacon
+    pub fn with_immutable_file_number(
+        mut self,
+        immutable_file_number: ImmutableFileNumber,
+    ) -> Self {
+        self.immutable_file_number = Some(immutable_file_number);
+        self
+    }
+
+    /// Bound the number of items to return to `max_limit`, using it as the limit if none is set
+    pub fn with_max_limit(mut self, max_limit: usize) -> Self {
+        self.limit = Some(self.limit.map_or(max_limit, |limit| limit.min(max_limit)));
+        self
+    }
+
+    /// Encode the parameters as an URL query string, empty if no parameter is set.
+    pub fn to_query_string(&self) -> String {
+        let mut pairs = vec![];
+        if let Some(limit) = self.limit {
+            pairs.push(format!("limit={limit}"));
+        }
+        if let Some(offset) = self.offset {
+            pairs.push(format!("offset={offset}"));
+        }
+        if let Some(before) = &self.before {
+            pairs.push(format!("before={before}"));
+        }
+        if let Some(from_epoch) = self.from_epoch {
+            pairs.push(format!("from_epoch={from_epoch}"));
+        }
+        if let Some(to_epoch) = self.to_epoch {
+            pairs.push(format!("to_epoch={to_epoch}"));
+        }
+        if let Some(signed_entity_type) = &self.signed_entity_type {
+            pairs.push(format!(
+                "signed_entity_type={}",
+                signed_entity_type.as_ref()
+            ));
+        }
+        if let Some(immutable_file_number) = self.immutable_file_number {
+            pairs.push(format!("immutable_file_number={immutable_file_number}"));
+        }
+
+        pairs.join("&")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_parameters_give_an_empty_query_string() {
+        assert_eq!("", ListQueryParameters::default().to_query_string());
+    }
+
+    #[test]
+    fn max_limit_bounds_the_limit_and_is_used_when_none_is_set() {
+        assert_eq!(
+            Some(20),
+            ListQueryParameters::default().with_max_limit(20).limit
+        );
+        assert_eq!(
+            Some(5),
+            ListQueryParameters::default()
+                .with_limit(5)
+                .with_max_limit(20)
+                .limit
+        );
+        assert_eq!(
+            Some(20),
+            ListQueryParameters::default()
+                .with_limit(50)
+                .with_max_limit(20)
+                .limit
+        );
+    }
+
+    #[test]
+    fn all_parameters_are_encoded_in_the_query_string() {
+        let parameters = ListQueryParameters::default()
+            .with_limit(10)
+            .with_offset(20)
+            .with_before("certificate-hash-123")
+            .with_from_epoch(Epoch(3))
+            .with_to_epoch(Epoch(5))
+            .with_signed_entity_type(SignedEntityTypeDiscriminants::CardanoImmutableFilesFull)
+            .with_immutable_file_number(1234);
+
+        assert_eq!(
+            "limit=10&offset=20&before=certificate-hash-123&from_epoch=3&to_epoch=5&signed_entity_type=CardanoImmutableFilesFull&immutable_file_number=1234",
+            parameters.to_query_string()
+        );
+    }
+}
@@ -1,13 +1,17 @@
 //! Messages module
 //! This module aims at providing shared structures for API communications.
+mod cardano_stake_distribution;
+mod cardano_stake_distribution_list;
 mod cardano_transaction_snapshot;
 mod cardano_transaction_snapshot_list;
 mod cardano_transactions_proof;
+mod cardano_transactions_proofs_request;
 mod certificate;
 mod certificate_list;
 mod certificate_pending;
 mod epoch_settings;
 mod interface;
+mod list_query_parameters;
 mod message_parts;
 mod mithril_stake_distribution;
 mod mithril_stake_distribution_list;
@@ -17,6 +21,10 @@ mod snapshot;
 mod snapshot_download;
 mod snapshot_list;
 
+pub use cardano_stake_distribution::CardanoStakeDistributionMessage;
+pub use cardano_stake_distribution_list::{
+    CardanoStakeDistributionListItemMessage, CardanoStakeDistributionListMessage,
+};
 pub use cardano_transaction_snapshot::CardanoTransactionSnapshotMessage;
 pub use cardano_transaction_snapshot_list::{
     CardanoTransactionSnapshotListItemMessage, CardanoTransactionSnapshotListMessage,
@@ -25,6 +33,9 @@ pub use cardano_transactions_proof::{
     CardanoTransactionsProofsMessage, VerifiedCardanoTransactions,
     VerifyCardanoTransactionsProofsError,
 };
+pub use cardano_transactions_proofs_request::{
+    CardanoTransactionsProofsRequestMessage, MAX_TRANSACTIONS_HASHES_BY_REQUEST,
+};
 pub use certificate::CertificateMessage;
 pub use certificate_list::{
     CertificateListItemMessage, CertificateListItemMessageMetadata, CertificateListMessage,
@@ -32,6 +43,7 @@ pub use certificate_list::{
 pub use certificate_pending::CertificatePendingMessage;
 pub use epoch_settings::EpochSettingsMessage;
 pub use interface::*;
+pub use list_query_parameters::ListQueryParameters;
 pub use message_parts::*;
 pub use mithril_stake_distribution::MithrilStakeDistributionMessage;
 pub use mithril_stake_distribution_list::{
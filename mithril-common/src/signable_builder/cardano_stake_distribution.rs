@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+
+use crate::{
+    crypto_helper::{MKTree, MKTreeNode},
+    entities::{Epoch, ProtocolMessage, ProtocolMessagePartKey, StakeDistribution},
+    signable_builder::SignableBuilder,
+    StdResult,
+};
+
+#[cfg(test)]
+use mockall::automock;
+
+/// Stake Distribution Retriever
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait StakeDistributionRetriever: Send + Sync {
+    /// Retrieve the [StakeDistribution] for a given epoch
+    async fn retrieve(&self, epoch: Epoch) -> StdResult<Option<StakeDistribution>>;
+}
+
+/// A [CardanoStakeDistributionSignableBuilder] builder
+pub struct CardanoStakeDistributionSignableBuilder {
+    cardano_stake_distribution_retriever: Arc<dyn StakeDistributionRetriever>,
+}
+
+impl CardanoStakeDistributionSignableBuilder {
+    /// Constructor
+    pub fn new(cardano_stake_distribution_retriever: Arc<dyn StakeDistributionRetriever>) -> Self {
+        Self {
+            cardano_stake_distribution_retriever,
+        }
+    }
+
+    /// Compute the Merkle tree whose leaves are the pools of a stake distribution with their stake
+    pub fn compute_merkle_tree_from_stake_distribution(
+        stake_distribution: &StakeDistribution,
+    ) -> StdResult<MKTree> {
+        let leaves: Vec<MKTreeNode> = stake_distribution
+            .iter()
+            .map(|(pool_id, stake)| format!("{pool_id}{stake}").into())
+            .collect();
+
+        MKTree::new(&leaves)
+            .with_context(|| "Could not compute Merkle tree from stake distribution")
+    }
+}
+
+#[async_trait]
+impl SignableBuilder<Epoch> for CardanoStakeDistributionSignableBuilder {
+    async fn compute_protocol_message(&self, epoch: Epoch) -> StdResult<ProtocolMessage> {
+        // The stake distribution read from the chain during an epoch is recorded with the
+        // recording offset, this is the one that is certified for this epoch.
+        let stake_distribution = self
+            .cardano_stake_distribution_retriever
+            .retrieve(epoch.offset_to_recording_epoch())
+            .await?
+            .ok_or(anyhow!(
+                "CardanoStakeDistributionSignableBuilder could not find the stake distribution for epoch: '{epoch}'"
+            ))?;
+
+        let mk_root = Self::compute_merkle_tree_from_stake_distribution(&stake_distribution)?
+            .compute_root()?;
+
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message.set_message_part(
+            ProtocolMessagePartKey::CardanoStakeDistributionEpoch,
+            epoch.to_string(),
+        );
+        protocol_message.set_message_part(
+            ProtocolMessagePartKey::CardanoStakeDistributionMerkleRoot,
+            mk_root.to_hex(),
+        );
+
+        Ok(protocol_message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::eq;
+
+    use super::*;
+
+    fn stake_distribution() -> StakeDistribution {
+        StakeDistribution::from([
+            ("pool-123".to_string(), 100),
+            ("pool-456".to_string(), 150),
+            ("pool-789".to_string(), 200),
+        ])
+    }
+
+    #[test]
+    fn compute_merkle_tree_from_stake_distribution_is_deterministic() {
+        let tree =
+            CardanoStakeDistributionSignableBuilder::compute_merkle_tree_from_stake_distribution(
+                &stake_distribution(),
+            )
+            .unwrap();
+        let other_tree =
+            CardanoStakeDistributionSignableBuilder::compute_merkle_tree_from_stake_distribution(
+                &stake_distribution(),
+            )
+            .unwrap();
+
+        assert_eq!(3, tree.total_leaves());
+        assert_eq!(
+            tree.compute_root().unwrap(),
+            other_tree.compute_root().unwrap()
+        );
+    }
+
+    #[test]
+    fn compute_merkle_tree_from_stake_distribution_depends_on_stakes() {
+        let mut other_stake_distribution = stake_distribution();
+        other_stake_distribution.insert("pool-123".to_string(), 101);
+
+        let tree =
+            CardanoStakeDistributionSignableBuilder::compute_merkle_tree_from_stake_distribution(
+                &stake_distribution(),
+            )
+            .unwrap();
+        let other_tree =
+            CardanoStakeDistributionSignableBuilder::compute_merkle_tree_from_stake_distribution(
+                &other_stake_distribution,
+            )
+            .unwrap();
+
+        assert_ne!(
+            tree.compute_root().unwrap(),
+            other_tree.compute_root().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn compute_protocol_message_returns_epoch_and_merkle_root() {
+        let epoch = Epoch(6);
+        let mut retriever = MockStakeDistributionRetriever::new();
+        retriever
+            .expect_retrieve()
+            .with(eq(epoch.offset_to_recording_epoch()))
+            .return_once(|_| Ok(Some(stake_distribution())));
+        let signable_builder = CardanoStakeDistributionSignableBuilder::new(Arc::new(retriever));
+
+        let signable = signable_builder
+            .compute_protocol_message(epoch)
+            .await
+            .unwrap();
+
+        let expected_mk_root =
+            CardanoStakeDistributionSignableBuilder::compute_merkle_tree_from_stake_distribution(
+                &stake_distribution(),
+            )
+            .unwrap()
+            .compute_root()
+            .unwrap();
+        let mut signable_expected = ProtocolMessage::new();
+        signable_expected.set_message_part(
+            ProtocolMessagePartKey::CardanoStakeDistributionEpoch,
+            epoch.to_string(),
+        );
+        signable_expected.set_message_part(
+            ProtocolMessagePartKey::CardanoStakeDistributionMerkleRoot,
+            expected_mk_root.to_hex(),
+        );
+        assert_eq!(signable_expected, signable);
+    }
+
+    #[tokio::test]
+    async fn compute_protocol_message_fails_when_no_stake_distribution_found() {
+        let mut retriever = MockStakeDistributionRetriever::new();
+        retriever.expect_retrieve().return_once(|_| Ok(None));
+        let signable_builder = CardanoStakeDistributionSignableBuilder::new(Arc::new(retriever));
+
+        signable_builder
+            .compute_protocol_message(Epoch(6))
+            .await
+            .expect_err("Should fail when no stake distribution is found");
+    }
+}
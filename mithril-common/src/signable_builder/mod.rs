@@ -1,9 +1,11 @@
 //! The module used for building signables
 
+mod cardano_stake_distribution;
 mod interface;
 mod mithril_stake_distribution;
 mod signable_builder_service;
 
+pub use cardano_stake_distribution::*;
 pub use interface::*;
 pub use mithril_stake_distribution::*;
 pub use signable_builder_service::*;
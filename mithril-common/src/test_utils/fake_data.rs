@@ -9,7 +9,7 @@ use crate::{
     entities::{
         self, CertificateMetadata, CertificateSignature, CompressionAlgorithm, Epoch, LotteryIndex,
         ProtocolMessage, ProtocolMessagePartKey, SignedEntityType, SingleSignatures,
-        StakeDistributionParty,
+        StakeDistribution, StakeDistributionParty,
     },
     test_utils::MithrilFixtureBuilder,
 };
@@ -239,6 +239,24 @@ pub fn mithril_stake_distributions(total: u64) -> Vec<entities::MithrilStakeDist
         .collect::<Vec<entities::MithrilStakeDistribution>>()
 }
 
+/// Fake Cardano Stake Distribution
+pub fn cardano_stake_distributions(total: u64) -> Vec<entities::CardanoStakeDistribution> {
+    (1..total + 1)
+        .map(|epoch_idx| cardano_stake_distribution(Epoch(epoch_idx)))
+        .collect::<Vec<entities::CardanoStakeDistribution>>()
+}
+
+/// Fake Cardano Stake Distribution
+pub fn cardano_stake_distribution(epoch: Epoch) -> entities::CardanoStakeDistribution {
+    let stake_distribution =
+        StakeDistribution::from([("pool-1".to_string(), 100), ("pool-2".to_string(), 100)]);
+    entities::CardanoStakeDistribution {
+        hash: format!("hash-epoch-{epoch}"),
+        epoch,
+        stake_distribution,
+    }
+}
+
 /// Fake Cardano Transactions
 pub fn cardano_transactions_snapshot(total: u64) -> Vec<entities::CardanoTransactionsSnapshot> {
     (1..total + 1)
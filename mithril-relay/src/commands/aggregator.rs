@@ -4,6 +4,7 @@ use libp2p::Multiaddr;
 use mithril_common::StdResult;
 use slog_scope::error;
 
+use super::DiscoveryArgs;
 use crate::AggregatorRelay;
 
 #[derive(Parser, Debug, Clone)]
@@ -16,6 +17,9 @@ pub struct AggregatorCommand {
     #[clap(long, env = "DIAL_TO")]
     dial_to: Option<Multiaddr>,
 
+    #[clap(flatten)]
+    discovery: DiscoveryArgs,
+
     /// Aggregator endpoint URL.
     #[clap(long, env = "AGGREGATOR_ENDPOINT")]
     aggregator_endpoint: String,
@@ -28,10 +32,10 @@ impl AggregatorCommand {
         let addr: Multiaddr = format!("/ip4/0.0.0.0/tcp/{}", self.listen_port).parse()?;
         let aggregator_endpoint = self.aggregator_endpoint.to_owned();
 
-        let mut relay = AggregatorRelay::start(&addr, &aggregator_endpoint).await?;
-        if let Some(dial_to_address) = dial_to {
-            relay.dial_peer(dial_to_address.clone())?;
-        }
+        let peer_configuration = self.discovery.to_peer_configuration(dial_to);
+
+        let mut relay =
+            AggregatorRelay::start(&addr, &peer_configuration, &aggregator_endpoint).await?;
 
         loop {
             if let Err(err) = relay.tick().await {
@@ -0,0 +1,48 @@
+use std::{path::PathBuf, time::Duration};
+
+use libp2p::Multiaddr;
+
+use crate::p2p::PeerConfiguration;
+
+/// Identity and discovery parameters of the relay peer, shared by all the relay commands
+#[derive(clap::Args, Debug, Clone)]
+pub struct DiscoveryArgs {
+    /// File where the node key of the peer is stored, a new key is generated and stored if the
+    /// file does not exist (a new key is generated at each start if not set)
+    #[clap(long, env = "NODE_KEY_FILE")]
+    node_key_file: Option<PathBuf>,
+
+    /// Comma separated list of peer multi-addresses to connect to at startup and to reconnect to
+    /// when disconnected (e.g. /ip4/0.0.0.0/tcp/1234/p2p/12D3KooW...)
+    #[clap(long, env = "BOOTSTRAP_PEERS", value_delimiter = ',')]
+    bootstrap_peers: Vec<Multiaddr>,
+
+    /// Discover the peers of the local network with mDNS
+    #[clap(long, env = "ENABLE_MDNS")]
+    enable_mdns: bool,
+
+    /// Interval at which the peer reconnects to its bootstrap peers and looks for new peers in
+    /// milliseconds (defaults to 30 seconds)
+    #[clap(long, env = "DISCOVERY_INTERVAL", default_value_t = 30 * 1_000)]
+    discovery_interval: u64,
+}
+
+impl DiscoveryArgs {
+    /// Build the configuration of the relay peer, the `dial_to` peer is handled as a bootstrap
+    /// peer so the relay reconnects to it when disconnected
+    pub fn to_peer_configuration(&self, dial_to: Option<Multiaddr>) -> PeerConfiguration {
+        let mut bootstrap_peers = self.bootstrap_peers.clone();
+        if let Some(dial_to) = dial_to {
+            if !bootstrap_peers.contains(&dial_to) {
+                bootstrap_peers.push(dial_to);
+            }
+        }
+
+        PeerConfiguration {
+            node_key_file: self.node_key_file.clone(),
+            bootstrap_peers,
+            enable_mdns: self.enable_mdns,
+            discovery_interval: Duration::from_millis(self.discovery_interval),
+        }
+    }
+}
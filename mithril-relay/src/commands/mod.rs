@@ -1,9 +1,11 @@
 mod aggregator;
+mod discovery;
 mod passive;
 mod relay;
 mod signer;
 
 pub use aggregator::AggregatorCommand;
+pub use discovery::DiscoveryArgs;
 pub use passive::PassiveCommand;
 pub use relay::RelayCommands;
 pub use signer::SignerCommand;
@@ -4,6 +4,7 @@ use libp2p::Multiaddr;
 use mithril_common::StdResult;
 use slog_scope::error;
 
+use super::DiscoveryArgs;
 use crate::PassiveRelay;
 
 #[derive(Parser, Debug, Clone)]
@@ -15,6 +16,9 @@ pub struct PassiveCommand {
     /// Dial to peer multi-address (e.g. /ip4/0.0.0.0/tcp/1234)
     #[clap(long, env = "DIAL_TO")]
     dial_to: Option<Multiaddr>,
+
+    #[clap(flatten)]
+    discovery: DiscoveryArgs,
 }
 
 impl PassiveCommand {
@@ -23,10 +27,9 @@ impl PassiveCommand {
         let dial_to = self.dial_to.to_owned();
         let addr: Multiaddr = format!("/ip4/0.0.0.0/tcp/{}", self.listen_port).parse()?;
 
-        let mut relay = PassiveRelay::start(&addr).await?;
-        if let Some(dial_to_address) = dial_to {
-            relay.dial_peer(dial_to_address.clone())?;
-        }
+        let peer_configuration = self.discovery.to_peer_configuration(dial_to);
+
+        let mut relay = PassiveRelay::start(&addr, &peer_configuration).await?;
         loop {
             if let Err(err) = relay.tick().await {
                 error!("P2PClient: tick error"; "error" => format!("{err:#?}"));
@@ -6,6 +6,7 @@ use libp2p::Multiaddr;
 use mithril_common::StdResult;
 use slog_scope::error;
 
+use super::DiscoveryArgs;
 use crate::SignerRelay;
 
 #[derive(Parser, Debug, Clone)]
@@ -22,6 +23,9 @@ pub struct SignerCommand {
     #[clap(long, env = "DIAL_TO")]
     dial_to: Option<Multiaddr>,
 
+    #[clap(flatten)]
+    discovery: DiscoveryArgs,
+
     /// Aggregator endpoint URL.
     #[clap(long, env = "AGGREGATOR_ENDPOINT")]
     aggregator_endpoint: String,
@@ -39,17 +43,16 @@ impl SignerCommand {
         let addr: Multiaddr = format!("/ip4/0.0.0.0/tcp/{}", self.listen_port).parse()?;
         let aggregator_endpoint = self.aggregator_endpoint.to_owned();
         let signer_repeater_delay = Duration::from_millis(self.signer_repeater_delay);
+        let peer_configuration = self.discovery.to_peer_configuration(dial_to);
 
         let mut relay = SignerRelay::start(
             &addr,
+            &peer_configuration,
             &server_port,
             &aggregator_endpoint,
             &signer_repeater_delay,
         )
         .await?;
-        if let Some(dial_to_address) = dial_to {
-            relay.dial_peer(dial_to_address.clone())?;
-        }
 
         loop {
             if let Err(err) = relay.tick().await {
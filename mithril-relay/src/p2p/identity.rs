@@ -0,0 +1,118 @@
+use anyhow::Context;
+use libp2p::identity::Keypair;
+use mithril_common::StdResult;
+use slog_scope::info;
+use std::{fs, io::Write, path::Path};
+
+/// Load the keypair of a [Peer][crate::p2p::Peer] from a node key file, or generate a new one
+/// and store it in this file if it does not exist yet.
+///
+/// Keeping the keypair between restarts allows a relay to keep the same peer id, so the other
+/// peers can use it in their bootstrap peers.
+pub fn load_or_generate_keypair(node_key_file: &Path) -> StdResult<Keypair> {
+    if node_key_file.exists() {
+        let bytes = fs::read(node_key_file).with_context(|| {
+            format!("Could not read node key file '{}'", node_key_file.display())
+        })?;
+        let keypair = Keypair::from_protobuf_encoding(&bytes).with_context(|| {
+            format!(
+                "Could not decode node key file '{}'",
+                node_key_file.display()
+            )
+        })?;
+
+        return Ok(keypair);
+    }
+
+    let keypair = Keypair::generate_ed25519();
+    if let Some(parent_dir) = node_key_file.parent() {
+        fs::create_dir_all(parent_dir).with_context(|| {
+            format!(
+                "Could not create node key file directory '{}'",
+                parent_dir.display()
+            )
+        })?;
+    }
+    let key_bytes = keypair.to_protobuf_encoding()?;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        // The key is only readable by its owner from the creation of the file
+        options.mode(0o600);
+    }
+    let mut file = options.open(node_key_file).with_context(|| {
+        format!(
+            "Could not create node key file '{}'",
+            node_key_file.display()
+        )
+    })?;
+    file.write_all(&key_bytes).with_context(|| {
+        format!(
+            "Could not write node key file '{}'",
+            node_key_file.display()
+        )
+    })?;
+    info!("Peer: generated a new node key"; "node_key_file" => node_key_file.display().to_string(), "peer_id" => keypair.public().to_peer_id().to_string());
+
+    Ok(keypair)
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn generate_and_store_a_keypair_if_the_node_key_file_does_not_exist() {
+        let node_key_file =
+            TempDir::create("relay_identity", "generate_and_store_keypair").join("node.key");
+
+        let keypair = load_or_generate_keypair(&node_key_file).unwrap();
+
+        assert!(node_key_file.exists());
+        assert_eq!(
+            keypair.to_protobuf_encoding().unwrap(),
+            fs::read(&node_key_file).unwrap()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn generated_node_key_file_is_only_readable_by_its_owner() {
+        use std::os::unix::fs::PermissionsExt;
+        let node_key_file =
+            TempDir::create("relay_identity", "node_key_file_permissions").join("node.key");
+
+        load_or_generate_keypair(&node_key_file).unwrap();
+
+        let mode = fs::metadata(&node_key_file).unwrap().permissions().mode();
+        assert_eq!(0o600, mode & 0o777);
+    }
+
+    #[test]
+    fn load_the_same_keypair_from_an_existing_node_key_file() {
+        let node_key_file =
+            TempDir::create("relay_identity", "load_existing_keypair").join("node.key");
+        let generated_keypair = load_or_generate_keypair(&node_key_file).unwrap();
+
+        let loaded_keypair = load_or_generate_keypair(&node_key_file).unwrap();
+
+        assert_eq!(
+            generated_keypair.public().to_peer_id(),
+            loaded_keypair.public().to_peer_id()
+        );
+    }
+
+    #[test]
+    fn fail_to_load_an_invalid_node_key_file() {
+        let node_key_file =
+            TempDir::create("relay_identity", "load_invalid_keypair").join("node.key");
+        fs::write(&node_key_file, b"invalid key").unwrap();
+
+        load_or_generate_keypair(&node_key_file)
+            .expect_err("Loading an invalid node key file should fail");
+    }
+}
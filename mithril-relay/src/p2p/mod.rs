@@ -1,5 +1,11 @@
 mod error;
+mod identity;
 mod peer;
+mod seen_cache;
+mod validator;
 
 pub use error::*;
+pub use identity::*;
 pub use peer::*;
+pub use seen_cache::*;
+pub use validator::*;
@@ -1,31 +1,71 @@
 #![allow(missing_docs)]
 use anyhow::{anyhow, Context};
 use libp2p::{
-    core::{muxing::StreamMuxerBox, transport::dummy::DummyTransport},
+    core::{muxing::StreamMuxerBox, transport::dummy::DummyTransport, ConnectedPoint},
     futures::StreamExt,
-    gossipsub::{self, ValidationMode},
+    gossipsub::{self, PeerScoreParams, PeerScoreThresholds, TopicScoreParams, ValidationMode},
+    identify,
+    identity::Keypair,
+    kad, mdns,
+    multiaddr::Protocol,
     noise, ping,
-    swarm::{self, DialError, NetworkBehaviour},
-    tls, yamux, Multiaddr, PeerId, Swarm, SwarmBuilder,
+    swarm::{self, behaviour::toggle::Toggle, DialError, NetworkBehaviour},
+    tls, yamux, Multiaddr, PeerId, StreamProtocol, Swarm, SwarmBuilder,
 };
 use mithril_common::{
     messages::{RegisterSignatureMessage, RegisterSignerMessage},
     StdResult,
 };
 use serde::{Deserialize, Serialize};
-use slog_scope::{debug, info};
-use std::{collections::HashMap, time::Duration};
+use sha2::{Digest, Sha256};
+use slog_scope::{debug, info, warn};
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
+use tokio::{
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        OwnedSemaphorePermit, Semaphore,
+    },
+    time::{interval_at, Instant, Interval, MissedTickBehavior},
+};
 
-use crate::{mithril_p2p_topic, p2p::PeerError};
+use crate::{
+    mithril_p2p_topic,
+    p2p::{
+        load_or_generate_keypair, BroadcastMessageValidator, MessageValidation, PeerError,
+        SeenMessagesCache, WellFormedMessageValidator,
+    },
+};
 
 /// The idle connection timeout for a P2P connection
 const P2P_IDLE_CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// The interval at which a peer reconnects to its bootstrap peers and looks for new peers
+const P2P_DISCOVERY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The protocol name of the Kademlia DHT used to discover the peers of a Mithril network
+const KADEMLIA_PROTOCOL_NAME: StreamProtocol = StreamProtocol::new("/mithril/kad/1.0.0");
+
+/// The protocol version advertised with the identify protocol
+const IDENTIFY_PROTOCOL_VERSION: &str = "/mithril/relay/1.0.0";
+
+/// The maximum number of received messages kept to detect duplicates
+const SEEN_MESSAGES_CACHE_CAPACITY: usize = 10_000;
+
+/// The duration during which a received message is considered as a duplicate if received again
+const SEEN_MESSAGES_CACHE_TIME_TO_LIVE: Duration = Duration::from_secs(600);
+
+/// The maximum number of received messages being validated or waiting for their validation
+/// result to be reported, the messages received beyond are ignored
+const MAX_PENDING_MESSAGE_VALIDATIONS: usize = 256;
+
 /// [Peer] custom network behaviour
 #[derive(NetworkBehaviour)]
 pub struct PeerBehaviour {
     gossipsub: gossipsub::Behaviour,
     ping: ping::Behaviour,
+    identify: identify::Behaviour,
+    kademlia: kad::Behaviour<kad::store::MemoryStore>,
+    mdns: Toggle<mdns::tokio::Behaviour>,
 }
 
 /// [Peer] event that is polled from the swarm
@@ -68,11 +108,58 @@ pub enum BroadcastMessage {
     RegisterSignature(RegisterSignatureMessage),
 }
 
+/// A gossipsub message with the outcome of its validation
+struct ValidatedMessage {
+    propagation_source: PeerId,
+    message_id: gossipsub::MessageId,
+    content_id: gossipsub::MessageId,
+    message: gossipsub::Message,
+    validation: MessageValidation,
+    /// Released once the validation result is reported
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+/// Configuration of the identity and of the discovery of a [Peer]
+#[derive(Debug, Clone)]
+pub struct PeerConfiguration {
+    /// File where the keypair of the peer is stored, so its peer id is kept between restarts
+    /// (a new keypair is generated at each start if not set)
+    pub node_key_file: Option<PathBuf>,
+
+    /// Addresses of the peers to connect to at startup and to reconnect to when disconnected
+    pub bootstrap_peers: Vec<Multiaddr>,
+
+    /// Discover the peers of the local network with mDNS
+    pub enable_mdns: bool,
+
+    /// Interval at which the peer reconnects to its bootstrap peers and looks for new peers
+    pub discovery_interval: Duration,
+}
+
+impl Default for PeerConfiguration {
+    fn default() -> Self {
+        Self {
+            node_key_file: None,
+            bootstrap_peers: vec![],
+            enable_mdns: false,
+            discovery_interval: P2P_DISCOVERY_INTERVAL,
+        }
+    }
+}
+
 /// A peer in the P2P network
 pub struct Peer {
     topics: HashMap<TopicName, gossipsub::IdentTopic>,
     swarm: Option<Swarm<PeerBehaviour>>,
     addr: Multiaddr,
+    configuration: PeerConfiguration,
+    discovery_interval: Option<Interval>,
+    bootstrap_peer_ids: HashMap<Multiaddr, PeerId>,
+    message_validator: Arc<dyn BroadcastMessageValidator>,
+    seen_messages: SeenMessagesCache,
+    pending_validations: Arc<Semaphore>,
+    validated_messages_tx: Sender<ValidatedMessage>,
+    validated_messages_rx: Receiver<ValidatedMessage>,
     /// Multi address on which the peer is listening
     pub addr_peer: Option<Multiaddr>,
 }
@@ -80,14 +167,43 @@ pub struct Peer {
 impl Peer {
     /// Peer factory
     pub fn new(addr: &Multiaddr) -> Self {
+        let (validated_messages_tx, validated_messages_rx) =
+            channel(MAX_PENDING_MESSAGE_VALIDATIONS);
         Self {
             topics: Self::build_topics(),
             swarm: None,
             addr: addr.to_owned(),
+            configuration: PeerConfiguration::default(),
+            discovery_interval: None,
+            bootstrap_peer_ids: HashMap::new(),
+            message_validator: Arc::new(WellFormedMessageValidator::new()),
+            seen_messages: SeenMessagesCache::new(
+                SEEN_MESSAGES_CACHE_CAPACITY,
+                SEEN_MESSAGES_CACHE_TIME_TO_LIVE,
+            ),
+            pending_validations: Arc::new(Semaphore::new(MAX_PENDING_MESSAGE_VALIDATIONS)),
+            validated_messages_tx,
+            validated_messages_rx,
             addr_peer: None,
         }
     }
 
+    /// Set the identity and discovery configuration of the peer
+    pub fn with_configuration(mut self, configuration: PeerConfiguration) -> Self {
+        self.configuration = configuration;
+        self
+    }
+
+    /// Set the validator of the messages received from the P2P network (defaults to a
+    /// [WellFormedMessageValidator]), the messages are validated outside of the swarm event loop
+    pub fn with_message_validator(
+        mut self,
+        message_validator: Arc<dyn BroadcastMessageValidator>,
+    ) -> Self {
+        self.message_validator = message_validator;
+        self
+    }
+
     fn build_topics() -> HashMap<TopicName, gossipsub::IdentTopic> {
         HashMap::from([
             (
@@ -101,10 +217,39 @@ impl Peer {
         ])
     }
 
+    fn build_peer_score_params(&self) -> PeerScoreParams {
+        let topic_score_params = TopicScoreParams {
+            // The Mithril topics have a low and irregular traffic: the peers are not penalized
+            // for not delivering enough messages in the mesh.
+            mesh_message_deliveries_weight: 0.0,
+            mesh_failure_penalty_weight: 0.0,
+            invalid_message_deliveries_weight: -10.0,
+            invalid_message_deliveries_decay: 0.99,
+            ..TopicScoreParams::default()
+        };
+
+        PeerScoreParams {
+            topics: self
+                .topics
+                .values()
+                .map(|topic| (topic.hash(), topic_score_params.clone()))
+                .collect(),
+            // The relays of a Mithril network are often hosted on the same machine or network.
+            ip_colocation_factor_weight: 0.0,
+            ..PeerScoreParams::default()
+        }
+    }
+
     /// Start the peer
     pub async fn start(mut self) -> StdResult<Self> {
         debug!("Peer: starting...");
-        let mut swarm = SwarmBuilder::with_new_identity()
+        let peer_score_params = self.build_peer_score_params();
+        let keypair = match &self.configuration.node_key_file {
+            Some(node_key_file) => load_or_generate_keypair(node_key_file)?,
+            None => Keypair::generate_ed25519(),
+        };
+        let enable_mdns = self.configuration.enable_mdns;
+        let mut swarm = SwarmBuilder::with_existing_identity(keypair)
             .with_tokio()
             .with_tcp(
                 Default::default(),
@@ -127,14 +272,42 @@ impl Peer {
                     .history_length(10)
                     .history_gossip(10)
                     .validation_mode(ValidationMode::Strict)
+                    .validate_messages()
                     .build()?;
+                let mut gossipsub = gossipsub::Behaviour::new(
+                    gossipsub::MessageAuthenticity::Signed(key.clone()),
+                    gossipsub_config,
+                )
+                .expect("Valid configuration");
+                gossipsub.with_peer_score(peer_score_params, PeerScoreThresholds::default())?;
+                let local_peer_id = key.public().to_peer_id();
+                let mut kademlia_config = kad::Config::default();
+                kademlia_config.set_protocol_names(vec![KADEMLIA_PROTOCOL_NAME]);
+                let mut kademlia = kad::Behaviour::with_config(
+                    local_peer_id,
+                    kad::store::MemoryStore::new(local_peer_id),
+                    kademlia_config,
+                );
+                // The relays have no confirmed external address when they are run in a private
+                // network, they must be forced to answer the Kademlia requests.
+                kademlia.set_mode(Some(kad::Mode::Server));
+                let mdns = if enable_mdns {
+                    Some(mdns::tokio::Behaviour::new(
+                        mdns::Config::default(),
+                        local_peer_id,
+                    )?)
+                } else {
+                    None
+                };
                 Ok(PeerBehaviour {
-                    gossipsub: gossipsub::Behaviour::new(
-                        gossipsub::MessageAuthenticity::Signed(key.clone()),
-                        gossipsub_config,
-                    )
-                    .expect("Valid configuration"),
+                    gossipsub,
                     ping: ping::Behaviour::new(ping::Config::new()),
+                    identify: identify::Behaviour::new(identify::Config::new(
+                        IDENTIFY_PROTOCOL_VERSION.to_string(),
+                        key.public(),
+                    )),
+                    kademlia,
+                    mdns: mdns.into(),
                 })
             })?
             .with_swarm_config(|c| c.with_idle_connection_timeout(P2P_IDLE_CONNECTION_TIMEOUT))
@@ -147,6 +320,11 @@ impl Peer {
 
         let _listener_id = swarm.listen_on(self.addr.clone())?;
         self.swarm = Some(swarm);
+        let discovery_interval = self.configuration.discovery_interval;
+        let mut discovery_interval =
+            interval_at(Instant::now() + discovery_interval, discovery_interval);
+        discovery_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        self.discovery_interval = Some(discovery_interval);
 
         loop {
             if let Some(PeerEvent::ListeningOnAddr { address }) = self.tick_swarm().await? {
@@ -156,6 +334,12 @@ impl Peer {
             }
         }
 
+        for address in self.configuration.bootstrap_peers.clone() {
+            if let Err(error) = self.dial(address.clone()) {
+                warn!("Peer: can not dial bootstrap peer"; "address" => format!("{address:?}"), "error" => format!("{error:#}"));
+            }
+        }
+
         Ok(self)
     }
 
@@ -175,14 +359,25 @@ impl Peer {
     /// Tick the peer swarm to receive the next event
     pub async fn tick_swarm(&mut self) -> StdResult<Option<PeerEvent>> {
         debug!("Peer: reading next event"; "local_peer_id" => format!("{:?}", self.local_peer_id()));
-        match self
-            .swarm
-            .as_mut()
-            .ok_or(PeerError::UnavailableSwarm())
-            .with_context(|| "Can not publish signature without swarm")?
-            .next()
-            .await
-        {
+        let (Some(swarm), Some(discovery_interval)) =
+            (self.swarm.as_mut(), self.discovery_interval.as_mut())
+        else {
+            return Err(PeerError::UnavailableSwarm())
+                .with_context(|| "Can not read next event without swarm");
+        };
+        let validated_messages_rx = &mut self.validated_messages_rx;
+        let event = tokio::select! {
+            event = swarm.next() => event,
+            Some(validated_message) = validated_messages_rx.recv() => {
+                return self.handle_validated_message(validated_message);
+            }
+            _ = discovery_interval.tick() => {
+                self.discover_peers()?;
+                return Ok(None);
+            }
+        };
+
+        match event {
             Some(swarm::SwarmEvent::NewListenAddr { address, .. }) => {
                 debug!("Peer: received listening address event"; "address" => format!("{address:?}"), "local_peer_id" => format!("{:?}", self.local_peer_id()));
                 Ok(Some(PeerEvent::ListeningOnAddr { address }))
@@ -191,11 +386,26 @@ impl Peer {
                 debug!("Peer: received outgoing connection error event"; "error" => format!("{error:#?}"), "remote_peer_id" => format!("{peer_id:?}"), "local_peer_id" => format!("{:?}", self.local_peer_id()));
                 Ok(Some(PeerEvent::OutgoingConnectionError { peer_id, error }))
             }
-            Some(swarm::SwarmEvent::ConnectionEstablished { peer_id, .. }) => {
+            Some(swarm::SwarmEvent::ConnectionEstablished {
+                peer_id, endpoint, ..
+            }) => {
+                if let ConnectedPoint::Dialer { address, .. } = endpoint {
+                    if self.configuration.bootstrap_peers.contains(&address) {
+                        self.bootstrap_peer_ids.insert(address, peer_id);
+                    }
+                }
                 debug!("Peer: received connection established event"; "remote_peer_id" => format!("{peer_id:?}"), "local_peer_id" => format!("{:?}", self.local_peer_id()));
                 Ok(Some(PeerEvent::ConnectionEstablished { peer_id }))
             }
+            Some(swarm::SwarmEvent::Behaviour(PeerBehaviourEvent::Gossipsub(
+                gossipsub::Event::Message {
+                    propagation_source,
+                    message_id,
+                    message,
+                },
+            ))) => self.validate_gossipsub_message(propagation_source, message_id, message),
             Some(swarm::SwarmEvent::Behaviour(event)) => {
+                self.handle_discovery_event(&event)?;
                 debug!("Peer: received behaviour event"; "event" => format!("{event:#?}"), "local_peer_id" => format!("{:?}", self.local_peer_id()));
                 Ok(Some(PeerEvent::Behaviour { event }))
             }
@@ -207,6 +417,213 @@ impl Peer {
         }
     }
 
+    fn swarm_mut(&mut self) -> StdResult<&mut Swarm<PeerBehaviour>> {
+        self.swarm
+            .as_mut()
+            .ok_or(PeerError::UnavailableSwarm())
+            .with_context(|| "Can not discover peers without swarm")
+    }
+
+    /// Add the peers found by the identify and mDNS behaviours to the Kademlia routing table and
+    /// look for the peers close to the local peer once the routing table is not empty anymore.
+    fn handle_discovery_event(&mut self, event: &PeerBehaviourEvent) -> StdResult<()> {
+        match event {
+            PeerBehaviourEvent::Identify(identify::Event::Received { peer_id, info }) => {
+                let kademlia = &mut self.swarm_mut()?.behaviour_mut().kademlia;
+                for address in &info.listen_addrs {
+                    kademlia.add_address(peer_id, address.to_owned());
+                }
+            }
+            PeerBehaviourEvent::Mdns(mdns::Event::Discovered(peers)) => {
+                let swarm = self.swarm_mut()?;
+                for (peer_id, address) in peers {
+                    debug!("Peer: discovered peer with mDNS"; "remote_peer_id" => format!("{peer_id:?}"), "address" => format!("{address:?}"));
+                    swarm
+                        .behaviour_mut()
+                        .kademlia
+                        .add_address(peer_id, address.to_owned());
+                    if !swarm.is_connected(peer_id) {
+                        if let Err(error) = swarm.dial(address.to_owned()) {
+                            debug!("Peer: can not dial peer discovered with mDNS"; "address" => format!("{address:?}"), "error" => format!("{error:?}"));
+                        }
+                    }
+                }
+            }
+            PeerBehaviourEvent::Kademlia(kad::Event::RoutingUpdated {
+                is_new_peer: true, ..
+            }) => {
+                let kademlia = &mut self.swarm_mut()?.behaviour_mut().kademlia;
+                if kademlia
+                    .kbuckets()
+                    .map(|bucket| bucket.num_entries())
+                    .sum::<usize>()
+                    == 1
+                {
+                    // The bootstrap can't be done before the first peer is known.
+                    let _ = kademlia.bootstrap();
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Reconnect to the bootstrap peers that are disconnected and look for new peers
+    fn discover_peers(&mut self) -> StdResult<()> {
+        let bootstrap_peers = self.configuration.bootstrap_peers.clone();
+        let bootstrap_peer_ids = self.bootstrap_peer_ids.clone();
+        let swarm = self.swarm_mut()?;
+        for address in bootstrap_peers {
+            let peer_id = address
+                .iter()
+                .find_map(|protocol| match protocol {
+                    Protocol::P2p(peer_id) => Some(peer_id),
+                    _ => None,
+                })
+                .or_else(|| bootstrap_peer_ids.get(&address).copied());
+            if peer_id.is_some_and(|peer_id| swarm.is_connected(&peer_id)) {
+                continue;
+            }
+            debug!("Peer: reconnecting to bootstrap peer"; "address" => format!("{address:?}"));
+            if let Err(error) = swarm.dial(address.clone()) {
+                debug!("Peer: can not dial bootstrap peer"; "address" => format!("{address:?}"), "error" => format!("{error:?}"));
+            }
+        }
+        if let Err(error) = swarm.behaviour_mut().kademlia.bootstrap() {
+            debug!("Peer: can not look for new peers"; "error" => format!("{error:?}"));
+        }
+
+        Ok(())
+    }
+
+    /// Validate a received gossipsub message in a separate task, its validation result is
+    /// handled by [Self::handle_validated_message] once available.
+    ///
+    /// At most [MAX_PENDING_MESSAGE_VALIDATIONS] messages are pending, the messages received
+    /// beyond are ignored so that a flooding peer can not exhaust the resources of the relay.
+    fn validate_gossipsub_message(
+        &mut self,
+        propagation_source: PeerId,
+        message_id: gossipsub::MessageId,
+        message: gossipsub::Message,
+    ) -> StdResult<Option<PeerEvent>> {
+        // Duplicates are detected on the content of the messages as the same message can be
+        // published several times, by different peers, with different message ids.
+        let content_id = gossipsub::MessageId::new(
+            &Sha256::new()
+                .chain_update(message.topic.as_str())
+                .chain_update(&message.data)
+                .finalize(),
+        );
+        if self.seen_messages.contains(&content_id) {
+            return self.handle_validated_message(ValidatedMessage {
+                propagation_source,
+                message_id,
+                content_id,
+                message,
+                validation: MessageValidation::Ignore("duplicate message".to_string()),
+                _permit: None,
+            });
+        }
+
+        let permit = match self.pending_validations.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                return self.handle_validated_message(ValidatedMessage {
+                    propagation_source,
+                    message_id,
+                    content_id,
+                    message,
+                    validation: MessageValidation::Ignore(
+                        "too many messages pending validation".to_string(),
+                    ),
+                    _permit: None,
+                });
+            }
+        };
+        let message_validator = self.message_validator.clone();
+        let validated_messages_tx = self.validated_messages_tx.clone();
+        tokio::spawn(async move {
+            let validation = match serde_json::from_slice::<BroadcastMessage>(&message.data) {
+                Ok(broadcast_message) => message_validator.validate(&broadcast_message).await,
+                Err(error) => MessageValidation::Reject(format!("invalid message format: {error}")),
+            };
+            // The channel has room for all the pending validations, and its receiver is only
+            // dropped with the peer
+            let _ = validated_messages_tx
+                .send(ValidatedMessage {
+                    propagation_source,
+                    message_id,
+                    content_id,
+                    message,
+                    validation,
+                    _permit: Some(permit),
+                })
+                .await;
+        });
+
+        Ok(None)
+    }
+
+    /// Report the validation result of a message to the gossipsub behaviour, and return the
+    /// message if it is accepted
+    fn handle_validated_message(
+        &mut self,
+        validated_message: ValidatedMessage,
+    ) -> StdResult<Option<PeerEvent>> {
+        let ValidatedMessage {
+            propagation_source,
+            message_id,
+            content_id,
+            message,
+            validation,
+            _permit,
+        } = validated_message;
+        // Only the accepted messages are recorded as seen: an ignored message may be accepted
+        // when received again (ie. once the aggregator has opened its signed entity).
+        let validation = match validation {
+            MessageValidation::Accept if !self.seen_messages.insert(content_id) => {
+                // The same message was accepted while this one was validated
+                MessageValidation::Ignore("duplicate message".to_string())
+            }
+            validation => validation,
+        };
+        self.swarm
+            .as_mut()
+            .ok_or(PeerError::UnavailableSwarm())
+            .with_context(|| "Can not report message validation without swarm")?
+            .behaviour_mut()
+            .gossipsub
+            .report_message_validation_result(
+                &message_id,
+                &propagation_source,
+                (&validation).into(),
+            )
+            .with_context(|| "Can not report message validation result")?;
+
+        match validation {
+            MessageValidation::Accept => {
+                debug!("Peer: received valid message"; "message_id" => format!("{message_id:?}"), "remote_peer_id" => format!("{propagation_source:?}"), "local_peer_id" => format!("{:?}", self.local_peer_id()));
+                Ok(Some(PeerEvent::Behaviour {
+                    event: PeerBehaviourEvent::Gossipsub(gossipsub::Event::Message {
+                        propagation_source,
+                        message_id,
+                        message,
+                    }),
+                }))
+            }
+            MessageValidation::Reject(reason) => {
+                warn!("Peer: rejected invalid message"; "reason" => reason, "message_id" => format!("{message_id:?}"), "remote_peer_id" => format!("{propagation_source:?}"), "local_peer_id" => format!("{:?}", self.local_peer_id()));
+                Ok(None)
+            }
+            MessageValidation::Ignore(reason) => {
+                debug!("Peer: ignored message"; "reason" => reason, "message_id" => format!("{message_id:?}"), "remote_peer_id" => format!("{propagation_source:?}"), "local_peer_id" => format!("{:?}", self.local_peer_id()));
+                Ok(None)
+            }
+        }
+    }
+
     /// Publish a signature on the P2P pubsub
     pub fn publish_signature(
         &mut self,
@@ -276,6 +693,14 @@ impl Peer {
             .map_err(|e| anyhow!(e))
     }
 
+    /// Get the ids of the peers connected to the local peer
+    pub fn connected_peers(&self) -> Vec<PeerId> {
+        self.swarm
+            .as_ref()
+            .map(|swarm| swarm.connected_peers().copied().collect())
+            .unwrap_or_default()
+    }
+
     /// Get the local peer id (if any)
     pub fn local_peer_id(&self) -> Option<PeerId> {
         self.swarm.as_ref().map(|s| s.local_peer_id().to_owned())
@@ -0,0 +1,133 @@
+use libp2p::gossipsub::MessageId;
+use std::{
+    collections::{HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// A bounded cache of the ids of the messages already received by a [Peer][crate::p2p::Peer].
+///
+/// The oldest ids are evicted when the cache is full or when they are older than the cache
+/// time to live, so a message that is published again long after its first reception (ie. a
+/// repeated signer registration) is not dropped.
+pub struct SeenMessagesCache {
+    capacity: usize,
+    time_to_live: Duration,
+    ids: HashSet<MessageId>,
+    insertions: VecDeque<(Instant, MessageId)>,
+}
+
+impl SeenMessagesCache {
+    /// [SeenMessagesCache] factory
+    pub fn new(capacity: usize, time_to_live: Duration) -> Self {
+        Self {
+            capacity,
+            time_to_live,
+            ids: HashSet::with_capacity(capacity),
+            insertions: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Record a message id, return `false` if it was already seen.
+    pub fn insert(&mut self, message_id: MessageId) -> bool {
+        self.insert_at(message_id, Instant::now())
+    }
+
+    /// Check if a message id was already seen, without recording it.
+    pub fn contains(&mut self, message_id: &MessageId) -> bool {
+        self.evict_expired(Instant::now());
+        self.ids.contains(message_id)
+    }
+
+    fn insert_at(&mut self, message_id: MessageId, now: Instant) -> bool {
+        self.evict_expired(now);
+        if self.ids.contains(&message_id) {
+            return false;
+        }
+
+        if self.insertions.len() >= self.capacity {
+            if let Some((_, oldest_id)) = self.insertions.pop_front() {
+                self.ids.remove(&oldest_id);
+            }
+        }
+        self.ids.insert(message_id.clone());
+        self.insertions.push_back((now, message_id));
+
+        true
+    }
+
+    fn evict_expired(&mut self, now: Instant) {
+        while let Some((inserted_at, _)) = self.insertions.front() {
+            if now.duration_since(*inserted_at) < self.time_to_live {
+                break;
+            }
+            if let Some((_, expired_id)) = self.insertions.pop_front() {
+                self.ids.remove(&expired_id);
+            }
+        }
+    }
+
+    /// Number of message ids in the cache
+    pub fn len(&self) -> usize {
+        self.insertions.len()
+    }
+
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.insertions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message_id(id: &str) -> MessageId {
+        MessageId::from(id)
+    }
+
+    #[test]
+    fn insert_return_false_for_an_already_seen_message() {
+        let mut cache = SeenMessagesCache::new(10, Duration::from_secs(60));
+
+        assert!(cache.insert(message_id("a")));
+        assert!(cache.insert(message_id("b")));
+        assert!(!cache.insert(message_id("a")));
+        assert_eq!(2, cache.len());
+    }
+
+    #[test]
+    fn contains_does_not_record_the_message() {
+        let mut cache = SeenMessagesCache::new(10, Duration::from_secs(60));
+
+        assert!(!cache.contains(&message_id("a")));
+        assert!(cache.is_empty());
+        cache.insert(message_id("a"));
+        assert!(cache.contains(&message_id("a")));
+    }
+
+    #[test]
+    fn oldest_messages_are_evicted_when_the_cache_is_full() {
+        let mut cache = SeenMessagesCache::new(2, Duration::from_secs(60));
+
+        cache.insert(message_id("a"));
+        cache.insert(message_id("b"));
+        cache.insert(message_id("c"));
+
+        assert_eq!(2, cache.len());
+        assert!(!cache.insert(message_id("c")));
+        assert!(cache.insert(message_id("a")));
+    }
+
+    #[test]
+    fn messages_are_evicted_after_the_time_to_live() {
+        let mut cache = SeenMessagesCache::new(10, Duration::from_secs(60));
+        let now = Instant::now();
+
+        cache.insert_at(message_id("a"), now);
+        cache.insert_at(message_id("b"), now + Duration::from_secs(30));
+
+        assert!(!cache.insert_at(message_id("a"), now + Duration::from_secs(59)));
+        assert!(cache.insert_at(message_id("a"), now + Duration::from_secs(61)));
+        assert!(!cache.insert_at(message_id("b"), now + Duration::from_secs(61)));
+    }
+}
@@ -0,0 +1,269 @@
+use async_trait::async_trait;
+use libp2p::gossipsub::MessageAcceptance;
+use mithril_common::{
+    crypto_helper::{
+        ProtocolKeyRegistration, ProtocolOpCert, ProtocolSignerVerificationKey,
+        ProtocolSignerVerificationKeySignature, ProtocolSingleSignature,
+    },
+    messages::{RegisterSignatureMessage, RegisterSignerMessage},
+};
+
+use crate::p2p::BroadcastMessage;
+
+/// Outcome of the validation of a [BroadcastMessage] received from the P2P network
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageValidation {
+    /// The message is valid: it is consumed and propagated to the other peers
+    Accept,
+
+    /// The message is invalid: it is dropped and the peer that propagated it is penalized
+    Reject(String),
+
+    /// The message can not be validated (ie. duplicate or stale message): it is dropped without
+    /// penalizing the peer that propagated it
+    Ignore(String),
+}
+
+impl MessageValidation {
+    /// Check if the message is accepted
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accept)
+    }
+}
+
+impl From<&MessageValidation> for MessageAcceptance {
+    fn from(validation: &MessageValidation) -> Self {
+        match validation {
+            MessageValidation::Accept => MessageAcceptance::Accept,
+            MessageValidation::Reject(_) => MessageAcceptance::Reject,
+            MessageValidation::Ignore(_) => MessageAcceptance::Ignore,
+        }
+    }
+}
+
+/// A validator of the [BroadcastMessage] received from the P2P network, called before they are
+/// consumed by the relay or propagated to the other peers
+#[async_trait]
+pub trait BroadcastMessageValidator: Send + Sync {
+    /// Validate a broadcast message
+    async fn validate(&self, message: &BroadcastMessage) -> MessageValidation;
+}
+
+/// A [BroadcastMessageValidator] that only checks that the keys and signatures of the messages
+/// are well-formed, without any knowledge of the state of the Mithril network
+#[derive(Default)]
+pub struct WellFormedMessageValidator;
+
+impl WellFormedMessageValidator {
+    /// [WellFormedMessageValidator] factory
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn validate_signer_registration(message: &RegisterSignerMessage) -> MessageValidation {
+        let verification_key =
+            match ProtocolSignerVerificationKey::from_json_hex(&message.verification_key) {
+                Ok(key) => key,
+                Err(error) => {
+                    return MessageValidation::Reject(format!(
+                        "invalid verification key: {error:#}"
+                    ))
+                }
+            };
+        let verification_key_signature = match &message.verification_key_signature {
+            Some(signature) => {
+                match ProtocolSignerVerificationKeySignature::from_json_hex(signature) {
+                    Ok(signature) => Some(signature),
+                    Err(error) => {
+                        return MessageValidation::Reject(format!(
+                            "invalid verification key signature: {error:#}"
+                        ))
+                    }
+                }
+            }
+            None => None,
+        };
+        let operational_certificate = match &message.operational_certificate {
+            Some(opcert) => match ProtocolOpCert::from_json_hex(opcert) {
+                Ok(opcert) => Some(opcert),
+                Err(error) => {
+                    return MessageValidation::Reject(format!(
+                        "invalid operational certificate: {error:#}"
+                    ))
+                }
+            },
+            None => None,
+        };
+
+        match (operational_certificate, verification_key_signature) {
+            (Some(opcert), Some(signature)) => {
+                let pool_id = match opcert.compute_protocol_party_id() {
+                    Ok(pool_id) => pool_id,
+                    Err(error) => {
+                        return MessageValidation::Reject(format!(
+                            "invalid operational certificate pool id: {error:#}"
+                        ))
+                    }
+                };
+                // The registration is made against a stake distribution that only contains the
+                // pool of the operational certificate: only the certification of the key is
+                // checked here, the stake of the pool is checked by the aggregator.
+                let mut key_registration = ProtocolKeyRegistration::init(&vec![(pool_id, 1)]);
+                match key_registration.register(
+                    None,
+                    Some(opcert),
+                    Some(signature),
+                    message.kes_period,
+                    verification_key,
+                ) {
+                    Ok(_) => MessageValidation::Accept,
+                    Err(error) => MessageValidation::Reject(format!(
+                        "invalid verification key certification: {error:#}"
+                    )),
+                }
+            }
+            (None, None) => MessageValidation::Accept,
+            (Some(_), None) => MessageValidation::Reject(
+                "operational certificate given without verification key signature".to_string(),
+            ),
+            (None, Some(_)) => MessageValidation::Reject(
+                "verification key signature given without operational certificate".to_string(),
+            ),
+        }
+    }
+
+    fn validate_signature_registration(message: &RegisterSignatureMessage) -> MessageValidation {
+        if let Err(error) = ProtocolSingleSignature::from_json_hex(&message.signature) {
+            return MessageValidation::Reject(format!("invalid single signature: {error:#}"));
+        }
+        if message.won_indexes.is_empty() {
+            return MessageValidation::Reject("single signature without won indexes".to_string());
+        }
+
+        MessageValidation::Accept
+    }
+}
+
+#[async_trait]
+impl BroadcastMessageValidator for WellFormedMessageValidator {
+    async fn validate(&self, message: &BroadcastMessage) -> MessageValidation {
+        match message {
+            BroadcastMessage::RegisterSigner(message) => {
+                Self::validate_signer_registration(message)
+            }
+            BroadcastMessage::RegisterSignature(message) => {
+                Self::validate_signature_registration(message)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::fake_keys;
+
+    use super::*;
+
+    async fn validate(message: BroadcastMessage) -> MessageValidation {
+        WellFormedMessageValidator::new().validate(&message).await
+    }
+
+    fn assert_is_rejected(validation: MessageValidation) {
+        assert!(
+            matches!(validation, MessageValidation::Reject(_)),
+            "message should have been rejected, got: {validation:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn accept_well_formed_certified_signer_registration() {
+        let validation = validate(BroadcastMessage::RegisterSigner(
+            RegisterSignerMessage::dummy(),
+        ))
+        .await;
+
+        assert_eq!(MessageValidation::Accept, validation);
+    }
+
+    #[tokio::test]
+    async fn accept_well_formed_signer_registration_without_certification() {
+        let validation = validate(BroadcastMessage::RegisterSigner(RegisterSignerMessage {
+            verification_key_signature: None,
+            operational_certificate: None,
+            kes_period: None,
+            ..RegisterSignerMessage::dummy()
+        }))
+        .await;
+
+        assert_eq!(MessageValidation::Accept, validation);
+    }
+
+    #[tokio::test]
+    async fn reject_signer_registration_with_malformed_verification_key() {
+        let validation = validate(BroadcastMessage::RegisterSigner(RegisterSignerMessage {
+            verification_key: "not-a-key".to_string(),
+            ..RegisterSignerMessage::dummy()
+        }))
+        .await;
+
+        assert_is_rejected(validation);
+    }
+
+    #[tokio::test]
+    async fn reject_signer_registration_with_verification_key_not_signed_by_the_kes_key() {
+        let validation = validate(BroadcastMessage::RegisterSigner(RegisterSignerMessage {
+            verification_key: fake_keys::signer_verification_key()[1].to_string(),
+            ..RegisterSignerMessage::dummy()
+        }))
+        .await;
+
+        assert_is_rejected(validation);
+    }
+
+    #[tokio::test]
+    async fn reject_signer_registration_with_partial_certification() {
+        let validation = validate(BroadcastMessage::RegisterSigner(RegisterSignerMessage {
+            operational_certificate: None,
+            ..RegisterSignerMessage::dummy()
+        }))
+        .await;
+
+        assert_is_rejected(validation);
+    }
+
+    #[tokio::test]
+    async fn accept_well_formed_signature_registration() {
+        let validation = validate(BroadcastMessage::RegisterSignature(
+            RegisterSignatureMessage::dummy(),
+        ))
+        .await;
+
+        assert_eq!(MessageValidation::Accept, validation);
+    }
+
+    #[tokio::test]
+    async fn reject_signature_registration_with_malformed_signature() {
+        let validation = validate(BroadcastMessage::RegisterSignature(
+            RegisterSignatureMessage {
+                signature: "not-a-signature".to_string(),
+                ..RegisterSignatureMessage::dummy()
+            },
+        ))
+        .await;
+
+        assert_is_rejected(validation);
+    }
+
+    #[tokio::test]
+    async fn reject_signature_registration_without_won_indexes() {
+        let validation = validate(BroadcastMessage::RegisterSignature(
+            RegisterSignatureMessage {
+                won_indexes: vec![],
+                ..RegisterSignatureMessage::dummy()
+            },
+        ))
+        .await;
+
+        assert_is_rejected(validation);
+    }
+}
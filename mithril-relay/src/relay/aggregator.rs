@@ -1,4 +1,5 @@
-use crate::p2p::{BroadcastMessage, Peer, PeerEvent};
+use crate::p2p::{BroadcastMessage, Peer, PeerConfiguration, PeerEvent};
+use crate::relay::AggregatorMessageValidator;
 use anyhow::anyhow;
 use libp2p::Multiaddr;
 use mithril_common::{
@@ -7,6 +8,7 @@ use mithril_common::{
 };
 use reqwest::StatusCode;
 use slog_scope::{error, info};
+use std::sync::Arc;
 
 /// A relay for a Mithril aggregator
 pub struct AggregatorRelay {
@@ -16,10 +18,20 @@ pub struct AggregatorRelay {
 
 impl AggregatorRelay {
     /// Start a relay for a Mithril aggregator
-    pub async fn start(addr: &Multiaddr, aggregator_endpoint: &str) -> StdResult<Self> {
+    pub async fn start(
+        addr: &Multiaddr,
+        peer_configuration: &PeerConfiguration,
+        aggregator_endpoint: &str,
+    ) -> StdResult<Self> {
         Ok(Self {
             aggregator_endpoint: aggregator_endpoint.to_owned(),
-            peer: Peer::new(addr).start().await?,
+            peer: Peer::new(addr)
+                .with_configuration(peer_configuration.to_owned())
+                .with_message_validator(Arc::new(AggregatorMessageValidator::new(
+                    aggregator_endpoint,
+                )?))
+                .start()
+                .await?,
         })
     }
 
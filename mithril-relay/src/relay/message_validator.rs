@@ -0,0 +1,694 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use mithril_common::{
+    crypto_helper::ProtocolOpCert,
+    entities::{Epoch, PartyId, StakeDistribution},
+    messages::{
+        CardanoStakeDistributionListMessage, CardanoStakeDistributionMessage,
+        CertificatePendingMessage, EpochSettingsMessage, RegisterSignatureMessage,
+        RegisterSignerMessage,
+    },
+    StdResult,
+};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::p2p::{
+    BroadcastMessage, BroadcastMessageValidator, MessageValidation, WellFormedMessageValidator,
+};
+
+/// The duration during which the state fetched from the aggregator is reused
+const AGGREGATOR_STATE_TIME_TO_LIVE: Duration = Duration::from_secs(10);
+
+/// The minimum duration between two fetches of the state of the aggregator triggered by ignored
+/// messages
+const AGGREGATOR_STATE_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The timeout of the requests sent to the aggregator
+const AGGREGATOR_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The state of the aggregator used to validate the messages
+struct AggregatorState {
+    epoch_settings: EpochSettingsMessage,
+    certificate_pending: Option<CertificatePendingMessage>,
+    stake_distribution: Option<StakeDistribution>,
+    registered_party_ids: HashSet<PartyId>,
+}
+
+/// The signers registered by the aggregator for an epoch, as returned by its
+/// `/signers/registered/{epoch}` route
+#[derive(Deserialize)]
+struct RegisteredSignersMessage {
+    registrations: Vec<RegisteredSignerMessage>,
+}
+
+#[derive(Deserialize)]
+struct RegisteredSignerMessage {
+    party_id: PartyId,
+}
+
+/// The state of the aggregator with the instant it was fetched at
+#[derive(Clone)]
+struct CachedAggregatorState {
+    fetched_at: Instant,
+    state: Arc<AggregatorState>,
+}
+
+/// A [BroadcastMessageValidator] that checks the messages against the current state of an
+/// aggregator, after checking that they are well-formed:
+/// - a signer registration must be made for the current registration epoch, by a pool of the
+///   latest Cardano stake distribution certified by the aggregator or by a pool whose signer the
+///   aggregator registered for the current or the previous epoch,
+/// - a signature must be made for the currently pending signed entity, by one of its signers.
+///
+/// The messages that can not be checked because the aggregator is unreachable are ignored.
+pub struct AggregatorMessageValidator {
+    aggregator_endpoint: String,
+    client: reqwest::Client,
+    well_formed_validator: WellFormedMessageValidator,
+    state: RwLock<Option<CachedAggregatorState>>,
+    refresh_lock: Mutex<()>,
+}
+
+impl AggregatorMessageValidator {
+    /// [AggregatorMessageValidator] factory
+    pub fn new(aggregator_endpoint: &str) -> StdResult<Self> {
+        Ok(Self {
+            aggregator_endpoint: aggregator_endpoint.to_owned(),
+            client: reqwest::Client::builder()
+                .timeout(AGGREGATOR_REQUEST_TIMEOUT)
+                .build()?,
+            well_formed_validator: WellFormedMessageValidator::new(),
+            state: RwLock::new(None),
+            refresh_lock: Mutex::new(()),
+        })
+    }
+
+    async fn fetch_state(&self) -> StdResult<AggregatorState> {
+        let epoch_settings = self
+            .client
+            .get(format!("{}/epoch-settings", self.aggregator_endpoint))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<EpochSettingsMessage>()
+            .await?;
+        let response = self
+            .client
+            .get(format!("{}/certificate-pending", self.aggregator_endpoint))
+            .send()
+            .await?;
+        let certificate_pending = match response.status() {
+            StatusCode::OK => Some(response.json::<CertificatePendingMessage>().await?),
+            StatusCode::NO_CONTENT => None,
+            status => {
+                return Err(anyhow!(
+                    "Get `/certificate-pending` should have returned a 200 or 204 status code, got: {status}"
+                ))
+            }
+        };
+
+        let mut registered_party_ids = self
+            .fetch_registered_party_ids(epoch_settings.epoch)
+            .await?;
+        if let Ok(previous_epoch) = epoch_settings.epoch.previous() {
+            registered_party_ids.extend(self.fetch_registered_party_ids(previous_epoch).await?);
+        }
+
+        Ok(AggregatorState {
+            epoch_settings,
+            certificate_pending,
+            stake_distribution: self.fetch_latest_stake_distribution().await?,
+            registered_party_ids,
+        })
+    }
+
+    /// Fetch the party ids of the signers registered by the aggregator at the given epoch
+    async fn fetch_registered_party_ids(&self, epoch: Epoch) -> StdResult<HashSet<PartyId>> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/signers/registered/{epoch}",
+                self.aggregator_endpoint
+            ))
+            .send()
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(HashSet::new());
+        }
+        let registered_signers = response
+            .error_for_status()?
+            .json::<RegisteredSignersMessage>()
+            .await?;
+
+        Ok(registered_signers
+            .registrations
+            .into_iter()
+            .map(|signer| signer.party_id)
+            .collect())
+    }
+
+    async fn fetch_latest_stake_distribution(&self) -> StdResult<Option<StakeDistribution>> {
+        let stake_distributions = self
+            .client
+            .get(format!(
+                "{}/artifact/cardano-stake-distributions",
+                self.aggregator_endpoint
+            ))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<CardanoStakeDistributionListMessage>()
+            .await?;
+        let Some(latest) = stake_distributions.first() else {
+            return Ok(None);
+        };
+        let stake_distribution = self
+            .client
+            .get(format!(
+                "{}/artifact/cardano-stake-distribution/{}",
+                self.aggregator_endpoint, latest.hash
+            ))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<CardanoStakeDistributionMessage>()
+            .await?
+            .stake_distribution;
+
+        Ok(Some(stake_distribution))
+    }
+
+    async fn cached_state(&self) -> Option<CachedAggregatorState> {
+        self.state.read().await.clone()
+    }
+
+    /// Fetch the state of the aggregator, unless it was already refreshed after the given
+    /// cached state by another validation
+    async fn refresh_state(
+        &self,
+        previous_fetched_at: Option<Instant>,
+    ) -> StdResult<Arc<AggregatorState>> {
+        let _refresh_guard = self.refresh_lock.lock().await;
+        if let Some(cached) = self.cached_state().await {
+            if previous_fetched_at.map_or(true, |previous| cached.fetched_at > previous) {
+                return Ok(cached.state);
+            }
+        }
+
+        let state = Arc::new(self.fetch_state().await?);
+        *self.state.write().await = Some(CachedAggregatorState {
+            fetched_at: Instant::now(),
+            state: state.clone(),
+        });
+
+        Ok(state)
+    }
+
+    /// Id of the pool that registers: the one of the operational certificate if the
+    /// registration is certified, the party id of the message otherwise
+    fn registering_party_id(message: &RegisterSignerMessage) -> StdResult<PartyId> {
+        match &message.operational_certificate {
+            Some(opcert) => ProtocolOpCert::from_json_hex(opcert)?.compute_protocol_party_id(),
+            None => Ok(message.party_id.clone()),
+        }
+    }
+
+    fn validate_signer_registration(
+        message: &RegisterSignerMessage,
+        state: &AggregatorState,
+    ) -> MessageValidation {
+        let registration_epoch = state.epoch_settings.epoch.offset_to_recording_epoch();
+        if let Some(epoch) = message.epoch {
+            if epoch != registration_epoch {
+                return MessageValidation::Ignore(format!(
+                    "signer registration for epoch {epoch} while the registration epoch is {registration_epoch}"
+                ));
+            }
+        }
+
+        let party_id = match Self::registering_party_id(message) {
+            Ok(party_id) => party_id,
+            Err(error) => {
+                return MessageValidation::Reject(format!(
+                    "invalid operational certificate pool id: {error:#}"
+                ))
+            }
+        };
+        if state.registered_party_ids.contains(&party_id) {
+            return MessageValidation::Accept;
+        }
+        // No Cardano stake distribution is certified yet by the aggregator: the registration
+        // can not be checked.
+        let Some(stake_distribution) = &state.stake_distribution else {
+            return MessageValidation::Ignore(format!(
+                "no certified Cardano stake distribution to check the registration of party id {party_id}"
+            ));
+        };
+        match stake_distribution.get(&party_id) {
+            Some(stake) if *stake > 0 => MessageValidation::Accept,
+            _ => MessageValidation::Reject(format!(
+                "party id {party_id} is neither in the latest certified Cardano stake distribution nor a registered signer"
+            )),
+        }
+    }
+
+    fn validate_signature_registration(
+        message: &RegisterSignatureMessage,
+        state: &AggregatorState,
+    ) -> MessageValidation {
+        let Some(certificate_pending) = &state.certificate_pending else {
+            return MessageValidation::Ignore("no pending certificate".to_string());
+        };
+        match &message.signed_entity_type {
+            Some(signed_entity_type)
+                if signed_entity_type != &certificate_pending.signed_entity_type =>
+            {
+                MessageValidation::Ignore(format!(
+                    "signature for {signed_entity_type:?} while the pending signed entity is {:?}",
+                    certificate_pending.signed_entity_type
+                ))
+            }
+            _ if !certificate_pending
+                .signers
+                .iter()
+                .any(|signer| signer.party_id == message.party_id) =>
+            {
+                MessageValidation::Reject(format!(
+                    "party id {} is not a signer of the pending certificate",
+                    message.party_id
+                ))
+            }
+            _ => MessageValidation::Accept,
+        }
+    }
+
+    fn validate_with_state(
+        message: &BroadcastMessage,
+        state: &AggregatorState,
+    ) -> MessageValidation {
+        match message {
+            BroadcastMessage::RegisterSigner(message) => {
+                Self::validate_signer_registration(message, state)
+            }
+            BroadcastMessage::RegisterSignature(message) => {
+                Self::validate_signature_registration(message, state)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl BroadcastMessageValidator for AggregatorMessageValidator {
+    async fn validate(&self, message: &BroadcastMessage) -> MessageValidation {
+        let validation = self.well_formed_validator.validate(message).await;
+        if !validation.is_accepted() {
+            return validation;
+        }
+
+        let cached_state = self.cached_state().await;
+        if let Some(cached) = &cached_state {
+            let validation = Self::validate_with_state(message, &cached.state);
+            let age = cached.fetched_at.elapsed();
+            // A message ignored with a recent state may be valid with the next state of the
+            // aggregator (ie. a signature for a signed entity that has just been opened).
+            let should_refresh = age >= AGGREGATOR_STATE_TIME_TO_LIVE
+                || (matches!(validation, MessageValidation::Ignore(_))
+                    && age >= AGGREGATOR_STATE_MIN_REFRESH_INTERVAL);
+            if !should_refresh {
+                return validation;
+            }
+        }
+
+        match self
+            .refresh_state(cached_state.map(|cached| cached.fetched_at))
+            .await
+        {
+            Ok(state) => Self::validate_with_state(message, &state),
+            Err(error) => MessageValidation::Ignore(format!(
+                "can not retrieve the state of the aggregator: {error:#}"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::{
+        entities::SignedEntityType,
+        messages::CardanoStakeDistributionListItemMessage,
+        test_utils::test_http_server::{test_http_server, TestHttpServer},
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use warp::Filter;
+
+    use super::*;
+
+    fn aggregator_server(
+        epoch_settings: EpochSettingsMessage,
+        certificate_pending: Option<CertificatePendingMessage>,
+        stake_distribution: Option<CardanoStakeDistributionMessage>,
+        registered_party_ids: Vec<PartyId>,
+        epoch_settings_calls: Arc<AtomicUsize>,
+    ) -> TestHttpServer {
+        let registered_signers = serde_json::json!({
+            "registrations": registered_party_ids
+                .iter()
+                .map(|party_id| serde_json::json!({ "party_id": party_id, "stake": 1000 }))
+                .collect::<Vec<_>>(),
+        });
+        let stake_distributions: CardanoStakeDistributionListMessage = stake_distribution
+            .iter()
+            .map(|message| CardanoStakeDistributionListItemMessage {
+                epoch: message.epoch,
+                hash: message.hash.clone(),
+                certificate_hash: message.certificate_hash.clone(),
+                created_at: message.created_at,
+            })
+            .collect();
+
+        test_http_server(
+            warp::path("epoch-settings")
+                .map(move || {
+                    epoch_settings_calls.fetch_add(1, Ordering::SeqCst);
+                    warp::reply::json(&epoch_settings)
+                })
+                .or(
+                    warp::path("certificate-pending").map(move || match &certificate_pending {
+                        Some(certificate_pending) => {
+                            Box::new(warp::reply::json(certificate_pending)) as Box<dyn warp::Reply>
+                        }
+                        None => Box::new(warp::http::StatusCode::NO_CONTENT),
+                    }),
+                )
+                .or(warp::path!("signers" / "registered" / String)
+                    .map(move |_epoch| warp::reply::json(&registered_signers)))
+                .or(warp::path!("artifact" / "cardano-stake-distributions")
+                    .map(move || warp::reply::json(&stake_distributions)))
+                .or(
+                    warp::path!("artifact" / "cardano-stake-distribution" / String).map(
+                        move |hash: String| match stake_distribution
+                            .as_ref()
+                            .filter(|message| message.hash == hash)
+                        {
+                            Some(message) => {
+                                Box::new(warp::reply::json(message)) as Box<dyn warp::Reply>
+                            }
+                            None => Box::new(warp::http::StatusCode::NOT_FOUND),
+                        },
+                    ),
+                ),
+        )
+    }
+
+    fn aggregator_validator(
+        certificate_pending: Option<CertificatePendingMessage>,
+        stake_distribution: Option<CardanoStakeDistributionMessage>,
+    ) -> (TestHttpServer, AggregatorMessageValidator) {
+        let server = aggregator_server(
+            EpochSettingsMessage::dummy(),
+            certificate_pending,
+            stake_distribution,
+            vec![],
+            Arc::new(AtomicUsize::new(0)),
+        );
+        let validator = AggregatorMessageValidator::new(&server.url()).unwrap();
+
+        (server, validator)
+    }
+
+    fn signer_registration_for_the_registration_epoch() -> RegisterSignerMessage {
+        RegisterSignerMessage {
+            epoch: Some(
+                EpochSettingsMessage::dummy()
+                    .epoch
+                    .offset_to_recording_epoch(),
+            ),
+            ..RegisterSignerMessage::dummy()
+        }
+    }
+
+    fn stake_distribution_with(party_id: PartyId) -> CardanoStakeDistributionMessage {
+        CardanoStakeDistributionMessage {
+            stake_distribution: StakeDistribution::from([(party_id, 1000)]),
+            ..CardanoStakeDistributionMessage::dummy()
+        }
+    }
+
+    fn assert_is_ignored(validation: MessageValidation) {
+        assert!(
+            matches!(validation, MessageValidation::Ignore(_)),
+            "message should have been ignored, got: {validation:?}"
+        );
+    }
+
+    fn assert_is_rejected(validation: MessageValidation) {
+        assert!(
+            matches!(validation, MessageValidation::Reject(_)),
+            "message should have been rejected, got: {validation:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_malformed_message_without_requesting_the_aggregator() {
+        let validator = AggregatorMessageValidator::new("http://127.0.0.1:1").unwrap();
+
+        let validation = validator
+            .validate(&BroadcastMessage::RegisterSignature(
+                RegisterSignatureMessage {
+                    won_indexes: vec![],
+                    ..RegisterSignatureMessage::dummy()
+                },
+            ))
+            .await;
+
+        assert_is_rejected(validation);
+    }
+
+    #[tokio::test]
+    async fn ignore_message_when_the_aggregator_is_unreachable() {
+        let validator = AggregatorMessageValidator::new("http://127.0.0.1:1").unwrap();
+
+        let validation = validator
+            .validate(&BroadcastMessage::RegisterSigner(
+                RegisterSignerMessage::dummy(),
+            ))
+            .await;
+
+        assert_is_ignored(validation);
+    }
+
+    #[tokio::test]
+    async fn ignore_signer_registration_for_another_epoch() {
+        let (_server, validator) = aggregator_validator(None, None);
+
+        let validation = validator
+            .validate(&BroadcastMessage::RegisterSigner(RegisterSignerMessage {
+                epoch: Some(Epoch(1)),
+                ..RegisterSignerMessage::dummy()
+            }))
+            .await;
+
+        assert_is_ignored(validation);
+    }
+
+    #[tokio::test]
+    async fn accept_signature_of_a_signer_for_the_pending_signed_entity() {
+        let certificate_pending = CertificatePendingMessage::dummy();
+        let (_server, validator) = aggregator_validator(Some(certificate_pending.clone()), None);
+
+        let validation = validator
+            .validate(&BroadcastMessage::RegisterSignature(
+                RegisterSignatureMessage {
+                    signed_entity_type: Some(certificate_pending.signed_entity_type),
+                    party_id: certificate_pending.signers[0].party_id.clone(),
+                    ..RegisterSignatureMessage::dummy()
+                },
+            ))
+            .await;
+
+        assert_eq!(MessageValidation::Accept, validation);
+    }
+
+    #[tokio::test]
+    async fn reject_signature_of_a_party_that_is_not_a_signer_of_the_pending_signed_entity() {
+        let certificate_pending = CertificatePendingMessage::dummy();
+        let (_server, validator) = aggregator_validator(Some(certificate_pending.clone()), None);
+
+        let validation = validator
+            .validate(&BroadcastMessage::RegisterSignature(
+                RegisterSignatureMessage {
+                    signed_entity_type: Some(certificate_pending.signed_entity_type),
+                    party_id: "unknown-party".to_string(),
+                    ..RegisterSignatureMessage::dummy()
+                },
+            ))
+            .await;
+
+        assert_is_rejected(validation);
+    }
+
+    #[tokio::test]
+    async fn ignore_signature_for_another_signed_entity() {
+        let certificate_pending = CertificatePendingMessage::dummy();
+        let (_server, validator) = aggregator_validator(Some(certificate_pending.clone()), None);
+
+        let validation = validator
+            .validate(&BroadcastMessage::RegisterSignature(
+                RegisterSignatureMessage {
+                    signed_entity_type: Some(SignedEntityType::CardanoStakeDistribution(Epoch(
+                        123,
+                    ))),
+                    party_id: certificate_pending.signers[0].party_id.clone(),
+                    ..RegisterSignatureMessage::dummy()
+                },
+            ))
+            .await;
+
+        assert_is_ignored(validation);
+    }
+
+    #[tokio::test]
+    async fn ignore_signature_when_there_is_no_pending_certificate() {
+        let (_server, validator) = aggregator_validator(None, None);
+
+        let validation = validator
+            .validate(&BroadcastMessage::RegisterSignature(
+                RegisterSignatureMessage::dummy(),
+            ))
+            .await;
+
+        assert_is_ignored(validation);
+    }
+
+    #[tokio::test]
+    async fn reuse_the_aggregator_state_to_validate_successive_messages() {
+        let epoch_settings_calls = Arc::new(AtomicUsize::new(0));
+        let server = aggregator_server(
+            EpochSettingsMessage::dummy(),
+            None,
+            None,
+            vec![],
+            epoch_settings_calls.clone(),
+        );
+        let validator = AggregatorMessageValidator::new(&server.url()).unwrap();
+        let message =
+            BroadcastMessage::RegisterSigner(signer_registration_for_the_registration_epoch());
+
+        validator.validate(&message).await;
+        validator.validate(&message).await;
+
+        assert_eq!(1, epoch_settings_calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn accept_signer_registration_of_a_pool_of_the_latest_stake_distribution() {
+        let message = signer_registration_for_the_registration_epoch();
+        let pool_id = AggregatorMessageValidator::registering_party_id(&message).unwrap();
+        let (_server, validator) =
+            aggregator_validator(None, Some(stake_distribution_with(pool_id)));
+
+        let validation = validator
+            .validate(&BroadcastMessage::RegisterSigner(message))
+            .await;
+
+        assert_eq!(MessageValidation::Accept, validation);
+    }
+
+    #[tokio::test]
+    async fn accept_signer_registration_of_a_pool_registered_by_the_aggregator() {
+        let message = signer_registration_for_the_registration_epoch();
+        let pool_id = AggregatorMessageValidator::registering_party_id(&message).unwrap();
+        let server = aggregator_server(
+            EpochSettingsMessage::dummy(),
+            None,
+            None,
+            vec![pool_id],
+            Arc::new(AtomicUsize::new(0)),
+        );
+        let validator = AggregatorMessageValidator::new(&server.url()).unwrap();
+
+        let validation = validator
+            .validate(&BroadcastMessage::RegisterSigner(message))
+            .await;
+
+        assert_eq!(MessageValidation::Accept, validation);
+    }
+
+    #[tokio::test]
+    async fn ignore_signer_registration_when_no_stake_distribution_is_certified() {
+        let (_server, validator) = aggregator_validator(None, None);
+
+        let validation = validator
+            .validate(&BroadcastMessage::RegisterSigner(
+                signer_registration_for_the_registration_epoch(),
+            ))
+            .await;
+
+        assert_is_ignored(validation);
+    }
+
+    #[tokio::test]
+    async fn reject_signer_registration_of_an_unknown_pool() {
+        let (_server, validator) = aggregator_validator(
+            None,
+            Some(stake_distribution_with(
+                "pool-without-registration".to_string(),
+            )),
+        );
+
+        let validation = validator
+            .validate(&BroadcastMessage::RegisterSigner(
+                signer_registration_for_the_registration_epoch(),
+            ))
+            .await;
+
+        assert_is_rejected(validation);
+    }
+
+    #[tokio::test]
+    async fn check_the_stake_of_the_pool_of_the_operational_certificate_not_the_party_id() {
+        let message = RegisterSignerMessage {
+            party_id: "pool-with-stake".to_string(),
+            ..signer_registration_for_the_registration_epoch()
+        };
+        let (_server, validator) = aggregator_validator(
+            None,
+            Some(stake_distribution_with("pool-with-stake".to_string())),
+        );
+
+        let validation = validator
+            .validate(&BroadcastMessage::RegisterSigner(message))
+            .await;
+
+        assert_is_rejected(validation);
+    }
+
+    #[tokio::test]
+    async fn do_not_refresh_the_aggregator_state_for_each_ignored_message() {
+        let epoch_settings_calls = Arc::new(AtomicUsize::new(0));
+        let server = aggregator_server(
+            EpochSettingsMessage::dummy(),
+            None,
+            None,
+            vec![],
+            epoch_settings_calls.clone(),
+        );
+        let validator = AggregatorMessageValidator::new(&server.url()).unwrap();
+        let message = BroadcastMessage::RegisterSignature(RegisterSignatureMessage::dummy());
+
+        assert_is_ignored(validator.validate(&message).await);
+        assert_is_ignored(validator.validate(&message).await);
+
+        assert_eq!(1, epoch_settings_calls.load(Ordering::SeqCst));
+    }
+}
@@ -1,7 +1,9 @@
 mod aggregator;
+mod message_validator;
 mod passive;
 mod signer;
 
 pub use aggregator::AggregatorRelay;
+pub use message_validator::AggregatorMessageValidator;
 pub use passive::PassiveRelay;
 pub use signer::SignerRelay;
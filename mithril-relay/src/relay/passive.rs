@@ -1,4 +1,4 @@
-use crate::p2p::{BroadcastMessage, Peer, PeerEvent};
+use crate::p2p::{BroadcastMessage, Peer, PeerConfiguration, PeerEvent};
 use libp2p::Multiaddr;
 use mithril_common::StdResult;
 use slog_scope::{debug, info};
@@ -12,10 +12,16 @@ pub struct PassiveRelay {
 
 impl PassiveRelay {
     /// Start a passive relay
-    pub async fn start(addr: &Multiaddr) -> StdResult<Self> {
+    pub async fn start(
+        addr: &Multiaddr,
+        peer_configuration: &PeerConfiguration,
+    ) -> StdResult<Self> {
         debug!("PassiveRelay: starting...");
         Ok(Self {
-            peer: Peer::new(addr).start().await?,
+            peer: Peer::new(addr)
+                .with_configuration(peer_configuration.to_owned())
+                .start()
+                .await?,
         })
     }
 
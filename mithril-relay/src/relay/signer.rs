@@ -1,5 +1,6 @@
 use crate::{
-    p2p::{Peer, PeerEvent},
+    p2p::{Peer, PeerConfiguration, PeerEvent},
+    relay::AggregatorMessageValidator,
     repeater::MessageRepeater,
 };
 use libp2p::Multiaddr;
@@ -26,6 +27,7 @@ impl SignerRelay {
     /// Start a relay for a Mithril signer
     pub async fn start(
         address: &Multiaddr,
+        peer_configuration: &PeerConfiguration,
         server_port: &u16,
         aggregator_endpoint: &str,
         signer_repeater_delay: &Duration,
@@ -37,7 +39,13 @@ impl SignerRelay {
             signer_tx.clone(),
             signer_repeater_delay.to_owned(),
         ));
-        let peer = Peer::new(address).start().await?;
+        let peer = Peer::new(address)
+            .with_configuration(peer_configuration.to_owned())
+            .with_message_validator(Arc::new(AggregatorMessageValidator::new(
+                aggregator_endpoint,
+            )?))
+            .start()
+            .await?;
         let server = Self::start_http_server(
             server_port,
             aggregator_endpoint,
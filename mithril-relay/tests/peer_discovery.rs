@@ -0,0 +1,89 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use libp2p::{multiaddr::Protocol, Multiaddr, PeerId};
+use mithril_relay::{p2p::PeerConfiguration, PassiveRelay};
+use slog::{Drain, Level, Logger};
+use slog_scope::info;
+use tokio::sync::watch;
+
+// Launch several relays that only know a single bootstrap relay. The relays must discover each
+// other and be connected to all the other relays of the network.
+
+fn build_logger(log_level: Level) -> Logger {
+    let decorator = slog_term::TermDecorator::new().build();
+    let drain = slog_term::CompactFormat::new(decorator).build().fuse();
+    let drain = slog::LevelFilter::new(drain, log_level).fuse();
+    let drain = slog_async::Async::new(drain).build().fuse();
+
+    Logger::root(Arc::new(drain), slog::o!())
+}
+
+/// Tick a relay in a dedicated task and publish the peers it is connected to
+fn spawn_relay(mut relay: PassiveRelay) -> watch::Receiver<HashSet<PeerId>> {
+    let (connected_peers_tx, connected_peers_rx) = watch::channel(HashSet::new());
+    tokio::spawn(async move {
+        loop {
+            let _ = relay.tick_peer().await;
+            connected_peers_tx.send_replace(relay.peer.connected_peers().into_iter().collect());
+        }
+    });
+
+    connected_peers_rx
+}
+
+#[tokio::test]
+async fn should_discover_all_relays_from_a_single_bootstrap_relay() {
+    let _guard = slog_scope::set_global_logger(build_logger(Level::Info));
+
+    let total_relays = 4;
+    let addr: Multiaddr = "/ip4/127.0.0.1/tcp/0".parse().unwrap();
+    let peer_configuration = PeerConfiguration {
+        discovery_interval: Duration::from_millis(500),
+        ..PeerConfiguration::default()
+    };
+
+    let bootstrap_relay = PassiveRelay::start(&addr, &peer_configuration)
+        .await
+        .expect("Bootstrap relay start failed");
+    let bootstrap_relay_address = bootstrap_relay
+        .peer_address()
+        .unwrap()
+        .with(Protocol::P2p(bootstrap_relay.peer.local_peer_id().unwrap()));
+    info!("Test: bootstrap relay address is '{bootstrap_relay_address:?}'");
+
+    let mut peer_ids = vec![bootstrap_relay.peer.local_peer_id().unwrap()];
+    let mut connected_peers_receivers = vec![spawn_relay(bootstrap_relay)];
+    for _ in 1..total_relays {
+        let relay = PassiveRelay::start(
+            &addr,
+            &PeerConfiguration {
+                bootstrap_peers: vec![bootstrap_relay_address.clone()],
+                ..peer_configuration.clone()
+            },
+        )
+        .await
+        .expect("Relay start failed");
+        peer_ids.push(relay.peer.local_peer_id().unwrap());
+        connected_peers_receivers.push(spawn_relay(relay));
+    }
+
+    info!("Test: wait for all the relays to be connected to each other");
+    tokio::time::timeout(Duration::from_secs(30), async {
+        for (peer_id, connected_peers_rx) in
+            peer_ids.iter().zip(connected_peers_receivers.iter_mut())
+        {
+            let expected_peers: HashSet<PeerId> = peer_ids
+                .iter()
+                .filter(|id| *id != peer_id)
+                .copied()
+                .collect();
+            connected_peers_rx
+                .wait_for(|connected_peers| connected_peers.is_superset(&expected_peers))
+                .await
+                .unwrap();
+            info!("Test: relay {peer_id} is connected to all the other relays");
+        }
+    })
+    .await
+    .expect("All the relays should have discovered each other");
+}
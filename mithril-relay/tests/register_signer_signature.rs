@@ -3,7 +3,7 @@ use std::{sync::Arc, time::Duration};
 use libp2p::{gossipsub, Multiaddr};
 use mithril_common::messages::{RegisterSignatureMessage, RegisterSignerMessage};
 use mithril_relay::{
-    p2p::{BroadcastMessage, PeerBehaviourEvent, PeerEvent},
+    p2p::{BroadcastMessage, PeerBehaviourEvent, PeerConfiguration, PeerEvent},
     PassiveRelay, SignerRelay,
 };
 use reqwest::StatusCode;
@@ -36,8 +36,10 @@ async fn should_receive_registrations_from_signers_when_subscribed_to_pubsub() {
     let server_port = 0;
     let aggregator_endpoint = "http://0.0.0.0:1234".to_string();
     let signer_repeater_delay = Duration::from_secs(100);
+    let peer_configuration = PeerConfiguration::default();
     let mut signer_relay = SignerRelay::start(
         &addr,
+        &peer_configuration,
         &server_port,
         &aggregator_endpoint,
         &signer_repeater_delay,
@@ -48,7 +50,7 @@ async fn should_receive_registrations_from_signers_when_subscribed_to_pubsub() {
     let relay_peer_address = signer_relay.peer_address().unwrap();
     info!("Test: relay_address is '{relay_address:?}'");
 
-    let mut p2p_client1 = PassiveRelay::start(&addr)
+    let mut p2p_client1 = PassiveRelay::start(&addr, &peer_configuration)
         .await
         .expect("P2P client start failed");
     p2p_client1
@@ -56,7 +58,7 @@ async fn should_receive_registrations_from_signers_when_subscribed_to_pubsub() {
         .dial(relay_peer_address.clone())
         .expect("P2P client dial to the relay should not fail");
 
-    let mut p2p_client2 = PassiveRelay::start(&addr)
+    let mut p2p_client2 = PassiveRelay::start(&addr, &peer_configuration)
         .await
         .expect("P2P client start failed");
     p2p_client2
@@ -136,7 +138,7 @@ async fn should_receive_registrations_from_signers_when_subscribed_to_pubsub() {
     loop {
         tokio::select! {
             event =  p2p_client1.tick_peer() => {
-                if let Ok(Some(BroadcastMessage::RegisterSigner(signer_message_received))) = p2p_client1.convert_peer_event_to_message(event.unwrap().unwrap())
+                if let Some(Ok(Some(BroadcastMessage::RegisterSigner(signer_message_received)))) = event.unwrap().map(|peer_event| p2p_client1.convert_peer_event_to_message(peer_event))
                 {
                     info!("Test: client1 consumed signer registration: {signer_message_received:#?}");
                     assert_eq!(signer_message_sent, signer_message_received);
@@ -144,7 +146,7 @@ async fn should_receive_registrations_from_signers_when_subscribed_to_pubsub() {
                 }
             }
             event =  p2p_client2.tick_peer() => {
-                if let Ok(Some(BroadcastMessage::RegisterSigner(signer_message_received))) = p2p_client2.convert_peer_event_to_message(event.unwrap().unwrap())
+                if let Some(Ok(Some(BroadcastMessage::RegisterSigner(signer_message_received)))) = event.unwrap().map(|peer_event| p2p_client2.convert_peer_event_to_message(peer_event))
                 {
                     info!("Test: client2 consumed signer registration: {signer_message_received:#?}");
                     assert_eq!(signer_message_sent, signer_message_received);
@@ -181,7 +183,7 @@ async fn should_receive_registrations_from_signers_when_subscribed_to_pubsub() {
     loop {
         tokio::select! {
             event =  p2p_client1.tick_peer() => {
-                if let Ok(Some(BroadcastMessage::RegisterSignature(signature_message_received))) = p2p_client1.convert_peer_event_to_message(event.unwrap().unwrap())
+                if let Some(Ok(Some(BroadcastMessage::RegisterSignature(signature_message_received)))) = event.unwrap().map(|peer_event| p2p_client1.convert_peer_event_to_message(peer_event))
                 {
                     info!("Test: client1 consumed signature: {signature_message_received:#?}");
                     assert_eq!(signature_message_sent, signature_message_received);
@@ -189,7 +191,7 @@ async fn should_receive_registrations_from_signers_when_subscribed_to_pubsub() {
                 }
             }
             event =  p2p_client2.tick_peer() => {
-                if let Ok(Some(BroadcastMessage::RegisterSignature(signature_message_received))) = p2p_client2.convert_peer_event_to_message(event.unwrap().unwrap())
+                if let Some(Ok(Some(BroadcastMessage::RegisterSignature(signature_message_received)))) = event.unwrap().map(|peer_event| p2p_client2.convert_peer_event_to_message(peer_event))
                 {
                     info!("Test: client2 consumed signature: {signature_message_received:#?}");
                     assert_eq!(signature_message_sent, signature_message_received);
@@ -428,6 +428,7 @@ mod tests {
             network_magic: Some(42),
             network: "testnet".to_string(),
             aggregator_endpoint: server.url(""),
+            additional_aggregator_endpoints: None,
             relay_endpoint: None,
             party_id: Some("0".to_string()),
             run_interval: 100,
@@ -436,6 +437,8 @@ mod tests {
             store_retention_limit: None,
             kes_secret_key_path: None,
             operational_certificate_path: None,
+            store_encryption_passphrase: None,
+            store_encryption_key_path: None,
             disable_digests_cache: false,
             reset_digests_cache: false,
             era_reader_adapter_type: EraReaderAdapterType::Bootstrap,
@@ -444,6 +447,9 @@ mod tests {
             metrics_server_ip: "0.0.0.0".to_string(),
             metrics_server_port: 9090,
             allow_unparsable_block: false,
+            enable_transaction_pruning: false,
+            transactions_pruning_retained_block_ranges: 150,
+            enable_chain_sync_block_scanner: false,
         };
         let era_checker = EraChecker::new(SupportedEra::dummy(), Epoch(1));
         let api_version_provider = APIVersionProvider::new(Arc::new(era_checker));
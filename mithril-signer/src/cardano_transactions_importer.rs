@@ -3,15 +3,21 @@ use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use anyhow::Context;
 use async_trait::async_trait;
-use slog::{debug, Logger};
+use slog::{debug, warn, Logger};
+use tokio::sync::Mutex;
 
-use mithril_common::cardano_block_scanner::BlockScanner;
+use mithril_common::cardano_block_scanner::{BlockScanner, ChainScannedBlocks};
 use mithril_common::crypto_helper::{MKTree, MKTreeNode};
-use mithril_common::entities::{BlockNumber, BlockRange, CardanoTransaction, ImmutableFileNumber};
+use mithril_common::entities::{
+    BlockNumber, BlockRange, CardanoTransaction, ChainPoint, ImmutableFileNumber,
+};
 use mithril_common::signable_builder::TransactionsImporter;
 use mithril_common::StdResult;
 
+use crate::MetricsService;
+
 /// Cardano transactions store
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
@@ -44,6 +50,28 @@ pub trait TransactionStore: Send + Sync {
         &self,
         block_ranges: Vec<(BlockRange, MKTreeNode)>,
     ) -> StdResult<()>;
+
+    /// Get a transaction of the highest stored block up to the given beacon
+    async fn get_highest_block_transaction_up_to(
+        &self,
+        immutable_file_number: ImmutableFileNumber,
+    ) -> StdResult<Option<CardanoTransaction>>;
+
+    /// Remove the transactions and the block range roots after the given block number, or all
+    /// of them if no block number is given
+    async fn remove_rolled_back_transactions_and_block_range(
+        &self,
+        block_number: Option<BlockNumber>,
+    ) -> StdResult<()>;
+}
+
+/// Cardano transactions pruner
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait TransactionPruner: Send + Sync {
+    /// Prune the transactions that are not in the last `number_of_block_ranges_to_keep` block
+    /// ranges, returns the number of pruned transactions
+    async fn prune(&self, number_of_block_ranges_to_keep: u64) -> StdResult<usize>;
 }
 
 /// Import and store [CardanoTransaction].
@@ -53,6 +81,9 @@ pub struct CardanoTransactionsImporter {
     logger: Logger,
     rescan_offset: Option<usize>,
     dirpath: PathBuf,
+    /// Highest stored block known to be in the immutable files, the divergence with the
+    /// immutable files is only checked again once the highest stored block changes
+    verified_chain_point: Mutex<Option<ChainPoint>>,
 }
 
 impl CardanoTransactionsImporter {
@@ -74,7 +105,144 @@ impl CardanoTransactionsImporter {
             logger,
             rescan_offset,
             dirpath: dirpath.to_owned(),
+            verified_chain_point: Mutex::new(None),
+        }
+    }
+
+    /// Get a transaction of the highest stored block
+    async fn get_highest_stored_block_transaction(&self) -> StdResult<Option<CardanoTransaction>> {
+        match self.transaction_store.get_highest_beacon().await? {
+            Some(beacon) => {
+                self.transaction_store
+                    .get_highest_block_transaction_up_to(beacon)
+                    .await
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn chain_point_of(transaction: &CardanoTransaction) -> ChainPoint {
+        ChainPoint {
+            slot_number: transaction.slot_number,
+            block_number: transaction.block_number,
+            block_hash: transaction.block_hash.clone(),
+        }
+    }
+
+    /// Remember the highest stored block as verified, it was just checked or imported from the
+    /// immutable files
+    async fn record_verified_chain_point(&self) -> StdResult<()> {
+        let highest_stored_chain_point = self
+            .get_highest_stored_block_transaction()
+            .await?
+            .map(|transaction| Self::chain_point_of(&transaction));
+        *self.verified_chain_point.lock().await = highest_stored_chain_point;
+
+        Ok(())
+    }
+
+    /// Detect if the stored transactions diverge from the chain read by the block scanner (ie:
+    /// if the immutable files were restored, truncated or replaced), and if so remove the
+    /// stored transactions and block range roots after the fork point so they are imported
+    /// again.
+    ///
+    /// Nothing is checked if the highest stored block is the one verified by the previous
+    /// import. Otherwise the fork point, the highest stored block that is still found in the
+    /// immutable files, is searched with a binary search on the stored immutable file numbers.
+    async fn rollback_if_chain_diverged(&self) -> StdResult<()> {
+        let highest_stored_block_transaction =
+            match self.get_highest_stored_block_transaction().await? {
+                Some(transaction) => transaction,
+                None => return Ok(()),
+            };
+        let highest_stored_chain_point = Self::chain_point_of(&highest_stored_block_transaction);
+        if self.verified_chain_point.lock().await.as_ref() == Some(&highest_stored_chain_point) {
+            return Ok(());
+        }
+        if self
+            .is_block_in_immutable_files(&highest_stored_block_transaction)
+            .await?
+        {
+            return Ok(());
+        }
+
+        // Search the first immutable file whose highest stored block is not in the immutable
+        // files, the fork point is the highest stored block before it.
+        let (mut low, mut high) = (0, highest_stored_block_transaction.immutable_file_number);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self
+                .is_highest_block_up_to_in_immutable_files(middle)
+                .await?
+            {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        let fork_point_block_number = match low.checked_sub(1) {
+            Some(fork_point_immutable) => self
+                .transaction_store
+                .get_highest_block_transaction_up_to(fork_point_immutable)
+                .await?
+                .map(|transaction| transaction.block_number),
+            None => None,
+        };
+
+        warn!(
+            self.logger,
+            "TransactionsImporter detected that the stored transactions diverge from the immutable files, rolling back";
+            "highest_stored_block_number" => highest_stored_chain_point.block_number,
+            "fork_point_block_number" => ?fork_point_block_number,
+        );
+        self.transaction_store
+            .remove_rolled_back_transactions_and_block_range(fork_point_block_number)
+            .await
+    }
+
+    /// Check if the highest stored block up to the given immutable file number is in the
+    /// immutable files, `true` if there's no such block.
+    async fn is_highest_block_up_to_in_immutable_files(
+        &self,
+        immutable_file_number: ImmutableFileNumber,
+    ) -> StdResult<bool> {
+        match self
+            .transaction_store
+            .get_highest_block_transaction_up_to(immutable_file_number)
+            .await?
+        {
+            Some(transaction) => self.is_block_in_immutable_files(&transaction).await,
+            None => Ok(true),
+        }
+    }
+
+    /// Check if the block of the given transaction, with the same hash, is in the immutable
+    /// file the transaction was imported from.
+    async fn is_block_in_immutable_files(
+        &self,
+        transaction: &CardanoTransaction,
+    ) -> StdResult<bool> {
+        let mut streamer = self
+            .block_scanner
+            .scan(
+                &self.dirpath,
+                Some(transaction.immutable_file_number),
+                transaction.immutable_file_number,
+            )
+            .await?;
+
+        while let Some(scanned_blocks) = streamer.poll_next().await? {
+            if let ChainScannedBlocks::RollForwards(blocks) = scanned_blocks {
+                if blocks.iter().any(|block| {
+                    block.block_number == transaction.block_number
+                        && block.block_hash == transaction.block_hash
+                }) {
+                    return Ok(true);
+                }
+            }
         }
+
+        Ok(false)
     }
 
     async fn import_transactions(&self, up_to_beacon: ImmutableFileNumber) -> StdResult<()> {
@@ -110,15 +278,28 @@ impl CardanoTransactionsImporter {
 
         let mut streamer = self.block_scanner.scan(&self.dirpath, from, until).await?;
 
-        while let Some(blocks) = streamer.poll_next().await? {
-            let parsed_transactions: Vec<CardanoTransaction> = blocks
-                .into_iter()
-                .flat_map(|b| b.into_transactions())
-                .collect();
-
-            self.transaction_store
-                .store_transactions(parsed_transactions)
-                .await?;
+        while let Some(scanned_blocks) = streamer.poll_next().await? {
+            match scanned_blocks {
+                ChainScannedBlocks::RollForwards(blocks) => {
+                    let parsed_transactions: Vec<CardanoTransaction> = blocks
+                        .into_iter()
+                        .flat_map(|b| b.into_transactions())
+                        .collect();
+
+                    self.transaction_store
+                        .store_transactions(parsed_transactions)
+                        .await?;
+                }
+                ChainScannedBlocks::RollBackward(block_number) => {
+                    debug!(
+                        self.logger,
+                        "TransactionsImporter rolls back the stored Cardano transactions to block number '{block_number}'",
+                    );
+                    self.transaction_store
+                        .remove_rolled_back_transactions_and_block_range(Some(block_number))
+                        .await?;
+                }
+            }
         }
 
         Ok(())
@@ -175,11 +356,90 @@ impl CardanoTransactionsImporter {
 #[async_trait]
 impl TransactionsImporter for CardanoTransactionsImporter {
     async fn import(&self, up_to_beacon: ImmutableFileNumber) -> StdResult<()> {
+        self.rollback_if_chain_diverged().await?;
         self.import_transactions(up_to_beacon).await?;
+        self.record_verified_chain_point().await?;
         self.import_block_ranges().await
     }
 }
 
+/// A [TransactionsImporter] decorator that prunes the stored transactions after each import.
+pub struct TransactionsImporterWithPruner {
+    number_of_block_ranges_to_keep: u64,
+    transaction_pruner: Arc<dyn TransactionPruner>,
+    wrapped_importer: Arc<dyn TransactionsImporter>,
+    metrics_service: Arc<MetricsService>,
+    logger: Logger,
+}
+
+impl TransactionsImporterWithPruner {
+    /// Constructor
+    pub fn new(
+        number_of_block_ranges_to_keep: u64,
+        transaction_pruner: Arc<dyn TransactionPruner>,
+        wrapped_importer: Arc<dyn TransactionsImporter>,
+        metrics_service: Arc<MetricsService>,
+        logger: Logger,
+    ) -> Self {
+        Self {
+            number_of_block_ranges_to_keep,
+            transaction_pruner,
+            wrapped_importer,
+            metrics_service,
+            logger,
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionsImporter for TransactionsImporterWithPruner {
+    async fn import(&self, up_to_beacon: ImmutableFileNumber) -> StdResult<()> {
+        self.wrapped_importer.import(up_to_beacon).await?;
+
+        let pruned_transactions = self
+            .transaction_pruner
+            .prune(self.number_of_block_ranges_to_keep)
+            .await
+            .with_context(|| "TransactionsImporterWithPruner can not prune transactions")?;
+        self.metrics_service
+            .cardano_transactions_pruned_since_startup_counter_increment_by(
+                pruned_transactions as u64,
+            );
+        debug!(
+            self.logger,
+            "TransactionsImporterWithPruner: pruned {pruned_transactions} transactions";
+            "number_of_block_ranges_to_keep" => self.number_of_block_ranges_to_keep,
+        );
+
+        Ok(())
+    }
+}
+
+/// A [TransactionsImporter] decorator that runs one import at a time, so the importer can be
+/// shared by the state machines of all the aggregators the signer contributes to.
+pub struct TransactionsImporterWithLock {
+    wrapped_importer: Arc<dyn TransactionsImporter>,
+    import_lock: Mutex<()>,
+}
+
+impl TransactionsImporterWithLock {
+    /// Constructor
+    pub fn new(wrapped_importer: Arc<dyn TransactionsImporter>) -> Self {
+        Self {
+            wrapped_importer,
+            import_lock: Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionsImporter for TransactionsImporterWithLock {
+    async fn import(&self, up_to_beacon: ImmutableFileNumber) -> StdResult<()> {
+        let _import_guard = self.import_lock.lock().await;
+        self.wrapped_importer.import(up_to_beacon).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mockall::mock;
@@ -671,4 +931,364 @@ mod tests {
         // If sub overflow it should be 0
         assert_eq!(Some(0), from);
     }
+
+    mod rollback {
+        use super::*;
+
+        /// Scanner that only returns the given blocks that are in the scanned immutable files
+        fn scanner_for_immutable_files(blocks: Vec<ScannedBlock>) -> MockBlockScannerImpl {
+            let mut scanner = MockBlockScannerImpl::new();
+            scanner.expect_scan().returning(move |_, from, until| {
+                let scanned_blocks = blocks
+                    .iter()
+                    .filter(|block| {
+                        from.unwrap_or(0) <= block.immutable_file_number
+                            && block.immutable_file_number <= until
+                    })
+                    .cloned()
+                    .collect();
+                Ok(Box::new(DumbBlockStreamer::new(vec![scanned_blocks])))
+            });
+            scanner
+        }
+
+        async fn repository_with_stored_chain() -> Arc<CardanoTransactionRepository> {
+            let connection = cardano_tx_db_connection().unwrap();
+            let repository = Arc::new(CardanoTransactionRepository::new(Arc::new(connection)));
+            let blocks = vec![
+                ScannedBlock::new("block_hash-10", 10, 100, 1, vec!["tx_hash-10"]),
+                ScannedBlock::new("block_hash-20", 20, 200, 2, vec!["tx_hash-20"]),
+                ScannedBlock::new("block_hash-30", 30, 300, 3, vec!["tx_hash-30"]),
+                ScannedBlock::new("block_hash-40", 40, 400, 4, vec!["tx_hash-40"]),
+            ];
+            repository
+                .store_transactions(into_transactions(&blocks))
+                .await
+                .unwrap();
+            repository
+                .store_block_range_roots(
+                    [0, BlockRange::LENGTH, BlockRange::LENGTH * 2]
+                        .into_iter()
+                        .map(|start| {
+                            (
+                                BlockRange::from_block_number(start),
+                                MKTreeNode::from_hex("AAAA").unwrap(),
+                            )
+                        })
+                        .collect(),
+                )
+                .await
+                .unwrap();
+
+            repository
+        }
+
+        fn stored_block_ranges(repository: &CardanoTransactionRepository) -> Vec<BlockRange> {
+            repository
+                .get_all_block_range_root()
+                .unwrap()
+                .into_iter()
+                .map(|record| record.range)
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn nothing_is_rolled_back_if_the_stored_chain_is_in_the_immutable_files() {
+            let repository = repository_with_stored_chain().await;
+            let transactions_before_rollback = repository.get_all().await.unwrap();
+            let block_ranges_before_rollback = stored_block_ranges(&repository);
+            let scanner = scanner_for_immutable_files(vec![
+                ScannedBlock::new("block_hash-10", 10, 100, 1, vec!["tx_hash-10"]),
+                ScannedBlock::new("block_hash-20", 20, 200, 2, vec!["tx_hash-20"]),
+                ScannedBlock::new("block_hash-30", 30, 300, 3, vec!["tx_hash-30"]),
+                ScannedBlock::new("block_hash-40", 40, 400, 4, vec!["tx_hash-40"]),
+                ScannedBlock::new("block_hash-50", 50, 500, 5, vec!["tx_hash-50"]),
+            ]);
+            let importer =
+                CardanoTransactionsImporter::new_for_test(Arc::new(scanner), repository.clone());
+
+            importer
+                .rollback_if_chain_diverged()
+                .await
+                .expect("Rollback check should not fail");
+
+            assert_eq!(
+                transactions_before_rollback,
+                repository.get_all().await.unwrap()
+            );
+            assert_eq!(
+                block_ranges_before_rollback,
+                stored_block_ranges(&repository)
+            );
+        }
+
+        #[tokio::test]
+        async fn transactions_and_block_ranges_after_the_fork_point_are_rolled_back() {
+            let repository = repository_with_stored_chain().await;
+            // The immutable file 3 was replaced and the immutable file 4 removed
+            let scanner = scanner_for_immutable_files(vec![
+                ScannedBlock::new("block_hash-10", 10, 100, 1, vec!["tx_hash-10"]),
+                ScannedBlock::new("block_hash-20", 20, 200, 2, vec!["tx_hash-20"]),
+                ScannedBlock::new("block_hash-30-fork", 30, 305, 3, vec!["tx_hash-30-fork"]),
+            ]);
+            let importer =
+                CardanoTransactionsImporter::new_for_test(Arc::new(scanner), repository.clone());
+
+            importer
+                .rollback_if_chain_diverged()
+                .await
+                .expect("Rollback check should not fail");
+
+            assert_eq!(
+                vec![
+                    CardanoTransaction::new("tx_hash-10", 10, 100, "block_hash-10", 1),
+                    CardanoTransaction::new("tx_hash-20", 20, 200, "block_hash-20", 2),
+                ],
+                repository.get_all().await.unwrap()
+            );
+            assert_eq!(
+                vec![BlockRange::from_block_number(0)],
+                stored_block_ranges(&repository)
+            );
+        }
+
+        #[tokio::test]
+        async fn everything_is_rolled_back_if_no_stored_block_is_in_the_immutable_files() {
+            let repository = repository_with_stored_chain().await;
+            let scanner = scanner_for_immutable_files(vec![ScannedBlock::new(
+                "other_block_hash-10",
+                10,
+                100,
+                1,
+                vec!["tx_hash-10"],
+            )]);
+            let importer =
+                CardanoTransactionsImporter::new_for_test(Arc::new(scanner), repository.clone());
+
+            importer
+                .rollback_if_chain_diverged()
+                .await
+                .expect("Rollback check should not fail");
+
+            assert_eq!(
+                Vec::<CardanoTransaction>::new(),
+                repository.get_all().await.unwrap()
+            );
+            assert_eq!(Vec::<BlockRange>::new(), stored_block_ranges(&repository));
+        }
+
+        #[tokio::test]
+        async fn divergence_is_checked_again_only_if_the_highest_stored_block_changed() {
+            let repository = repository_with_stored_chain().await;
+            let highest_stored_block =
+                ScannedBlock::new("block_hash-40", 40, 400, 4, vec!["tx_hash-40"]);
+            let scanner = {
+                let mut scanner = MockBlockScannerImpl::new();
+                // Only the check of the first import scans the highest stored immutable file
+                scanner
+                    .expect_scan()
+                    .withf(|_, from, until| from == &Some(4) && until == &4)
+                    .returning(move |_, _, _| {
+                        Ok(Box::new(DumbBlockStreamer::new(vec![vec![
+                            highest_stored_block.clone(),
+                        ]])))
+                    })
+                    .times(1);
+                scanner
+            };
+            let importer =
+                CardanoTransactionsImporter::new_for_test(Arc::new(scanner), repository.clone());
+
+            importer.import(4).await.expect("Import should not fail");
+            importer.import(4).await.expect("Import should not fail");
+        }
+
+        #[tokio::test]
+        async fn import_reimport_the_forked_chain_after_rolling_back() {
+            let repository = repository_with_stored_chain().await;
+            let blocks = vec![
+                ScannedBlock::new("block_hash-10", 10, 100, 1, vec!["tx_hash-10"]),
+                ScannedBlock::new("block_hash-20", 20, 200, 2, vec!["tx_hash-20"]),
+                ScannedBlock::new("block_hash-30-fork", 30, 305, 3, vec!["tx_hash-30-fork"]),
+            ];
+            let importer = CardanoTransactionsImporter::new_for_test(
+                Arc::new(scanner_for_immutable_files(blocks.clone())),
+                repository.clone(),
+            );
+
+            importer.import(4).await.expect("Import should not fail");
+
+            assert_eq!(
+                into_transactions(&blocks),
+                repository.get_all().await.unwrap()
+            );
+            assert_eq!(
+                vec![
+                    BlockRange::from_block_number(0),
+                    BlockRange::from_block_number(BlockRange::LENGTH)
+                ],
+                stored_block_ranges(&repository)
+            );
+        }
+
+        #[tokio::test]
+        async fn rollback_sent_by_the_block_streamer_remove_the_rolled_back_transactions() {
+            let connection = cardano_tx_db_connection().unwrap();
+            let repository = Arc::new(CardanoTransactionRepository::new(Arc::new(connection)));
+            let blocks = vec![
+                ScannedBlock::new("block_hash-10", 10, 100, 1, vec!["tx_hash-10"]),
+                ScannedBlock::new("block_hash-20", 20, 200, 1, vec!["tx_hash-20"]),
+                ScannedBlock::new("block_hash-30", 30, 300, 1, vec!["tx_hash-30"]),
+            ];
+            let forked_blocks = vec![ScannedBlock::new(
+                "block_hash-20-fork",
+                20,
+                205,
+                1,
+                vec!["tx_hash-20-fork"],
+            )];
+            let scanner = {
+                let blocks = blocks.clone();
+                let forked_blocks = forked_blocks.clone();
+                let mut scanner = MockBlockScannerImpl::new();
+                scanner.expect_scan().return_once(move |_, _, _| {
+                    Ok(Box::new(
+                        DumbBlockStreamer::new(vec![blocks])
+                            .rollback(10)
+                            .forwards(forked_blocks),
+                    ))
+                });
+                scanner
+            };
+            let importer =
+                CardanoTransactionsImporter::new_for_test(Arc::new(scanner), repository.clone());
+
+            importer
+                .parse_and_store_transactions_not_imported_yet(None, 1)
+                .await
+                .expect("Transactions Importer should succeed");
+
+            assert_eq!(
+                into_transactions(&[blocks[0].clone(), forked_blocks[0].clone()]),
+                repository.get_all().await.unwrap()
+            );
+        }
+    }
+
+    mod transactions_importer_with_lock {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        use super::*;
+
+        #[derive(Default)]
+        struct ConcurrentImportsRecorder {
+            running_imports: AtomicUsize,
+            max_running_imports: AtomicUsize,
+        }
+
+        #[async_trait]
+        impl TransactionsImporter for ConcurrentImportsRecorder {
+            async fn import(&self, _up_to_beacon: ImmutableFileNumber) -> StdResult<()> {
+                let running_imports = self.running_imports.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max_running_imports
+                    .fetch_max(running_imports, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                self.running_imports.fetch_sub(1, Ordering::SeqCst);
+                Ok(())
+            }
+        }
+
+        #[tokio::test]
+        async fn run_one_import_at_a_time() {
+            let recorder = Arc::new(ConcurrentImportsRecorder::default());
+            let importer = TransactionsImporterWithLock::new(recorder.clone());
+
+            let (first, second, third) = tokio::join!(
+                importer.import(12),
+                importer.import(12),
+                importer.import(13)
+            );
+            first.unwrap();
+            second.unwrap();
+            third.unwrap();
+
+            assert_eq!(1, recorder.max_running_imports.load(Ordering::SeqCst));
+        }
+    }
+
+    mod transactions_importer_with_pruner {
+        use mockall::predicate::eq;
+
+        use super::*;
+
+        mock! {
+            pub TransactionImporterImpl { }
+
+            #[async_trait]
+            impl TransactionsImporter for TransactionImporterImpl {
+                async fn import(&self, up_to_beacon: ImmutableFileNumber) -> StdResult<()>;
+            }
+        }
+
+        fn importer_with_pruner(
+            number_of_block_ranges_to_keep: u64,
+            pruner_mock_config: impl FnOnce(&mut MockTransactionPruner),
+            metrics_service: Arc<MetricsService>,
+        ) -> TransactionsImporterWithPruner {
+            let mut transaction_pruner = MockTransactionPruner::new();
+            pruner_mock_config(&mut transaction_pruner);
+            let mut wrapped_importer = MockTransactionImporterImpl::new();
+            wrapped_importer
+                .expect_import()
+                .with(eq(12))
+                .returning(|_| Ok(()))
+                .once();
+
+            TransactionsImporterWithPruner::new(
+                number_of_block_ranges_to_keep,
+                Arc::new(transaction_pruner),
+                Arc::new(wrapped_importer),
+                metrics_service,
+                crate::test_tools::logger_for_tests(),
+            )
+        }
+
+        #[tokio::test]
+        async fn prune_after_import_and_record_the_number_of_pruned_transactions() {
+            let metrics_service = Arc::new(MetricsService::new().unwrap());
+            let importer = importer_with_pruner(
+                5,
+                |mock| {
+                    mock.expect_prune().with(eq(5)).returning(|_| Ok(42)).once();
+                },
+                metrics_service.clone(),
+            );
+
+            importer.import(12).await.expect("Import should not fail");
+
+            assert_eq!(
+                42,
+                metrics_service.cardano_transactions_pruned_since_startup_counter_get()
+            );
+        }
+
+        #[tokio::test]
+        async fn fail_if_pruning_fail() {
+            let importer = importer_with_pruner(
+                5,
+                |mock| {
+                    mock.expect_prune()
+                        .returning(|_| Err(anyhow::anyhow!("pruning error")))
+                        .once();
+                },
+                Arc::new(MetricsService::new().unwrap()),
+            );
+
+            importer
+                .import(12)
+                .await
+                .expect_err("Import should fail when pruning fail");
+        }
+    }
 }
@@ -0,0 +1,213 @@
+use anyhow::{anyhow, Context};
+use clap::{Parser, Subcommand};
+use slog_scope::debug;
+
+use mithril_common::entities::Epoch;
+use mithril_common::messages::TryToMessageAdapter;
+use mithril_common::StdResult;
+
+use crate::diagnostic::{self, DiagnosticReport};
+use crate::{
+    Configuration, ProductionServiceBuilder, ProtocolInitializerStore,
+    ToRegisterSignerMessageAdapter,
+};
+
+/// Diagnostic commands to troubleshoot the signer configuration without reading its logs
+#[derive(Parser, Debug, Clone)]
+pub struct DiagnosticCommand {
+    /// commands
+    #[clap(subcommand)]
+    pub diagnostic_subcommand: DiagnosticSubCommand,
+}
+
+impl DiagnosticCommand {
+    /// Execute the diagnostic command with the given configuration.
+    pub async fn execute(&self, config: Configuration) -> StdResult<()> {
+        self.diagnostic_subcommand.execute(config).await
+    }
+}
+
+/// Diagnostic subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum DiagnosticSubCommand {
+    /// Inspect the protocol initializer stored for an epoch: verification key, stake and party id.
+    ProtocolInitializer(ProtocolInitializerDiagnosticCommand),
+
+    /// Check that the KES secret key and the operational certificate match the pool and the
+    /// current KES period.
+    Kes(KesDiagnosticCommand),
+
+    /// Compute the signer registration message sent to the aggregator from the stored protocol
+    /// initializer.
+    RegistrationMessage(RegistrationMessageDiagnosticCommand),
+
+    /// Check the Cardano database directory and the immutables digests cache.
+    Database(DatabaseDiagnosticCommand),
+}
+
+impl DiagnosticSubCommand {
+    /// Execute the diagnostic subcommand with the given configuration.
+    pub async fn execute(&self, config: Configuration) -> StdResult<()> {
+        debug!("DIAGNOSTIC command"; "config" => format!("{config:?}"));
+        match self {
+            Self::ProtocolInitializer(cmd) => cmd.execute(config).await,
+            Self::Kes(cmd) => cmd.execute(config).await,
+            Self::RegistrationMessage(cmd) => cmd.execute(config).await,
+            Self::Database(cmd) => cmd.execute(config).await,
+        }
+    }
+}
+
+/// Protocol initializer diagnostic command.
+#[derive(Parser, Debug, Clone)]
+pub struct ProtocolInitializerDiagnosticCommand {
+    /// Epoch at which the protocol initializer is recorded, ie. the epoch of the registration
+    /// offset to the recording epoch.
+    #[clap(long)]
+    epoch: u64,
+
+    /// Endpoint of the aggregator the protocol initializer was registered to, default to the
+    /// main aggregator.
+    #[clap(long)]
+    aggregator_endpoint: Option<String>,
+}
+
+impl ProtocolInitializerDiagnosticCommand {
+    /// Execute the protocol initializer diagnostic command.
+    pub async fn execute(&self, config: Configuration) -> StdResult<()> {
+        let builder = ProductionServiceBuilder::new(&config);
+        let aggregator_endpoint = self
+            .aggregator_endpoint
+            .as_ref()
+            .unwrap_or(&config.aggregator_endpoint);
+        let protocol_initializer_store =
+            open_protocol_initializer_store(&builder, &config, aggregator_endpoint)?;
+        let inspection = diagnostic::inspect_protocol_initializer(
+            &protocol_initializer_store,
+            builder.compute_protocol_party_id()?,
+            Epoch(self.epoch),
+        )
+        .await?;
+
+        println!("{}", serde_json::to_string_pretty(&inspection)?);
+
+        Ok(())
+    }
+}
+
+/// Open the protocol initializers store of the given aggregator without modifying it.
+fn open_protocol_initializer_store(
+    builder: &ProductionServiceBuilder,
+    config: &Configuration,
+    aggregator_endpoint: &str,
+) -> StdResult<ProtocolInitializerStore> {
+    let sqlite_file_name = builder.get_aggregator_sqlite_file_name(aggregator_endpoint);
+    let connection = builder
+        .build_read_only_sqlite_connection(&sqlite_file_name)
+        .with_context(|| {
+            format!("No signer stores found for aggregator `{aggregator_endpoint}`")
+        })?;
+
+    Ok(ProtocolInitializerStore::new(
+        Box::new(
+            builder.build_protocol_initializer_adapter(connection, config.build_store_cipher()?)?,
+        ),
+        None,
+    ))
+}
+
+/// KES diagnostic command.
+#[derive(Parser, Debug, Clone)]
+pub struct KesDiagnosticCommand {}
+
+impl KesDiagnosticCommand {
+    /// Execute the KES diagnostic command.
+    pub async fn execute(&self, config: Configuration) -> StdResult<()> {
+        let chain_observer = ProductionServiceBuilder::new(&config).build_chain_observer()?;
+        let report = diagnostic::check_kes(&config, chain_observer.as_ref()).await;
+
+        print_report(report)
+    }
+}
+
+/// Registration message diagnostic command.
+#[derive(Parser, Debug, Clone)]
+pub struct RegistrationMessageDiagnosticCommand {
+    /// Epoch at which the signer registered, default to the current epoch of the Cardano node.
+    #[clap(long)]
+    epoch: Option<u64>,
+
+    /// Endpoint of the aggregator to compute the registration message for, default to the main
+    /// aggregator.
+    #[clap(long)]
+    aggregator_endpoint: Option<String>,
+}
+
+impl RegistrationMessageDiagnosticCommand {
+    /// Execute the registration message diagnostic command.
+    pub async fn execute(&self, config: Configuration) -> StdResult<()> {
+        let builder = ProductionServiceBuilder::new(&config);
+        let aggregator_endpoint = self
+            .aggregator_endpoint
+            .as_ref()
+            .unwrap_or(&config.aggregator_endpoint);
+        if !config
+            .get_aggregator_endpoints()
+            .contains(aggregator_endpoint)
+        {
+            return Err(anyhow!(
+                "`{aggregator_endpoint}` is not one of the configured aggregator endpoints"
+            ));
+        }
+        let chain_observer = builder.build_chain_observer()?;
+        let epoch = match self.epoch {
+            Some(epoch) => Epoch(epoch),
+            None => chain_observer
+                .get_current_epoch()
+                .await?
+                .ok_or_else(|| anyhow!("The Cardano node returned no current epoch"))?,
+        };
+        // The protocol initializer is stored for the epoch at which the registration is recorded
+        let recording_epoch = epoch.offset_to_recording_epoch();
+        let protocol_initializer_store =
+            open_protocol_initializer_store(&builder, &config, aggregator_endpoint)?;
+        let signer = diagnostic::compute_stored_signer_registration(
+            &config,
+            &protocol_initializer_store,
+            chain_observer.as_ref(),
+            builder.compute_protocol_party_id()?,
+            recording_epoch,
+        )
+        .await?;
+        let message = ToRegisterSignerMessageAdapter::try_adapt((recording_epoch, signer))?;
+
+        println!("{}", serde_json::to_string_pretty(&message)?);
+
+        Ok(())
+    }
+}
+
+/// Database diagnostic command.
+#[derive(Parser, Debug, Clone)]
+pub struct DatabaseDiagnosticCommand {}
+
+impl DatabaseDiagnosticCommand {
+    /// Execute the database diagnostic command.
+    pub async fn execute(&self, config: Configuration) -> StdResult<()> {
+        let digests_cache_file_name =
+            ProductionServiceBuilder::new(&config).get_digests_cache_file_name();
+        let report = diagnostic::check_database(&config, &digests_cache_file_name).await;
+
+        print_report(report)
+    }
+}
+
+fn print_report(report: DiagnosticReport) -> StdResult<()> {
+    print!("{report}");
+
+    if report.is_success() {
+        Ok(())
+    } else {
+        Err(anyhow!("Some diagnostic checks failed"))
+    }
+}
@@ -0,0 +1,5 @@
+mod diagnostic_command;
+mod rekey_store_command;
+
+pub use diagnostic_command::DiagnosticCommand;
+pub use rekey_store_command::RekeyStoreCommand;
@@ -0,0 +1,79 @@
+use anyhow::{anyhow, Context};
+use clap::{ArgGroup, Parser};
+use slog_scope::debug;
+use std::{path::PathBuf, sync::Arc};
+
+use mithril_common::StdResult;
+
+use crate::{Configuration, ProductionServiceBuilder, StoreCipher, StoreEncryptionPassphrase};
+
+/// Re-encrypt the protocol initializers stores with a new passphrase or key file.
+///
+/// The stores are decrypted with the `store_encryption_passphrase` or `store_encryption_key_path`
+/// of the configuration, or read in plain if none of them is set.
+#[derive(Parser, Debug, Clone)]
+#[clap(group(
+    ArgGroup::new("new_secret")
+        .required(true)
+        .args(["new_passphrase", "new_key_path"]),
+))]
+pub struct RekeyStoreCommand {
+    /// New passphrase from which the key encrypting the stores is derived.
+    #[clap(long, env = "NEW_STORE_ENCRYPTION_PASSPHRASE", hide_env_values = true)]
+    new_passphrase: Option<StoreEncryptionPassphrase>,
+
+    /// File path to the new key file from which the key encrypting the stores is derived.
+    #[clap(long)]
+    new_key_path: Option<PathBuf>,
+}
+
+impl RekeyStoreCommand {
+    /// Execute the re-key store command.
+    pub async fn execute(&self, config: Configuration) -> StdResult<()> {
+        debug!("REKEY STORE command"; "config" => format!("{config:?}"));
+        let current_cipher = config.build_store_cipher()?;
+        let new_cipher = match (&self.new_passphrase, &self.new_key_path) {
+            (Some(passphrase), None) => StoreCipher::from_passphrase(passphrase)?,
+            (None, Some(key_path)) => StoreCipher::from_key_file(key_path)?,
+            _ => {
+                return Err(anyhow!(
+                    "Exactly one of `--new-passphrase` and `--new-key-path` must be set"
+                ))
+            }
+        };
+        let new_cipher = Arc::new(new_cipher);
+
+        let builder = ProductionServiceBuilder::new(&config);
+        for aggregator_endpoint in config.get_aggregator_endpoints() {
+            let sqlite_file_name = builder.get_aggregator_sqlite_file_name(&aggregator_endpoint);
+            let sqlite_file_path = config.data_stores_directory.join(&sqlite_file_name);
+            if !sqlite_file_path.exists() {
+                println!("No signer stores found for aggregator `{aggregator_endpoint}`, skipped");
+                continue;
+            }
+
+            let connection = builder
+                .build_sqlite_connection(
+                    &sqlite_file_name,
+                    crate::database::migration::get_migrations(),
+                )
+                .await?;
+            let mut adapter =
+                builder.build_protocol_initializer_adapter(connection, current_cipher.clone())?;
+            let nb_records = adapter
+                .rekey(Some(new_cipher.clone()))
+                .await
+                .with_context(|| {
+                    format!("Could not re-key the protocol initializers store of aggregator `{aggregator_endpoint}`")
+                })?;
+
+            println!(
+                "Re-encrypted {nb_records} protocol initializers for aggregator `{aggregator_endpoint}`"
+            );
+        }
+
+        println!("The signer configuration must be updated with the new passphrase or key file before restarting the signer.");
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use config::{ConfigError, Map, Source, Value, ValueKind};
 use mithril_doc::{Documenter, DocumenterDefault, StructDoc};
 use serde::{Deserialize, Serialize};
@@ -15,6 +15,8 @@ use mithril_common::{
     CardanoNetwork, StdResult,
 };
 
+use crate::{StoreCipher, StoreEncryptionPassphrase};
+
 /// Client configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Documenter)]
 pub struct Configuration {
@@ -40,7 +42,12 @@ pub struct Configuration {
     #[example = "`https://aggregator.pre-release-preview.api.mithril.network/aggregator`"]
     pub aggregator_endpoint: String,
 
-    /// Relay endpoint
+    /// Comma separated list of the endpoints of other aggregators of the same network to which
+    /// the signer also registers and sends its single signatures.
+    #[example = "`https://aggregator-a.example.com/aggregator,https://aggregator-b.example.com/aggregator`"]
+    pub additional_aggregator_endpoints: Option<String>,
+
+    /// Relay endpoint, used only to reach the main aggregator
     pub relay_endpoint: Option<String>,
 
     /// Party Id
@@ -68,6 +75,17 @@ pub struct Configuration {
     /// File path to the operational certificate of the pool
     pub operational_certificate_path: Option<PathBuf>,
 
+    /// Passphrase from which the key encrypting the protocol initializers stores is derived.
+    ///
+    /// If neither `store_encryption_passphrase` nor `store_encryption_key_path` is set, the
+    /// protocol initializers are stored unencrypted.
+    pub store_encryption_passphrase: Option<StoreEncryptionPassphrase>,
+
+    /// File path to a key file from which the key encrypting the protocol initializers stores is
+    /// derived.
+    #[example = "`./mithril-signer/store.key`"]
+    pub store_encryption_key_path: Option<PathBuf>,
+
     /// Disable immutables digests cache.
     pub disable_digests_cache: bool,
 
@@ -95,6 +113,22 @@ pub struct Configuration {
     ///
     /// Will be ignored on (pre)production networks.
     pub allow_unparsable_block: bool,
+
+    /// If set, the Cardano transactions that are not needed anymore to compute the
+    /// Merkle roots are pruned from the database after each import.
+    pub enable_transaction_pruning: bool,
+
+    /// Number of block ranges, ending with the last one whose Merkle root has been computed,
+    /// for which the Cardano transactions are kept when pruning.
+    pub transactions_pruning_retained_block_ranges: u64,
+
+    /// If set, the Cardano transactions are imported by following the chain of the Cardano node
+    /// with the chain-sync mini-protocol up to the last completed immutable file, instead of
+    /// reading the immutable files.
+    ///
+    /// Experimental: all the nodes of a Mithril network must use the same block scanner,
+    /// otherwise they won't compute the same Merkle roots.
+    pub enable_chain_sync_block_scanner: bool,
 }
 
 impl Configuration {
@@ -104,6 +138,7 @@ impl Configuration {
         let signer_temp_dir = tests_setup::setup_temp_directory_for_signer(party_id, false);
         Self {
             aggregator_endpoint: "http://0.0.0.0:8000".to_string(),
+            additional_aggregator_endpoints: None,
             relay_endpoint: None,
             cardano_cli_path: PathBuf::new(),
             cardano_node_socket_path: PathBuf::new(),
@@ -118,6 +153,8 @@ impl Configuration {
             operational_certificate_path: signer_temp_dir
                 .as_ref()
                 .map(|dir| dir.join("opcert.cert")),
+            store_encryption_passphrase: None,
+            store_encryption_key_path: None,
             disable_digests_cache: false,
             reset_digests_cache: false,
             era_reader_adapter_type: EraReaderAdapterType::Bootstrap,
@@ -126,6 +163,9 @@ impl Configuration {
             metrics_server_ip: "0.0.0.0".to_string(),
             metrics_server_port: 9090,
             allow_unparsable_block: false,
+            enable_transaction_pruning: false,
+            transactions_pruning_retained_block_ranges: 150,
+            enable_chain_sync_block_scanner: false,
         }
     }
 
@@ -139,6 +179,58 @@ impl Configuration {
         })
     }
 
+    /// Check that at least one block range is retained when pruning the Cardano transactions,
+    /// pruning all of them would prevent computing the Merkle root of the next block range.
+    pub fn check_transactions_pruning_retained_block_ranges(&self) -> StdResult<()> {
+        if self.transactions_pruning_retained_block_ranges < 1 {
+            return Err(anyhow!(
+                "transactions_pruning_retained_block_ranges must be at least 1, got {}",
+                self.transactions_pruning_retained_block_ranges
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Return the endpoints of all the aggregators the signer contributes to, starting with the
+    /// main `aggregator_endpoint`, without duplicates.
+    pub fn get_aggregator_endpoints(&self) -> Vec<String> {
+        let mut endpoints = vec![self.aggregator_endpoint.clone()];
+        let additional_endpoints = self
+            .additional_aggregator_endpoints
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|endpoint| !endpoint.is_empty());
+
+        for endpoint in additional_endpoints {
+            if !endpoints.iter().any(|e| e == endpoint) {
+                endpoints.push(endpoint.to_string());
+            }
+        }
+
+        endpoints
+    }
+
+    /// Build the cipher encrypting the protocol initializers stores, if a passphrase or a key
+    /// file is configured.
+    pub fn build_store_cipher(&self) -> StdResult<Option<Arc<StoreCipher>>> {
+        match (
+            &self.store_encryption_passphrase,
+            &self.store_encryption_key_path,
+        ) {
+            (Some(_), Some(_)) => Err(anyhow!(
+                "Only one of `store_encryption_passphrase` and `store_encryption_key_path` can be set"
+            )),
+            (Some(passphrase), None) => Ok(Some(Arc::new(StoreCipher::from_passphrase(
+                passphrase,
+            )?))),
+            (None, Some(key_path)) => Ok(Some(Arc::new(StoreCipher::from_key_file(key_path)?))),
+            (None, None) => Ok(None),
+        }
+    }
+
     /// Create the SQL store directory if not exist and return the path of the
     /// SQLite3 file.
     pub fn get_sqlite_file(&self, sqlite_file_name: &str) -> StdResult<PathBuf> {
@@ -186,6 +278,15 @@ pub struct DefaultConfiguration {
 
     /// Metrics HTTP server listening port.
     pub metrics_server_port: u16,
+
+    /// Cardano transactions pruning enabled
+    pub enable_transaction_pruning: bool,
+
+    /// Number of block ranges for which the Cardano transactions are kept when pruning
+    pub transactions_pruning_retained_block_ranges: u64,
+
+    /// Chain-sync block scanner enabled
+    pub enable_chain_sync_block_scanner: bool,
 }
 
 impl Default for DefaultConfiguration {
@@ -194,6 +295,9 @@ impl Default for DefaultConfiguration {
             era_reader_adapter_type: "bootstrap".to_string(),
             metrics_server_ip: "0.0.0.0".to_string(),
             metrics_server_port: 9090,
+            enable_transaction_pruning: true,
+            transactions_pruning_retained_block_ranges: 150,
+            enable_chain_sync_block_scanner: false,
         }
     }
 }
@@ -229,6 +333,133 @@ impl Source for DefaultConfiguration {
             ),
         );
 
+        result.insert(
+            "enable_transaction_pruning".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.enable_transaction_pruning),
+            ),
+        );
+
+        result.insert(
+            "transactions_pruning_retained_block_ranges".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.transactions_pruning_retained_block_ranges),
+            ),
+        );
+
+        result.insert(
+            "enable_chain_sync_block_scanner".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.enable_chain_sync_block_scanner),
+            ),
+        );
+
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_aggregator_endpoints_without_additional_endpoints() {
+        let config = Configuration {
+            aggregator_endpoint: "http://aggregator-a".to_string(),
+            additional_aggregator_endpoints: None,
+            ..Configuration::new_sample(&"party".to_string())
+        };
+
+        assert_eq!(
+            vec!["http://aggregator-a".to_string()],
+            config.get_aggregator_endpoints()
+        );
+    }
+
+    #[test]
+    fn get_aggregator_endpoints_with_additional_endpoints() {
+        let config = Configuration {
+            aggregator_endpoint: "http://aggregator-a".to_string(),
+            additional_aggregator_endpoints: Some(
+                " http://aggregator-b ,,http://aggregator-a,http://aggregator-c,http://aggregator-b"
+                    .to_string(),
+            ),
+            ..Configuration::new_sample(&"party".to_string())
+        };
+
+        assert_eq!(
+            vec![
+                "http://aggregator-a".to_string(),
+                "http://aggregator-b".to_string(),
+                "http://aggregator-c".to_string()
+            ],
+            config.get_aggregator_endpoints()
+        );
+    }
+
+    #[test]
+    fn build_store_cipher_without_secret() {
+        let config = Configuration {
+            store_encryption_passphrase: None,
+            store_encryption_key_path: None,
+            ..Configuration::new_sample(&"party".to_string())
+        };
+
+        assert!(config.build_store_cipher().unwrap().is_none());
+    }
+
+    #[test]
+    fn build_store_cipher_with_passphrase() {
+        let config = Configuration {
+            store_encryption_passphrase: Some(StoreEncryptionPassphrase::from(
+                "passphrase".to_string(),
+            )),
+            store_encryption_key_path: None,
+            ..Configuration::new_sample(&"party".to_string())
+        };
+
+        assert!(config.build_store_cipher().unwrap().is_some());
+    }
+
+    #[test]
+    fn build_store_cipher_with_both_passphrase_and_key_file_fails() {
+        let config = Configuration {
+            store_encryption_passphrase: Some(StoreEncryptionPassphrase::from(
+                "passphrase".to_string(),
+            )),
+            store_encryption_key_path: Some(PathBuf::from("store.key")),
+            ..Configuration::new_sample(&"party".to_string())
+        };
+
+        assert!(config.build_store_cipher().is_err());
+    }
+
+    #[test]
+    fn check_transactions_pruning_retained_block_ranges_rejects_zero() {
+        let config = Configuration {
+            transactions_pruning_retained_block_ranges: 0,
+            ..Configuration::new_sample(&"party".to_string())
+        };
+
+        config
+            .check_transactions_pruning_retained_block_ranges()
+            .expect_err("Retaining no block range should be rejected");
+    }
+
+    #[test]
+    fn check_transactions_pruning_retained_block_ranges_accepts_one_or_more() {
+        for retained_block_ranges in [1, 150] {
+            let config = Configuration {
+                transactions_pruning_retained_block_ranges: retained_block_ranges,
+                ..Configuration::new_sample(&"party".to_string())
+            };
+
+            config
+                .check_transactions_pruning_retained_block_ranges()
+                .unwrap();
+        }
+    }
+}
@@ -23,5 +23,23 @@ drop table db_version;
 alter table new_db_version rename to db_version;
             ",
         ),
+        // Migration 2
+        // Add the `pending_single_signature` table to persist the single signatures that could
+        // not be registered to the aggregator yet.
+        SqlMigration::new(
+            2,
+            r"
+create table pending_single_signature (
+    signed_entity_type_id integer   not null,
+    beacon                text      not null,
+    single_signatures     text      not null,
+    retry_count           integer   not null,
+    next_retry_at         text      not null,
+    expires_at            text,
+    created_at            text      not null,
+    primary key (signed_entity_type_id, beacon)
+);
+            ",
+        ),
     ]
 }
@@ -23,4 +23,12 @@ pub mod test_utils {
             .build()?;
         Ok(connection)
     }
+
+    pub fn main_db_connection() -> StdResult<ConnectionThreadSafe> {
+        let connection = ConnectionBuilder::open_memory()
+            .with_options(&[ConnectionOptions::ForceDisableForeignKeys])
+            .with_migrations(migration::get_migrations())
+            .build()?;
+        Ok(connection)
+    }
 }
@@ -0,0 +1,137 @@
+use sqlite::Value;
+
+use mithril_common::entities::BlockNumber;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    EntityCursor, Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::BlockRangeRootRecord;
+
+/// Query to delete [BlockRangeRootRecord] from the sqlite database
+pub struct DeleteBlockRangeRootProvider<'conn> {
+    connection: &'conn SqliteConnection,
+}
+
+impl<'conn> Provider<'conn> for DeleteBlockRangeRootProvider<'conn> {
+    type Entity = BlockRangeRootRecord;
+
+    fn get_connection(&'conn self) -> &'conn SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        // it is important to alias the fields with the same name as the table
+        // since the table cannot be aliased in a RETURNING statement in SQLite.
+        let projection = Self::Entity::get_projection().expand(SourceAlias::new(&[(
+            "{:block_range_root:}",
+            "block_range_root",
+        )]));
+
+        format!("delete from block_range_root where {condition} returning {projection}")
+    }
+}
+
+impl<'conn> DeleteBlockRangeRootProvider<'conn> {
+    /// Create a new instance
+    pub fn new(connection: &'conn SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Create the SQL condition to select the block ranges that contain blocks after the given
+    /// block number, or all the block ranges if no block number is given.
+    fn get_rollback_condition(&self, block_number: Option<BlockNumber>) -> WhereCondition {
+        match block_number {
+            // A block range contains blocks after the given block number if its excluded end is
+            // strictly above the next block number.
+            Some(block_number) => {
+                WhereCondition::new("end > ?*", vec![Value::Integer(block_number as i64 + 1)])
+            }
+            None => WhereCondition::default(),
+        }
+    }
+
+    /// Remove the block ranges that contain blocks after the given block number, or all the
+    /// block ranges if no block number is given.
+    pub fn rollback_to_block_number(
+        &self,
+        block_number: Option<BlockNumber>,
+    ) -> StdResult<EntityCursor<'_, BlockRangeRootRecord>> {
+        let filters = self.get_rollback_condition(block_number);
+
+        self.find(filters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::crypto_helper::MKTreeNode;
+    use mithril_common::entities::BlockRange;
+    use mithril_persistence::sqlite::GetAllProvider;
+
+    use crate::database::provider::{GetBlockRangeRootProvider, InsertBlockRangeRootProvider};
+    use crate::database::test_utils::cardano_tx_db_connection;
+
+    use super::*;
+
+    fn insert_block_range_roots(connection: &SqliteConnection, records: Vec<BlockRangeRootRecord>) {
+        let provider = InsertBlockRangeRootProvider::new(connection);
+        let condition = provider.get_insert_many_condition(records).unwrap();
+        provider.find(condition).unwrap().count();
+    }
+
+    fn block_range_root_dataset() -> Vec<BlockRangeRootRecord> {
+        [
+            BlockRange::from_block_number(0),
+            BlockRange::from_block_number(BlockRange::LENGTH),
+            BlockRange::from_block_number(BlockRange::LENGTH * 2),
+        ]
+        .into_iter()
+        .map(|range| BlockRangeRootRecord {
+            range,
+            merkle_root: MKTreeNode::from_hex("AAAA").unwrap(),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_rollback_without_block_number_remove_all_block_ranges() {
+        let connection = cardano_tx_db_connection().unwrap();
+        insert_block_range_roots(&connection, block_range_root_dataset());
+
+        let provider = DeleteBlockRangeRootProvider::new(&connection);
+        let cursor = provider.rollback_to_block_number(None).unwrap();
+        assert_eq!(3, cursor.count());
+
+        let provider = GetBlockRangeRootProvider::new(&connection);
+        assert_eq!(0, provider.get_all().unwrap().count());
+    }
+
+    #[test]
+    fn test_rollback_remove_block_ranges_that_contain_blocks_after_given_block_number() {
+        let dataset = block_range_root_dataset();
+
+        for (block_number, expected_remaining) in [
+            (BlockRange::LENGTH - 2, &dataset[0..0]),
+            (BlockRange::LENGTH - 1, &dataset[0..1]),
+            (BlockRange::LENGTH * 2 - 1, &dataset[0..2]),
+            (BlockRange::LENGTH * 3, &dataset[0..3]),
+        ] {
+            let connection = cardano_tx_db_connection().unwrap();
+            insert_block_range_roots(&connection, dataset.clone());
+
+            let provider = DeleteBlockRangeRootProvider::new(&connection);
+            provider
+                .rollback_to_block_number(Some(block_number))
+                .unwrap()
+                .count();
+
+            let provider = GetBlockRangeRootProvider::new(&connection);
+            assert_eq!(
+                expected_remaining.to_vec(),
+                provider.get_all().unwrap().collect::<Vec<_>>(),
+                "Rollback to block number {block_number}"
+            );
+        }
+    }
+}
@@ -1,7 +1,9 @@
+mod delete_block_range_root;
 mod get_block_range_root;
 mod get_interval_without_block_range_provider;
 mod insert_block_range;
 
+pub use delete_block_range_root::*;
 pub use get_block_range_root::*;
 pub use get_interval_without_block_range_provider::*;
 pub use insert_block_range::*;
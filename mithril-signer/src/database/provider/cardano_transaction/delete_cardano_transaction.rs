@@ -0,0 +1,188 @@
+use sqlite::Value;
+
+use mithril_common::entities::BlockNumber;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    EntityCursor, Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::CardanoTransactionRecord;
+
+/// Query to delete old [CardanoTransactionRecord] from the sqlite database
+pub struct DeleteCardanoTransactionProvider<'conn> {
+    connection: &'conn SqliteConnection,
+}
+
+impl<'conn> Provider<'conn> for DeleteCardanoTransactionProvider<'conn> {
+    type Entity = CardanoTransactionRecord;
+
+    fn get_connection(&'conn self) -> &'conn SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        // it is important to alias the fields with the same name as the table
+        // since the table cannot be aliased in a RETURNING statement in SQLite.
+        let projection = Self::Entity::get_projection()
+            .expand(SourceAlias::new(&[("{:cardano_tx:}", "cardano_tx")]));
+
+        format!("delete from cardano_tx where {condition} returning {projection}")
+    }
+}
+
+impl<'conn> DeleteCardanoTransactionProvider<'conn> {
+    /// Create a new instance
+    pub fn new(connection: &'conn SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Create the SQL condition to prune transactions in blocks below the given block number.
+    fn get_prune_condition(&self, block_number_threshold: BlockNumber) -> WhereCondition {
+        WhereCondition::new(
+            "block_number < ?*",
+            vec![Value::Integer(block_number_threshold as i64)],
+        )
+    }
+
+    /// Prune the transactions in blocks below the given block number.
+    pub fn prune(
+        &self,
+        block_number_threshold: BlockNumber,
+    ) -> StdResult<EntityCursor<'_, CardanoTransactionRecord>> {
+        let filters = self.get_prune_condition(block_number_threshold);
+
+        self.find(filters)
+    }
+
+    /// Create the SQL condition to select the transactions in blocks after the given block
+    /// number, or all the transactions if no block number is given.
+    fn get_rollback_condition(&self, block_number: Option<BlockNumber>) -> WhereCondition {
+        match block_number {
+            Some(block_number) => WhereCondition::new(
+                "block_number > ?*",
+                vec![Value::Integer(block_number as i64)],
+            ),
+            None => WhereCondition::default(),
+        }
+    }
+
+    /// Remove the transactions in blocks after the given block number, or all the
+    /// transactions if no block number is given.
+    pub fn rollback_to_block_number(
+        &self,
+        block_number: Option<BlockNumber>,
+    ) -> StdResult<EntityCursor<'_, CardanoTransactionRecord>> {
+        let filters = self.get_rollback_condition(block_number);
+
+        self.find(filters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::CardanoTransaction;
+    use mithril_persistence::sqlite::GetAllProvider;
+
+    use crate::database::provider::{
+        GetCardanoTransactionProvider, InsertCardanoTransactionProvider,
+    };
+    use crate::database::test_utils::cardano_tx_db_connection;
+
+    use super::*;
+
+    fn insert_transactions(connection: &SqliteConnection, records: Vec<CardanoTransactionRecord>) {
+        let provider = InsertCardanoTransactionProvider::new(connection);
+        let condition = provider.get_insert_many_condition(records).unwrap();
+        provider.find(condition).unwrap().count();
+    }
+
+    fn test_transaction_set() -> Vec<CardanoTransactionRecord> {
+        [
+            CardanoTransaction::new("tx-hash-0", 10, 50, "block-hash-10", 1),
+            CardanoTransaction::new("tx-hash-1", 10, 51, "block-hash-10", 1),
+            CardanoTransaction::new("tx-hash-2", 11, 52, "block-hash-11", 1),
+            CardanoTransaction::new("tx-hash-3", 11, 53, "block-hash-11", 1),
+            CardanoTransaction::new("tx-hash-4", 12, 54, "block-hash-12", 1),
+            CardanoTransaction::new("tx-hash-5", 12, 55, "block-hash-12", 1),
+        ]
+        .into_iter()
+        .map(CardanoTransactionRecord::from)
+        .collect()
+    }
+
+    #[test]
+    fn test_prune_work_even_without_transactions_in_db() {
+        let connection = cardano_tx_db_connection().unwrap();
+
+        let provider = DeleteCardanoTransactionProvider::new(&connection);
+        let cursor = provider.prune(100).unwrap();
+
+        assert_eq!(0, cursor.count());
+    }
+
+    #[test]
+    fn test_prune_all_data_if_given_block_number_is_larger_than_stored_number_of_block() {
+        let connection = cardano_tx_db_connection().unwrap();
+        insert_transactions(&connection, test_transaction_set());
+
+        let provider = DeleteCardanoTransactionProvider::new(&connection);
+        let cursor = provider.prune(100_000).unwrap();
+        assert_eq!(test_transaction_set().len(), cursor.count());
+
+        let provider = GetCardanoTransactionProvider::new(&connection);
+        let cursor = provider.get_all().unwrap();
+        assert_eq!(0, cursor.count());
+    }
+
+    #[test]
+    fn test_prune_keep_transactions_in_blocks_equal_or_above_given_block_number() {
+        let connection = cardano_tx_db_connection().unwrap();
+        insert_transactions(&connection, test_transaction_set());
+
+        let provider = DeleteCardanoTransactionProvider::new(&connection);
+        let cursor = provider.prune(11).unwrap();
+        assert_eq!(2, cursor.count());
+
+        let provider = GetCardanoTransactionProvider::new(&connection);
+        let cursor = provider.get_all().unwrap();
+        assert_eq!(
+            vec!["tx-hash-2", "tx-hash-3", "tx-hash-4", "tx-hash-5"],
+            cursor
+                .map(|record| record.transaction_hash)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rollback_without_block_number_remove_all_transactions() {
+        let connection = cardano_tx_db_connection().unwrap();
+        insert_transactions(&connection, test_transaction_set());
+
+        let provider = DeleteCardanoTransactionProvider::new(&connection);
+        let cursor = provider.rollback_to_block_number(None).unwrap();
+        assert_eq!(test_transaction_set().len(), cursor.count());
+
+        let provider = GetCardanoTransactionProvider::new(&connection);
+        let cursor = provider.get_all().unwrap();
+        assert_eq!(0, cursor.count());
+    }
+
+    #[test]
+    fn test_rollback_remove_transactions_in_blocks_after_given_block_number() {
+        let connection = cardano_tx_db_connection().unwrap();
+        insert_transactions(&connection, test_transaction_set());
+
+        let provider = DeleteCardanoTransactionProvider::new(&connection);
+        let cursor = provider.rollback_to_block_number(Some(11)).unwrap();
+        assert_eq!(2, cursor.count());
+
+        let provider = GetCardanoTransactionProvider::new(&connection);
+        let cursor = provider.get_all().unwrap();
+        assert_eq!(
+            vec!["tx-hash-0", "tx-hash-1", "tx-hash-2", "tx-hash-3"],
+            cursor
+                .map(|record| record.transaction_hash)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -2,6 +2,8 @@
 
 mod block_range_root;
 mod cardano_transaction;
+mod pending_single_signature;
 
 pub use block_range_root::*;
 pub use cardano_transaction::*;
+pub use pending_single_signature::*;
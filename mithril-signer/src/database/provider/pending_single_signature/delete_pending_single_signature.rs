@@ -0,0 +1,64 @@
+use sqlite::Value;
+
+use mithril_common::entities::SignedEntityType;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    EntityCursor, Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::PendingSingleSignatureRecord;
+
+/// Query to delete [PendingSingleSignatureRecord] from the sqlite database
+pub struct DeletePendingSingleSignatureProvider<'conn> {
+    connection: &'conn SqliteConnection,
+}
+
+impl<'conn> Provider<'conn> for DeletePendingSingleSignatureProvider<'conn> {
+    type Entity = PendingSingleSignatureRecord;
+
+    fn get_connection(&'conn self) -> &'conn SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        // it is important to alias the fields with the same name as the table
+        // since the table cannot be aliased in a RETURNING statement in SQLite.
+        let projection = Self::Entity::get_projection().expand(SourceAlias::new(&[(
+            "{:pending_single_signature:}",
+            "pending_single_signature",
+        )]));
+
+        format!("delete from pending_single_signature where {condition} returning {projection}")
+    }
+}
+
+impl<'conn> DeletePendingSingleSignatureProvider<'conn> {
+    /// Create a new instance
+    pub fn new(connection: &'conn SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Create the SQL condition to select the record of the given signed entity type.
+    fn get_signed_entity_type_condition(
+        &self,
+        signed_entity_type: &SignedEntityType,
+    ) -> StdResult<WhereCondition> {
+        Ok(WhereCondition::new(
+            "signed_entity_type_id = ?* and beacon = ?*",
+            vec![
+                Value::Integer(signed_entity_type.index() as i64),
+                Value::String(signed_entity_type.get_json_beacon()?),
+            ],
+        ))
+    }
+
+    /// Remove the pending single signatures of the given signed entity type.
+    pub fn delete_by_signed_entity_type(
+        &self,
+        signed_entity_type: &SignedEntityType,
+    ) -> StdResult<EntityCursor<'_, PendingSingleSignatureRecord>> {
+        let filters = self.get_signed_entity_type_condition(signed_entity_type)?;
+
+        self.find(filters)
+    }
+}
@@ -0,0 +1,37 @@
+use mithril_persistence::sqlite::{
+    GetAllCondition, Provider, SourceAlias, SqLiteEntity, SqliteConnection,
+};
+
+use crate::database::record::PendingSingleSignatureRecord;
+
+/// Simple queries to retrieve [PendingSingleSignatureRecord] from the sqlite database.
+pub struct GetPendingSingleSignatureProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> GetPendingSingleSignatureProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+}
+
+impl GetAllCondition for GetPendingSingleSignatureProvider<'_> {}
+
+impl<'client> Provider<'client> for GetPendingSingleSignatureProvider<'client> {
+    type Entity = PendingSingleSignatureRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases =
+            SourceAlias::new(&[("{:pending_single_signature:}", "pending_single_signature")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!(
+            "select {projection} from pending_single_signature where {condition} order by created_at, signed_entity_type_id"
+        )
+    }
+}
@@ -0,0 +1,60 @@
+use sqlite::Value;
+
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::PendingSingleSignatureRecord;
+
+/// Query to insert or replace [PendingSingleSignatureRecord] in the sqlite database
+pub struct InsertOrReplacePendingSingleSignatureProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> InsertOrReplacePendingSingleSignatureProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Condition to insert or replace a record.
+    pub fn get_insert_or_replace_condition(
+        &self,
+        record: &PendingSingleSignatureRecord,
+    ) -> StdResult<WhereCondition> {
+        let expression = "(signed_entity_type_id, beacon, single_signatures, retry_count, next_retry_at, expires_at, created_at) values (?*, ?*, ?*, ?*, ?*, ?*, ?*)";
+        let parameters = vec![
+            Value::Integer(record.signed_entity_type.index() as i64),
+            Value::String(record.signed_entity_type.get_json_beacon()?),
+            Value::String(serde_json::to_string(&record.single_signatures)?),
+            Value::Integer(record.retry_count as i64),
+            Value::String(record.next_retry_at.to_rfc3339()),
+            record
+                .expires_at
+                .map(|datetime| Value::String(datetime.to_rfc3339()))
+                .unwrap_or(Value::Null),
+            Value::String(record.created_at.to_rfc3339()),
+        ];
+
+        Ok(WhereCondition::new(expression, parameters))
+    }
+}
+
+impl<'client> Provider<'client> for InsertOrReplacePendingSingleSignatureProvider<'client> {
+    type Entity = PendingSingleSignatureRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases =
+            SourceAlias::new(&[("{:pending_single_signature:}", "pending_single_signature")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!(
+            "insert or replace into pending_single_signature {condition} returning {projection}"
+        )
+    }
+}
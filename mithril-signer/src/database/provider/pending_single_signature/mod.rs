@@ -0,0 +1,7 @@
+mod delete_pending_single_signature;
+mod get_pending_single_signature;
+mod insert_or_replace_pending_single_signature;
+
+pub use delete_pending_single_signature::*;
+pub use get_pending_single_signature::*;
+pub use insert_or_replace_pending_single_signature::*;
@@ -3,10 +3,12 @@
 mod block_range_root;
 mod cardano_transaction;
 mod interval_without_block_range_root;
+mod pending_single_signature;
 
 pub use block_range_root::*;
 pub use cardano_transaction::*;
 pub use interval_without_block_range_root::*;
+pub use pending_single_signature::*;
 
 // TODO: this probably should be in `mithril-persistence` crate
 pub(crate) mod hydrator {
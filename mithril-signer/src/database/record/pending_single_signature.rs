@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use sqlite::Row;
+
+use mithril_common::entities::{SignedEntityType, SingleSignatures};
+use mithril_persistence::database::SignedEntityTypeHydrator;
+use mithril_persistence::sqlite::{HydrationError, Projection, SqLiteEntity};
+
+/// Pending single signature record is the representation of single signatures that have been
+/// computed by the signer but that are not yet registered to the aggregator.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PendingSingleSignatureRecord {
+    /// Signed entity type that the single signatures are computed for
+    pub signed_entity_type: SignedEntityType,
+
+    /// Single signatures to register
+    pub single_signatures: SingleSignatures,
+
+    /// Number of registration attempts that failed
+    pub retry_count: u32,
+
+    /// Date after which the next registration attempt can be made
+    pub next_retry_at: DateTime<Utc>,
+
+    /// Date after which the open message of the signed entity type is expired, if any
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Date of creation of the record
+    pub created_at: DateTime<Utc>,
+}
+
+fn parse_datetime(field: &str, value: &str) -> Result<DateTime<Utc>, HydrationError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|datetime| datetime.with_timezone(&Utc))
+        .map_err(|e| {
+            HydrationError::InvalidData(format!(
+                "Could not turn {field} field value '{value}' to rfc3339 Datetime. Error: {e}"
+            ))
+        })
+}
+
+impl SqLiteEntity for PendingSingleSignatureRecord {
+    fn hydrate(row: Row) -> Result<Self, HydrationError>
+    where
+        Self: Sized,
+    {
+        let signed_entity_type_id = usize::try_from(row.read::<i64, _>(0)).map_err(|e| {
+            HydrationError::InvalidData(format!(
+                "Integer field pending_single_signature.signed_entity_type_id cannot be turned into usize: {e}"
+            ))
+        })?;
+        let beacon = row.read::<&str, _>(1);
+        let signed_entity_type = SignedEntityTypeHydrator::hydrate(signed_entity_type_id, beacon)?;
+        let single_signatures_json = row.read::<&str, _>(2);
+        let single_signatures = serde_json::from_str(single_signatures_json).map_err(|e| {
+            HydrationError::InvalidData(format!(
+                "Could not turn pending_single_signature.single_signatures field value '{single_signatures_json}' to SingleSignatures. Error: {e}"
+            ))
+        })?;
+        let retry_count = u32::try_from(row.read::<i64, _>(3)).map_err(|e| {
+            HydrationError::InvalidData(format!(
+                "Integer field pending_single_signature.retry_count cannot be turned into u32: {e}"
+            ))
+        })?;
+        let next_retry_at = parse_datetime(
+            "pending_single_signature.next_retry_at",
+            row.read::<&str, _>(4),
+        )?;
+        let expires_at = row
+            .read::<Option<&str>, _>(5)
+            .map(|datetime| parse_datetime("pending_single_signature.expires_at", datetime))
+            .transpose()?;
+        let created_at = parse_datetime(
+            "pending_single_signature.created_at",
+            row.read::<&str, _>(6),
+        )?;
+
+        Ok(Self {
+            signed_entity_type,
+            single_signatures,
+            retry_count,
+            next_retry_at,
+            expires_at,
+            created_at,
+        })
+    }
+
+    fn get_projection() -> Projection {
+        Projection::from(&[
+            (
+                "signed_entity_type_id",
+                "{:pending_single_signature:}.signed_entity_type_id",
+                "int",
+            ),
+            ("beacon", "{:pending_single_signature:}.beacon", "text"),
+            (
+                "single_signatures",
+                "{:pending_single_signature:}.single_signatures",
+                "text",
+            ),
+            (
+                "retry_count",
+                "{:pending_single_signature:}.retry_count",
+                "int",
+            ),
+            (
+                "next_retry_at",
+                "{:pending_single_signature:}.next_retry_at",
+                "text",
+            ),
+            (
+                "expires_at",
+                "{:pending_single_signature:}.expires_at",
+                "text",
+            ),
+            (
+                "created_at",
+                "{:pending_single_signature:}.created_at",
+                "text",
+            ),
+        ])
+    }
+}
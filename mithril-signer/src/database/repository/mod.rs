@@ -1,5 +1,7 @@
 //! Signer related database repositories
 
 mod cardano_transaction_repository;
+mod pending_single_signature_repository;
 
 pub use cardano_transaction_repository::*;
+pub use pending_single_signature_repository::*;
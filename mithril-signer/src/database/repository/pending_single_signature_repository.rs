@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use async_trait::async_trait;
+
+use mithril_common::entities::SignedEntityType;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{GetAllProvider, Provider, SqliteConnection};
+
+use crate::database::provider::{
+    DeletePendingSingleSignatureProvider, GetPendingSingleSignatureProvider,
+    InsertOrReplacePendingSingleSignatureProvider,
+};
+use crate::database::record::PendingSingleSignatureRecord;
+use crate::PendingSingleSignatureStore;
+
+/// ## Pending single signature repository
+///
+/// This is a business oriented layer to perform actions on the database through
+/// providers.
+pub struct PendingSingleSignatureRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl PendingSingleSignatureRepository {
+    /// Instantiate service
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl PendingSingleSignatureStore for PendingSingleSignatureRepository {
+    async fn save_pending_single_signature(
+        &self,
+        record: PendingSingleSignatureRecord,
+    ) -> StdResult<()> {
+        let provider = InsertOrReplacePendingSingleSignatureProvider::new(&self.connection);
+        let filters = provider.get_insert_or_replace_condition(&record)?;
+        provider.find(filters)?.next().with_context(|| {
+            format!(
+                "No entity returned by the persister, signed_entity_type = {:?}",
+                record.signed_entity_type
+            )
+        })?;
+
+        Ok(())
+    }
+
+    async fn get_pending_single_signatures(&self) -> StdResult<Vec<PendingSingleSignatureRecord>> {
+        let provider = GetPendingSingleSignatureProvider::new(&self.connection);
+        let records = provider.get_all()?.collect();
+
+        Ok(records)
+    }
+
+    async fn remove_pending_single_signature(
+        &self,
+        signed_entity_type: &SignedEntityType,
+    ) -> StdResult<()> {
+        let provider = DeletePendingSingleSignatureProvider::new(&self.connection);
+        provider
+            .delete_by_signed_entity_type(signed_entity_type)?
+            .count();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use mithril_common::entities::{CardanoDbBeacon, Epoch};
+    use mithril_common::test_utils::fake_data;
+
+    use crate::database::test_utils::main_db_connection;
+
+    use super::*;
+
+    fn build_record(
+        signed_entity_type: SignedEntityType,
+        created_at: &str,
+    ) -> PendingSingleSignatureRecord {
+        let created_at = DateTime::parse_from_rfc3339(created_at)
+            .unwrap()
+            .with_timezone(&Utc);
+
+        PendingSingleSignatureRecord {
+            signed_entity_type,
+            single_signatures: fake_data::single_signatures(vec![1, 4, 9]),
+            retry_count: 0,
+            next_retry_at: created_at,
+            expires_at: None,
+            created_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn save_and_get_pending_single_signatures() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let repository = PendingSingleSignatureRepository::new(connection);
+        let records = vec![
+            build_record(
+                SignedEntityType::MithrilStakeDistribution(Epoch(4)),
+                "2024-01-01T10:00:00Z",
+            ),
+            PendingSingleSignatureRecord {
+                expires_at: Some(
+                    DateTime::parse_from_rfc3339("2024-01-01T10:11:00Z")
+                        .unwrap()
+                        .with_timezone(&Utc),
+                ),
+                ..build_record(
+                    SignedEntityType::CardanoTransactions(CardanoDbBeacon::new("devnet", 4, 57)),
+                    "2024-01-01T10:01:00Z",
+                )
+            },
+        ];
+
+        for record in records.clone() {
+            repository
+                .save_pending_single_signature(record)
+                .await
+                .unwrap();
+        }
+
+        let stored_records = repository.get_pending_single_signatures().await.unwrap();
+        assert_eq!(records, stored_records);
+    }
+
+    #[tokio::test]
+    async fn save_pending_single_signature_replace_the_record_of_the_same_signed_entity_type() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let repository = PendingSingleSignatureRepository::new(connection);
+        let record = build_record(
+            SignedEntityType::MithrilStakeDistribution(Epoch(4)),
+            "2024-01-01T10:00:00Z",
+        );
+        repository
+            .save_pending_single_signature(record.clone())
+            .await
+            .unwrap();
+
+        let updated_record = PendingSingleSignatureRecord {
+            retry_count: 3,
+            ..record
+        };
+        repository
+            .save_pending_single_signature(updated_record.clone())
+            .await
+            .unwrap();
+
+        let stored_records = repository.get_pending_single_signatures().await.unwrap();
+        assert_eq!(vec![updated_record], stored_records);
+    }
+
+    #[tokio::test]
+    async fn remove_pending_single_signature_only_remove_the_given_signed_entity_type() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let repository = PendingSingleSignatureRepository::new(connection);
+        let records = vec![
+            build_record(
+                SignedEntityType::MithrilStakeDistribution(Epoch(4)),
+                "2024-01-01T10:00:00Z",
+            ),
+            build_record(
+                SignedEntityType::CardanoStakeDistribution(Epoch(4)),
+                "2024-01-01T10:01:00Z",
+            ),
+        ];
+        for record in records.clone() {
+            repository
+                .save_pending_single_signature(record)
+                .await
+                .unwrap();
+        }
+
+        repository
+            .remove_pending_single_signature(&SignedEntityType::MithrilStakeDistribution(Epoch(4)))
+            .await
+            .unwrap();
+
+        let stored_records = repository.get_pending_single_signatures().await.unwrap();
+        assert_eq!(vec![records[1].clone()], stored_records);
+    }
+}
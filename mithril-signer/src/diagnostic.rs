@@ -0,0 +1,771 @@
+//! Diagnostics used by the signer command line to help troubleshooting a signer node without
+//! reading its logs.
+
+use anyhow::{anyhow, Context};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+use mithril_common::chain_observer::ChainObserver;
+use mithril_common::crypto_helper::{
+    KESPeriod, OpCert, ProtocolKeyRegistration, ProtocolOpCert, ProtocolSignerVerificationKey,
+    SerDeShelleyFileFormat,
+};
+use mithril_common::digesters::cache::{
+    ImmutableFileDigestCacheProvider, JsonImmutableFileDigestCacheProvider,
+};
+use mithril_common::digesters::ImmutableFile;
+use mithril_common::entities::{Epoch, PartyId, ProtocolParameters, Signer, Stake};
+use mithril_common::StdResult;
+
+use crate::{Configuration, MithrilProtocolInitializerBuilder, ProtocolInitializerStorer};
+
+/// Maximum number of KES periods an operational certificate is valid for on the Cardano
+/// networks (`maxKESEvolutions` of the Shelley genesis).
+pub const MAX_KES_EVOLUTIONS: KESPeriod = 62;
+
+/// Outcome of a single diagnostic check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticCheck {
+    /// Name of what is checked
+    pub name: String,
+
+    /// Success or failure message of the check
+    pub outcome: Result<String, String>,
+}
+
+impl DiagnosticCheck {
+    /// Create a successful check.
+    pub fn success<T: Into<String>, M: Into<String>>(name: T, message: M) -> Self {
+        Self {
+            name: name.into(),
+            outcome: Ok(message.into()),
+        }
+    }
+
+    /// Create a failed check.
+    pub fn failure<T: Into<String>, M: Into<String>>(name: T, message: M) -> Self {
+        Self {
+            name: name.into(),
+            outcome: Err(message.into()),
+        }
+    }
+
+    /// Check if the check succeeded.
+    pub fn is_success(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+impl Display for DiagnosticCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.outcome {
+            Ok(message) => write!(f, "[OK] {}: {message}", self.name),
+            Err(message) => write!(f, "[FAILED] {}: {message}", self.name),
+        }
+    }
+}
+
+/// List of the checks run by a diagnostic.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiagnosticReport {
+    /// Checks in the order they were run
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+impl DiagnosticReport {
+    fn push(&mut self, check: DiagnosticCheck) -> &mut Self {
+        self.checks.push(check);
+        self
+    }
+
+    /// Check if all the checks of the report succeeded.
+    pub fn is_success(&self) -> bool {
+        self.checks.iter().all(DiagnosticCheck::is_success)
+    }
+}
+
+impl Display for DiagnosticReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for check in &self.checks {
+            writeln!(f, "{check}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Content of a protocol initializer stored by the signer.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProtocolInitializerInspection {
+    /// Epoch for which the protocol initializer is stored
+    pub epoch: Epoch,
+
+    /// Party id of the signer
+    pub party_id: PartyId,
+
+    /// Stake of the signer
+    pub stake: Stake,
+
+    /// Verification key of the signer, json hex encoded
+    pub verification_key: String,
+
+    /// KES signature of the verification key, json hex encoded
+    pub verification_key_signature: Option<String>,
+
+    /// Protocol parameters of the protocol initializer
+    pub protocol_parameters: ProtocolParameters,
+}
+
+/// Read the protocol initializer stored for the given epoch.
+pub async fn inspect_protocol_initializer(
+    protocol_initializer_store: &dyn ProtocolInitializerStorer,
+    party_id: PartyId,
+    epoch: Epoch,
+) -> StdResult<ProtocolInitializerInspection> {
+    let protocol_initializer = protocol_initializer_store
+        .get_protocol_initializer(epoch)
+        .await?
+        .ok_or_else(|| anyhow!("No protocol initializer stored for epoch {epoch}"))?;
+    let verification_key: ProtocolSignerVerificationKey =
+        protocol_initializer.verification_key().into();
+
+    Ok(ProtocolInitializerInspection {
+        epoch,
+        party_id,
+        stake: protocol_initializer.get_stake(),
+        verification_key: verification_key.to_json_hex()?,
+        verification_key_signature: protocol_initializer
+            .verification_key_signature()
+            .map(|signature| signature.to_json_hex())
+            .transpose()?,
+        protocol_parameters: protocol_initializer.get_protocol_parameters().into(),
+    })
+}
+
+/// Compute the signer registered to the aggregator with the protocol initializer stored for the
+/// given recording epoch.
+///
+/// The KES period of the registration is not stored, the current KES period is used instead.
+pub async fn compute_stored_signer_registration(
+    config: &Configuration,
+    protocol_initializer_store: &dyn ProtocolInitializerStorer,
+    chain_observer: &dyn ChainObserver,
+    party_id: PartyId,
+    recording_epoch: Epoch,
+) -> StdResult<Signer> {
+    let protocol_initializer = protocol_initializer_store
+        .get_protocol_initializer(recording_epoch)
+        .await?
+        .ok_or_else(|| {
+            anyhow!("No protocol initializer stored for epoch {recording_epoch}, the signer has not registered yet")
+        })?;
+    let (operational_certificate, kes_period) = match config.operational_certificate_path {
+        Some(_) => {
+            let opcert = read_operational_certificate(config)?;
+            let kes_period = compute_kes_period_offset(chain_observer, &opcert).await?;
+            (Some(ProtocolOpCert::new(opcert)), Some(kes_period))
+        }
+        None => (None, None),
+    };
+
+    Ok(Signer::new(
+        party_id,
+        protocol_initializer.verification_key().into(),
+        protocol_initializer.verification_key_signature(),
+        operational_certificate,
+        kes_period,
+    ))
+}
+
+/// Check that the KES secret key and the operational certificate of the configuration match
+/// the pool and the current KES period.
+pub async fn check_kes(
+    config: &Configuration,
+    chain_observer: &dyn ChainObserver,
+) -> DiagnosticReport {
+    let mut report = DiagnosticReport::default();
+
+    let opcert = match read_operational_certificate(config) {
+        Ok(opcert) => {
+            report.push(DiagnosticCheck::success(
+                "Operational certificate",
+                format!("issued at KES period {}", opcert.start_kes_period),
+            ));
+            opcert
+        }
+        Err(error) => {
+            report.push(DiagnosticCheck::failure(
+                "Operational certificate",
+                format!("{error:#}"),
+            ));
+            return report;
+        }
+    };
+
+    if let Err(error) = opcert.validate() {
+        report.push(DiagnosticCheck::failure(
+            "Operational certificate signature",
+            format!("{error}"),
+        ));
+        return report;
+    }
+    report.push(DiagnosticCheck::success(
+        "Operational certificate signature",
+        "signed by the pool cold key",
+    ));
+
+    let pool_id = match opcert.compute_protocol_party_id() {
+        Ok(pool_id) => pool_id,
+        Err(error) => {
+            report.push(DiagnosticCheck::failure("Pool id", format!("{error}")));
+            return report;
+        }
+    };
+    report.push(check_pool_stake(chain_observer, &pool_id).await);
+
+    let kes_period = match compute_kes_period_offset(chain_observer, &opcert).await {
+        Ok(kes_period) => {
+            report.push(DiagnosticCheck::success(
+                "KES period",
+                format!(
+                    "current KES period is {} evolutions after the start of the operational certificate, {} remaining",
+                    kes_period,
+                    MAX_KES_EVOLUTIONS - kes_period
+                ),
+            ));
+            kes_period
+        }
+        Err(error) => {
+            report.push(DiagnosticCheck::failure("KES period", format!("{error:#}")));
+            return report;
+        }
+    };
+
+    report.push(
+        match check_kes_secret_key(config, opcert, &pool_id, kes_period) {
+            Ok(()) => DiagnosticCheck::success(
+                "KES secret key",
+                "matches the operational certificate at the current KES period",
+            ),
+            Err(error) => DiagnosticCheck::failure("KES secret key", format!("{error:#}")),
+        },
+    );
+
+    report
+}
+
+fn read_operational_certificate(config: &Configuration) -> StdResult<OpCert> {
+    let operational_certificate_path = config
+        .operational_certificate_path
+        .as_ref()
+        .ok_or_else(|| anyhow!("`operational_certificate_path` is not set"))?;
+
+    OpCert::from_file(operational_certificate_path).with_context(|| {
+        format!(
+            "Could not decode operational certificate: `{}`",
+            operational_certificate_path.display()
+        )
+    })
+}
+
+async fn check_pool_stake(
+    chain_observer: &dyn ChainObserver,
+    pool_id: &PartyId,
+) -> DiagnosticCheck {
+    let name = "Pool stake";
+    match chain_observer.get_current_stake_distribution().await {
+        Ok(Some(stake_distribution)) => match stake_distribution.get(pool_id) {
+            Some(stake) => {
+                DiagnosticCheck::success(name, format!("{pool_id} has a stake of {stake}"))
+            }
+            None => DiagnosticCheck::failure(
+                name,
+                format!("{pool_id} is not in the current stake distribution"),
+            ),
+        },
+        Ok(None) => {
+            DiagnosticCheck::failure(name, "the Cardano node returned no stake distribution")
+        }
+        Err(error) => DiagnosticCheck::failure(
+            name,
+            format!("could not read the stake distribution: {error:#}"),
+        ),
+    }
+}
+
+async fn compute_kes_period_offset(
+    chain_observer: &dyn ChainObserver,
+    opcert: &OpCert,
+) -> StdResult<KESPeriod> {
+    let current_kes_period = chain_observer
+        .get_current_kes_period(opcert)
+        .await?
+        .ok_or_else(|| anyhow!("the Cardano node returned no current KES period"))?;
+    let start_kes_period = KESPeriod::try_from(opcert.start_kes_period)
+        .with_context(|| "Invalid start KES period in the operational certificate")?;
+
+    match current_kes_period.checked_sub(start_kes_period) {
+        None => Err(anyhow!(
+            "the operational certificate starts at KES period {start_kes_period}, after the current KES period {current_kes_period}"
+        )),
+        Some(offset) if offset >= MAX_KES_EVOLUTIONS => Err(anyhow!(
+            "the operational certificate issued at KES period {start_kes_period} is expired since KES period {}, a new one must be issued",
+            start_kes_period + MAX_KES_EVOLUTIONS
+        )),
+        Some(offset) => Ok(offset),
+    }
+}
+
+fn check_kes_secret_key(
+    config: &Configuration,
+    opcert: OpCert,
+    pool_id: &PartyId,
+    kes_period: KESPeriod,
+) -> StdResult<()> {
+    let kes_secret_key_path = config
+        .kes_secret_key_path
+        .clone()
+        .ok_or_else(|| anyhow!("`kes_secret_key_path` is not set"))?;
+    let stake = 1;
+    // Sign a verification key with the KES secret key the same way as when registering, the
+    // protocol parameters don't matter here
+    let protocol_initializer = MithrilProtocolInitializerBuilder::build(
+        &stake,
+        &ProtocolParameters::new(1, 1, 1.0),
+        Some(kes_secret_key_path),
+        Some(kes_period),
+    )?;
+
+    let mut key_registration = ProtocolKeyRegistration::init(&vec![(pool_id.to_owned(), stake)]);
+    key_registration
+        .register(
+            None,
+            Some(ProtocolOpCert::new(opcert)),
+            protocol_initializer.verification_key_signature(),
+            Some(kes_period),
+            protocol_initializer.verification_key().into(),
+        )
+        .with_context(|| {
+            "The signature made with the KES secret key is rejected with the operational certificate"
+        })?;
+
+    Ok(())
+}
+
+/// Check the Cardano database directory and the immutables digests cache of the configuration.
+pub async fn check_database(
+    config: &Configuration,
+    digests_cache_file_name: &str,
+) -> DiagnosticReport {
+    let mut report = DiagnosticReport::default();
+
+    if !config.db_directory.is_dir() {
+        report.push(DiagnosticCheck::failure(
+            "Cardano database directory",
+            format!("`{}` is not a directory", config.db_directory.display()),
+        ));
+        return report;
+    }
+    report.push(DiagnosticCheck::success(
+        "Cardano database directory",
+        format!("{}", config.db_directory.display()),
+    ));
+
+    let immutable_files = match ImmutableFile::list_completed_in_dir(&config.db_directory) {
+        Ok(immutable_files) if immutable_files.is_empty() => {
+            report.push(DiagnosticCheck::failure(
+                "Immutable files",
+                "no completed immutable file found",
+            ));
+            return report;
+        }
+        Ok(immutable_files) => {
+            let last_number = immutable_files.last().map(|f| f.number).unwrap_or_default();
+            report.push(DiagnosticCheck::success(
+                "Immutable files",
+                format!(
+                    "{} files found for the completed immutables, the last completed immutable file number is {last_number}",
+                    immutable_files.len()
+                ),
+            ));
+            immutable_files
+        }
+        Err(error) => {
+            report.push(DiagnosticCheck::failure(
+                "Immutable files",
+                format!("{:#}", anyhow!(error)),
+            ));
+            return report;
+        }
+    };
+
+    report.push(check_data_stores_directory(&config.data_stores_directory));
+
+    if config.disable_digests_cache {
+        report.push(DiagnosticCheck::success(
+            "Immutables digests cache",
+            "disabled",
+        ));
+        return report;
+    }
+    let cache_path = config.data_stores_directory.join(digests_cache_file_name);
+    let cache_provider = JsonImmutableFileDigestCacheProvider::new(&cache_path);
+    let number_of_immutable_files = immutable_files.len();
+    report.push(match cache_provider.get(immutable_files).await {
+        Ok(cached_digests) => DiagnosticCheck::success(
+            "Immutables digests cache",
+            format!(
+                "{} of the {number_of_immutable_files} completed immutable files have a cached digest in `{}`",
+                cached_digests.values().filter(|d| d.is_some()).count(),
+                cache_path.display()
+            ),
+        ),
+        Err(error) => DiagnosticCheck::failure(
+            "Immutables digests cache",
+            format!(
+                "could not read `{}`, it can be rebuilt by starting the signer with `--reset-digests-cache`: {:#}",
+                cache_path.display(),
+                anyhow!(error)
+            ),
+        ),
+    });
+
+    report
+}
+
+fn check_data_stores_directory(data_stores_directory: &Path) -> DiagnosticCheck {
+    let name = "Data stores directory";
+    if data_stores_directory.is_dir() {
+        DiagnosticCheck::success(name, format!("{}", data_stores_directory.display()))
+    } else if data_stores_directory.exists() {
+        DiagnosticCheck::failure(
+            name,
+            format!("`{}` is not a directory", data_stores_directory.display()),
+        )
+    } else {
+        DiagnosticCheck::success(
+            name,
+            format!(
+                "`{}` does not exist yet, it will be created when the signer starts",
+                data_stores_directory.display()
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kes_summed_ed25519::kes::Sum6Kes;
+    use kes_summed_ed25519::traits::KesSk;
+    use std::path::PathBuf;
+
+    use mithril_common::chain_observer::FakeObserver;
+    use mithril_common::crypto_helper::{ColdKeyGenerator, Sum6KesBytes};
+    use mithril_common::digesters::DummyImmutablesDbBuilder;
+    use mithril_common::test_utils::{fake_data, MithrilFixtureBuilder, TempDir};
+    use mithril_persistence::store::adapter::MemoryAdapter;
+
+    use crate::ProtocolInitializerStore;
+
+    use super::*;
+
+    fn write_kes_secret_key(path: &Path, kes_seed: u8) -> kes_summed_ed25519::PublicKey {
+        let mut buffer = [0u8; Sum6Kes::SIZE + 4];
+        let mut seed = [kes_seed; 32];
+        let (kes_secret_key, kes_verification_key) = Sum6Kes::keygen(&mut buffer, &mut seed);
+        let mut kes_bytes = Sum6KesBytes([0u8; Sum6Kes::SIZE + 4]);
+        kes_bytes.0.copy_from_slice(&kes_secret_key.clone_sk());
+        kes_bytes.to_file(path).unwrap();
+
+        kes_verification_key
+    }
+
+    /// Write a KES secret key and an operational certificate for it, return the configuration
+    /// using them and the id of the pool.
+    fn certified_signer_config(test_name: &str, start_kes_period: u64) -> (Configuration, PartyId) {
+        let dir = TempDir::create("signer_diagnostic", test_name);
+        let kes_secret_key_path = dir.join("kes.sk");
+        let operational_certificate_path = dir.join("opcert.cert");
+        let kes_verification_key = write_kes_secret_key(&kes_secret_key_path, 1);
+        let opcert = OpCert::new(
+            kes_verification_key,
+            0,
+            start_kes_period,
+            ColdKeyGenerator::create_deterministic_keypair([1; 32]),
+        );
+        opcert.to_file(&operational_certificate_path).unwrap();
+        let pool_id = opcert.compute_protocol_party_id().unwrap();
+
+        let config = Configuration {
+            kes_secret_key_path: Some(kes_secret_key_path),
+            operational_certificate_path: Some(operational_certificate_path),
+            ..Configuration::new_sample(&pool_id)
+        };
+
+        (config, pool_id)
+    }
+
+    async fn chain_observer_with_pool_stake(pool_id: &PartyId) -> FakeObserver {
+        let chain_observer = FakeObserver::new(None);
+        let mut signer = fake_data::signers_with_stakes(1)[0].clone();
+        signer.party_id = pool_id.to_owned();
+        chain_observer.set_signers(vec![signer]).await;
+
+        chain_observer
+    }
+
+    #[tokio::test]
+    async fn inspect_stored_protocol_initializer() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(1).build();
+        let signer = &fixture.signers_fixture()[0];
+        let store = ProtocolInitializerStore::new(
+            Box::new(
+                MemoryAdapter::new(Some(vec![(Epoch(4), signer.protocol_initializer.clone())]))
+                    .unwrap(),
+            ),
+            None,
+        );
+
+        let inspection = inspect_protocol_initializer(&store, signer.party_id(), Epoch(4))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ProtocolInitializerInspection {
+                epoch: Epoch(4),
+                party_id: signer.party_id(),
+                stake: signer.signer_with_stake.stake,
+                verification_key: signer
+                    .signer_with_stake
+                    .verification_key
+                    .to_json_hex()
+                    .unwrap(),
+                verification_key_signature: signer
+                    .signer_with_stake
+                    .verification_key_signature
+                    .map(|signature| signature.to_json_hex().unwrap()),
+                protocol_parameters: fixture.protocol_parameters(),
+            },
+            inspection
+        );
+        inspect_protocol_initializer(&store, signer.party_id(), Epoch(5))
+            .await
+            .expect_err("Inspecting a missing protocol initializer should fail");
+    }
+
+    #[tokio::test]
+    async fn compute_signer_registration_from_the_stored_protocol_initializer() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(1).build();
+        let signer = &fixture.signers_fixture()[0];
+        let store = ProtocolInitializerStore::new(
+            Box::new(
+                MemoryAdapter::new(Some(vec![(Epoch(4), signer.protocol_initializer.clone())]))
+                    .unwrap(),
+            ),
+            None,
+        );
+        let config = Configuration {
+            operational_certificate_path: None,
+            ..Configuration::new_sample(&signer.party_id())
+        };
+
+        let registered_signer = compute_stored_signer_registration(
+            &config,
+            &store,
+            &FakeObserver::new(None),
+            signer.party_id(),
+            Epoch(4),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            Signer::new(
+                signer.party_id(),
+                signer.signer_with_stake.verification_key,
+                signer.signer_with_stake.verification_key_signature,
+                None,
+                None,
+            ),
+            registered_signer
+        );
+        compute_stored_signer_registration(
+            &config,
+            &store,
+            &FakeObserver::new(None),
+            signer.party_id(),
+            Epoch(5),
+        )
+        .await
+        .expect_err("Computing the registration without stored protocol initializer should fail");
+    }
+
+    #[tokio::test]
+    async fn check_kes_succeeds_with_matching_kes_key_and_operational_certificate() {
+        let (config, pool_id) = certified_signer_config("check_kes_succeeds", 0);
+        let chain_observer = chain_observer_with_pool_stake(&pool_id).await;
+
+        let report = check_kes(&config, &chain_observer).await;
+
+        assert!(report.is_success(), "{report}");
+        assert_eq!(5, report.checks.len(), "{report}");
+    }
+
+    #[tokio::test]
+    async fn check_kes_fails_when_the_kes_key_is_not_the_one_of_the_operational_certificate() {
+        let (config, pool_id) = certified_signer_config("check_kes_fails_with_other_kes_key", 0);
+        write_kes_secret_key(config.kes_secret_key_path.as_ref().unwrap(), 2);
+        let chain_observer = chain_observer_with_pool_stake(&pool_id).await;
+
+        let report = check_kes(&config, &chain_observer).await;
+
+        let last_check = report.checks.last().unwrap();
+        assert_eq!("KES secret key", last_check.name, "{report}");
+        assert!(!last_check.is_success(), "{report}");
+    }
+
+    #[tokio::test]
+    async fn check_kes_fails_when_the_operational_certificate_starts_after_the_current_kes_period()
+    {
+        let (config, pool_id) = certified_signer_config("check_kes_fails_with_future_opcert", 5);
+        let chain_observer = chain_observer_with_pool_stake(&pool_id).await;
+
+        let report = check_kes(&config, &chain_observer).await;
+
+        let last_check = report.checks.last().unwrap();
+        assert_eq!("KES period", last_check.name, "{report}");
+        assert!(!last_check.is_success(), "{report}");
+    }
+
+    #[tokio::test]
+    async fn check_kes_fails_when_the_pool_is_not_in_the_stake_distribution() {
+        let (config, _pool_id) = certified_signer_config("check_kes_fails_without_stake", 0);
+        let chain_observer = FakeObserver::new(None);
+
+        let report = check_kes(&config, &chain_observer).await;
+
+        assert!(!report.is_success(), "{report}");
+        assert!(
+            report
+                .checks
+                .iter()
+                .any(|c| c.name == "Pool stake" && !c.is_success()),
+            "{report}"
+        );
+    }
+
+    #[tokio::test]
+    async fn check_kes_fails_without_operational_certificate() {
+        let config = Configuration {
+            operational_certificate_path: None,
+            ..Configuration::new_sample(&fake_data::signers(1)[0].party_id)
+        };
+
+        let report = check_kes(&config, &FakeObserver::new(None)).await;
+
+        assert_eq!(
+            vec![DiagnosticCheck::failure(
+                "Operational certificate",
+                "`operational_certificate_path` is not set"
+            )],
+            report.checks
+        );
+    }
+
+    #[tokio::test]
+    async fn check_database_with_immutable_files_and_digests_cache() {
+        let db =
+            DummyImmutablesDbBuilder::new("check_database_with_immutable_files_and_digests_cache")
+                .with_immutables(&[1, 2, 3])
+                .append_immutable_trio()
+                .build();
+        let data_stores_directory =
+            TempDir::create("signer_diagnostic", "check_database_with_immutable_files");
+        JsonImmutableFileDigestCacheProvider::new(&data_stores_directory.join("cache.json"))
+            .store(vec![
+                ("00001.chunk".to_string(), "digest-1".to_string()),
+                ("00001.primary".to_string(), "digest-2".to_string()),
+            ])
+            .await
+            .unwrap();
+        let config = Configuration {
+            db_directory: db.dir.clone(),
+            data_stores_directory,
+            disable_digests_cache: false,
+            ..Configuration::new_sample(&fake_data::signers(1)[0].party_id)
+        };
+
+        let report = check_database(&config, "cache.json").await;
+
+        assert!(report.is_success(), "{report}");
+        assert_eq!(
+            Ok(
+                "9 files found for the completed immutables, the last completed immutable file number is 3"
+                    .to_string()
+            ),
+            report.checks[1].outcome
+        );
+        assert!(
+            report.checks[3]
+                .outcome
+                .as_ref()
+                .is_ok_and(|message| message.starts_with("2 of the 9 completed immutable files")),
+            "{report}"
+        );
+    }
+
+    #[tokio::test]
+    async fn check_database_fails_with_corrupted_digests_cache() {
+        let db = DummyImmutablesDbBuilder::new("check_database_fails_with_corrupted_digests_cache")
+            .with_immutables(&[1, 2])
+            .build();
+        let data_stores_directory =
+            TempDir::create("signer_diagnostic", "check_database_with_corrupted_cache");
+        std::fs::write(data_stores_directory.join("cache.json"), "not json").unwrap();
+        let config = Configuration {
+            db_directory: db.dir.clone(),
+            data_stores_directory,
+            disable_digests_cache: false,
+            ..Configuration::new_sample(&fake_data::signers(1)[0].party_id)
+        };
+
+        let report = check_database(&config, "cache.json").await;
+
+        assert!(!report.is_success(), "{report}");
+        assert_eq!("Immutables digests cache", report.checks[3].name);
+    }
+
+    #[tokio::test]
+    async fn check_database_fails_when_the_database_directory_does_not_exist() {
+        let config = Configuration {
+            db_directory: PathBuf::from("/not/a/cardano/db"),
+            ..Configuration::new_sample(&fake_data::signers(1)[0].party_id)
+        };
+
+        let report = check_database(&config, "cache.json").await;
+
+        assert_eq!(
+            vec![DiagnosticCheck::failure(
+                "Cardano database directory",
+                "`/not/a/cardano/db` is not a directory"
+            )],
+            report.checks
+        );
+    }
+
+    #[test]
+    fn report_display_every_check() {
+        let report = DiagnosticReport {
+            checks: vec![
+                DiagnosticCheck::success("First", "all good"),
+                DiagnosticCheck::failure("Second", "something is wrong"),
+            ],
+        };
+
+        assert!(!report.is_success());
+        assert_eq!(
+            "[OK] First: all good\n[FAILED] Second: something is wrong\n",
+            report.to_string()
+        );
+    }
+}
@@ -8,18 +8,23 @@
 
 mod aggregator_client;
 mod cardano_transactions_importer;
+mod commands;
 mod configuration;
 pub mod database;
+pub mod diagnostic;
 mod message_adapters;
 pub mod metrics;
 mod protocol_initializer_store;
 mod runtime;
+mod single_signature_publisher;
 mod single_signer;
+mod store_encryption;
 
 #[cfg(test)]
 pub use aggregator_client::dumb::DumbAggregatorClient;
 pub use aggregator_client::*;
 pub use cardano_transactions_importer::*;
+pub use commands::{DiagnosticCommand, RekeyStoreCommand};
 pub use configuration::{Configuration, DefaultConfiguration};
 pub use message_adapters::{
     FromEpochSettingsAdapter, FromPendingCertificateMessageAdapter, ToRegisterSignerMessageAdapter,
@@ -27,7 +32,9 @@ pub use message_adapters::{
 pub use metrics::*;
 pub use protocol_initializer_store::{ProtocolInitializerStore, ProtocolInitializerStorer};
 pub use runtime::*;
+pub use single_signature_publisher::*;
 pub use single_signer::*;
+pub use store_encryption::*;
 
 /// HTTP request timeout duration in milliseconds
 const HTTP_REQUEST_TIMEOUT_DURATION: u64 = 30000;
@@ -4,7 +4,7 @@ use config::{Map, Value};
 use mithril_doc::{Documenter, DocumenterDefault, StructDoc};
 
 use slog::{o, Drain, Level, Logger};
-use slog_scope::{crit, debug};
+use slog_scope::{crit, debug, info};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
@@ -17,8 +17,9 @@ use tokio::{
 use mithril_common::StdResult;
 use mithril_doc::GenerateDocCommands;
 use mithril_signer::{
-    Configuration, DefaultConfiguration, MetricsServer, ProductionServiceBuilder, ServiceBuilder,
-    SignerRunner, SignerState, StateMachine,
+    Configuration, DefaultConfiguration, DiagnosticCommand, MetricsServer,
+    ProductionServiceBuilder, RekeyStoreCommand, ServiceBuilder, SignerRunner, SignerState,
+    StateMachine,
 };
 
 /// CLI args
@@ -108,6 +109,12 @@ fn build_logger(min_level: Level) -> Logger {
 
 #[derive(Subcommand, Debug, Clone)]
 enum SignerCommands {
+    /// Diagnose the signer configuration, keys and stores without running the signer
+    Diagnostic(DiagnosticCommand),
+
+    /// Re-encrypt the protocol initializers stores with a new passphrase or key file
+    RekeyStore(RekeyStoreCommand),
+
     #[clap(alias("doc"), hide(true))]
     GenerateDoc(GenerateDocCommands),
 }
@@ -158,30 +165,47 @@ async fn main() -> StdResult<()> {
         .with_context(|| "configuration build error")?
         .try_deserialize()
         .with_context(|| "configuration deserialize error")?;
+    config.check_transactions_pruning_retained_block_ranges()?;
+
+    match &args.command {
+        Some(SignerCommands::Diagnostic(cmd)) => return cmd.execute(config).await,
+        Some(SignerCommands::RekeyStore(cmd)) => return cmd.execute(config).await,
+        _ => {}
+    }
 
-    let services = ProductionServiceBuilder::new(&config)
+    let all_services = ProductionServiceBuilder::new(&config)
         .build()
         .await
         .with_context(|| "services initialization error")?;
 
-    let metrics_service = services.metrics_service.clone();
+    // The metrics of all the aggregators are exported by the metrics service of the main one
+    let metrics_service = all_services[0].metrics_service.clone();
 
     debug!("Started"; "run_mode" => &args.run_mode, "config" => format!("{config:?}"));
-    let state_machine = StateMachine::new(
-        SignerState::Init,
-        Box::new(SignerRunner::new(config.clone(), services)),
-        Duration::from_millis(config.run_interval),
-        metrics_service.clone(),
-    );
-
     let mut join_set = JoinSet::new();
-    join_set.spawn(async move {
-        state_machine
-            .run()
-            .await
-            .map_err(|e| anyhow!(e))
-            .map(|_| None)
-    });
+    // Each aggregator is followed by its own state machine, so an unavailable aggregator doesn't
+    // prevent the signer from contributing to the other ones
+    for (aggregator_endpoint, services) in config
+        .get_aggregator_endpoints()
+        .into_iter()
+        .zip(all_services)
+    {
+        info!("Starting state machine"; "aggregator_endpoint" => &aggregator_endpoint);
+        let state_machine_metrics_service = services.metrics_service.clone();
+        let state_machine = StateMachine::new(
+            SignerState::Init,
+            Box::new(SignerRunner::new(config.clone(), services)),
+            Duration::from_millis(config.run_interval),
+            state_machine_metrics_service,
+        );
+        join_set.spawn(async move {
+            state_machine
+                .run()
+                .await
+                .map_err(|e| anyhow!(e))
+                .map(|_| None)
+        });
+    }
 
     let (metrics_server_shutdown_tx, metrics_server_shutdown_rx) = oneshot::channel();
     if config.enable_metrics_server {
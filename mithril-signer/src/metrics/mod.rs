@@ -7,6 +7,10 @@ mod service;
 pub use server::MetricsServer;
 pub use service::MetricsService;
 
+/// Name of the label holding the aggregator endpoint when the signer contributes to several
+/// aggregators
+pub const AGGREGATOR_LABEL: &str = "aggregator";
+
 /// 'signer_registration_success_since_startup' metric name
 pub const SIGNER_REGISTRATION_SUCCESS_SINCE_STARTUP_METRIC_NAME: &str =
     "mithril_signer_signer_registration_success_since_startup";
@@ -49,6 +53,20 @@ pub const SIGNATURE_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_NAME: &str =
 pub const SIGNATURE_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_HELP: &str =
     "Latest epoch at which signature successfully registered on a Mithril signature node";
 
+/// 'signature_registration_queued_since_startup' metric name
+pub const SIGNATURE_REGISTRATION_QUEUED_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_signer_signature_registration_queued_since_startup";
+/// 'signature_registration_queued_since_startup' metric help
+pub const SIGNATURE_REGISTRATION_QUEUED_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of single signatures queued for a later retry since startup on a Mithril signer node";
+
+/// 'signature_registration_dropped_since_startup' metric name
+pub const SIGNATURE_REGISTRATION_DROPPED_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_signer_signature_registration_dropped_since_startup";
+/// 'signature_registration_dropped_since_startup' metric help
+pub const SIGNATURE_REGISTRATION_DROPPED_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of queued single signatures dropped without being registered since startup on a Mithril signer node";
+
 /// 'runtime_cycle_success_since_startup' metric name
 pub const RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_NAME: &str =
     "mithril_signer_runtime_cycle_success_since_startup";
@@ -62,3 +80,10 @@ pub const RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME: &str =
 /// 'runtime_cycle_total_since_startup' metric help
 pub const RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_HELP: &str =
     "Number of runtime cycles since startup on a Mithril signer node";
+
+/// 'cardano_transactions_pruned_since_startup' metric name
+pub const CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_signer_cardano_transactions_pruned_since_startup";
+/// 'cardano_transactions_pruned_since_startup' metric help
+pub const CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of Cardano transactions pruned from the database since startup on a Mithril signer node";
@@ -1,11 +1,19 @@
+use std::collections::HashMap;
+
 use mithril_common::{entities::Epoch, StdResult};
 use prometheus::{Counter, Encoder, Gauge, Opts, Registry, TextEncoder};
 use slog_scope::debug;
 
 use super::{
+    AGGREGATOR_LABEL, CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_HELP,
+    CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_NAME,
     RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_HELP,
     RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_NAME, RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_HELP,
     RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME,
+    SIGNATURE_REGISTRATION_DROPPED_SINCE_STARTUP_METRIC_HELP,
+    SIGNATURE_REGISTRATION_DROPPED_SINCE_STARTUP_METRIC_NAME,
+    SIGNATURE_REGISTRATION_QUEUED_SINCE_STARTUP_METRIC_HELP,
+    SIGNATURE_REGISTRATION_QUEUED_SINCE_STARTUP_METRIC_NAME,
     SIGNATURE_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_HELP,
     SIGNATURE_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_NAME,
     SIGNATURE_REGISTRATION_SUCCESS_SINCE_STARTUP_METRIC_HELP,
@@ -35,20 +43,44 @@ pub struct MetricsService {
     signature_registration_success_since_startup_counter: Box<Counter>,
     signature_registration_total_since_startup_counter: Box<Counter>,
     signature_registration_success_last_epoch_gauge: Box<Gauge>,
+    signature_registration_queued_since_startup_counter: Box<Counter>,
+    signature_registration_dropped_since_startup_counter: Box<Counter>,
     runtime_cycle_success_since_startup_counter: Box<Counter>,
     runtime_cycle_total_since_startup_counter: Box<Counter>,
+    cardano_transactions_pruned_since_startup_counter: Box<Counter>,
 }
 
 impl MetricsService {
     /// Create a new `MetricsService` instance.
     pub fn new() -> StdResult<Self> {
+        Self::new_with_registry(Registry::new(), HashMap::new())
+    }
+
+    /// Create a `MetricsService` instance for each of the given aggregator endpoints.
+    ///
+    /// All the instances share the same registry, so exporting the metrics of one of them
+    /// exports the metrics of all of them, each one labeled with its aggregator endpoint.
+    pub fn new_for_aggregators(aggregator_endpoints: &[String]) -> StdResult<Vec<Self>> {
         let registry = Registry::new();
 
+        aggregator_endpoints
+            .iter()
+            .map(|endpoint| {
+                Self::new_with_registry(
+                    registry.clone(),
+                    HashMap::from([(AGGREGATOR_LABEL.to_string(), endpoint.to_owned())]),
+                )
+            })
+            .collect()
+    }
+
+    fn new_with_registry(registry: Registry, labels: HashMap<String, String>) -> StdResult<Self> {
         // Signer registration metrics
         let signer_registration_success_since_startup_counter =
             Box::new(Self::create_metric_counter(
                 SIGNER_REGISTRATION_SUCCESS_SINCE_STARTUP_METRIC_NAME,
                 SIGNER_REGISTRATION_SUCCESS_SINCE_STARTUP_METRIC_HELP,
+                &labels,
             )?);
         registry.register(signer_registration_success_since_startup_counter.clone())?;
 
@@ -56,12 +88,14 @@ impl MetricsService {
             Box::new(Self::create_metric_counter(
                 SIGNER_REGISTRATION_TOTAL_SINCE_STARTUP_METRIC_NAME,
                 SIGNER_REGISTRATION_TOTAL_SINCE_STARTUP_METRIC_HELP,
+                &labels,
             )?);
         registry.register(signer_registration_total_since_startup_counter.clone())?;
 
         let signer_registration_success_last_epoch_gauge = Box::new(Self::create_metric_gauge(
             SIGNER_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_NAME,
             SIGNER_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_HELP,
+            &labels,
         )?);
         registry.register(signer_registration_success_last_epoch_gauge.clone())?;
 
@@ -70,6 +104,7 @@ impl MetricsService {
             Box::new(Self::create_metric_counter(
                 SIGNATURE_REGISTRATION_SUCCESS_SINCE_STARTUP_METRIC_NAME,
                 SIGNATURE_REGISTRATION_SUCCESS_SINCE_STARTUP_METRIC_HELP,
+                &labels,
             )?);
         registry.register(signature_registration_success_since_startup_counter.clone())?;
 
@@ -77,12 +112,14 @@ impl MetricsService {
             Box::new(Self::create_metric_counter(
                 SIGNATURE_REGISTRATION_TOTAL_SINCE_STARTUP_METRIC_NAME,
                 SIGNATURE_REGISTRATION_TOTAL_SINCE_STARTUP_METRIC_HELP,
+                &labels,
             )?);
         registry.register(signature_registration_total_since_startup_counter.clone())?;
 
         let signature_registration_success_last_epoch_gauge = Box::new(Self::create_metric_gauge(
             SIGNATURE_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_NAME,
             SIGNATURE_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_HELP,
+            &labels,
         )?);
         registry.register(signature_registration_success_last_epoch_gauge.clone())?;
 
@@ -90,15 +127,43 @@ impl MetricsService {
         let runtime_cycle_success_since_startup_counter = Box::new(Self::create_metric_counter(
             RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_NAME,
             RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_HELP,
+            &labels,
         )?);
         registry.register(runtime_cycle_success_since_startup_counter.clone())?;
 
         let runtime_cycle_total_since_startup_counter = Box::new(Self::create_metric_counter(
             RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME,
             RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_HELP,
+            &labels,
         )?);
         registry.register(runtime_cycle_total_since_startup_counter.clone())?;
 
+        // Signature registration retry metrics
+        let signature_registration_queued_since_startup_counter =
+            Box::new(Self::create_metric_counter(
+                SIGNATURE_REGISTRATION_QUEUED_SINCE_STARTUP_METRIC_NAME,
+                SIGNATURE_REGISTRATION_QUEUED_SINCE_STARTUP_METRIC_HELP,
+                &labels,
+            )?);
+        registry.register(signature_registration_queued_since_startup_counter.clone())?;
+
+        let signature_registration_dropped_since_startup_counter =
+            Box::new(Self::create_metric_counter(
+                SIGNATURE_REGISTRATION_DROPPED_SINCE_STARTUP_METRIC_NAME,
+                SIGNATURE_REGISTRATION_DROPPED_SINCE_STARTUP_METRIC_HELP,
+                &labels,
+            )?);
+        registry.register(signature_registration_dropped_since_startup_counter.clone())?;
+
+        // Cardano transactions metrics
+        let cardano_transactions_pruned_since_startup_counter =
+            Box::new(Self::create_metric_counter(
+                CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_NAME,
+                CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_HELP,
+                &labels,
+            )?);
+        registry.register(cardano_transactions_pruned_since_startup_counter.clone())?;
+
         Ok(Self {
             registry,
             signer_registration_success_since_startup_counter,
@@ -107,20 +172,31 @@ impl MetricsService {
             signature_registration_success_since_startup_counter,
             signature_registration_total_since_startup_counter,
             signature_registration_success_last_epoch_gauge,
+            signature_registration_queued_since_startup_counter,
+            signature_registration_dropped_since_startup_counter,
             runtime_cycle_success_since_startup_counter,
             runtime_cycle_total_since_startup_counter,
+            cardano_transactions_pruned_since_startup_counter,
         })
     }
 
-    fn create_metric_counter(name: &MetricName, help: &str) -> StdResult<Counter> {
-        let counter_opts = Opts::new(name, help);
+    fn create_metric_counter(
+        name: &MetricName,
+        help: &str,
+        labels: &HashMap<String, String>,
+    ) -> StdResult<Counter> {
+        let counter_opts = Opts::new(name, help).const_labels(labels.to_owned());
         let counter = Counter::with_opts(counter_opts)?;
 
         Ok(counter)
     }
 
-    fn create_metric_gauge(name: &MetricName, help: &str) -> StdResult<Gauge> {
-        let gauge_opts = Opts::new(name, help);
+    fn create_metric_gauge(
+        name: &MetricName,
+        help: &str,
+        labels: &HashMap<String, String>,
+    ) -> StdResult<Gauge> {
+        let gauge_opts = Opts::new(name, help).const_labels(labels.to_owned());
         let gauge = Gauge::with_opts(gauge_opts)?;
 
         Ok(gauge)
@@ -225,6 +301,38 @@ impl MetricsService {
         )
     }
 
+    /// Increment the `signature_registration_queued_since_startup` counter.
+    pub fn signature_registration_queued_since_startup_counter_increment(&self) {
+        debug!(
+            "MetricsService: incrementing 'signature_registration_queued_since_startup' counter"
+        );
+        self.signature_registration_queued_since_startup_counter
+            .inc();
+    }
+
+    /// Get the `signature_registration_queued_since_startup` counter.
+    pub fn signature_registration_queued_since_startup_counter_get(&self) -> CounterValue {
+        self.signature_registration_queued_since_startup_counter
+            .get()
+            .round() as CounterValue
+    }
+
+    /// Increment the `signature_registration_dropped_since_startup` counter.
+    pub fn signature_registration_dropped_since_startup_counter_increment(&self) {
+        debug!(
+            "MetricsService: incrementing 'signature_registration_dropped_since_startup' counter"
+        );
+        self.signature_registration_dropped_since_startup_counter
+            .inc();
+    }
+
+    /// Get the `signature_registration_dropped_since_startup` counter.
+    pub fn signature_registration_dropped_since_startup_counter_get(&self) -> CounterValue {
+        self.signature_registration_dropped_since_startup_counter
+            .get()
+            .round() as CounterValue
+    }
+
     /// Increment the `runtime_cycle_total_since_startup` counter.
     pub fn runtime_cycle_total_since_startup_counter_increment(&self) {
         debug!("MetricsService: incrementing 'runtime_cycle_total_since_startup' counter");
@@ -248,6 +356,20 @@ impl MetricsService {
             .get()
             .round() as CounterValue
     }
+
+    /// Increment the `cardano_transactions_pruned_since_startup` counter by the given value.
+    pub fn cardano_transactions_pruned_since_startup_counter_increment_by(&self, value: u64) {
+        debug!("MetricsService: incrementing 'cardano_transactions_pruned_since_startup' counter by {value}");
+        self.cardano_transactions_pruned_since_startup_counter
+            .inc_by(value as f64);
+    }
+
+    /// Get the `cardano_transactions_pruned_since_startup` counter.
+    pub fn cardano_transactions_pruned_since_startup_counter_get(&self) -> CounterValue {
+        self.cardano_transactions_pruned_since_startup_counter
+            .get()
+            .round() as CounterValue
+    }
 }
 
 #[cfg(test)]
@@ -275,6 +397,10 @@ mod tests {
         let parsed_metrics = parse_metrics(&exported_metrics).unwrap();
 
         let parsed_metrics_expected = BTreeMap::from([
+            (
+                CARDANO_TRANSACTIONS_PRUNED_SINCE_STARTUP_METRIC_NAME.to_string(),
+                Value::Counter(0.0),
+            ),
             (
                 RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_NAME.to_string(),
                 Value::Counter(0.0),
@@ -283,6 +409,14 @@ mod tests {
                 RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME.to_string(),
                 Value::Counter(0.0),
             ),
+            (
+                SIGNATURE_REGISTRATION_DROPPED_SINCE_STARTUP_METRIC_NAME.to_string(),
+                Value::Counter(0.0),
+            ),
+            (
+                SIGNATURE_REGISTRATION_QUEUED_SINCE_STARTUP_METRIC_NAME.to_string(),
+                Value::Counter(0.0),
+            ),
             (
                 SIGNATURE_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_NAME.to_string(),
                 Value::Gauge(0.0),
@@ -311,6 +445,34 @@ mod tests {
         assert_eq!(parsed_metrics_expected, parsed_metrics);
     }
 
+    #[test]
+    fn test_export_metrics_of_several_aggregators() {
+        let metrics_services = MetricsService::new_for_aggregators(&[
+            "http://aggregator-a".to_string(),
+            "http://aggregator-b".to_string(),
+        ])
+        .unwrap();
+        metrics_services[1].runtime_cycle_total_since_startup_counter_increment();
+
+        let exported_metrics = metrics_services[0].export_metrics().unwrap();
+
+        let samples: Vec<(String, Value)> =
+            prometheus_parse::Scrape::parse(exported_metrics.lines().map(|s| Ok(s.to_owned())))
+                .unwrap()
+                .samples
+                .into_iter()
+                .filter(|s| s.metric == RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME)
+                .map(|s| (s.labels[AGGREGATOR_LABEL].to_string(), s.value))
+                .collect();
+        assert_eq!(
+            vec![
+                ("http://aggregator-a".to_string(), Value::Counter(0.0)),
+                ("http://aggregator-b".to_string(), Value::Counter(1.0)),
+            ],
+            samples
+        );
+    }
+
     #[test]
     fn test_signer_registration_success_since_startup_counter_increment() {
         let metrics_service = MetricsService::new().unwrap();
@@ -430,4 +592,50 @@ mod tests {
             metrics_service.runtime_cycle_total_since_startup_counter_get(),
         );
     }
+
+    #[test]
+    fn test_signature_registration_queued_since_startup_counter_increment() {
+        let metrics_service = MetricsService::new().unwrap();
+        assert_eq!(
+            0,
+            metrics_service.signature_registration_queued_since_startup_counter_get(),
+        );
+
+        metrics_service.signature_registration_queued_since_startup_counter_increment();
+        assert_eq!(
+            1,
+            metrics_service.signature_registration_queued_since_startup_counter_get(),
+        );
+    }
+
+    #[test]
+    fn test_signature_registration_dropped_since_startup_counter_increment() {
+        let metrics_service = MetricsService::new().unwrap();
+        assert_eq!(
+            0,
+            metrics_service.signature_registration_dropped_since_startup_counter_get(),
+        );
+
+        metrics_service.signature_registration_dropped_since_startup_counter_increment();
+        assert_eq!(
+            1,
+            metrics_service.signature_registration_dropped_since_startup_counter_get(),
+        );
+    }
+
+    #[test]
+    fn test_cardano_transactions_pruned_since_startup_counter_increment_by() {
+        let metrics_service = MetricsService::new().unwrap();
+        assert_eq!(
+            0,
+            metrics_service.cardano_transactions_pruned_since_startup_counter_get(),
+        );
+
+        metrics_service.cardano_transactions_pruned_since_startup_counter_increment_by(15);
+        metrics_service.cardano_transactions_pruned_since_startup_counter_increment_by(10);
+        assert_eq!(
+            25,
+            metrics_service.cardano_transactions_pruned_since_startup_counter_get(),
+        );
+    }
 }
@@ -6,10 +6,13 @@ use thiserror::Error;
 #[cfg(test)]
 use mockall::automock;
 
-use mithril_common::crypto_helper::{KESPeriod, OpCert, ProtocolOpCert, SerDeShelleyFileFormat};
+use mithril_common::crypto_helper::{
+    KESPeriod, OpCert, ProtocolInitializer, ProtocolOpCert, SerDeShelleyFileFormat,
+};
 use mithril_common::entities::{
     CertificatePending, Epoch, EpochSettings, PartyId, ProtocolMessage, ProtocolMessagePartKey,
-    ProtocolParameters, SignedEntityType, Signer, SignerWithStake, SingleSignatures, TimePoint,
+    ProtocolParameters, SignedEntityType, Signer, SignerWithStake, SingleSignatures, Stake,
+    TimePoint,
 };
 use mithril_common::StdResult;
 use mithril_persistence::store::StakeStorer;
@@ -73,6 +76,9 @@ pub trait Runner: Send + Sync {
         maybe_signature: Option<SingleSignatures>,
     ) -> StdResult<()>;
 
+    /// Retry to send the single signatures that could not be sent to the aggregator yet.
+    async fn retry_pending_single_signatures(&self, current_epoch: Epoch) -> StdResult<()>;
+
     /// Read the current era and update the EraChecker.
     async fn update_era_checker(&self, epoch: Epoch) -> StdResult<()>;
 }
@@ -105,6 +111,56 @@ impl SignerRunner {
     pub fn new(config: Configuration, services: SignerServices) -> Self {
         Self { services, config }
     }
+
+    /// Compute the signer to register to the aggregator, with the protocol initializer used to
+    /// sign once registered, without sending anything to the aggregator.
+    async fn compute_signer_registration(
+        &self,
+        stake: &Stake,
+        protocol_parameters: &ProtocolParameters,
+    ) -> StdResult<(Signer, ProtocolInitializer)> {
+        let (operational_certificate, protocol_operational_certificate) = match &self
+            .config
+            .operational_certificate_path
+        {
+            Some(operational_certificate_path) => {
+                let opcert: OpCert = OpCert::from_file(operational_certificate_path)
+                    .map_err(|_| RunnerError::FileParse("operational_certificate_path".to_string()))
+                    .with_context(|| {
+                        "compute_signer_registration can not decode OpCert from file"
+                    })?;
+                (Some(opcert.clone()), Some(ProtocolOpCert::new(opcert)))
+            }
+            _ => (None, None),
+        };
+
+        let kes_period = match operational_certificate {
+            Some(operational_certificate) => Some(
+                self.services
+                    .chain_observer
+                    .get_current_kes_period(&operational_certificate)
+                    .await?
+                    .unwrap_or_default()
+                    - operational_certificate.start_kes_period as KESPeriod,
+            ),
+            None => None,
+        };
+        let protocol_initializer = MithrilProtocolInitializerBuilder::build(
+            stake,
+            protocol_parameters,
+            self.config.kes_secret_key_path.clone(),
+            kes_period,
+        )?;
+        let signer = Signer::new(
+            self.services.single_signer.get_party_id(),
+            protocol_initializer.verification_key().into(),
+            protocol_initializer.verification_key_signature(),
+            protocol_operational_certificate,
+            kes_period,
+        );
+
+        Ok((signer, protocol_initializer))
+    }
 }
 
 #[cfg_attr(test, automock)]
@@ -166,45 +222,9 @@ impl Runner for SignerRunner {
         let stake = stake_distribution
             .get(&self.services.single_signer.get_party_id())
             .ok_or_else(RunnerError::NoStakeForSelf)?;
-        let (operational_certificate, protocol_operational_certificate) = match &self
-            .config
-            .operational_certificate_path
-        {
-            Some(operational_certificate_path) => {
-                let opcert: OpCert = OpCert::from_file(operational_certificate_path)
-                    .map_err(|_| RunnerError::FileParse("operational_certificate_path".to_string()))
-                    .with_context(|| {
-                        "register_signer_to_aggregator can not decode OpCert from file"
-                    })?;
-                (Some(opcert.clone()), Some(ProtocolOpCert::new(opcert)))
-            }
-            _ => (None, None),
-        };
-
-        let kes_period = match operational_certificate {
-            Some(operational_certificate) => Some(
-                self.services
-                    .chain_observer
-                    .get_current_kes_period(&operational_certificate)
-                    .await?
-                    .unwrap_or_default()
-                    - operational_certificate.start_kes_period as KESPeriod,
-            ),
-            None => None,
-        };
-        let protocol_initializer = MithrilProtocolInitializerBuilder::build(
-            stake,
-            protocol_parameters,
-            self.config.kes_secret_key_path.clone(),
-            kes_period,
-        )?;
-        let signer = Signer::new(
-            self.services.single_signer.get_party_id(),
-            protocol_initializer.verification_key().into(),
-            protocol_initializer.verification_key_signature(),
-            protocol_operational_certificate,
-            kes_period,
-        );
+        let (signer, protocol_initializer) = self
+            .compute_signer_registration(stake, protocol_parameters)
+            .await?;
         self.services
             .certificate_handler
             .register_signer(epoch_offset_to_recording_epoch, &signer)
@@ -410,8 +430,8 @@ impl Runner for SignerRunner {
             debug!(" > there is a single signature to send");
 
             self.services
-                .certificate_handler
-                .register_signatures(signed_entity_type, &single_signatures)
+                .single_signature_publisher
+                .publish(signed_entity_type, &single_signatures)
                 .await?;
 
             Ok(())
@@ -422,6 +442,15 @@ impl Runner for SignerRunner {
         }
     }
 
+    async fn retry_pending_single_signatures(&self, current_epoch: Epoch) -> StdResult<()> {
+        debug!("RUNNER: retry_pending_single_signatures");
+
+        self.services
+            .single_signature_publisher
+            .retry_pending(current_epoch)
+            .await
+    }
+
     async fn update_era_checker(&self, epoch: Epoch) -> StdResult<()> {
         debug!("RUNNER: update_era_checker");
 
@@ -452,6 +481,7 @@ impl Runner for SignerRunner {
 
 #[cfg(test)]
 mod tests {
+    use anyhow::anyhow;
     use mithril_common::{
         api_version::APIVersionProvider,
         cardano_block_scanner::DumbBlockScanner,
@@ -465,8 +495,8 @@ mod tests {
         },
         signable_builder::{
             BlockRangeRootRetriever, CardanoImmutableFilesFullSignableBuilder,
-            CardanoTransactionsSignableBuilder, MithrilSignableBuilderService,
-            MithrilStakeDistributionSignableBuilder,
+            CardanoStakeDistributionSignableBuilder, CardanoTransactionsSignableBuilder,
+            MithrilSignableBuilderService, MithrilStakeDistributionSignableBuilder,
         },
         test_utils::{fake_data, MithrilFixtureBuilder},
         TimePointProvider, TimePointProviderImpl,
@@ -477,12 +507,16 @@ mod tests {
     use std::{
         path::{Path, PathBuf},
         sync::Arc,
+        time::Duration,
     };
 
     use crate::{
-        metrics::MetricsService, AggregatorClient, CardanoTransactionsImporter,
-        DumbAggregatorClient, MithrilSingleSigner, MockAggregatorClient, MockTransactionStore,
-        ProtocolInitializerStore, SingleSigner,
+        database::{repository::PendingSingleSignatureRepository, test_utils::main_db_connection},
+        metrics::MetricsService,
+        AggregatorClient, AggregatorClientError, CardanoTransactionsImporter, DumbAggregatorClient,
+        MithrilSingleSigner, MockAggregatorClient, MockSingleSignaturePublisher,
+        MockTransactionStore, PendingSingleSignatureStore, ProtocolInitializerStore,
+        SingleSignaturePublisherWithRetry, SingleSigner,
     };
 
     use super::*;
@@ -567,16 +601,30 @@ mod tests {
             block_range_root_retriever,
             slog_scope::logger(),
         ));
+        let stake_store = Arc::new(StakeStore::new(Box::new(DumbStoreAdapter::new()), None));
+        let cardano_stake_distribution_signable_builder = Arc::new(
+            CardanoStakeDistributionSignableBuilder::new(stake_store.clone()),
+        );
         let signable_builder_service = Arc::new(MithrilSignableBuilderService::new(
             mithril_stake_distribution_signable_builder,
             cardano_immutable_signable_builder,
             cardano_transactions_builder,
+            cardano_stake_distribution_signable_builder,
         ));
         let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let certificate_handler = Arc::new(DumbAggregatorClient::default());
+        let single_signature_publisher = Arc::new(SingleSignaturePublisherWithRetry::new(
+            certificate_handler.clone(),
+            Arc::new(PendingSingleSignatureRepository::new(Arc::new(
+                main_db_connection().unwrap(),
+            ))),
+            metrics_service.clone(),
+            slog_scope::logger(),
+        ));
 
         SignerServices {
-            stake_store: Arc::new(StakeStore::new(Box::new(DumbStoreAdapter::new()), None)),
-            certificate_handler: Arc::new(DumbAggregatorClient::default()),
+            stake_store,
+            certificate_handler,
             chain_observer,
             digester,
             single_signer: Arc::new(MithrilSingleSigner::new(party_id)),
@@ -590,6 +638,7 @@ mod tests {
             api_version_provider,
             signable_builder_service,
             metrics_service,
+            single_signature_publisher,
         }
     }
 
@@ -600,6 +649,7 @@ mod tests {
         let services = init_services().await;
         let config = Configuration {
             aggregator_endpoint: "http://0.0.0.0:3000".to_string(),
+            additional_aggregator_endpoints: None,
             relay_endpoint: None,
             cardano_cli_path: PathBuf::new(),
             cardano_node_socket_path: PathBuf::new(),
@@ -611,6 +661,8 @@ mod tests {
             data_stores_directory: PathBuf::new(),
             kes_secret_key_path: None,
             operational_certificate_path: None,
+            store_encryption_passphrase: None,
+            store_encryption_key_path: None,
             disable_digests_cache: false,
             store_retention_limit: None,
             reset_digests_cache: false,
@@ -620,6 +672,9 @@ mod tests {
             metrics_server_ip: "0.0.0.0".to_string(),
             metrics_server_port: 9090,
             allow_unparsable_block: false,
+            enable_transaction_pruning: false,
+            transactions_pruning_retained_block_ranges: 150,
+            enable_chain_sync_block_scanner: false,
         };
 
         SignerRunner::new(
@@ -906,12 +961,12 @@ mod tests {
     #[tokio::test]
     async fn test_send_single_signature() {
         let mut services = init_services().await;
-        let mut certificate_handler = MockAggregatorClient::new();
-        certificate_handler
-            .expect_register_signatures()
+        let mut single_signature_publisher = MockSingleSignaturePublisher::new();
+        single_signature_publisher
+            .expect_publish()
             .once()
             .returning(|_, _| Ok(()));
-        services.certificate_handler = Arc::new(certificate_handler);
+        services.single_signature_publisher = Arc::new(single_signature_publisher);
         let runner = init_runner(Some(services), None).await;
 
         runner
@@ -923,6 +978,72 @@ mod tests {
             .expect("send_single_signature should not fail");
     }
 
+    #[tokio::test]
+    async fn test_send_single_signature_queue_the_signature_when_the_aggregator_is_unreachable() {
+        let mut services = init_services().await;
+        let mut certificate_handler = MockAggregatorClient::new();
+        let mut register_attempts = 0;
+        certificate_handler
+            .expect_register_signatures()
+            .times(3)
+            .returning(move |_, _| {
+                register_attempts += 1;
+                // The aggregator is back online on the third attempt
+                if register_attempts < 3 {
+                    Err(AggregatorClientError::RemoteServerUnreachable(anyhow!(
+                        "unreachable"
+                    )))
+                } else {
+                    Ok(())
+                }
+            });
+        let pending_store = Arc::new(PendingSingleSignatureRepository::new(Arc::new(
+            main_db_connection().unwrap(),
+        )));
+        services.single_signature_publisher = Arc::new(
+            SingleSignaturePublisherWithRetry::new(
+                Arc::new(certificate_handler),
+                pending_store.clone(),
+                services.metrics_service.clone(),
+                slog_scope::logger(),
+            )
+            .with_retry_delays(Duration::ZERO, Duration::ZERO),
+        );
+        let runner = init_runner(Some(services), None).await;
+        let signed_entity_type = SignedEntityType::dummy();
+
+        runner
+            .send_single_signature(
+                &signed_entity_type,
+                Some(fake_data::single_signatures(vec![2, 5, 12])),
+            )
+            .await
+            .expect("send_single_signature should not fail when the aggregator is unreachable");
+        assert_eq!(
+            1,
+            pending_store
+                .get_pending_single_signatures()
+                .await
+                .unwrap()
+                .len()
+        );
+
+        for expected_pending_count in [1, 0] {
+            runner
+                .retry_pending_single_signatures(signed_entity_type.get_epoch())
+                .await
+                .expect("retry_pending_single_signatures should not fail");
+            assert_eq!(
+                expected_pending_count,
+                pending_store
+                    .get_pending_single_signatures()
+                    .await
+                    .unwrap()
+                    .len()
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_update_era_checker() {
         let services = init_services().await;
@@ -1,37 +1,43 @@
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
+use slog_scope::{info, warn};
 use std::{fs, sync::Arc, time::Duration};
 
 use mithril_common::{
     api_version::APIVersionProvider,
-    cardano_block_scanner::CardanoBlockScanner,
+    cardano_block_scanner::{BlockScanner, CardanoBlockScanner, ChainSyncBlockScanner},
     chain_observer::{CardanoCliRunner, ChainObserver, ChainObserverBuilder, ChainObserverType},
-    crypto_helper::{OpCert, ProtocolPartyId, SerDeShelleyFileFormat},
+    crypto_helper::{OpCert, ProtocolInitializer, ProtocolPartyId, SerDeShelleyFileFormat},
     digesters::{
         cache::{ImmutableFileDigestCacheProvider, JsonImmutableFileDigestCacheProviderBuilder},
         CardanoImmutableDigester, ImmutableDigester, ImmutableFileObserver,
         ImmutableFileSystemObserver,
     },
+    entities::Epoch,
     era::{EraChecker, EraReader},
     signable_builder::{
-        CardanoImmutableFilesFullSignableBuilder, CardanoTransactionsSignableBuilder,
-        MithrilSignableBuilderService, MithrilStakeDistributionSignableBuilder,
-        SignableBuilderService,
+        CardanoImmutableFilesFullSignableBuilder, CardanoStakeDistributionSignableBuilder,
+        CardanoTransactionsSignableBuilder, MithrilSignableBuilderService,
+        MithrilStakeDistributionSignableBuilder, SignableBuilderService, TransactionsImporter,
     },
     StdResult, TimePointProvider, TimePointProviderImpl,
 };
 use mithril_persistence::{
     database::{ApplicationNodeType, SqlMigration},
-    sqlite::{ConnectionBuilder, SqliteConnection},
+    sqlite::{ConnectionBuilder, ConnectionOptions, SqliteConnection},
     store::{adapter::SQLiteAdapter, StakeStore},
 };
 
 use crate::{
-    aggregator_client::AggregatorClient, database::repository::CardanoTransactionRepository,
-    metrics::MetricsService, single_signer::SingleSigner, AggregatorHTTPClient,
-    CardanoTransactionsImporter, Configuration, MithrilSingleSigner, ProtocolInitializerStore,
-    ProtocolInitializerStorer, HTTP_REQUEST_TIMEOUT_DURATION, SQLITE_FILE,
-    SQLITE_FILE_CARDANO_TRANSACTION,
+    aggregator_client::AggregatorClient,
+    database::repository::{CardanoTransactionRepository, PendingSingleSignatureRepository},
+    metrics::MetricsService,
+    single_signer::SingleSigner,
+    AggregatorHTTPClient, CardanoTransactionsImporter, Configuration, EncryptedStoreAdapter,
+    MithrilSingleSigner, ProtocolInitializerStore, ProtocolInitializerStorer,
+    SingleSignaturePublisher, SingleSignaturePublisherWithRetry, StoreCipher,
+    TransactionsImporterWithLock, TransactionsImporterWithPruner, HTTP_REQUEST_TIMEOUT_DURATION,
+    SQLITE_FILE, SQLITE_FILE_CARDANO_TRANSACTION,
 };
 
 type StakeStoreService = Arc<StakeStore>;
@@ -41,13 +47,15 @@ type DigesterService = Arc<dyn ImmutableDigester>;
 type SingleSignerService = Arc<dyn SingleSigner>;
 type TimePointProviderService = Arc<dyn TimePointProvider>;
 type ProtocolInitializerStoreService = Arc<dyn ProtocolInitializerStorer>;
+type SingleSignaturePublisherService = Arc<dyn SingleSignaturePublisher>;
 
 /// The ServiceBuilder is intended to manage Services instance creation.
 /// The goal of this is to put all this code out of the way of business code.
 #[async_trait]
 pub trait ServiceBuilder {
-    /// Create a SignerService instance.
-    async fn build(&self) -> StdResult<SignerServices>;
+    /// Create a SignerService instance for each aggregator the signer contributes to, the first
+    /// one being the main aggregator.
+    async fn build(&self) -> StdResult<Vec<SignerServices>>;
 }
 
 /// Create a SignerService instance for Production environment.
@@ -80,7 +88,8 @@ impl<'a> ProductionServiceBuilder<'a> {
                     cardano_node_socket_path,
                     cardano_network,
                     Some(cardano_cli_runner),
-                );
+                )
+                .with_logger(slog_scope::logger());
 
                 chain_observer_builder
                     .build()
@@ -124,7 +133,7 @@ impl<'a> ProductionServiceBuilder<'a> {
     }
 
     /// Compute protocol party id
-    fn compute_protocol_party_id(&self) -> StdResult<ProtocolPartyId> {
+    pub fn compute_protocol_party_id(&self) -> StdResult<ProtocolPartyId> {
         match &self.config.operational_certificate_path {
             Some(operational_certificate_path) => {
                 let opcert: OpCert = OpCert::from_file(operational_certificate_path)
@@ -141,6 +150,26 @@ impl<'a> ProductionServiceBuilder<'a> {
         }
     }
 
+    /// Build the chain observer.
+    pub fn build_chain_observer(&self) -> StdResult<ChainObserverService> {
+        let builder = self.chain_observer_builder;
+        builder(self.config)
+    }
+
+    /// Name of the SQLite file holding the stores of the given aggregator.
+    pub fn get_aggregator_sqlite_file_name(&self, aggregator_endpoint: &str) -> String {
+        if aggregator_endpoint == self.config.aggregator_endpoint {
+            SQLITE_FILE.to_string()
+        } else {
+            additional_aggregator_sqlite_file_name(aggregator_endpoint)
+        }
+    }
+
+    /// Name of the immutables digests cache file, in the data stores directory.
+    pub fn get_digests_cache_file_name(&self) -> String {
+        format!("immutables_digests_{}.json", self.config.network)
+    }
+
     async fn build_digester_cache_provider(
         &self,
     ) -> StdResult<Option<Arc<dyn ImmutableFileDigestCacheProvider>>> {
@@ -150,7 +179,7 @@ impl<'a> ProductionServiceBuilder<'a> {
 
         let cache_provider = JsonImmutableFileDigestCacheProviderBuilder::new(
             &self.config.data_stores_directory,
-            &format!("immutables_digests_{}.json", self.config.network),
+            &self.get_digests_cache_file_name(),
         )
         .should_reset_digests_cache(self.config.reset_digests_cache)
         .with_logger(slog_scope::logger())
@@ -176,12 +205,71 @@ impl<'a> ProductionServiceBuilder<'a> {
 
         Ok(Arc::new(connection))
     }
+
+    /// Build a read only SQLite connection to an existing database, without running its
+    /// migrations.
+    pub fn build_read_only_sqlite_connection(
+        &self,
+        sqlite_file_name: &str,
+    ) -> StdResult<Arc<SqliteConnection>> {
+        let sqlite_db_path = self.config.data_stores_directory.join(sqlite_file_name);
+        if !sqlite_db_path.exists() {
+            return Err(anyhow!(
+                "Database `{}` does not exist",
+                sqlite_db_path.display()
+            ));
+        }
+        let connection = ConnectionBuilder::open_file(&sqlite_db_path)
+            .with_options(&[ConnectionOptions::ReadOnly])
+            .with_logger(slog_scope::logger())
+            .build()
+            .with_context(|| "Database connection initialisation error")?;
+
+        Ok(Arc::new(connection))
+    }
+
+    /// Build the adapter of the protocol initializers store, encrypting the protocol
+    /// initializers with the given cipher if any.
+    pub fn build_protocol_initializer_adapter(
+        &self,
+        sqlite_connection: Arc<SqliteConnection>,
+        cipher: Option<Arc<StoreCipher>>,
+    ) -> StdResult<EncryptedStoreAdapter<Epoch, ProtocolInitializer>> {
+        Ok(EncryptedStoreAdapter::new(
+            Box::new(SQLiteAdapter::new(
+                "protocol_initializer",
+                sqlite_connection,
+            )?),
+            cipher,
+        ))
+    }
+
+    async fn build_protocol_initializer_store(
+        &self,
+        sqlite_connection: Arc<SqliteConnection>,
+        cipher: Option<Arc<StoreCipher>>,
+    ) -> StdResult<ProtocolInitializerStore> {
+        let mut adapter = self.build_protocol_initializer_adapter(sqlite_connection, cipher)?;
+        // Protocol initializers stored before the encryption was enabled are migrated
+        let nb_encrypted_records = adapter
+            .encrypt_plain_records()
+            .await
+            .with_context(|| "Could not encrypt the stored protocol initializers")?;
+        if nb_encrypted_records > 0 {
+            info!("Encrypted the protocol initializers stored in plain"; "nb_records" => nb_encrypted_records);
+        }
+
+        Ok(ProtocolInitializerStore::new(
+            Box::new(adapter),
+            self.config.store_retention_limit,
+        ))
+    }
 }
 
 #[async_trait]
 impl<'a> ServiceBuilder for ProductionServiceBuilder<'a> {
-    /// Build a Services for the Production environment.
-    async fn build(&self) -> StdResult<SignerServices> {
+    /// Build a Services for the Production environment, one for each aggregator.
+    async fn build(&self) -> StdResult<Vec<SignerServices>> {
         if !self.config.data_stores_directory.exists() {
             fs::create_dir_all(self.config.data_stores_directory.clone()).with_context(|| {
                 format!(
@@ -191,9 +279,16 @@ impl<'a> ServiceBuilder for ProductionServiceBuilder<'a> {
             })?;
         }
 
-        let sqlite_connection = self
-            .build_sqlite_connection(SQLITE_FILE, crate::database::migration::get_migrations())
-            .await?;
+        let aggregator_endpoints = self.config.get_aggregator_endpoints();
+        // Keep the metrics unlabeled when there's a single aggregator
+        let metrics_services: Vec<Arc<MetricsService>> = if aggregator_endpoints.len() == 1 {
+            vec![Arc::new(MetricsService::new()?)]
+        } else {
+            MetricsService::new_for_aggregators(&aggregator_endpoints)?
+                .into_iter()
+                .map(Arc::new)
+                .collect()
+        };
         let transaction_sqlite_connection = self
             .build_sqlite_connection(
                 SQLITE_FILE_CARDANO_TRANSACTION,
@@ -201,26 +296,12 @@ impl<'a> ServiceBuilder for ProductionServiceBuilder<'a> {
             )
             .await?;
 
-        let protocol_initializer_store = Arc::new(ProtocolInitializerStore::new(
-            Box::new(SQLiteAdapter::new(
-                "protocol_initializer",
-                sqlite_connection.clone(),
-            )?),
-            self.config.store_retention_limit,
-        ));
         let single_signer = Arc::new(MithrilSingleSigner::new(self.compute_protocol_party_id()?));
         let digester = Arc::new(CardanoImmutableDigester::new(
             self.build_digester_cache_provider().await?,
             slog_scope::logger(),
         ));
-        let stake_store = Arc::new(StakeStore::new(
-            Box::new(SQLiteAdapter::new("stake", sqlite_connection)?),
-            self.config.store_retention_limit,
-        ));
-        let chain_observer = {
-            let builder = self.chain_observer_builder;
-            builder(self.config)?
-        };
+        let chain_observer = self.build_chain_observer()?;
         let time_point_provider = {
             let builder = self.immutable_file_observer_builder;
             Arc::new(TimePointProviderImpl::new(
@@ -242,12 +323,6 @@ impl<'a> ServiceBuilder for ProductionServiceBuilder<'a> {
         ));
 
         let api_version_provider = Arc::new(APIVersionProvider::new(era_checker.clone()));
-        let certificate_handler = Arc::new(AggregatorHTTPClient::new(
-            self.config.aggregator_endpoint.clone(),
-            self.config.relay_endpoint.clone(),
-            api_version_provider.clone(),
-            Some(Duration::from_millis(HTTP_REQUEST_TIMEOUT_DURATION)),
-        ));
 
         let cardano_immutable_snapshot_builder =
             Arc::new(CardanoImmutableFilesFullSignableBuilder::new(
@@ -257,55 +332,152 @@ impl<'a> ServiceBuilder for ProductionServiceBuilder<'a> {
             ));
         let mithril_stake_distribution_signable_builder =
             Arc::new(MithrilStakeDistributionSignableBuilder::default());
-        let block_scanner = Arc::new(CardanoBlockScanner::new(
-            slog_scope::logger(),
-            self.config
-                .get_network()?
-                .compute_allow_unparsable_block(self.config.allow_unparsable_block)?,
-        ));
+        let allow_unparsable_block = self
+            .config
+            .get_network()?
+            .compute_allow_unparsable_block(self.config.allow_unparsable_block)?;
+        let block_scanner: Arc<dyn BlockScanner> = if self.config.enable_chain_sync_block_scanner {
+            Arc::new(ChainSyncBlockScanner::new(
+                &self.config.cardano_node_socket_path,
+                self.config.get_network()?,
+                allow_unparsable_block,
+                slog_scope::logger(),
+            ))
+        } else {
+            Arc::new(CardanoBlockScanner::new(
+                slog_scope::logger(),
+                allow_unparsable_block,
+            ))
+        };
         let transaction_store = Arc::new(CardanoTransactionRepository::new(
             transaction_sqlite_connection,
         ));
-        let transactions_importer = CardanoTransactionsImporter::new(
-            block_scanner,
-            transaction_store.clone(),
-            &self.config.db_directory,
-            // Rescan the last immutable when importing transactions, it may have been partially imported
-            Some(1),
-            slog_scope::logger(),
-        );
+        let transactions_importer: Arc<dyn TransactionsImporter> =
+            Arc::new(CardanoTransactionsImporter::new(
+                block_scanner,
+                transaction_store.clone(),
+                &self.config.db_directory,
+                // Rescan the last immutable when importing transactions, it may have been partially imported
+                Some(1),
+                slog_scope::logger(),
+            ));
+        let transactions_importer: Arc<dyn TransactionsImporter> =
+            if self.config.enable_transaction_pruning {
+                Arc::new(TransactionsImporterWithPruner::new(
+                    self.config.transactions_pruning_retained_block_ranges,
+                    transaction_store.clone(),
+                    transactions_importer,
+                    // The transactions are shared by all the aggregators, their metrics are
+                    // recorded with the ones of the main aggregator.
+                    metrics_services[0].clone(),
+                    slog_scope::logger(),
+                ))
+            } else {
+                transactions_importer
+            };
+        // The importer is shared by the state machines of all the aggregators
+        let transactions_importer =
+            Arc::new(TransactionsImporterWithLock::new(transactions_importer));
         let block_range_root_retriever = transaction_store.clone();
         let cardano_transactions_builder = Arc::new(CardanoTransactionsSignableBuilder::new(
-            Arc::new(transactions_importer),
+            transactions_importer,
             block_range_root_retriever,
             slog_scope::logger(),
         ));
-        let signable_builder_service = Arc::new(MithrilSignableBuilderService::new(
-            mithril_stake_distribution_signable_builder,
-            cardano_immutable_snapshot_builder,
-            cardano_transactions_builder,
-        ));
-        let metrics_service = Arc::new(MetricsService::new().unwrap());
-
-        let services = SignerServices {
-            time_point_provider,
-            certificate_handler,
-            chain_observer,
-            digester,
-            single_signer,
-            stake_store,
-            protocol_initializer_store,
-            era_checker,
-            era_reader,
-            api_version_provider,
-            signable_builder_service,
-            metrics_service,
-        };
+
+        let store_cipher = self.config.build_store_cipher()?;
+        if store_cipher.is_none() {
+            warn!("The protocol initializers are stored unencrypted, set `store_encryption_passphrase` or `store_encryption_key_path` to encrypt them");
+        }
+
+        let mut services = vec![];
+        for (index, (aggregator_endpoint, metrics_service)) in aggregator_endpoints
+            .into_iter()
+            .zip(metrics_services)
+            .enumerate()
+        {
+            // The relay forwards the messages to the main aggregator only
+            let relay_endpoint = if index == 0 {
+                self.config.relay_endpoint.clone()
+            } else {
+                None
+            };
+            // Each aggregator has its own stores since the signer registers to them with
+            // distinct protocol initializers.
+            let sqlite_connection = self
+                .build_sqlite_connection(
+                    &self.get_aggregator_sqlite_file_name(&aggregator_endpoint),
+                    crate::database::migration::get_migrations(),
+                )
+                .await?;
+            let protocol_initializer_store = Arc::new(
+                self.build_protocol_initializer_store(
+                    sqlite_connection.clone(),
+                    store_cipher.clone(),
+                )
+                .await?,
+            );
+            let stake_store = Arc::new(StakeStore::new(
+                Box::new(SQLiteAdapter::new("stake", sqlite_connection.clone())?),
+                self.config.store_retention_limit,
+            ));
+            let certificate_handler = Arc::new(AggregatorHTTPClient::new(
+                aggregator_endpoint,
+                relay_endpoint,
+                api_version_provider.clone(),
+                Some(Duration::from_millis(HTTP_REQUEST_TIMEOUT_DURATION)),
+            ));
+            let single_signature_publisher = Arc::new(SingleSignaturePublisherWithRetry::new(
+                certificate_handler.clone(),
+                Arc::new(PendingSingleSignatureRepository::new(sqlite_connection)),
+                metrics_service.clone(),
+                slog_scope::logger(),
+            ));
+            let cardano_stake_distribution_signable_builder = Arc::new(
+                CardanoStakeDistributionSignableBuilder::new(stake_store.clone()),
+            );
+            let signable_builder_service = Arc::new(MithrilSignableBuilderService::new(
+                mithril_stake_distribution_signable_builder.clone(),
+                cardano_immutable_snapshot_builder.clone(),
+                cardano_transactions_builder.clone(),
+                cardano_stake_distribution_signable_builder,
+            ));
+
+            services.push(SignerServices {
+                time_point_provider: time_point_provider.clone(),
+                certificate_handler,
+                chain_observer: chain_observer.clone(),
+                digester: digester.clone(),
+                single_signer: single_signer.clone(),
+                stake_store,
+                protocol_initializer_store,
+                era_checker: era_checker.clone(),
+                era_reader: era_reader.clone(),
+                api_version_provider: api_version_provider.clone(),
+                signable_builder_service,
+                metrics_service,
+                single_signature_publisher,
+            });
+        }
 
         Ok(services)
     }
 }
 
+/// Name of the SQLite file holding the stores of an additional aggregator, derived from its
+/// endpoint so the stores are kept if the list of aggregators changes.
+fn additional_aggregator_sqlite_file_name(aggregator_endpoint: &str) -> String {
+    let endpoint = aggregator_endpoint
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let sanitized_endpoint: String = endpoint
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+
+    format!("signer-{}.sqlite3", sanitized_endpoint.trim_matches('-'))
+}
+
 /// This structure groups all the services required by the state machine.
 pub struct SignerServices {
     /// Time point provider service
@@ -343,6 +515,9 @@ pub struct SignerServices {
 
     /// Metrics service
     pub metrics_service: Arc<MetricsService>,
+
+    /// Single signature publisher service
+    pub single_signature_publisher: SingleSignaturePublisherService,
 }
 
 #[cfg(test)]
@@ -357,29 +532,30 @@ mod tests {
 
     use super::*;
 
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
     fn get_test_dir(test_name: &str) -> PathBuf {
         TempDir::create("signer_service", test_name)
     }
 
-    #[tokio::test]
-    async fn test_auto_create_stores_directory() {
-        let stores_dir = get_test_dir("test_auto_create_stores_directory").join("stores");
-        let config = Configuration {
+    fn test_config(stores_dir: &Path) -> Configuration {
+        Configuration {
             cardano_cli_path: PathBuf::new(),
             cardano_node_socket_path: PathBuf::new(),
             network_magic: None,
             network: "preview".to_string(),
             aggregator_endpoint: "".to_string(),
+            additional_aggregator_endpoints: None,
             relay_endpoint: None,
             party_id: Some("party-123456".to_string()),
             run_interval: 1000,
             db_directory: PathBuf::new(),
-            data_stores_directory: stores_dir.clone(),
+            data_stores_directory: stores_dir.to_path_buf(),
             store_retention_limit: None,
             kes_secret_key_path: None,
             operational_certificate_path: None,
+            store_encryption_passphrase: None,
+            store_encryption_key_path: None,
             disable_digests_cache: false,
             reset_digests_cache: false,
             era_reader_adapter_type: EraReaderAdapterType::Bootstrap,
@@ -388,9 +564,13 @@ mod tests {
             metrics_server_ip: "0.0.0.0".to_string(),
             metrics_server_port: 9090,
             allow_unparsable_block: false,
-        };
+            enable_transaction_pruning: false,
+            transactions_pruning_retained_block_ranges: 150,
+            enable_chain_sync_block_scanner: false,
+        }
+    }
 
-        assert!(!stores_dir.exists());
+    async fn build_services(config: &Configuration) -> Vec<SignerServices> {
         let chain_observer_builder: fn(&Configuration) -> StdResult<ChainObserverService> =
             |_config| {
                 Ok(Arc::new(FakeObserver::new(Some(TimePoint {
@@ -404,13 +584,55 @@ mod tests {
             -> StdResult<Arc<dyn ImmutableFileObserver>> =
             |_config: &Configuration| Ok(Arc::new(DumbImmutableFileObserver::default()));
 
-        let mut service_builder = ProductionServiceBuilder::new(&config);
+        let mut service_builder = ProductionServiceBuilder::new(config);
         service_builder
             .override_chain_observer_builder(chain_observer_builder)
             .override_immutable_file_observer_builder(immutable_file_observer_builder)
             .build()
             .await
-            .expect("service builder build should not fail");
+            .expect("service builder build should not fail")
+    }
+
+    #[tokio::test]
+    async fn test_auto_create_stores_directory() {
+        let stores_dir = get_test_dir("test_auto_create_stores_directory").join("stores");
+        let config = test_config(&stores_dir);
+
+        assert!(!stores_dir.exists());
+        build_services(&config).await;
         assert!(stores_dir.exists());
     }
+
+    #[tokio::test]
+    async fn test_build_services_for_each_aggregator_with_their_own_stores() {
+        let stores_dir =
+            get_test_dir("test_build_services_for_each_aggregator_with_their_own_stores");
+        let config = Configuration {
+            aggregator_endpoint: "http://aggregator-a:8080/aggregator".to_string(),
+            additional_aggregator_endpoints: Some(
+                "https://aggregator-b.example.com/aggregator".to_string(),
+            ),
+            ..test_config(&stores_dir)
+        };
+
+        let services = build_services(&config).await;
+
+        assert_eq!(2, services.len());
+        assert!(stores_dir.join(SQLITE_FILE).exists());
+        assert!(stores_dir
+            .join("signer-aggregator-b-example-com-aggregator.sqlite3")
+            .exists());
+    }
+
+    #[test]
+    fn additional_aggregator_sqlite_file_name_is_derived_from_the_endpoint() {
+        assert_eq!(
+            "signer-aggregator-b-example-com-aggregator.sqlite3",
+            additional_aggregator_sqlite_file_name("https://aggregator-b.example.com/aggregator/")
+        );
+        assert_eq!(
+            "signer-127-0-0-1-8080.sqlite3",
+            additional_aggregator_sqlite_file_name("http://127.0.0.1:8080")
+        );
+    }
 }
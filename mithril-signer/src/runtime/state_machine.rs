@@ -1,4 +1,4 @@
-use slog_scope::{crit, debug, error, info};
+use slog_scope::{crit, debug, error, info, warn};
 use std::{fmt::Display, ops::Deref, sync::Arc, time::Duration};
 use tokio::{sync::Mutex, time::sleep};
 
@@ -146,6 +146,11 @@ impl StateMachine {
         self.metrics_service
             .runtime_cycle_total_since_startup_counter_increment();
 
+        if let SignerState::Registered { epoch } | SignerState::Signed { epoch, .. } = state.deref()
+        {
+            self.retry_pending_single_signatures(*epoch).await;
+        }
+
         match state.deref() {
             SignerState::Init => {
                 *state = self.transition_from_init_to_unregistered().await?;
@@ -261,6 +266,14 @@ impl StateMachine {
         Ok(())
     }
 
+    /// Retry to send the pending single signatures, a failure only being logged since it must not
+    /// prevent the signer from signing the next messages.
+    async fn retry_pending_single_signatures(&self, epoch: Epoch) {
+        if let Err(e) = self.runner.retry_pending_single_signatures(epoch).await {
+            warn!("could not retry to send the pending single signatures"; "epoch" => ?epoch, "error" => ?e);
+        }
+    }
+
     /// Return the new epoch if the epoch is different than the given one.
     async fn has_epoch_changed(&self, epoch: Epoch) -> Result<Option<Epoch>, RuntimeError> {
         let current_time_point = self
@@ -471,7 +484,10 @@ mod tests {
     use super::*;
     use crate::runtime::runner::MockSignerRunner;
 
-    fn init_state_machine(init_state: SignerState, runner: MockSignerRunner) -> StateMachine {
+    fn init_state_machine(init_state: SignerState, mut runner: MockSignerRunner) -> StateMachine {
+        runner
+            .expect_retry_pending_single_signatures()
+            .returning(|_| Ok(()));
         let metrics_service = Arc::new(MetricsService::new().unwrap());
         StateMachine {
             state: init_state.into(),
@@ -829,6 +845,47 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn failing_retry_of_pending_single_signatures_does_not_fail_the_cycle() {
+        let time_point = TimePoint {
+            immutable_file_number: 99,
+            epoch: Epoch(9),
+        };
+        let time_point_clone = time_point.clone();
+        let state = SignerState::Signed {
+            epoch: time_point.epoch,
+            signed_entity_type: SignedEntityType::dummy(),
+        };
+
+        let mut runner = MockSignerRunner::new();
+        runner
+            .expect_retry_pending_single_signatures()
+            .withf(move |epoch| *epoch == time_point.epoch)
+            .once()
+            .returning(|_| Err(anyhow::anyhow!("retry failure")));
+        runner
+            .expect_get_current_time_point()
+            .once()
+            .returning(move || Ok(time_point_clone.to_owned()));
+        runner
+            .expect_get_pending_certificate()
+            .once()
+            .returning(move || Ok(None));
+
+        let state_machine = StateMachine::new(
+            state.clone(),
+            Box::new(runner),
+            Duration::from_millis(100),
+            Arc::new(MetricsService::new().unwrap()),
+        );
+        state_machine
+            .cycle()
+            .await
+            .expect("Cycling the state machine should not fail");
+
+        assert_eq!(state, state_machine.get_state().await);
+    }
+
     #[tokio::test]
     async fn signed_to_signed_unsigned_pending_certificate() {
         let time_point = TimePoint {
@@ -0,0 +1,591 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use slog::{debug, info, warn, Logger};
+
+use mithril_common::entities::{Epoch, SignedEntityType, SingleSignatures};
+use mithril_common::StdResult;
+
+use crate::database::record::PendingSingleSignatureRecord;
+use crate::{AggregatorClient, AggregatorClientError, MetricsService};
+
+/// Store of the single signatures that are not yet registered to the aggregator
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PendingSingleSignatureStore: Send + Sync {
+    /// Save the given pending single signatures, replacing the ones already stored for the
+    /// same signed entity type
+    async fn save_pending_single_signature(
+        &self,
+        record: PendingSingleSignatureRecord,
+    ) -> StdResult<()>;
+
+    /// Get all the pending single signatures
+    async fn get_pending_single_signatures(&self) -> StdResult<Vec<PendingSingleSignatureRecord>>;
+
+    /// Remove the pending single signatures of the given signed entity type
+    async fn remove_pending_single_signature(
+        &self,
+        signed_entity_type: &SignedEntityType,
+    ) -> StdResult<()>;
+}
+
+/// Publish single signatures to the aggregator
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SingleSignaturePublisher: Send + Sync {
+    /// Register the given single signatures to the aggregator
+    async fn publish(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        single_signatures: &SingleSignatures,
+    ) -> StdResult<()>;
+
+    /// Retry the registration of the single signatures that could not be registered yet
+    async fn retry_pending(&self, current_epoch: Epoch) -> StdResult<()>;
+}
+
+/// A [SingleSignaturePublisher] that persists the single signatures before registering them
+/// and keeps them for a later retry when the aggregator can't be reached.
+///
+/// The pending single signatures are retried with an exponential backoff until they are
+/// registered or until the open message of their signed entity type expires: either after
+/// the signed entity type open message timeout, or when the epoch changes for the signed entity
+/// types without timeout.
+pub struct SingleSignaturePublisherWithRetry {
+    aggregator_client: Arc<dyn AggregatorClient>,
+    pending_store: Arc<dyn PendingSingleSignatureStore>,
+    metrics_service: Arc<MetricsService>,
+    min_retry_delay: Duration,
+    max_retry_delay: Duration,
+    logger: Logger,
+}
+
+impl SingleSignaturePublisherWithRetry {
+    /// Default delay before the first retry
+    pub const DEFAULT_MIN_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+    /// Default maximum delay between two retries
+    pub const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(120);
+
+    /// Constructor
+    pub fn new(
+        aggregator_client: Arc<dyn AggregatorClient>,
+        pending_store: Arc<dyn PendingSingleSignatureStore>,
+        metrics_service: Arc<MetricsService>,
+        logger: Logger,
+    ) -> Self {
+        Self {
+            aggregator_client,
+            pending_store,
+            metrics_service,
+            min_retry_delay: Self::DEFAULT_MIN_RETRY_DELAY,
+            max_retry_delay: Self::DEFAULT_MAX_RETRY_DELAY,
+            logger,
+        }
+    }
+
+    /// Set the bounds of the exponential backoff between two retries
+    pub fn with_retry_delays(
+        mut self,
+        min_retry_delay: Duration,
+        max_retry_delay: Duration,
+    ) -> Self {
+        self.min_retry_delay = min_retry_delay;
+        self.max_retry_delay = max_retry_delay;
+        self
+    }
+
+    /// Only the errors that may be solved by waiting for the aggregator to be back are retried,
+    /// the other ones would fail the same way on each attempt.
+    fn is_retryable(error: &AggregatorClientError) -> bool {
+        matches!(
+            error,
+            AggregatorClientError::RemoteServerUnreachable(_)
+                | AggregatorClientError::RemoteServerTechnical(_)
+                | AggregatorClientError::IOError(_)
+        )
+    }
+
+    fn compute_next_retry_at(&self, now: DateTime<Utc>, retry_count: u32) -> DateTime<Utc> {
+        let delay = self
+            .min_retry_delay
+            .checked_mul(2_u32.saturating_pow(retry_count))
+            .unwrap_or(self.max_retry_delay)
+            .min(self.max_retry_delay);
+
+        now + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::zero())
+    }
+
+    fn is_expired(
+        record: &PendingSingleSignatureRecord,
+        now: DateTime<Utc>,
+        current_epoch: Epoch,
+    ) -> bool {
+        match record.expires_at {
+            Some(expires_at) => expires_at <= now,
+            None => record.signed_entity_type.get_epoch() < current_epoch,
+        }
+    }
+
+    async fn drop_pending(&self, record: &PendingSingleSignatureRecord) -> StdResult<()> {
+        self.pending_store
+            .remove_pending_single_signature(&record.signed_entity_type)
+            .await?;
+        self.metrics_service
+            .signature_registration_dropped_since_startup_counter_increment();
+
+        Ok(())
+    }
+
+    async fn retry_record(
+        &self,
+        record: PendingSingleSignatureRecord,
+        now: DateTime<Utc>,
+    ) -> StdResult<()> {
+        match self
+            .aggregator_client
+            .register_signatures(&record.signed_entity_type, &record.single_signatures)
+            .await
+        {
+            Ok(()) => {
+                info!(
+                    self.logger, "SingleSignaturePublisher: pending single signatures registered";
+                    "signed_entity_type" => ?record.signed_entity_type, "retry_count" => record.retry_count + 1
+                );
+                self.pending_store
+                    .remove_pending_single_signature(&record.signed_entity_type)
+                    .await
+            }
+            Err(error) if Self::is_retryable(&error) => {
+                let retry_count = record.retry_count + 1;
+                let next_retry_at = self.compute_next_retry_at(now, retry_count);
+                debug!(
+                    self.logger, "SingleSignaturePublisher: retry failed, will try again later";
+                    "signed_entity_type" => ?record.signed_entity_type, "retry_count" => retry_count,
+                    "next_retry_at" => %next_retry_at, "error" => ?error
+                );
+                self.pending_store
+                    .save_pending_single_signature(PendingSingleSignatureRecord {
+                        retry_count,
+                        next_retry_at,
+                        ..record
+                    })
+                    .await
+            }
+            Err(error) => {
+                warn!(
+                    self.logger, "SingleSignaturePublisher: pending single signatures rejected by the aggregator, dropping them";
+                    "signed_entity_type" => ?record.signed_entity_type, "error" => ?error
+                );
+                self.drop_pending(&record).await
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SingleSignaturePublisher for SingleSignaturePublisherWithRetry {
+    async fn publish(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        single_signatures: &SingleSignatures,
+    ) -> StdResult<()> {
+        let now = Utc::now();
+        let record = PendingSingleSignatureRecord {
+            signed_entity_type: signed_entity_type.to_owned(),
+            single_signatures: single_signatures.to_owned(),
+            retry_count: 0,
+            next_retry_at: self.compute_next_retry_at(now, 0),
+            expires_at: signed_entity_type
+                .get_open_message_timeout()
+                .and_then(|timeout| chrono::Duration::from_std(timeout).ok())
+                .map(|timeout| now + timeout),
+            created_at: now,
+        };
+        self.pending_store
+            .save_pending_single_signature(record)
+            .await?;
+
+        match self
+            .aggregator_client
+            .register_signatures(signed_entity_type, single_signatures)
+            .await
+        {
+            Ok(()) => {
+                self.pending_store
+                    .remove_pending_single_signature(signed_entity_type)
+                    .await
+            }
+            Err(error) if Self::is_retryable(&error) => {
+                warn!(
+                    self.logger, "SingleSignaturePublisher: could not register single signatures, queuing them for a later retry";
+                    "signed_entity_type" => ?signed_entity_type, "error" => ?error
+                );
+                self.metrics_service
+                    .signature_registration_queued_since_startup_counter_increment();
+
+                Ok(())
+            }
+            Err(error) => {
+                self.pending_store
+                    .remove_pending_single_signature(signed_entity_type)
+                    .await?;
+
+                Err(anyhow!(error))
+            }
+        }
+    }
+
+    async fn retry_pending(&self, current_epoch: Epoch) -> StdResult<()> {
+        let now = Utc::now();
+
+        for record in self.pending_store.get_pending_single_signatures().await? {
+            if Self::is_expired(&record, now, current_epoch) {
+                warn!(
+                    self.logger, "SingleSignaturePublisher: open message expired, dropping pending single signatures";
+                    "signed_entity_type" => ?record.signed_entity_type, "retry_count" => record.retry_count
+                );
+                self.drop_pending(&record).await?;
+            } else if record.next_retry_at <= now {
+                self.retry_record(record, now).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::CardanoDbBeacon;
+    use mithril_common::test_utils::fake_data;
+
+    use crate::database::repository::PendingSingleSignatureRepository;
+    use crate::database::test_utils::main_db_connection;
+    use crate::test_tools::logger_for_tests;
+    use crate::MockAggregatorClient;
+
+    use super::*;
+
+    struct TestSetup {
+        publisher: SingleSignaturePublisherWithRetry,
+        pending_store: Arc<PendingSingleSignatureRepository>,
+        metrics_service: Arc<MetricsService>,
+    }
+
+    fn setup(aggregator_client_mock_config: impl FnOnce(&mut MockAggregatorClient)) -> TestSetup {
+        let mut aggregator_client = MockAggregatorClient::new();
+        aggregator_client_mock_config(&mut aggregator_client);
+        let pending_store = Arc::new(PendingSingleSignatureRepository::new(Arc::new(
+            main_db_connection().unwrap(),
+        )));
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let publisher = SingleSignaturePublisherWithRetry::new(
+            Arc::new(aggregator_client),
+            pending_store.clone(),
+            metrics_service.clone(),
+            logger_for_tests(),
+        )
+        .with_retry_delays(Duration::ZERO, Duration::ZERO);
+
+        TestSetup {
+            publisher,
+            pending_store,
+            metrics_service,
+        }
+    }
+
+    fn unreachable_error() -> AggregatorClientError {
+        AggregatorClientError::RemoteServerUnreachable(anyhow!("unreachable"))
+    }
+
+    fn pending_record(
+        signed_entity_type: SignedEntityType,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> PendingSingleSignatureRecord {
+        PendingSingleSignatureRecord {
+            signed_entity_type,
+            single_signatures: fake_data::single_signatures(vec![2, 5, 12]),
+            retry_count: 0,
+            next_retry_at: Utc::now(),
+            expires_at,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn publish_registers_the_signatures_and_does_not_keep_them() {
+        let test = setup(|client| {
+            client
+                .expect_register_signatures()
+                .once()
+                .returning(|_, _| Ok(()));
+        });
+
+        test.publisher
+            .publish(
+                &SignedEntityType::dummy(),
+                &fake_data::single_signatures(vec![2, 5, 12]),
+            )
+            .await
+            .unwrap();
+
+        let pending = test
+            .pending_store
+            .get_pending_single_signatures()
+            .await
+            .unwrap();
+        assert!(pending.is_empty());
+        assert_eq!(
+            0,
+            test.metrics_service
+                .signature_registration_queued_since_startup_counter_get()
+        );
+    }
+
+    #[tokio::test]
+    async fn publish_queues_the_signatures_when_the_aggregator_is_unreachable() {
+        let test = setup(|client| {
+            client
+                .expect_register_signatures()
+                .once()
+                .returning(|_, _| Err(unreachable_error()));
+        });
+        let signed_entity_type =
+            SignedEntityType::CardanoTransactions(CardanoDbBeacon::new("devnet", 4, 57));
+
+        test.publisher
+            .publish(
+                &signed_entity_type,
+                &fake_data::single_signatures(vec![2, 5, 12]),
+            )
+            .await
+            .expect("An unreachable aggregator should not fail the publication");
+
+        let pending = test
+            .pending_store
+            .get_pending_single_signatures()
+            .await
+            .unwrap();
+        assert_eq!(1, pending.len());
+        assert_eq!(signed_entity_type, pending[0].signed_entity_type);
+        assert_eq!(
+            Some(pending[0].created_at + chrono::Duration::seconds(600)),
+            pending[0].expires_at
+        );
+        assert_eq!(
+            1,
+            test.metrics_service
+                .signature_registration_queued_since_startup_counter_get()
+        );
+    }
+
+    #[tokio::test]
+    async fn publish_fails_without_queuing_when_the_aggregator_rejects_the_signatures() {
+        let test = setup(|client| {
+            client
+                .expect_register_signatures()
+                .once()
+                .returning(|_, _| {
+                    Err(AggregatorClientError::RemoteServerLogical(anyhow!(
+                        "already registered single signatures"
+                    )))
+                });
+        });
+
+        test.publisher
+            .publish(
+                &SignedEntityType::dummy(),
+                &fake_data::single_signatures(vec![2, 5, 12]),
+            )
+            .await
+            .expect_err("A rejected registration should fail the publication");
+
+        let pending = test
+            .pending_store
+            .get_pending_single_signatures()
+            .await
+            .unwrap();
+        assert!(pending.is_empty());
+    }
+
+    #[tokio::test]
+    async fn retry_pending_removes_the_signatures_once_registered() {
+        let test = setup(|client| {
+            client
+                .expect_register_signatures()
+                .once()
+                .returning(|_, _| Ok(()));
+        });
+        test.pending_store
+            .save_pending_single_signature(pending_record(SignedEntityType::dummy(), None))
+            .await
+            .unwrap();
+
+        test.publisher
+            .retry_pending(SignedEntityType::dummy().get_epoch())
+            .await
+            .unwrap();
+
+        let pending = test
+            .pending_store
+            .get_pending_single_signatures()
+            .await
+            .unwrap();
+        assert!(pending.is_empty());
+        assert_eq!(
+            0,
+            test.metrics_service
+                .signature_registration_dropped_since_startup_counter_get()
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_pending_keeps_the_signatures_and_increments_the_retry_count_when_the_aggregator_is_still_unreachable(
+    ) {
+        let test = setup(|client| {
+            client
+                .expect_register_signatures()
+                .times(2)
+                .returning(|_, _| Err(unreachable_error()));
+        });
+        test.pending_store
+            .save_pending_single_signature(pending_record(SignedEntityType::dummy(), None))
+            .await
+            .unwrap();
+
+        for _ in 0..2 {
+            test.publisher
+                .retry_pending(SignedEntityType::dummy().get_epoch())
+                .await
+                .unwrap();
+        }
+
+        let pending = test
+            .pending_store
+            .get_pending_single_signatures()
+            .await
+            .unwrap();
+        assert_eq!(1, pending.len());
+        assert_eq!(2, pending[0].retry_count);
+    }
+
+    #[tokio::test]
+    async fn retry_pending_skips_the_signatures_whose_next_retry_is_not_due() {
+        let test = setup(|client| {
+            client.expect_register_signatures().never();
+        });
+        test.pending_store
+            .save_pending_single_signature(PendingSingleSignatureRecord {
+                next_retry_at: Utc::now() + chrono::Duration::minutes(5),
+                ..pending_record(SignedEntityType::dummy(), None)
+            })
+            .await
+            .unwrap();
+
+        test.publisher
+            .retry_pending(SignedEntityType::dummy().get_epoch())
+            .await
+            .unwrap();
+
+        let pending = test
+            .pending_store
+            .get_pending_single_signatures()
+            .await
+            .unwrap();
+        assert_eq!(1, pending.len());
+    }
+
+    #[tokio::test]
+    async fn retry_pending_drops_the_signatures_whose_open_message_expired() {
+        let test = setup(|client| {
+            client.expect_register_signatures().never();
+        });
+        let current_epoch = Epoch(5);
+        for record in [
+            pending_record(
+                SignedEntityType::CardanoStakeDistribution(current_epoch),
+                Some(Utc::now() - chrono::Duration::seconds(1)),
+            ),
+            pending_record(
+                SignedEntityType::MithrilStakeDistribution(current_epoch - 1),
+                None,
+            ),
+        ] {
+            test.pending_store
+                .save_pending_single_signature(record)
+                .await
+                .unwrap();
+        }
+
+        test.publisher.retry_pending(current_epoch).await.unwrap();
+
+        let pending = test
+            .pending_store
+            .get_pending_single_signatures()
+            .await
+            .unwrap();
+        assert!(pending.is_empty());
+        assert_eq!(
+            2,
+            test.metrics_service
+                .signature_registration_dropped_since_startup_counter_get()
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_pending_drops_the_signatures_rejected_by_the_aggregator() {
+        let test = setup(|client| {
+            client
+                .expect_register_signatures()
+                .once()
+                .returning(|_, _| {
+                    Err(AggregatorClientError::RemoteServerLogical(anyhow!(
+                        "bad request"
+                    )))
+                });
+        });
+        test.pending_store
+            .save_pending_single_signature(pending_record(SignedEntityType::dummy(), None))
+            .await
+            .unwrap();
+
+        test.publisher
+            .retry_pending(SignedEntityType::dummy().get_epoch())
+            .await
+            .unwrap();
+
+        let pending = test
+            .pending_store
+            .get_pending_single_signatures()
+            .await
+            .unwrap();
+        assert!(pending.is_empty());
+        assert_eq!(
+            1,
+            test.metrics_service
+                .signature_registration_dropped_since_startup_counter_get()
+        );
+    }
+
+    #[test]
+    fn next_retry_delay_grows_exponentially_up_to_the_max_delay() {
+        let test = setup(|_| {});
+        let publisher = test
+            .publisher
+            .with_retry_delays(Duration::from_secs(5), Duration::from_secs(30));
+        let now = Utc::now();
+
+        let delays: Vec<i64> = (0..5)
+            .map(|retry_count| {
+                (publisher.compute_next_retry_at(now, retry_count) - now).num_seconds()
+            })
+            .collect();
+
+        assert_eq!(vec![5, 10, 20, 30, 30], delays);
+    }
+}
@@ -0,0 +1,649 @@
+//! Encryption at rest of the signer stores holding secret material.
+//!
+//! The records are encrypted with XChaCha20-Poly1305 using a key derived with Argon2id from a
+//! secret provided by the operator, either a passphrase or the content of a key file.
+
+use anyhow::{anyhow, Context};
+use argon2::Argon2;
+use async_trait::async_trait;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use zeroize::{Zeroize, Zeroizing};
+
+use mithril_common::StdResult;
+use mithril_persistence::store::adapter::{AdapterError, StoreAdapter};
+
+/// Version of the format of the encrypted records.
+const ENCRYPTED_RECORD_VERSION: u8 = 1;
+
+/// Size of the salt of the key derivation.
+const SALT_SIZE: usize = 16;
+
+/// Size of the key derivation output.
+const KEY_SIZE: usize = 32;
+
+/// Passphrase from which the key encrypting the signer stores is derived.
+///
+/// It is never displayed and is zeroized when dropped.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StoreEncryptionPassphrase(String);
+
+impl From<String> for StoreEncryptionPassphrase {
+    fn from(passphrase: String) -> Self {
+        Self(passphrase)
+    }
+}
+
+impl Debug for StoreEncryptionPassphrase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StoreEncryptionPassphrase(**redacted**)")
+    }
+}
+
+impl Drop for StoreEncryptionPassphrase {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// An encrypted record, its fields are hex encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedRecord {
+    /// Version of the format of the encrypted record.
+    pub version: u8,
+
+    /// Salt of the derivation of the encryption key.
+    pub salt: String,
+
+    /// Nonce of the encryption.
+    pub nonce: String,
+
+    /// Encrypted JSON serialization of the record, with its authentication tag.
+    pub ciphertext: String,
+}
+
+/// Record of a store whose records can be encrypted.
+///
+/// Records written before the encryption of the store was enabled are kept in plain until they
+/// are migrated with [EncryptedStoreAdapter::encrypt_plain_records].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StoredRecord<R> {
+    /// Encrypted record.
+    Encrypted(EncryptedRecord),
+
+    /// Record written in plain.
+    Plain(R),
+}
+
+/// Encrypt and decrypt store records with a key derived from an operator secret.
+///
+/// A random salt is drawn when the cipher is created and is written along each record it
+/// encrypts, the keys derived for the salts of the records read are cached.
+pub struct StoreCipher {
+    secret: Zeroizing<Vec<u8>>,
+    salt: [u8; SALT_SIZE],
+    derived_keys: Mutex<HashMap<[u8; SALT_SIZE], Zeroizing<[u8; KEY_SIZE]>>>,
+}
+
+impl StoreCipher {
+    /// Create a cipher whose key is derived from the given passphrase.
+    pub fn from_passphrase(passphrase: &StoreEncryptionPassphrase) -> StdResult<Self> {
+        Self::new(Zeroizing::new(passphrase.0.as_bytes().to_vec()))
+    }
+
+    /// Create a cipher whose key is derived from the content of the given key file.
+    pub fn from_key_file(key_path: &Path) -> StdResult<Self> {
+        let secret = Zeroizing::new(std::fs::read(key_path).with_context(|| {
+            format!(
+                "Could not read the store encryption key file: '{}'",
+                key_path.display()
+            )
+        })?);
+
+        Self::new(secret)
+    }
+
+    fn new(secret: Zeroizing<Vec<u8>>) -> StdResult<Self> {
+        if secret.is_empty() {
+            return Err(anyhow!("The store encryption secret can not be empty"));
+        }
+        let mut salt = [0u8; SALT_SIZE];
+        OsRng.fill_bytes(&mut salt);
+        let cipher = Self {
+            secret,
+            salt,
+            derived_keys: Mutex::new(HashMap::new()),
+        };
+        // Derive the key used for encryption upfront so that the cost of the derivation is not
+        // paid when the first record is written.
+        cipher.get_aead(&salt)?;
+
+        Ok(cipher)
+    }
+
+    fn get_aead(&self, salt: &[u8; SALT_SIZE]) -> StdResult<XChaCha20Poly1305> {
+        let mut derived_keys = self
+            .derived_keys
+            .lock()
+            .map_err(|_| anyhow!("The store encryption keys cache is poisoned"))?;
+        if !derived_keys.contains_key(salt) {
+            let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+            Argon2::default()
+                .hash_password_into(&self.secret, salt, key.as_mut())
+                .map_err(|e| anyhow!("Could not derive the store encryption key: {e}"))?;
+            derived_keys.insert(*salt, key);
+        }
+
+        Ok(XChaCha20Poly1305::new(Key::from_slice(
+            derived_keys[salt].as_ref(),
+        )))
+    }
+
+    /// Encrypt the given plaintext.
+    pub fn encrypt(&self, plaintext: &[u8]) -> StdResult<EncryptedRecord> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .get_aead(&self.salt)?
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| anyhow!("Could not encrypt the record"))?;
+
+        Ok(EncryptedRecord {
+            version: ENCRYPTED_RECORD_VERSION,
+            salt: hex::encode(self.salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypt the given encrypted record.
+    pub fn decrypt(&self, record: &EncryptedRecord) -> StdResult<Zeroizing<Vec<u8>>> {
+        if record.version != ENCRYPTED_RECORD_VERSION {
+            return Err(anyhow!(
+                "Unsupported encrypted record version: {}",
+                record.version
+            ));
+        }
+        let salt: [u8; SALT_SIZE] = hex::decode(&record.salt)
+            .with_context(|| "Could not decode the salt of the encrypted record")?
+            .try_into()
+            .map_err(|_| anyhow!("Invalid salt size in the encrypted record"))?;
+        let nonce = hex::decode(&record.nonce)
+            .with_context(|| "Could not decode the nonce of the encrypted record")?;
+        if nonce.len() != XNonce::default().len() {
+            return Err(anyhow!("Invalid nonce size in the encrypted record"));
+        }
+        let ciphertext = hex::decode(&record.ciphertext)
+            .with_context(|| "Could not decode the ciphertext of the encrypted record")?;
+
+        let plaintext = self
+            .get_aead(&salt)?
+            .decrypt(XNonce::from_slice(&nonce), ciphertext.as_ref())
+            .map_err(|_| {
+                anyhow!(
+                    "Could not decrypt the record: the store encryption secret is wrong or the record is corrupted"
+                )
+            })?;
+
+        Ok(Zeroizing::new(plaintext))
+    }
+}
+
+/// A [StoreAdapter] that encrypts the records of the adapter it wraps.
+///
+/// Without a cipher the records are written in plain, and reading an encrypted record fails.
+/// Once the plain records are encrypted with a cipher, reading a plain record fails.
+pub struct EncryptedStoreAdapter<K, R> {
+    adapter: Box<dyn StoreAdapter<Key = K, Record = StoredRecord<R>>>,
+    cipher: Option<Arc<StoreCipher>>,
+    plain_records_encrypted: bool,
+}
+
+impl<K, R> EncryptedStoreAdapter<K, R>
+where
+    K: Sync + Send,
+    R: Clone + Serialize + DeserializeOwned + Sync + Send,
+{
+    /// Create a new EncryptedStoreAdapter.
+    pub fn new(
+        adapter: Box<dyn StoreAdapter<Key = K, Record = StoredRecord<R>>>,
+        cipher: Option<Arc<StoreCipher>>,
+    ) -> Self {
+        Self {
+            adapter,
+            cipher,
+            plain_records_encrypted: false,
+        }
+    }
+
+    fn seal(&self, record: &R) -> StdResult<StoredRecord<R>> {
+        Self::seal_with(&self.cipher, record)
+    }
+
+    fn seal_with(cipher: &Option<Arc<StoreCipher>>, record: &R) -> StdResult<StoredRecord<R>> {
+        match cipher {
+            Some(cipher) => {
+                let plaintext = Zeroizing::new(
+                    serde_json::to_vec(record)
+                        .with_context(|| "Could not serialize the record to encrypt")?,
+                );
+
+                Ok(StoredRecord::Encrypted(cipher.encrypt(&plaintext)?))
+            }
+            None => Ok(StoredRecord::Plain(record.clone())),
+        }
+    }
+
+    fn open(&self, record: StoredRecord<R>) -> StdResult<R> {
+        match record {
+            StoredRecord::Encrypted(record) => {
+                let cipher = self.cipher.as_ref().ok_or_else(|| {
+                    anyhow!(
+                        "The store is encrypted: `store_encryption_passphrase` or `store_encryption_key_path` must be set"
+                    )
+                })?;
+                let plaintext = cipher.decrypt(&record)?;
+
+                serde_json::from_slice(&plaintext)
+                    .with_context(|| "Could not deserialize the decrypted record")
+            }
+            StoredRecord::Plain(_) if self.plain_records_encrypted => Err(anyhow!(
+                "Found a plain record in a store whose records are encrypted"
+            )),
+            StoredRecord::Plain(record) => Ok(record),
+        }
+    }
+
+    /// Encrypt the records written in plain, returns the number of encrypted records.
+    ///
+    /// Once done, the plain records read are rejected. Does nothing if the adapter has no cipher.
+    pub async fn encrypt_plain_records(&mut self) -> StdResult<usize> {
+        if self.cipher.is_none() {
+            return Ok(0);
+        }
+
+        let records = self
+            .adapter
+            .get_all_records()
+            .await?
+            .into_iter()
+            .filter_map(|(key, record)| match record {
+                StoredRecord::Plain(record) => Some((key, record)),
+                StoredRecord::Encrypted(_) => None,
+            })
+            .map(|(key, record)| Ok((key, self.seal(&record)?)))
+            .collect::<StdResult<Vec<_>>>()?;
+        self.adapter.store_records(&records).await?;
+        self.plain_records_encrypted = true;
+
+        Ok(records.len())
+    }
+
+    /// Encrypt all the records with the given cipher, or write them in plain if it is `None`,
+    /// and use it for the next records. Returns the number of rewritten records.
+    ///
+    /// All the records are decrypted and encrypted again before they are rewritten at once, so
+    /// the store and the cipher in use are left untouched if one of them can not be decrypted or
+    /// written.
+    pub async fn rekey(&mut self, cipher: Option<Arc<StoreCipher>>) -> StdResult<usize> {
+        let records = self
+            .adapter
+            .get_all_records()
+            .await?
+            .into_iter()
+            .map(|(key, record)| Ok((key, Self::seal_with(&cipher, &self.open(record)?)?)))
+            .collect::<StdResult<Vec<_>>>()?;
+        self.adapter.store_records(&records).await?;
+        self.plain_records_encrypted = cipher.is_some();
+        self.cipher = cipher;
+
+        Ok(records.len())
+    }
+}
+
+#[async_trait]
+impl<K, R> StoreAdapter for EncryptedStoreAdapter<K, R>
+where
+    K: Clone + Sync + Send,
+    R: Clone + Serialize + DeserializeOwned + Sync + Send,
+{
+    type Key = K;
+    type Record = R;
+
+    async fn store_record(
+        &mut self,
+        key: &Self::Key,
+        record: &Self::Record,
+    ) -> Result<(), AdapterError> {
+        let record = self.seal(record).map_err(AdapterError::GeneralError)?;
+
+        self.adapter.store_record(key, &record).await
+    }
+
+    async fn store_records(
+        &mut self,
+        records: &[(Self::Key, Self::Record)],
+    ) -> Result<(), AdapterError> {
+        let records = records
+            .iter()
+            .map(|(key, record)| Ok((key.clone(), self.seal(record)?)))
+            .collect::<StdResult<Vec<_>>>()
+            .map_err(AdapterError::GeneralError)?;
+
+        self.adapter.store_records(&records).await
+    }
+
+    async fn get_record(&self, key: &Self::Key) -> Result<Option<Self::Record>, AdapterError> {
+        self.adapter
+            .get_record(key)
+            .await?
+            .map(|record| self.open(record))
+            .transpose()
+            .map_err(AdapterError::ParsingDataError)
+    }
+
+    async fn record_exists(&self, key: &Self::Key) -> Result<bool, AdapterError> {
+        self.adapter.record_exists(key).await
+    }
+
+    async fn get_last_n_records(
+        &self,
+        how_many: usize,
+    ) -> Result<Vec<(Self::Key, Self::Record)>, AdapterError> {
+        self.adapter
+            .get_last_n_records(how_many)
+            .await?
+            .into_iter()
+            .map(|(key, record)| Ok((key, self.open(record)?)))
+            .collect::<StdResult<Vec<_>>>()
+            .map_err(AdapterError::ParsingDataError)
+    }
+
+    async fn get_all_records(&self) -> Result<Vec<(Self::Key, Self::Record)>, AdapterError> {
+        self.adapter
+            .get_all_records()
+            .await?
+            .into_iter()
+            .map(|(key, record)| Ok((key, self.open(record)?)))
+            .collect::<StdResult<Vec<_>>>()
+            .map_err(AdapterError::ParsingDataError)
+    }
+
+    async fn remove(&mut self, key: &Self::Key) -> Result<Option<Self::Record>, AdapterError> {
+        self.adapter
+            .remove(key)
+            .await?
+            .map(|record| self.open(record))
+            .transpose()
+            .map_err(AdapterError::ParsingDataError)
+    }
+
+    async fn get_iter(&self) -> Result<Box<dyn Iterator<Item = Self::Record> + '_>, AdapterError> {
+        let records = self
+            .adapter
+            .get_iter()
+            .await?
+            .map(|record| self.open(record))
+            .collect::<StdResult<Vec<_>>>()
+            .map_err(AdapterError::ParsingDataError)?;
+
+        Ok(Box::new(records.into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::TempDir;
+    use mithril_persistence::{sqlite::SqliteConnection, store::adapter::SQLiteAdapter};
+
+    use crate::database::test_utils::main_db_connection;
+
+    use super::*;
+
+    fn passphrase_cipher(passphrase: &str) -> Arc<StoreCipher> {
+        Arc::new(
+            StoreCipher::from_passphrase(&StoreEncryptionPassphrase::from(passphrase.to_string()))
+                .unwrap(),
+        )
+    }
+
+    fn sqlite_adapter(
+        connection: &Arc<SqliteConnection>,
+    ) -> SQLiteAdapter<u64, StoredRecord<String>> {
+        SQLiteAdapter::new("test", connection.clone()).unwrap()
+    }
+
+    /// Build an adapter over a SQLite table and a raw adapter over the same table to inspect
+    /// what is actually written.
+    fn build_adapters(
+        cipher: Option<Arc<StoreCipher>>,
+    ) -> (
+        EncryptedStoreAdapter<u64, String>,
+        SQLiteAdapter<u64, StoredRecord<String>>,
+    ) {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let adapter = EncryptedStoreAdapter::new(Box::new(sqlite_adapter(&connection)), cipher);
+
+        (adapter, sqlite_adapter(&connection))
+    }
+
+    #[test]
+    fn encrypt_then_decrypt_with_the_same_secret() {
+        let cipher = passphrase_cipher("passphrase");
+        let encrypted_record = cipher.encrypt(b"secret").unwrap();
+
+        assert_eq!(
+            b"secret".to_vec(),
+            *cipher.decrypt(&encrypted_record).unwrap()
+        );
+        assert_eq!(
+            b"secret".to_vec(),
+            *passphrase_cipher("passphrase")
+                .decrypt(&encrypted_record)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_with_another_secret_fails() {
+        let encrypted_record = passphrase_cipher("passphrase").encrypt(b"secret").unwrap();
+
+        passphrase_cipher("another passphrase")
+            .decrypt(&encrypted_record)
+            .expect_err("Decrypting with another passphrase should fail");
+    }
+
+    #[test]
+    fn decrypt_tampered_record_fails() {
+        let cipher = passphrase_cipher("passphrase");
+        let mut encrypted_record = cipher.encrypt(b"secret").unwrap();
+        encrypted_record.ciphertext.replace_range(..2, "00");
+
+        cipher
+            .decrypt(&encrypted_record)
+            .expect_err("Decrypting a tampered record should fail");
+    }
+
+    #[test]
+    fn cipher_from_key_file() {
+        let key_path =
+            TempDir::create("store_encryption", "cipher_from_key_file").join("store.key");
+        std::fs::write(&key_path, [7u8; 32]).unwrap();
+        let encrypted_record = StoreCipher::from_key_file(&key_path)
+            .unwrap()
+            .encrypt(b"secret")
+            .unwrap();
+
+        assert_eq!(
+            b"secret".to_vec(),
+            *StoreCipher::from_key_file(&key_path)
+                .unwrap()
+                .decrypt(&encrypted_record)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn cipher_with_empty_secret_fails() {
+        let result = StoreCipher::from_passphrase(&StoreEncryptionPassphrase::from(String::new()));
+
+        assert!(result.is_err(), "An empty passphrase should be rejected");
+    }
+
+    #[test]
+    fn passphrase_is_not_displayed() {
+        let passphrase = StoreEncryptionPassphrase::from("my secret words".to_string());
+
+        assert!(!format!("{passphrase:?}").contains("my secret words"));
+    }
+
+    #[tokio::test]
+    async fn records_are_written_encrypted() {
+        let (mut adapter, raw_adapter) = build_adapters(Some(passphrase_cipher("passphrase")));
+        adapter
+            .store_record(&1, &"record".to_string())
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            raw_adapter.get_record(&1).await.unwrap(),
+            Some(StoredRecord::Encrypted(_))
+        ));
+        assert_eq!(
+            Some("record".to_string()),
+            adapter.get_record(&1).await.unwrap()
+        );
+        assert_eq!(
+            vec![(1, "record".to_string())],
+            adapter.get_last_n_records(1).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn reading_encrypted_records_without_cipher_fails() {
+        let (mut adapter, raw_adapter) = build_adapters(Some(passphrase_cipher("passphrase")));
+        adapter
+            .store_record(&1, &"record".to_string())
+            .await
+            .unwrap();
+        let adapter: EncryptedStoreAdapter<u64, String> =
+            EncryptedStoreAdapter::new(Box::new(raw_adapter), None);
+
+        adapter
+            .get_record(&1)
+            .await
+            .expect_err("Reading an encrypted record without cipher should fail");
+    }
+
+    #[tokio::test]
+    async fn plain_records_are_read_and_encrypted_by_migration() {
+        let (mut adapter, mut raw_adapter) = build_adapters(Some(passphrase_cipher("passphrase")));
+        for (key, record) in [(1, "record-1"), (2, "record-2")] {
+            raw_adapter
+                .store_record(&key, &StoredRecord::Plain(record.to_string()))
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            Some("record-1".to_string()),
+            adapter.get_record(&1).await.unwrap()
+        );
+
+        assert_eq!(2, adapter.encrypt_plain_records().await.unwrap());
+        assert_eq!(0, adapter.encrypt_plain_records().await.unwrap());
+
+        for (key, _) in raw_adapter.get_last_n_records(2).await.unwrap() {
+            assert!(matches!(
+                raw_adapter.get_record(&key).await.unwrap(),
+                Some(StoredRecord::Encrypted(_))
+            ));
+        }
+        assert_eq!(
+            vec![(2, "record-2".to_string()), (1, "record-1".to_string())],
+            adapter.get_last_n_records(2).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn plain_records_are_rejected_once_migrated() {
+        let (mut adapter, mut raw_adapter) = build_adapters(Some(passphrase_cipher("passphrase")));
+        adapter.encrypt_plain_records().await.unwrap();
+        raw_adapter
+            .store_record(&1, &StoredRecord::Plain("record-1".to_string()))
+            .await
+            .unwrap();
+
+        adapter
+            .get_record(&1)
+            .await
+            .expect_err("Reading a plain record once migrated should fail");
+    }
+
+    #[tokio::test]
+    async fn rekey_encrypts_records_with_the_new_secret() {
+        let (mut adapter, raw_adapter) = build_adapters(Some(passphrase_cipher("old")));
+        adapter
+            .store_record(&1, &"record".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            1,
+            adapter.rekey(Some(passphrase_cipher("new"))).await.unwrap()
+        );
+
+        let Some(StoredRecord::Encrypted(encrypted_record)) =
+            raw_adapter.get_record(&1).await.unwrap()
+        else {
+            panic!("The record should be encrypted");
+        };
+        passphrase_cipher("old")
+            .decrypt(&encrypted_record)
+            .expect_err("The record should not be encrypted with the old secret anymore");
+        assert_eq!(
+            b"\"record\"".to_vec(),
+            *passphrase_cipher("new").decrypt(&encrypted_record).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn rekey_with_wrong_secret_leaves_the_store_untouched() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let mut adapter = EncryptedStoreAdapter::new(
+            Box::new(sqlite_adapter(&connection)),
+            Some(passphrase_cipher("old")),
+        );
+        adapter
+            .store_record(&1, &"record".to_string())
+            .await
+            .unwrap();
+        let mut adapter: EncryptedStoreAdapter<u64, String> = EncryptedStoreAdapter::new(
+            Box::new(sqlite_adapter(&connection)),
+            Some(passphrase_cipher("wrong")),
+        );
+
+        adapter
+            .rekey(Some(passphrase_cipher("new")))
+            .await
+            .expect_err("Re-keying with a wrong secret should fail");
+
+        let adapter: EncryptedStoreAdapter<u64, String> = EncryptedStoreAdapter::new(
+            Box::new(sqlite_adapter(&connection)),
+            Some(passphrase_cipher("old")),
+        );
+        assert_eq!(
+            Some("record".to_string()),
+            adapter.get_record(&1).await.unwrap()
+        );
+    }
+}
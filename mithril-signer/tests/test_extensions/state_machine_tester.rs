@@ -13,18 +13,21 @@ use mithril_common::{
     entities::{Epoch, SignerWithStake, TimePoint},
     era::{adapters::EraReaderDummyAdapter, EraChecker, EraMarker, EraReader, SupportedEra},
     signable_builder::{
-        CardanoImmutableFilesFullSignableBuilder, CardanoTransactionsSignableBuilder,
-        MithrilSignableBuilderService, MithrilStakeDistributionSignableBuilder,
+        CardanoImmutableFilesFullSignableBuilder, CardanoStakeDistributionSignableBuilder,
+        CardanoTransactionsSignableBuilder, MithrilSignableBuilderService,
+        MithrilStakeDistributionSignableBuilder,
     },
     StdError, TimePointProvider, TimePointProviderImpl,
 };
 use mithril_persistence::store::{adapter::MemoryAdapter, StakeStore, StakeStorer};
 
 use mithril_signer::{
-    database::repository::CardanoTransactionRepository, metrics::*, AggregatorClient,
-    CardanoTransactionsImporter, Configuration, MetricsService, MithrilSingleSigner,
-    ProductionServiceBuilder, ProtocolInitializerStore, ProtocolInitializerStorer, RuntimeError,
-    SignerRunner, SignerServices, SignerState, StateMachine,
+    database::repository::{CardanoTransactionRepository, PendingSingleSignatureRepository},
+    metrics::*,
+    AggregatorClient, CardanoTransactionsImporter, Configuration, MetricsService,
+    MithrilSingleSigner, ProductionServiceBuilder, ProtocolInitializerStore,
+    ProtocolInitializerStorer, RuntimeError, SignerRunner, SignerServices, SignerState,
+    SingleSignaturePublisherWithRetry, StateMachine,
 };
 
 use super::FakeAggregator;
@@ -85,6 +88,13 @@ impl StateMachineTester {
             )
             .await
             .unwrap();
+        let sqlite_connection = production_service_builder
+            .build_sqlite_connection(
+                ":memory:",
+                mithril_signer::database::migration::get_migrations(),
+            )
+            .await
+            .unwrap();
 
         let decorator = slog_term::PlainDecorator::new(slog_term::TestStdoutWriter);
         let drain = slog_term::CompactFormat::new(decorator).build().fuse();
@@ -167,13 +177,23 @@ impl StateMachineTester {
             block_range_root_retriever,
             slog_scope::logger(),
         ));
+        let cardano_stake_distribution_signable_builder = Arc::new(
+            CardanoStakeDistributionSignableBuilder::new(stake_store.clone()),
+        );
         let signable_builder_service = Arc::new(MithrilSignableBuilderService::new(
             mithril_stake_distribution_signable_builder,
             cardano_immutable_snapshot_builder,
             cardano_transactions_builder,
+            cardano_stake_distribution_signable_builder,
         ));
         let metrics_service = Arc::new(MetricsService::new().unwrap());
         let expected_metrics_service = Arc::new(MetricsService::new().unwrap());
+        let single_signature_publisher = Arc::new(SingleSignaturePublisherWithRetry::new(
+            certificate_handler.clone(),
+            Arc::new(PendingSingleSignatureRepository::new(sqlite_connection)),
+            metrics_service.clone(),
+            slog_scope::logger(),
+        ));
 
         let services = SignerServices {
             certificate_handler: certificate_handler.clone(),
@@ -188,6 +208,7 @@ impl StateMachineTester {
             api_version_provider,
             signable_builder_service,
             metrics_service: metrics_service.clone(),
+            single_signature_publisher,
         };
         // set up stake distribution
         chain_observer
@@ -4,6 +4,8 @@ use blake2::{
     Blake2b, Digest,
 };
 use mithril_stm::key_reg::KeyReg;
+#[cfg(feature = "compact-aggregate")]
+use mithril_stm::stm::AggregateSignatureType;
 use mithril_stm::stm::{
     CoreVerifier, Stake, StmClerk, StmInitializer, StmParameters, StmSig, StmSigRegParty,
     StmSigner, StmVerificationKey,
@@ -61,6 +63,15 @@ where
         nparties,
         aggr.to_bytes().len(),
     );
+    #[cfg(feature = "compact-aggregate")]
+    println!(
+        "k = {} | m = {} | nr parties = {}; {} bytes (compact)",
+        k,
+        m,
+        nparties,
+        aggr.to_versioned_bytes(AggregateSignatureType::Compact)
+            .len(),
+    );
 }
 
 fn core_size<H>(k: u64, m: u64, nparties: usize)
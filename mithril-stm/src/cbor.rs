@@ -0,0 +1,463 @@
+//! Versioned CBOR encoding of the STM keys, signatures and parameters.
+//!
+//! The byte representations given by the `to_bytes` functions are fixed layouts tied to this
+//! implementation. This module provides a documented [CBOR](https://www.rfc-editor.org/rfc/rfc8949)
+//! encoding of the types that are exchanged with other implementations or stored, so that they
+//! can be decoded by any CBOR library, and so that a change of their format does not break the
+//! data encoded with a previous version.
+//!
+//! Every encoded item is wrapped in an envelope carrying the version of the encoding, a decoder
+//! rejects a version it does not know with [CborEncodingError::UnsupportedVersion]. The data
+//! items are encoded with the core deterministic encoding requirements of RFC 8949 (shortest
+//! form of integers, lengths and floats, definite lengths), hence a value has a single encoding.
+//! A decoder rejects any other encoding of a value with [CborEncodingError::NonCanonicalEncoding].
+//!
+//! The nodes keep exchanging and storing these types with the hex encoded JSON of the
+//! `mithril-common` codec: switching it to this encoding changes the messages of the network, it
+//! needs a dedicated and coordinated upgrade of all the nodes and is out of the scope of this module.
+//!
+//! # Schema
+//! The version `1` of the encoding is described by the following CDDL:
+//! ```text
+//! versioned<T> = [version: 1, body: T]
+//!
+//! stm-parameters = versioned<parameters>
+//! stm-verification-key-pop = versioned<verification-key-pop>
+//! stm-sig = versioned<sig>
+//! stm-aggr-verification-key = versioned<aggr-verification-key>
+//! stm-aggr-sig = versioned<aggr-sig>
+//!
+//! parameters = [m: uint, k: uint, phi_f: float]
+//! verification-key-pop = [vk: verification-key, pop: bstr .size 96]
+//! sig = [sigma: signature, indexes: [* uint], signer_index: uint]
+//! aggr-verification-key = [root: digest, nr_leaves: uint, total_stake: uint]
+//! aggr-sig = [signatures: [* sig-reg-party], batch_proof: batch-path]
+//!
+//! sig-reg-party = [sig: sig, reg_party: [vk: verification-key, stake: uint]]
+//! batch-path = [values: [* digest], indices: [* uint]]
+//!
+//! verification-key = bstr .size 96   ; compressed G2 point
+//! signature = bstr .size 48          ; compressed G1 point
+//! digest = bstr                      ; output of the digest of the merkle tree
+//! ```
+//!
+//! # Example
+//! ```
+//! use mithril_stm::stm::StmParameters;
+//!
+//! let params = StmParameters { m: 2728, k: 445, phi_f: 0.2 };
+//! let bytes = params.to_cbor_bytes();
+//!
+//! assert_eq!(params, StmParameters::from_cbor_bytes(&bytes).unwrap());
+//! ```
+
+use crate::error::CborEncodingError;
+use crate::merkle_tree::{BatchPath, MTLeaf, MerkleTreeCommitmentBatchCompat};
+use crate::multi_sig::{ProofOfPossession, Signature, VerificationKey};
+use crate::stm::{
+    Index, StmAggrSig, StmAggrVerificationKey, StmParameters, StmSig, StmSigRegParty,
+    StmVerificationKeyPoP,
+};
+use blake2::digest::{Digest, FixedOutput};
+use ciborium::value::Value;
+use std::marker::PhantomData;
+
+/// Version of the CBOR encoding written by this implementation.
+pub const CBOR_ENCODING_VERSION: u64 = 1;
+
+impl StmParameters {
+    /// Encode the parameters in their versioned CBOR representation.
+    pub fn to_cbor_bytes(&self) -> Vec<u8> {
+        to_versioned_cbor(Value::Array(vec![
+            self.m.into(),
+            self.k.into(),
+            self.phi_f.into(),
+        ]))
+    }
+
+    /// Decode the parameters from their versioned CBOR representation.
+    pub fn from_cbor_bytes(bytes: &[u8]) -> Result<Self, CborEncodingError> {
+        let [m, k, phi_f] = read_array(from_versioned_cbor(bytes)?, "parameters")?;
+
+        Ok(Self {
+            m: read_uint(m)?,
+            k: read_uint(k)?,
+            phi_f: phi_f
+                .into_float()
+                .map_err(|_| CborEncodingError::UnexpectedStructure("expected a float"))?,
+        })
+    }
+}
+
+impl StmVerificationKeyPoP {
+    /// Encode the verification key and its proof of possession in their versioned CBOR
+    /// representation.
+    pub fn to_cbor_bytes(self) -> Vec<u8> {
+        to_versioned_cbor(Value::Array(vec![
+            Value::Bytes(self.vk.to_bytes().to_vec()),
+            Value::Bytes(self.pop.to_bytes().to_vec()),
+        ]))
+    }
+
+    /// Decode the verification key and its proof of possession from their versioned CBOR
+    /// representation. The proof of possession is not checked.
+    pub fn from_cbor_bytes(bytes: &[u8]) -> Result<Self, CborEncodingError> {
+        let [vk, pop] = read_array(from_versioned_cbor(bytes)?, "verification key pop")?;
+
+        Ok(Self {
+            vk: read_verification_key(vk)?,
+            pop: ProofOfPossession::from_bytes(&read_bytes(pop, Some(96))?)
+                .map_err(|_| CborEncodingError::SerializationError)?,
+        })
+    }
+}
+
+impl StmSig {
+    /// Encode the signature in its versioned CBOR representation.
+    pub fn to_cbor_bytes(&self) -> Vec<u8> {
+        to_versioned_cbor(sig_to_value(self))
+    }
+
+    /// Decode the signature from its versioned CBOR representation.
+    pub fn from_cbor_bytes(bytes: &[u8]) -> Result<Self, CborEncodingError> {
+        sig_from_value(from_versioned_cbor(bytes)?)
+    }
+}
+
+impl<D: Clone + Digest + FixedOutput> StmAggrVerificationKey<D> {
+    /// Encode the aggregate verification key in its versioned CBOR representation.
+    pub fn to_cbor_bytes(&self) -> Vec<u8> {
+        to_versioned_cbor(Value::Array(vec![
+            Value::Bytes(self.mt_commitment.root.clone()),
+            (self.mt_commitment.nr_leaves as u64).into(),
+            self.total_stake.into(),
+        ]))
+    }
+
+    /// Decode the aggregate verification key from its versioned CBOR representation.
+    pub fn from_cbor_bytes(bytes: &[u8]) -> Result<Self, CborEncodingError> {
+        let [root, nr_leaves, total_stake] =
+            read_array(from_versioned_cbor(bytes)?, "aggregate verification key")?;
+
+        Ok(Self {
+            mt_commitment: MerkleTreeCommitmentBatchCompat {
+                root: read_bytes(root, Some(<D as Digest>::output_size()))?,
+                nr_leaves: read_usize(nr_leaves)?,
+                hasher: PhantomData,
+            },
+            total_stake: read_uint(total_stake)?,
+        })
+    }
+}
+
+impl<D: Clone + Digest + FixedOutput> StmAggrSig<D> {
+    /// Encode the aggregate signature in its versioned CBOR representation.
+    pub fn to_cbor_bytes(&self) -> Vec<u8> {
+        let signatures = self
+            .signatures
+            .iter()
+            .map(|sig_reg| {
+                Value::Array(vec![
+                    sig_to_value(&sig_reg.sig),
+                    Value::Array(vec![
+                        Value::Bytes(sig_reg.reg_party.0.to_bytes().to_vec()),
+                        sig_reg.reg_party.1.into(),
+                    ]),
+                ])
+            })
+            .collect();
+        let batch_proof = Value::Array(vec![
+            Value::Array(
+                self.batch_proof
+                    .values
+                    .iter()
+                    .map(|value| Value::Bytes(value.clone()))
+                    .collect(),
+            ),
+            Value::Array(
+                self.batch_proof
+                    .indices
+                    .iter()
+                    .map(|&index| (index as u64).into())
+                    .collect(),
+            ),
+        ]);
+
+        to_versioned_cbor(Value::Array(vec![Value::Array(signatures), batch_proof]))
+    }
+
+    /// Decode the aggregate signature from its versioned CBOR representation.
+    pub fn from_cbor_bytes(bytes: &[u8]) -> Result<Self, CborEncodingError> {
+        let [signatures, batch_proof] =
+            read_array(from_versioned_cbor(bytes)?, "aggregate signature")?;
+
+        let signatures = read_list(signatures)?
+            .into_iter()
+            .map(|sig_reg| {
+                let [sig, reg_party] = read_array(sig_reg, "signature with registered party")?;
+                let [vk, stake] = read_array(reg_party, "registered party")?;
+
+                Ok(StmSigRegParty {
+                    sig: sig_from_value(sig)?,
+                    reg_party: MTLeaf(read_verification_key(vk)?, read_uint(stake)?),
+                })
+            })
+            .collect::<Result<Vec<_>, CborEncodingError>>()?;
+
+        let [values, indices] = read_array(batch_proof, "batch path")?;
+        let values = read_list(values)?
+            .into_iter()
+            .map(|value| read_bytes(value, Some(<D as Digest>::output_size())))
+            .collect::<Result<Vec<_>, _>>()?;
+        let indices = read_list(indices)?
+            .into_iter()
+            .map(read_usize)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            signatures,
+            batch_proof: BatchPath {
+                values,
+                indices,
+                hasher: PhantomData,
+            },
+        })
+    }
+}
+
+fn sig_to_value(sig: &StmSig) -> Value {
+    Value::Array(vec![
+        Value::Bytes(sig.sigma.to_bytes().to_vec()),
+        Value::Array(sig.indexes.iter().map(|&index| index.into()).collect()),
+        sig.signer_index.into(),
+    ])
+}
+
+fn sig_from_value(value: Value) -> Result<StmSig, CborEncodingError> {
+    let [sigma, indexes, signer_index] = read_array(value, "signature")?;
+
+    Ok(StmSig {
+        sigma: Signature::from_bytes(&read_bytes(sigma, Some(48))?)
+            .map_err(|_| CborEncodingError::SerializationError)?,
+        indexes: read_list(indexes)?
+            .into_iter()
+            .map(read_uint)
+            .collect::<Result<Vec<Index>, _>>()?,
+        signer_index: read_uint(signer_index)?,
+    })
+}
+
+/// Wrap a body in the envelope of the current version and encode it.
+fn to_versioned_cbor(body: Value) -> Vec<u8> {
+    to_cbor(&Value::Array(vec![CBOR_ENCODING_VERSION.into(), body]))
+}
+
+/// Encode a value, `ciborium` writes the shortest form of the integers, lengths and floats and
+/// definite lengths only.
+fn to_cbor(value: &Value) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(value, &mut bytes)
+        .expect("Writing a CBOR value in a vector can not fail.");
+
+    bytes
+}
+
+/// Decode an envelope and return its body, if its version is supported.
+fn from_versioned_cbor(bytes: &[u8]) -> Result<Value, CborEncodingError> {
+    let mut reader = bytes;
+    let value: Value = ciborium::de::from_reader(&mut reader)
+        .map_err(|e| CborEncodingError::InvalidCbor(e.to_string()))?;
+    if !reader.is_empty() {
+        return Err(CborEncodingError::InvalidCbor(format!(
+            "{} trailing bytes",
+            reader.len()
+        )));
+    }
+    // The schema has no maps, so the deterministic encoding of the decoded value is unique:
+    // the input must be byte for byte that encoding.
+    if to_cbor(&value) != bytes {
+        return Err(CborEncodingError::NonCanonicalEncoding);
+    }
+
+    let [version, body] = read_array(value, "versioned envelope")?;
+    match read_uint(version)? {
+        CBOR_ENCODING_VERSION => Ok(body),
+        version => Err(CborEncodingError::UnsupportedVersion(version)),
+    }
+}
+
+fn read_list(value: Value) -> Result<Vec<Value>, CborEncodingError> {
+    value
+        .into_array()
+        .map_err(|_| CborEncodingError::UnexpectedStructure("expected an array"))
+}
+
+/// Read an array of exactly `N` elements.
+fn read_array<const N: usize>(
+    value: Value,
+    name: &'static str,
+) -> Result<[Value; N], CborEncodingError> {
+    read_list(value)?
+        .try_into()
+        .map_err(|_| CborEncodingError::UnexpectedStructure(name))
+}
+
+fn read_uint(value: Value) -> Result<u64, CborEncodingError> {
+    value
+        .into_integer()
+        .ok()
+        .and_then(|integer| u64::try_from(integer).ok())
+        .ok_or(CborEncodingError::UnexpectedStructure(
+            "expected an unsigned integer",
+        ))
+}
+
+fn read_usize(value: Value) -> Result<usize, CborEncodingError> {
+    usize::try_from(read_uint(value)?).map_err(|_| CborEncodingError::SerializationError)
+}
+
+/// Read a byte string, of length `len` if given.
+fn read_bytes(value: Value, len: Option<usize>) -> Result<Vec<u8>, CborEncodingError> {
+    let bytes = value
+        .into_bytes()
+        .map_err(|_| CborEncodingError::UnexpectedStructure("expected a byte string"))?;
+    match len {
+        Some(len) if bytes.len() != len => Err(CborEncodingError::SerializationError),
+        _ => Ok(bytes),
+    }
+}
+
+fn read_verification_key(value: Value) -> Result<VerificationKey, CborEncodingError> {
+    VerificationKey::from_bytes(&read_bytes(value, Some(96))?)
+        .map_err(|_| CborEncodingError::SerializationError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key_reg::KeyReg;
+    use crate::stm::{StmClerk, StmInitializer};
+    use blake2::{digest::consts::U32, Blake2b};
+    use proptest::prelude::*;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::{RngCore, SeedableRng};
+
+    type D = Blake2b<U32>;
+
+    fn aggregate(seed: [u8; 32], nparties: usize) -> (StmAggrSig<D>, StmAggrVerificationKey<D>) {
+        let params = StmParameters {
+            m: 10,
+            k: 3,
+            phi_f: 0.9,
+        };
+        let mut rng = ChaCha20Rng::from_seed(seed);
+        let mut msg = [0u8; 16];
+        rng.fill_bytes(&mut msg);
+
+        let initializers = (0..nparties)
+            .map(|_| StmInitializer::setup(params, 1 + rng.next_u64() % 100, &mut rng))
+            .collect::<Vec<_>>();
+        let mut key_reg = KeyReg::init();
+        for initializer in &initializers {
+            key_reg
+                .register(initializer.stake, initializer.verification_key())
+                .unwrap();
+        }
+        let closed_reg = key_reg.close::<D>();
+        let signatures = initializers
+            .into_iter()
+            .filter_map(|initializer| {
+                initializer
+                    .new_signer(closed_reg.clone())
+                    .unwrap()
+                    .sign(&msg)
+            })
+            .collect::<Vec<_>>();
+        let clerk = StmClerk::from_registration(&params, &closed_reg);
+
+        (
+            clerk.aggregate(&signatures, &msg).unwrap(),
+            clerk.compute_avk(),
+        )
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10))]
+
+        #[test]
+        fn parameters_roundtrip(m in any::<u64>(), k in any::<u64>(), phi_f in 0.0..=1.0_f64) {
+            let params = StmParameters { m, k, phi_f };
+            let decoded = StmParameters::from_cbor_bytes(&params.to_cbor_bytes()).unwrap();
+
+            prop_assert_eq!(params.to_bytes(), decoded.to_bytes());
+        }
+
+        #[test]
+        fn verification_key_pop_roundtrip(seed in any::<[u8; 32]>()) {
+            let mut rng = ChaCha20Rng::from_seed(seed);
+            let params = StmParameters { m: 10, k: 3, phi_f: 0.9 };
+            let vk_pop = StmInitializer::setup(params, 1, &mut rng).verification_key();
+            let decoded = StmVerificationKeyPoP::from_cbor_bytes(&vk_pop.to_cbor_bytes()).unwrap();
+
+            prop_assert_eq!(vk_pop.to_bytes(), decoded.to_bytes());
+        }
+
+        #[test]
+        fn aggregate_roundtrip(seed in any::<[u8; 32]>(), nparties in 2_usize..8) {
+            let (aggr, avk) = aggregate(seed, nparties);
+
+            let decoded = StmAggrSig::<D>::from_cbor_bytes(&aggr.to_cbor_bytes()).unwrap();
+            prop_assert_eq!(aggr.to_bytes(), decoded.to_bytes());
+            for sig_reg in &aggr.signatures {
+                let sig = StmSig::from_cbor_bytes(&sig_reg.sig.to_cbor_bytes()).unwrap();
+                prop_assert_eq!(&sig_reg.sig, &sig);
+            }
+
+            let decoded = StmAggrVerificationKey::<D>::from_cbor_bytes(&avk.to_cbor_bytes()).unwrap();
+            prop_assert_eq!(avk, decoded);
+        }
+    }
+
+    #[test]
+    fn unsupported_version_is_rejected() {
+        let mut bytes = StmParameters {
+            m: 10,
+            k: 3,
+            phi_f: 0.9,
+        }
+        .to_cbor_bytes();
+        // The envelope starts with an array of two elements (0x82) followed by the version.
+        assert_eq!([0x82, 0x01], bytes[..2]);
+        bytes[1] = 0x02;
+
+        assert_eq!(
+            Err(CborEncodingError::UnsupportedVersion(2)),
+            StmParameters::from_cbor_bytes(&bytes)
+        );
+    }
+
+    #[test]
+    fn trailing_bytes_are_rejected() {
+        let mut bytes = StmParameters {
+            m: 10,
+            k: 3,
+            phi_f: 0.9,
+        }
+        .to_cbor_bytes();
+        bytes.push(0);
+
+        assert!(matches!(
+            StmParameters::from_cbor_bytes(&bytes),
+            Err(CborEncodingError::InvalidCbor(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_structure_is_rejected() {
+        let (aggr, _) = aggregate([0u8; 32], 4);
+        let bytes = aggr.to_cbor_bytes();
+
+        assert!(StmSig::from_cbor_bytes(&bytes).is_err());
+        assert!(StmAggrSig::<D>::from_cbor_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! Compact encoding of `StmAggrSig`.
+//!
+//! The concatenation proving system carries every signature with its registered party, which
+//! can not be avoided: the lottery of an index is evaluated on the individual signature of its
+//! winner (`ev = H("map" || msg || index || σ)`), so the individual signatures can not be
+//! replaced by their aggregate without losing the ability to check the lotteries. This
+//! encoding instead removes the redundancy of the byte representation of `StmAggrSig`:
+//! * the lengths are encoded as LEB128 variable length integers instead of `u64`,
+//! * the indexes of a signature are delta encoded, as they are mostly increasing and close to
+//!   each other, with a zigzag encoding of the deltas so that any order is kept,
+//! * the merkle tree indexes of the signers are not encoded with each signature, as they are the
+//!   indices of the batch path (both are sorted by signer index),
+//! * the indices of the batch path are delta encoded.
+//!
+//! # Scope
+//! This is a lossless re-encoding of the concatenation proving system, it does not aggregate the
+//! signatures nor the verification keys per signer: a signer already contributes a single
+//! signature for all its won indexes, and its registered party is needed to check its lotteries
+//! and its membership to the batch path. Aggregating them would need another proving system, so
+//! the verification cost is the one of the concatenation proving system.
+//!
+//! # Layout
+//! * Number of signatures (varint)
+//! * Number of values of the batch path (varint)
+//! * Values of the batch path
+//! * Indices of the batch path, which are the signer indexes of the signatures (delta varints)
+//! * For each signature:
+//!     * Registered party (104 bytes)
+//!     * Signature (48 bytes)
+//!     * Number of indexes (varint)
+//!     * Indexes (delta varints)
+
+use crate::error::StmAggregateSignatureError;
+use crate::key_reg::RegParty;
+use crate::merkle_tree::BatchPath;
+use crate::multi_sig::Signature;
+use crate::stm::{Index, StmAggrSig, StmSig, StmSigRegParty};
+use blake2::digest::{Digest, FixedOutput};
+use std::marker::PhantomData;
+
+/// Encode an aggregate signature in the compact layout.
+pub(crate) fn to_compact_bytes<D: Clone + Digest + FixedOutput>(aggr: &StmAggrSig<D>) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_varint(&mut out, aggr.signatures.len() as u64);
+
+    write_varint(&mut out, aggr.batch_proof.values.len() as u64);
+    for value in &aggr.batch_proof.values {
+        out.extend_from_slice(value);
+    }
+    write_deltas(
+        &mut out,
+        aggr.signatures
+            .iter()
+            .map(|sig_reg| sig_reg.sig.signer_index),
+    );
+
+    for sig_reg in &aggr.signatures {
+        out.extend_from_slice(&sig_reg.reg_party.to_bytes());
+        out.extend_from_slice(&sig_reg.sig.sigma.to_bytes());
+        write_varint(&mut out, sig_reg.sig.indexes.len() as u64);
+        write_deltas(&mut out, sig_reg.sig.indexes.iter().copied());
+    }
+
+    out
+}
+
+/// Decode an aggregate signature from the compact layout.
+pub(crate) fn from_compact_bytes<D: Clone + Digest + FixedOutput>(
+    bytes: &[u8],
+) -> Result<StmAggrSig<D>, StmAggregateSignatureError<D>> {
+    let mut reader = Reader { bytes, offset: 0 };
+
+    let nr_signatures = reader.read_len()?;
+    let nr_values = reader.read_len()?;
+    let mut values = Vec::with_capacity(nr_values.min(bytes.len()));
+    for _ in 0..nr_values {
+        values.push(reader.read_bytes(<D as Digest>::output_size())?.to_vec());
+    }
+    let signer_indexes = reader.read_deltas(nr_signatures)?;
+
+    let mut signatures = Vec::with_capacity(nr_signatures.min(bytes.len()));
+    for &signer_index in &signer_indexes {
+        let reg_party = RegParty::from_bytes(reader.read_bytes(104)?)
+            .map_err(|_| StmAggregateSignatureError::SerializationError)?;
+        let sigma = Signature::from_bytes(reader.read_bytes(48)?)
+            .map_err(|_| StmAggregateSignatureError::SerializationError)?;
+        let nr_indexes = reader.read_len()?;
+        let indexes = reader.read_deltas(nr_indexes)?;
+
+        signatures.push(StmSigRegParty {
+            sig: StmSig {
+                sigma,
+                indexes,
+                signer_index,
+            },
+            reg_party,
+        });
+    }
+
+    if reader.offset != bytes.len() {
+        return Err(StmAggregateSignatureError::SerializationError);
+    }
+
+    let indices = signer_indexes
+        .into_iter()
+        .map(|index| {
+            usize::try_from(index).map_err(|_| StmAggregateSignatureError::SerializationError)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(StmAggrSig {
+        signatures,
+        batch_proof: BatchPath {
+            values,
+            indices,
+            hasher: PhantomData,
+        },
+    })
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Write the differences between consecutive values, starting from 0, zigzag encoded.
+fn write_deltas(out: &mut Vec<u8>, values: impl Iterator<Item = Index>) {
+    let mut previous: Index = 0;
+    for value in values {
+        let delta = value.wrapping_sub(previous) as i64;
+        write_varint(out, ((delta << 1) ^ (delta >> 63)) as u64);
+        previous = value;
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn read_bytes<D: Digest + FixedOutput>(
+        &mut self,
+        len: usize,
+    ) -> Result<&'a [u8], StmAggregateSignatureError<D>> {
+        let bytes = self
+            .bytes
+            .get(self.offset..self.offset.saturating_add(len))
+            .ok_or(StmAggregateSignatureError::SerializationError)?;
+        self.offset += len;
+
+        Ok(bytes)
+    }
+
+    fn read_varint<D: Digest + FixedOutput>(
+        &mut self,
+    ) -> Result<u64, StmAggregateSignatureError<D>> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_bytes(1)?[0];
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(StmAggregateSignatureError::SerializationError)
+    }
+
+    fn read_len<D: Digest + FixedOutput>(
+        &mut self,
+    ) -> Result<usize, StmAggregateSignatureError<D>> {
+        usize::try_from(self.read_varint()?)
+            .map_err(|_| StmAggregateSignatureError::SerializationError)
+    }
+
+    fn read_deltas<D: Digest + FixedOutput>(
+        &mut self,
+        len: usize,
+    ) -> Result<Vec<Index>, StmAggregateSignatureError<D>> {
+        let mut values = Vec::with_capacity(len.min(self.bytes.len()));
+        let mut previous: Index = 0;
+        for _ in 0..len {
+            let zigzag = self.read_varint()?;
+            let delta = ((zigzag >> 1) as i64) ^ -((zigzag & 1) as i64);
+            previous = previous.wrapping_add(delta as u64);
+            values.push(previous);
+        }
+
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blake2::{digest::consts::U32, Blake2b};
+    use proptest::prelude::*;
+
+    type D = Blake2b<U32>;
+
+    proptest! {
+        #[test]
+        fn deltas_roundtrip(values in proptest::collection::vec(any::<u64>(), 0..50)) {
+            let mut bytes = Vec::new();
+            write_deltas(&mut bytes, values.iter().copied());
+
+            let mut reader = Reader { bytes: &bytes, offset: 0 };
+            let decoded = reader.read_deltas::<D>(values.len()).unwrap();
+
+            prop_assert_eq!(values, decoded);
+            prop_assert_eq!(bytes.len(), reader.offset);
+        }
+    }
+
+    #[test]
+    fn increasing_close_indexes_are_encoded_on_a_single_byte() {
+        let mut bytes = Vec::new();
+        write_deltas(&mut bytes, [3, 10, 12, 40, 100].into_iter());
+
+        assert_eq!(5, bytes.len());
+    }
+
+    #[test]
+    fn truncated_varint_is_rejected() {
+        let mut reader = Reader {
+            bytes: &[0x80, 0x80],
+            offset: 0,
+        };
+
+        assert!(matches!(
+            reader.read_varint::<D>(),
+            Err(StmAggregateSignatureError::SerializationError)
+        ));
+    }
+}
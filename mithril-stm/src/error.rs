@@ -78,6 +78,10 @@ pub enum StmAggregateSignatureError<D: Digest + FixedOutput> {
     #[error("Batch verification of STM aggregate signatures failed")]
     BatchInvalid,
 
+    /// The encoding of a versioned aggregate signature is unknown, or not enabled
+    #[error("Unsupported aggregate signature encoding: {0}")]
+    UnsupportedEncoding(u8),
+
     /// `CoreVerifier` check failed
     #[error("Core verification error: {0}")]
     CoreVerificationError(#[source] CoreVerifierError),
@@ -151,6 +155,54 @@ pub enum RegisterError {
     UnregisteredInitializer,
 }
 
+/// Errors which can be outputted by the security analysis of `StmParameters`.
+#[derive(Debug, Clone, thiserror::Error, PartialEq)]
+pub enum StmParametersSecurityError {
+    /// `phi_f` is not in `(0, 1]`
+    #[error("phi_f, {0}, must be in (0, 1].")]
+    InvalidPhiF(f64),
+
+    /// The quorum `k` is not in `[1, m]`
+    #[error("The quorum, {0}, must be in [1, m], with m = {1}.")]
+    InvalidQuorum(u64, u64),
+
+    /// A fraction of the stake is not in `[0, 1]`
+    #[error("The stake fraction, {0}, must be in [0, 1].")]
+    InvalidStakeFraction(f64),
+
+    /// A probability is not in `[0, 1]`
+    #[error("The probability, {0}, must be in [0, 1].")]
+    InvalidProbability(f64),
+
+    /// The stake distribution has no stake
+    #[error("The stake distribution has no stake.")]
+    EmptyStakeDistribution,
+}
+
+/// Errors which can be outputted by the CBOR encoding of the STM types.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum CborEncodingError {
+    /// The bytes are not a well formed CBOR data item
+    #[error("Invalid CBOR: {0}")]
+    InvalidCbor(String),
+
+    /// The version of the encoding is unknown
+    #[error("Unsupported CBOR encoding version: {0}")]
+    UnsupportedVersion(u64),
+
+    /// The CBOR data item does not follow the deterministic encoding requirements
+    #[error("Non canonical CBOR encoding")]
+    NonCanonicalEncoding,
+
+    /// The CBOR data item does not follow the schema of the decoded type
+    #[error("Unexpected CBOR structure: {0}")]
+    UnexpectedStructure(&'static str),
+
+    /// The bytes of a key, a signature or a hash are invalid
+    #[error("Invalid bytes")]
+    SerializationError,
+}
+
 impl From<MultiSignatureError> for StmSignatureError {
     fn from(e: MultiSignatureError) -> Self {
         match e {
@@ -4,15 +4,19 @@
 
 extern crate core;
 
+pub mod cbor;
+#[cfg(feature = "compact-aggregate")]
+mod compact_encoding;
 mod eligibility_check;
 mod error;
 pub mod key_reg;
 mod merkle_tree;
+pub mod security;
 pub mod stm;
 
 pub use crate::error::{
-    AggregationError, CoreVerifierError, RegisterError, StmAggregateSignatureError,
-    StmSignatureError,
+    AggregationError, CborEncodingError, CoreVerifierError, RegisterError,
+    StmAggregateSignatureError, StmParametersSecurityError, StmSignatureError,
 };
 
 #[cfg(feature = "benchmark-internals")]
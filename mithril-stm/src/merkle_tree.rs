@@ -53,8 +53,8 @@ pub struct MerkleTreeCommitment<D: Digest> {
 pub struct MerkleTreeCommitmentBatchCompat<D: Digest> {
     /// Root of the merkle commitment.
     pub root: Vec<u8>,
-    nr_leaves: usize,
-    hasher: PhantomData<D>,
+    pub(crate) nr_leaves: usize,
+    pub(crate) hasher: PhantomData<D>,
 }
 
 impl<D: Digest> PartialEq for MerkleTreeCommitmentBatchCompat<D> {
@@ -87,7 +87,11 @@ impl MTLeaf {
         let pk = StmVerificationKey::from_bytes(bytes)
             .map_err(|_| MerkleTreeError::SerializationError)?;
         let mut u64_bytes = [0u8; 8];
-        u64_bytes.copy_from_slice(&bytes[96..]);
+        u64_bytes.copy_from_slice(
+            bytes
+                .get(96..104)
+                .ok_or(MerkleTreeError::SerializationError)?,
+        );
         let stake = Stake::from_be_bytes(u64_bytes);
         Ok(MTLeaf(pk, stake))
     }
@@ -145,17 +149,25 @@ impl<D: Digest + Clone + FixedOutput> Path<D> {
     /// This function fails if the bytes cannot retrieve path.
     pub fn from_bytes(bytes: &[u8]) -> Result<Path<D>, MerkleTreeError<D>> {
         let mut u64_bytes = [0u8; 8];
-        u64_bytes.copy_from_slice(&bytes[..8]);
+        u64_bytes.copy_from_slice(bytes.get(..8).ok_or(MerkleTreeError::SerializationError)?);
         let index = usize::try_from(u64::from_be_bytes(u64_bytes))
             .map_err(|_| MerkleTreeError::SerializationError)?;
-        u64_bytes.copy_from_slice(&bytes[8..16]);
+        u64_bytes.copy_from_slice(
+            bytes
+                .get(8..16)
+                .ok_or(MerkleTreeError::SerializationError)?,
+        );
         let len = usize::try_from(u64::from_be_bytes(u64_bytes))
             .map_err(|_| MerkleTreeError::SerializationError)?;
-        let mut values = Vec::with_capacity(len);
+        let mut values = Vec::with_capacity(len.min(bytes.len()));
         for i in 0..len {
             values.push(
-                bytes[16 + i * <D as Digest>::output_size()
-                    ..16 + (i + 1) * <D as Digest>::output_size()]
+                bytes
+                    .get(
+                        16 + i * <D as Digest>::output_size()
+                            ..16 + (i + 1) * <D as Digest>::output_size(),
+                    )
+                    .ok_or(MerkleTreeError::SerializationError)?
                     .to_vec(),
             );
         }
@@ -196,30 +208,41 @@ impl<D: Digest + FixedOutput> BatchPath<D> {
     }
 
     /// Try to convert a byte string into a `BatchPath`.
-    // todo: We should not panic if the size of the slice is invalid (I believe `bytes[offset + i * 8..offset + (i + 1) * 8]` will panic if bytes is not large enough.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, MerkleTreeError<D>> {
         let mut u64_bytes = [0u8; 8];
-        u64_bytes.copy_from_slice(&bytes[..8]);
+        u64_bytes.copy_from_slice(bytes.get(..8).ok_or(MerkleTreeError::SerializationError)?);
         let len_v = usize::try_from(u64::from_be_bytes(u64_bytes))
             .map_err(|_| MerkleTreeError::SerializationError)?;
 
-        u64_bytes.copy_from_slice(&bytes[8..16]);
+        u64_bytes.copy_from_slice(
+            bytes
+                .get(8..16)
+                .ok_or(MerkleTreeError::SerializationError)?,
+        );
         let len_i = usize::try_from(u64::from_be_bytes(u64_bytes))
             .map_err(|_| MerkleTreeError::SerializationError)?;
 
-        let mut values = Vec::with_capacity(len_v);
+        let mut values = Vec::with_capacity(len_v.min(bytes.len()));
         for i in 0..len_v {
             values.push(
-                bytes[16 + i * <D as Digest>::output_size()
-                    ..16 + (i + 1) * <D as Digest>::output_size()]
+                bytes
+                    .get(
+                        16 + i * <D as Digest>::output_size()
+                            ..16 + (i + 1) * <D as Digest>::output_size(),
+                    )
+                    .ok_or(MerkleTreeError::SerializationError)?
                     .to_vec(),
             );
         }
         let offset = 16 + len_v * <D as Digest>::output_size();
 
-        let mut indices = Vec::with_capacity(len_v);
+        let mut indices = Vec::with_capacity(len_i.min(bytes.len()));
         for i in 0..len_i {
-            u64_bytes.copy_from_slice(&bytes[offset + i * 8..offset + (i + 1) * 8]);
+            u64_bytes.copy_from_slice(
+                bytes
+                    .get(offset + i * 8..offset + (i + 1) * 8)
+                    .ok_or(MerkleTreeError::SerializationError)?,
+            );
             indices.push(
                 usize::try_from(u64::from_be_bytes(u64_bytes))
                     .map_err(|_| MerkleTreeError::SerializationError)?,
@@ -575,15 +598,23 @@ impl<D: Digest + FixedOutput> MerkleTree<D> {
     /// It returns error if conversion fails.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, MerkleTreeError<D>> {
         let mut u64_bytes = [0u8; 8];
-        u64_bytes.copy_from_slice(&bytes[..8]);
+        u64_bytes.copy_from_slice(bytes.get(..8).ok_or(MerkleTreeError::SerializationError)?);
         let n = usize::try_from(u64::from_be_bytes(u64_bytes))
             .map_err(|_| MerkleTreeError::SerializationError)?;
-        let num_nodes = n + n.next_power_of_two() - 1;
-        let mut nodes = Vec::with_capacity(num_nodes);
+        let num_nodes = n
+            .checked_next_power_of_two()
+            .and_then(|power| power.checked_add(n))
+            .and_then(|sum| sum.checked_sub(1))
+            .ok_or(MerkleTreeError::SerializationError)?;
+        let mut nodes = Vec::with_capacity(num_nodes.min(bytes.len()));
         for i in 0..num_nodes {
             nodes.push(
-                bytes[8 + i * <D as Digest>::output_size()
-                    ..8 + (i + 1) * <D as Digest>::output_size()]
+                bytes
+                    .get(
+                        8 + i * <D as Digest>::output_size()
+                            ..8 + (i + 1) * <D as Digest>::output_size(),
+                    )
+                    .ok_or(MerkleTreeError::SerializationError)?
                     .to_vec(),
             );
         }
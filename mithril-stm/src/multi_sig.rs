@@ -23,11 +23,12 @@ use std::{
     hash::{Hash, Hasher},
     iter::Sum,
 };
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 /// String used to generate the proofs of possession.
 const POP: &[u8] = b"PoP";
 
 /// MultiSig secret key, which is a wrapper over the BlstSk type from the blst
-/// library.
+/// library. The key is zeroized when dropped.
 #[derive(Debug, Clone)]
 pub struct SigningKey(BlstSk);
 
@@ -63,10 +64,10 @@ pub struct Signature(BlstSig);
 impl SigningKey {
     /// Generate a secret key
     pub fn gen(rng: &mut (impl RngCore + CryptoRng)) -> Self {
-        let mut ikm = [0u8; 32];
-        rng.fill_bytes(&mut ikm);
+        let mut ikm = Zeroizing::new([0u8; 32]);
+        rng.fill_bytes(ikm.as_mut());
         SigningKey(
-            BlstSk::key_gen(&ikm, &[])
+            BlstSk::key_gen(ikm.as_ref(), &[])
                 .expect("Error occurs when the length of ikm < 32. This will not happen here."),
         )
     }
@@ -77,6 +78,8 @@ impl SigningKey {
     }
 
     /// Convert the secret key into byte string.
+    ///
+    /// The returned bytes are secret, they should be zeroized once they are not needed anymore.
     pub fn to_bytes(&self) -> [u8; 32] {
         self.0.to_bytes()
     }
@@ -86,7 +89,10 @@ impl SigningKey {
     /// # Error
     /// Fails if the byte string represents a scalar larger than the group order.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, MultiSignatureError> {
-        match BlstSk::from_bytes(&bytes[..32]) {
+        let bytes = bytes
+            .get(..32)
+            .ok_or(MultiSignatureError::SerializationError)?;
+        match BlstSk::from_bytes(bytes) {
             Ok(sk) => Ok(Self(sk)),
             Err(e) => Err(blst_err_to_mithril(e, None)
                 .expect_err("If deserialization is not successful, blst returns and error different to SUCCESS."))
@@ -94,6 +100,20 @@ impl SigningKey {
     }
 }
 
+impl Zeroize for SigningKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SigningKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SigningKey {}
+
 impl VerificationKey {
     /// Convert an `VerificationKey` to its compressed byte representation.
     pub fn to_bytes(self) -> [u8; 96] {
@@ -106,7 +126,10 @@ impl VerificationKey {
     /// This function fails if the bytes do not represent a compressed point of the prime
     /// order subgroup of the curve Bls12-381.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, MultiSignatureError> {
-        match BlstVk::key_validate(&bytes[..96]) {
+        let bytes = bytes
+            .get(..96)
+            .ok_or(MultiSignatureError::SerializationError)?;
+        match BlstVk::key_validate(bytes) {
             Ok(vk) => Ok(Self(vk)),
             Err(e) => Err(blst_err_to_mithril(e, None)
                 .expect_err("If deserialization is not successful, blst returns and error different to SUCCESS."))
@@ -222,9 +245,13 @@ impl VerificationKeyPoP {
 
     /// Deserialize a byte string to a `PublicKeyPoP`.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, MultiSignatureError> {
-        let mvk = VerificationKey::from_bytes(&bytes[..96])?;
+        let mvk = VerificationKey::from_bytes(bytes)?;
 
-        let pop = ProofOfPossession::from_bytes(&bytes[96..])?;
+        let pop = ProofOfPossession::from_bytes(
+            bytes
+                .get(96..)
+                .ok_or(MultiSignatureError::SerializationError)?,
+        )?;
 
         Ok(Self { vk: mvk, pop })
     }
@@ -258,7 +285,11 @@ impl ProofOfPossession {
 
     /// Deserialize a byte string to a `PublicKeyPoP`.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, MultiSignatureError> {
-        let k1 = match BlstSig::from_bytes(&bytes[..48]) {
+        let k1 = match BlstSig::from_bytes(
+            bytes
+                .get(..48)
+                .ok_or(MultiSignatureError::SerializationError)?,
+        ) {
             Ok(key) => key,
             Err(e) => {
                 return Err(blst_err_to_mithril(e, None)
@@ -266,7 +297,11 @@ impl ProofOfPossession {
             }
         };
 
-        let k2 = uncompress_p1(&bytes[48..96])?;
+        let k2 = uncompress_p1(
+            bytes
+                .get(48..96)
+                .ok_or(MultiSignatureError::SerializationError)?,
+        )?;
 
         Ok(Self { k1, k2 })
     }
@@ -321,7 +356,10 @@ impl Signature {
     /// # Error
     /// Returns an error if the byte string does not represent a point in the curve.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, MultiSignatureError> {
-        match BlstSig::sig_validate(&bytes[..48], true) {
+        let bytes = bytes
+            .get(..48)
+            .ok_or(MultiSignatureError::SerializationError)?;
+        match BlstSig::sig_validate(bytes, true) {
             Ok(sig) => Ok(Self(sig)),
             Err(e) => Err(blst_err_to_mithril(e, None)
                 .expect_err("If deserialization is not successful, blst returns and error different to SUCCESS."))
@@ -472,7 +510,8 @@ macro_rules! impl_serde {
             {
                 use serde::ser::SerializeTuple;
                 let mut seq = serializer.serialize_tuple($size)?;
-                for e in self.to_bytes().iter() {
+                // The bytes of a signing key are secret.
+                for e in Zeroizing::new(self.to_bytes()).iter() {
                     seq.serialize_element(e)?;
                 }
                 seq.end()
@@ -501,7 +540,7 @@ macro_rules! impl_serde {
                     where
                         A: serde::de::SeqAccess<'de>,
                     {
-                        let mut bytes = [0u8; $size];
+                        let mut bytes = Zeroizing::new([0u8; $size]);
                         for i in 0..$size {
                             bytes[i] =
                                 seq.next_element()?.ok_or(serde::de::Error::invalid_length(
@@ -509,7 +548,7 @@ macro_rules! impl_serde {
                                     &format!("expected bytes{}", $size.to_string()).as_str(),
                                 ))?;
                         }
-                        <$st>::from_bytes(&bytes).map_err(|_| {
+                        <$st>::from_bytes(bytes.as_ref()).map_err(|_| {
                             serde::de::Error::custom(
                                 &format!("deserialization failed [{}]", stringify!($st)).as_str(),
                             )
@@ -776,6 +815,15 @@ mod tests {
 
     impl Eq for SigningKey {}
 
+    #[test]
+    fn test_zeroize() {
+        let mut sk = SigningKey::gen(&mut OsRng);
+        assert_ne!([0u8; 32], sk.to_bytes());
+
+        sk.zeroize();
+        assert_eq!([0u8; 32], sk.to_bytes());
+    }
+
     #[test]
     fn test_gen() {
         for _ in 0..128 {
@@ -0,0 +1,529 @@
+//! Security analysis of the STM parameters.
+//!
+//! Each of the `m` lotteries of a signature is won by a party holding a fraction `w` of the
+//! stake with probability `φ(w) = 1 - (1 - phi_f)^w`. As `φ` is independent of the way the stake
+//! is split between parties, a coalition holding a fraction `a` of the stake wins a lottery with
+//! probability `φ(a)`, and the number of lotteries it wins follows a binomial distribution of
+//! `m` experiments with success probability `φ(a)`. Given an assumed adversarial stake fraction
+//! `a`, the parameters are:
+//! * secure if the adversary can not win `k` lotteries on its own, ie. the binomial tail
+//!   `SUM[from i=k to i=m] Binomial(i successes, m experiments, φ(a))` is lower than `2^-100`
+//!   or thereabouts,
+//! * live if the honest parties, holding a fraction `1 - a` of the stake, win at least `k`
+//!   lotteries with a high probability.
+//!
+//! ```
+//! use mithril_stm::security::{SecurityRequirements, StmParametersSecurity};
+//! use mithril_stm::stm::StmParameters;
+//!
+//! let params = StmParameters { m: 20973, k: 2422, phi_f: 0.2 };
+//! let requirements = SecurityRequirements::default();
+//! let security =
+//!     StmParametersSecurity::compute(&params, requirements.adversarial_stake_fraction).unwrap();
+//!
+//! assert!(security.forgery_probability_log2 < -100.0);
+//! assert!(security.meets(&requirements));
+//! ```
+
+use crate::error::StmParametersSecurityError;
+use crate::stm::{Stake, StmParameters};
+
+/// Size in bytes of the hashes of the Merkle tree commitment of the registered parties, ie. the
+/// output size of `Blake2b<U32>`.
+const MERKLE_TREE_HASH_SIZE: f64 = 32.0;
+
+/// Size in bytes of the fixed part of a signature in an aggregate signature: the registered
+/// party (104 bytes), the number of indexes (8 bytes), the signature (48 bytes) and the signer
+/// index (8 bytes).
+const AGGREGATED_SIGNATURE_FIXED_SIZE: f64 = 168.0;
+
+/// Requirements that a set of `StmParameters` must meet to be considered safe.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecurityRequirements {
+    /// Maximum fraction of the total stake assumed to be held by an adversary.
+    pub adversarial_stake_fraction: f64,
+    /// Minimum number of bits of security, ie. the probability that the adversary forges a
+    /// signature on its own must be lower than `2^-security_bits`.
+    pub security_bits: f64,
+    /// Minimum probability that the honest parties win enough lotteries to reach the quorum.
+    pub liveness_probability: f64,
+}
+
+impl Default for SecurityRequirements {
+    /// An adversary holding up to 40% of the stake, 100 bits of security and a quorum reached
+    /// by the honest parties 99% of the time.
+    fn default() -> Self {
+        Self {
+            adversarial_stake_fraction: 0.4,
+            security_bits: 100.0,
+            liveness_probability: 0.99,
+        }
+    }
+}
+
+impl SecurityRequirements {
+    fn check(&self) -> Result<(), StmParametersSecurityError> {
+        check_fraction(self.adversarial_stake_fraction)?;
+        if !(0.0..=1.0).contains(&self.liveness_probability) {
+            return Err(StmParametersSecurityError::InvalidProbability(
+                self.liveness_probability,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Security and liveness of a set of `StmParameters` against an adversary holding a given
+/// fraction of the stake.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StmParametersSecurity {
+    /// Base 2 logarithm of the probability that the adversary wins at least `k` lotteries, ie.
+    /// forges a signature on its own.
+    pub forgery_probability_log2: f64,
+    /// Probability that the honest parties win at least `k` lotteries, ie. reach the quorum
+    /// without the adversary when they all sign.
+    pub liveness_probability: f64,
+}
+
+impl StmParametersSecurity {
+    /// Compute the security and liveness of `params` against an adversary holding
+    /// `adversarial_stake_fraction` of the total stake.
+    pub fn compute(
+        params: &StmParameters,
+        adversarial_stake_fraction: f64,
+    ) -> Result<Self, StmParametersSecurityError> {
+        check_params(params)?;
+        check_fraction(adversarial_stake_fraction)?;
+
+        Ok(Self {
+            forgery_probability_log2: ln_binomial_upper_tail(
+                params.m,
+                params.k,
+                phi(params.phi_f, adversarial_stake_fraction),
+            ) / std::f64::consts::LN_2,
+            liveness_probability: ln_binomial_upper_tail(
+                params.m,
+                params.k,
+                phi(params.phi_f, 1.0 - adversarial_stake_fraction),
+            )
+            .exp(),
+        })
+    }
+
+    /// Probability that the adversary wins at least `k` lotteries (may underflow to 0 for
+    /// secure parameters, see `forgery_probability_log2`).
+    pub fn forgery_probability(&self) -> f64 {
+        self.forgery_probability_log2.exp2()
+    }
+
+    /// Number of bits of security, ie. `-log2` of the forgery probability.
+    pub fn security_bits(&self) -> f64 {
+        -self.forgery_probability_log2
+    }
+
+    /// Check if the security and the liveness meet the given requirements, the adversarial stake
+    /// fraction of the requirements is expected to be the one used to compute `self`.
+    pub fn meets(&self, requirements: &SecurityRequirements) -> bool {
+        self.security_bits() >= requirements.security_bits
+            && self.liveness_probability >= requirements.liveness_probability
+    }
+}
+
+/// Expected costs of a set of `StmParameters` for a given stake distribution, when all the
+/// parties sign.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StmParametersCost {
+    /// Expected number of lotteries won by all the parties.
+    pub expected_winning_lotteries: f64,
+    /// Expected number of parties that win at least one lottery.
+    pub expected_winning_parties: f64,
+    /// Expected number of signatures kept in an aggregate signature: the aggregation only keeps
+    /// the signatures needed to reach `k` lotteries.
+    pub expected_aggregated_signatures: f64,
+    /// Estimated size in bytes of an aggregate signature, the size of the batch path of the
+    /// Merkle tree commitment is approximated.
+    pub expected_certificate_size: f64,
+}
+
+impl StmParametersCost {
+    /// Compute the expected costs of `params` for the given stake distribution.
+    pub fn compute(
+        params: &StmParameters,
+        stakes: &[Stake],
+    ) -> Result<Self, StmParametersSecurityError> {
+        check_params(params)?;
+        let total_stake: u128 = stakes.iter().map(|&stake| stake as u128).sum();
+        if total_stake == 0 {
+            return Err(StmParametersSecurityError::EmptyStakeDistribution);
+        }
+
+        let m = params.m as f64;
+        let k = params.k as f64;
+        let (expected_winning_lotteries, expected_winning_parties) = stakes
+            .iter()
+            .filter(|&&stake| stake > 0)
+            .map(|&stake| phi(params.phi_f, stake as f64 / total_stake as f64))
+            .fold((0.0, 0.0), |(lotteries, parties), p| {
+                (lotteries + m * p, parties + 1.0 - (1.0 - p).powf(m))
+            });
+        let expected_aggregated_signatures = if expected_winning_lotteries > k {
+            expected_winning_parties * k / expected_winning_lotteries
+        } else {
+            expected_winning_parties
+        };
+
+        let nr_parties = stakes.iter().filter(|&&stake| stake > 0).count() as f64;
+        let nr_signatures = expected_aggregated_signatures.max(1.0);
+        let batch_path_hashes = nr_signatures * (nr_parties / nr_signatures).log2().max(0.0);
+        let expected_certificate_size = 16.0
+            + nr_signatures * AGGREGATED_SIGNATURE_FIXED_SIZE
+            + 8.0 * k
+            + 16.0
+            + nr_signatures * 8.0
+            + batch_path_hashes * MERKLE_TREE_HASH_SIZE;
+
+        Ok(Self {
+            expected_winning_lotteries,
+            expected_winning_parties,
+            expected_aggregated_signatures,
+            expected_certificate_size,
+        })
+    }
+}
+
+/// Search the smallest security parameter `m`, up to `max_m`, for which a quorum `k` meets the
+/// requirements with the given `phi_f`, and return it with the smallest such quorum.
+///
+/// The quorum is the smallest one that meets the security requirement, as the liveness
+/// decreases with the quorum. The security parameter is searched by bisection, assuming that
+/// the requirements are met for any `m` greater than the first one found.
+pub fn search_parameters(
+    phi_f: f64,
+    requirements: &SecurityRequirements,
+    max_m: u64,
+) -> Result<Option<StmParameters>, StmParametersSecurityError> {
+    check_phi_f(phi_f)?;
+    requirements.check()?;
+
+    let mut upper_m = 1;
+    let mut found = loop {
+        if let Some(params) = parameters_for_m(phi_f, requirements, upper_m)? {
+            break params;
+        }
+        if upper_m >= max_m {
+            return Ok(None);
+        }
+        upper_m = (upper_m * 2).min(max_m);
+    };
+
+    let mut lower_m = upper_m / 2;
+    while upper_m - lower_m > 1 {
+        let middle_m = lower_m + (upper_m - lower_m) / 2;
+        match parameters_for_m(phi_f, requirements, middle_m)? {
+            Some(params) => {
+                upper_m = middle_m;
+                found = params;
+            }
+            None => lower_m = middle_m,
+        }
+    }
+
+    Ok(Some(found))
+}
+
+/// Find the smallest quorum meeting the security requirement for `m` and check its liveness.
+fn parameters_for_m(
+    phi_f: f64,
+    requirements: &SecurityRequirements,
+    m: u64,
+) -> Result<Option<StmParameters>, StmParametersSecurityError> {
+    let security = |k| {
+        StmParametersSecurity::compute(
+            &StmParameters { m, k, phi_f },
+            requirements.adversarial_stake_fraction,
+        )
+    };
+    if security(m)?.security_bits() < requirements.security_bits {
+        return Ok(None);
+    }
+
+    // The security increases with the quorum.
+    let (mut lower_k, mut upper_k) = (0, m);
+    while upper_k - lower_k > 1 {
+        let middle_k = lower_k + (upper_k - lower_k) / 2;
+        if security(middle_k)?.security_bits() >= requirements.security_bits {
+            upper_k = middle_k;
+        } else {
+            lower_k = middle_k;
+        }
+    }
+
+    let params = StmParameters {
+        m,
+        k: upper_k,
+        phi_f,
+    };
+    if security(upper_k)?.meets(requirements) {
+        Ok(Some(params))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Probability that a party holding a fraction `stake_fraction` of the stake wins a lottery.
+fn phi(phi_f: f64, stake_fraction: f64) -> f64 {
+    1.0 - (1.0 - phi_f).powf(stake_fraction)
+}
+
+/// Natural logarithm of `SUM[from i=k to i=m] Binomial(i successes, m experiments, p)`,
+/// computed in the log domain as the probabilities of secure parameters underflow `f64`.
+fn ln_binomial_upper_tail(m: u64, k: u64, p: f64) -> f64 {
+    if k == 0 || p >= 1.0 {
+        return 0.0;
+    }
+    if k > m || p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+
+    let (ln_p, ln_q) = (p.ln(), (-p).ln_1p());
+    // ln(C(m, k)) = SUM[from j=0 to j=k-1] ln(m - j) - ln(j + 1)
+    let mut ln_binomial_coefficient: f64 = (0..k)
+        .map(|j| ((m - j) as f64).ln() - ((j + 1) as f64).ln())
+        .sum();
+    let mut ln_terms = Vec::with_capacity((m - k + 1) as usize);
+    for i in k..=m {
+        ln_terms.push(ln_binomial_coefficient + i as f64 * ln_p + (m - i) as f64 * ln_q);
+        // ln(C(m, i + 1)) = ln(C(m, i)) + ln(m - i) - ln(i + 1)
+        if i < m {
+            ln_binomial_coefficient += ((m - i) as f64).ln() - ((i + 1) as f64).ln();
+        }
+    }
+
+    let max_ln_term = ln_terms.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let sum: f64 = ln_terms.iter().map(|t| (t - max_ln_term).exp()).sum();
+
+    // The tail of a probability distribution is at most 1.
+    (max_ln_term + sum.ln()).min(0.0)
+}
+
+fn check_phi_f(phi_f: f64) -> Result<(), StmParametersSecurityError> {
+    if phi_f > 0.0 && phi_f <= 1.0 {
+        Ok(())
+    } else {
+        Err(StmParametersSecurityError::InvalidPhiF(phi_f))
+    }
+}
+
+fn check_params(params: &StmParameters) -> Result<(), StmParametersSecurityError> {
+    check_phi_f(params.phi_f)?;
+    if params.k == 0 || params.k > params.m {
+        return Err(StmParametersSecurityError::InvalidQuorum(
+            params.k, params.m,
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_fraction(fraction: f64) -> Result<(), StmParametersSecurityError> {
+    if (0.0..=1.0).contains(&fraction) {
+        Ok(())
+    } else {
+        Err(StmParametersSecurityError::InvalidStakeFraction(fraction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Exact binomial tail, only usable for small `m`.
+    fn naive_binomial_upper_tail(m: u64, k: u64, p: f64) -> f64 {
+        (k..=m)
+            .map(|i| {
+                let coefficient: f64 = (0..i).map(|j| (m - j) as f64 / (j + 1) as f64).product();
+                coefficient * p.powi(i as i32) * (1.0 - p).powi((m - i) as i32)
+            })
+            .sum()
+    }
+
+    fn mainnet_params() -> StmParameters {
+        StmParameters {
+            m: 20973,
+            k: 2422,
+            phi_f: 0.2,
+        }
+    }
+
+    #[test]
+    fn binomial_upper_tail_edge_cases() {
+        assert_eq!(0.0, ln_binomial_upper_tail(10, 0, 0.5));
+        assert_eq!(0.0, ln_binomial_upper_tail(10, 4, 1.0));
+        assert_eq!(f64::NEG_INFINITY, ln_binomial_upper_tail(10, 4, 0.0));
+        assert_eq!(f64::NEG_INFINITY, ln_binomial_upper_tail(10, 11, 0.5));
+        assert!((ln_binomial_upper_tail(10, 10, 0.5) - 0.5f64.powi(10).ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn mainnet_parameters_meet_the_default_requirements() {
+        let requirements = SecurityRequirements::default();
+        let security = StmParametersSecurity::compute(
+            &mainnet_params(),
+            requirements.adversarial_stake_fraction,
+        )
+        .unwrap();
+
+        assert!(security.meets(&requirements), "{security:?}");
+        assert!(security.security_bits() > 150.0, "{security:?}");
+        assert!(security.liveness_probability > 0.9999, "{security:?}");
+    }
+
+    #[test]
+    fn test_parameters_do_not_meet_the_default_requirements() {
+        let requirements = SecurityRequirements::default();
+        let security = StmParametersSecurity::compute(
+            &StmParameters {
+                m: 100,
+                k: 5,
+                phi_f: 0.65,
+            },
+            requirements.adversarial_stake_fraction,
+        )
+        .unwrap();
+
+        assert!(!security.meets(&requirements), "{security:?}");
+    }
+
+    #[test]
+    fn compute_security_fails_with_invalid_inputs() {
+        let params = mainnet_params();
+
+        assert_eq!(
+            Err(StmParametersSecurityError::InvalidPhiF(0.0)),
+            StmParametersSecurity::compute(
+                &StmParameters {
+                    phi_f: 0.0,
+                    ..params
+                },
+                0.4
+            )
+        );
+        assert_eq!(
+            Err(StmParametersSecurityError::InvalidQuorum(0, params.m)),
+            StmParametersSecurity::compute(&StmParameters { k: 0, ..params }, 0.4)
+        );
+        assert_eq!(
+            Err(StmParametersSecurityError::InvalidStakeFraction(1.5)),
+            StmParametersSecurity::compute(&params, 1.5)
+        );
+    }
+
+    #[test]
+    fn cost_of_a_single_party_holding_all_the_stake() {
+        let params = StmParameters {
+            m: 100,
+            k: 10,
+            phi_f: 0.2,
+        };
+
+        let cost = StmParametersCost::compute(&params, &[1_000]).unwrap();
+
+        assert!((cost.expected_winning_lotteries - 20.0).abs() < 1e-9);
+        assert!((cost.expected_winning_parties - 1.0).abs() < 1e-9);
+        assert!(cost.expected_aggregated_signatures <= 1.0);
+        assert_eq!(
+            16.0 + 168.0 + 8.0 * 10.0 + 16.0 + 8.0,
+            cost.expected_certificate_size
+        );
+    }
+
+    #[test]
+    fn cost_increases_with_the_quorum() {
+        let stakes: Vec<Stake> = (1..=3_000).collect();
+        let cost = |k| {
+            StmParametersCost::compute(
+                &StmParameters {
+                    k,
+                    ..mainnet_params()
+                },
+                &stakes,
+            )
+            .unwrap()
+        };
+
+        assert!(cost(1_000).expected_certificate_size < cost(2_000).expected_certificate_size);
+        assert!(
+            cost(1_000).expected_aggregated_signatures < cost(2_000).expected_aggregated_signatures
+        );
+    }
+
+    #[test]
+    fn cost_fails_without_stake() {
+        assert_eq!(
+            Err(StmParametersSecurityError::EmptyStakeDistribution),
+            StmParametersCost::compute(&mainnet_params(), &[0, 0])
+        );
+    }
+
+    #[test]
+    fn search_parameters_meeting_the_requirements() {
+        let requirements = SecurityRequirements::default();
+
+        let params = search_parameters(0.2, &requirements, 100_000)
+            .unwrap()
+            .expect("Parameters meeting the default requirements should be found");
+
+        let security =
+            StmParametersSecurity::compute(&params, requirements.adversarial_stake_fraction)
+                .unwrap();
+        assert!(security.meets(&requirements), "{params:?}: {security:?}");
+        let lower_quorum_security = StmParametersSecurity::compute(
+            &StmParameters {
+                k: params.k - 1,
+                ..params
+            },
+            requirements.adversarial_stake_fraction,
+        )
+        .unwrap();
+        assert!(
+            !lower_quorum_security.meets(&requirements),
+            "{params:?}: {lower_quorum_security:?}"
+        );
+    }
+
+    #[test]
+    fn search_parameters_without_honest_majority_find_nothing() {
+        let requirements = SecurityRequirements {
+            adversarial_stake_fraction: 0.5,
+            ..SecurityRequirements::default()
+        };
+
+        assert_eq!(None, search_parameters(0.2, &requirements, 10_000).unwrap());
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(50))]
+
+        #[test]
+        fn binomial_upper_tail_matches_the_naive_computation(m in 1..60u64, k in 1..60u64, p in 0.01..0.99f64) {
+            prop_assume!(k <= m);
+            let expected = naive_binomial_upper_tail(m, k, p);
+
+            let computed = ln_binomial_upper_tail(m, k, p).exp();
+
+            prop_assert!((computed - expected).abs() <= 1e-9 * expected.max(1e-300), "{} != {}", computed, expected);
+        }
+
+        #[test]
+        fn security_increases_with_the_quorum(k in 1..2_000u64, a in 0.05..0.45f64) {
+            let params = StmParameters { m: 2_000, k, phi_f: 0.2 };
+            let security = StmParametersSecurity::compute(&params, a).unwrap();
+            let next_security = StmParametersSecurity::compute(&StmParameters { k: k + 1, ..params }, a).unwrap();
+
+            prop_assert!(next_security.forgery_probability_log2 <= security.forgery_probability_log2);
+            prop_assert!(next_security.liveness_probability <= security.liveness_probability);
+        }
+    }
+}
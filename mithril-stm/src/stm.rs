@@ -122,6 +122,7 @@ use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::{From, TryFrom, TryInto};
 use std::hash::{Hash, Hasher};
+use zeroize::Zeroize;
 
 /// The quantity of stake held by a party, represented as a `u64`.
 pub type Stake = u64;
@@ -137,12 +138,9 @@ pub type StmVerificationKeyPoP = VerificationKeyPoP;
 pub type StmVerificationKey = VerificationKey;
 
 /// Used to set protocol parameters.
-// todo: this is the criteria to consider parameters valid:
-// Let A = max assumed adversarial stake
-// Let a = A / max_stake
-// Let p = φ(a)  // f needs tuning, something close to 0.2 is reasonable
-// Then, we're secure if SUM[from i=k to i=m] Binomial(i successes, m experiments, p chance of success) <= 2^-100 or thereabouts.
-// The latter turns to 1 - BinomialCDF(k-1,m,p)
+///
+/// The security and the liveness of a set of parameters can be checked with
+/// [StmParametersSecurity](crate::security::StmParametersSecurity).
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct StmParameters {
     /// Security parameter, upper bound on indices.
@@ -156,6 +154,7 @@ pub struct StmParameters {
 /// Initializer for `StmSigner`.
 /// This is the data that is used during the key registration procedure.
 /// Once the latter is finished, this instance is consumed into an `StmSigner`.
+/// Its secret key is zeroized when it is dropped, by the `Drop` of its signing key.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StmInitializer {
     /// This participant's stake.
@@ -175,6 +174,8 @@ pub struct StmInitializer {
 /// * If the signer does not have `closed_reg`, then it is a core signer.
 ///     * This kind of signer cannot participate certificate generation.
 ///     * Signature generated can be verified by a full node verifier (core verifier).
+///
+/// Its secret key is zeroized when it is dropped, by the `Drop` of its signing key.
 #[derive(Debug, Clone)]
 pub struct StmSigner<D: Digest> {
     signer_index: u64,
@@ -213,8 +214,8 @@ pub struct StmSig {
     deserialize = "BatchPath<D>: Deserialize<'de>"
 ))]
 pub struct StmAggrVerificationKey<D: Clone + Digest + FixedOutput> {
-    mt_commitment: MerkleTreeCommitmentBatchCompat<D>,
-    total_stake: Stake,
+    pub(crate) mt_commitment: MerkleTreeCommitmentBatchCompat<D>,
+    pub(crate) total_stake: Stake,
 }
 
 impl<D: Digest + Clone + FixedOutput> PartialEq for StmAggrVerificationKey<D> {
@@ -260,6 +261,41 @@ pub struct StmAggrSig<D: Clone + Digest + FixedOutput> {
     pub batch_proof: BatchPath<D>,
 }
 
+/// Encoding of an `StmAggrSig` in its versioned byte representation, see
+/// [StmAggrSig::to_versioned_bytes].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AggregateSignatureType {
+    /// The layout of [StmAggrSig::to_bytes], with every signature and registered party
+    /// concatenated.
+    Concatenation,
+    /// A compact layout of the concatenation proving system, with delta encoded indexes and
+    /// without the signer indexes already carried by the batch path.
+    #[cfg(feature = "compact-aggregate")]
+    Compact,
+}
+
+impl AggregateSignatureType {
+    /// Byte prefixing the versioned byte representation of an aggregate signature.
+    pub fn to_version_byte(self) -> u8 {
+        match self {
+            Self::Concatenation => 0,
+            #[cfg(feature = "compact-aggregate")]
+            Self::Compact => 1,
+        }
+    }
+
+    /// Get the encoding of a versioned byte representation from its first byte, returns `None`
+    /// if the encoding is unknown or not enabled.
+    pub fn from_version_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Concatenation),
+            #[cfg(feature = "compact-aggregate")]
+            1 => Some(Self::Compact),
+            _ => None,
+        }
+    }
+}
+
 /// Full node verifier including the list of eligible signers and the total stake of the system.
 pub struct CoreVerifier {
     /// List of registered parties.
@@ -390,6 +426,9 @@ impl StmInitializer {
     }
 
     /// Convert to bytes
+    ///
+    /// The returned bytes contain the secret key, they should be zeroized once they are not
+    /// needed anymore.
     /// # Layout
     /// * Stake (u64)
     /// * Params
@@ -399,7 +438,9 @@ impl StmInitializer {
         let mut out = [0u8; 256];
         out[..8].copy_from_slice(&self.stake.to_be_bytes());
         out[8..32].copy_from_slice(&self.params.to_bytes());
-        out[32..64].copy_from_slice(&self.sk.to_bytes());
+        let mut sk_bytes = self.sk.to_bytes();
+        out[32..64].copy_from_slice(&sk_bytes);
+        sk_bytes.zeroize();
         out[64..].copy_from_slice(&self.pk.to_bytes());
         out
     }
@@ -424,6 +465,12 @@ impl StmInitializer {
     }
 }
 
+impl Zeroize for StmInitializer {
+    fn zeroize(&mut self) {
+        self.sk.zeroize();
+    }
+}
+
 impl<D: Clone + Digest + FixedOutput> StmSigner<D> {
     /// This function produces a signature following the description of Section 2.4.
     /// Once the signature is produced, this function checks whether any index in `[0,..,self.params.m]`
@@ -640,19 +687,35 @@ impl StmSig {
     ) -> Result<StmSig, StmSignatureError> {
         let mut u64_bytes = [0u8; 8];
 
-        u64_bytes.copy_from_slice(&bytes[0..8]);
+        u64_bytes.copy_from_slice(
+            bytes
+                .get(0..8)
+                .ok_or(StmSignatureError::SerializationError)?,
+        );
         let nr_indexes = u64::from_be_bytes(u64_bytes) as usize;
 
         let mut indexes = Vec::new();
         for i in 0..nr_indexes {
-            u64_bytes.copy_from_slice(&bytes[8 + i * 8..16 + i * 8]);
+            u64_bytes.copy_from_slice(
+                bytes
+                    .get(8 + i * 8..16 + i * 8)
+                    .ok_or(StmSignatureError::SerializationError)?,
+            );
             indexes.push(u64::from_be_bytes(u64_bytes));
         }
 
         let offset = 8 + nr_indexes * 8;
-        let sigma = Signature::from_bytes(&bytes[offset..offset + 48])?;
+        let sigma = Signature::from_bytes(
+            bytes
+                .get(offset..offset + 48)
+                .ok_or(StmSignatureError::SerializationError)?,
+        )?;
 
-        u64_bytes.copy_from_slice(&bytes[offset + 48..offset + 56]);
+        u64_bytes.copy_from_slice(
+            bytes
+                .get(offset + 48..offset + 56)
+                .ok_or(StmSignatureError::SerializationError)?,
+        );
         let signer_index = u64::from_be_bytes(u64_bytes);
 
         Ok(StmSig {
@@ -735,8 +798,16 @@ impl StmSigRegParty {
     pub fn from_bytes<D: Digest + Clone + FixedOutput>(
         bytes: &[u8],
     ) -> Result<StmSigRegParty, StmSignatureError> {
-        let reg_party = RegParty::from_bytes(&bytes[0..104])?;
-        let sig = StmSig::from_bytes::<D>(&bytes[104..])?;
+        let reg_party = RegParty::from_bytes(
+            bytes
+                .get(0..104)
+                .ok_or(StmSignatureError::SerializationError)?,
+        )?;
+        let sig = StmSig::from_bytes::<D>(
+            bytes
+                .get(104..)
+                .ok_or(StmSignatureError::SerializationError)?,
+        )?;
 
         Ok(StmSigRegParty { sig, reg_party })
     }
@@ -870,30 +941,87 @@ impl<D: Clone + Digest + FixedOutput + Send + Sync> StmAggrSig<D> {
     pub fn from_bytes(bytes: &[u8]) -> Result<StmAggrSig<D>, StmAggregateSignatureError<D>> {
         let mut u64_bytes = [0u8; 8];
 
-        u64_bytes.copy_from_slice(&bytes[..8]);
+        u64_bytes.copy_from_slice(
+            bytes
+                .get(..8)
+                .ok_or(StmAggregateSignatureError::SerializationError)?,
+        );
         let size = usize::try_from(u64::from_be_bytes(u64_bytes))
             .map_err(|_| StmAggregateSignatureError::SerializationError)?;
 
-        u64_bytes.copy_from_slice(&bytes[8..16]);
+        u64_bytes.copy_from_slice(
+            bytes
+                .get(8..16)
+                .ok_or(StmAggregateSignatureError::SerializationError)?,
+        );
         let sig_reg_size = usize::try_from(u64::from_be_bytes(u64_bytes))
             .map_err(|_| StmAggregateSignatureError::SerializationError)?;
 
-        let mut sig_reg_list = Vec::with_capacity(size);
+        // The sizes are read from the input: they are checked before allocating or slicing.
+        let sig_reg_offset = |i: usize| {
+            sig_reg_size
+                .checked_mul(i)
+                .and_then(|offset| offset.checked_add(16))
+                .ok_or(StmAggregateSignatureError::SerializationError)
+        };
+        let mut sig_reg_list = Vec::with_capacity(size.min(bytes.len()));
         for i in 0..size {
             let sig_reg = StmSigRegParty::from_bytes::<D>(
-                &bytes[16 + (sig_reg_size * i)..16 + (sig_reg_size * (i + 1))],
+                bytes
+                    .get(sig_reg_offset(i)?..sig_reg_offset(i + 1)?)
+                    .ok_or(StmAggregateSignatureError::SerializationError)?,
             )?;
             sig_reg_list.push(sig_reg);
         }
 
-        let offset = 16 + sig_reg_size * size;
-        let batch_proof = BatchPath::from_bytes(&bytes[offset..])?;
+        let offset = sig_reg_offset(size)?;
+        let batch_proof = BatchPath::from_bytes(
+            bytes
+                .get(offset..)
+                .ok_or(StmAggregateSignatureError::SerializationError)?,
+        )?;
 
         Ok(StmAggrSig {
             signatures: sig_reg_list,
             batch_proof,
         })
     }
+
+    /// Convert multi signature to bytes prefixed with the version byte of the given encoding,
+    /// so that verifiers can decode all the encodings with [StmAggrSig::from_versioned_bytes].
+    pub fn to_versioned_bytes(&self, aggregate_type: AggregateSignatureType) -> Vec<u8> {
+        let mut out = vec![aggregate_type.to_version_byte()];
+        match aggregate_type {
+            AggregateSignatureType::Concatenation => out.extend_from_slice(&self.to_bytes()),
+            #[cfg(feature = "compact-aggregate")]
+            AggregateSignatureType::Compact => {
+                out.extend_from_slice(&crate::compact_encoding::to_compact_bytes(self))
+            }
+        }
+
+        out
+    }
+
+    /// Extract a `StmAggrSig` from a versioned byte representation.
+    ///
+    /// # Error
+    /// Returns `UnsupportedEncoding` if the encoding is unknown, or not enabled.
+    pub fn from_versioned_bytes(
+        bytes: &[u8],
+    ) -> Result<StmAggrSig<D>, StmAggregateSignatureError<D>> {
+        let (&version, bytes) = bytes
+            .split_first()
+            .ok_or(StmAggregateSignatureError::SerializationError)?;
+
+        match AggregateSignatureType::from_version_byte(version) {
+            Some(AggregateSignatureType::Concatenation) => Self::from_bytes(bytes),
+            #[cfg(feature = "compact-aggregate")]
+            Some(AggregateSignatureType::Compact) => {
+                crate::compact_encoding::from_compact_bytes(bytes)
+            }
+            None => Err(StmAggregateSignatureError::UnsupportedEncoding(version)),
+        }
+    }
 }
 
 impl CoreVerifier {
@@ -1369,6 +1497,16 @@ mod tests {
             assert!(bincode::deserialize::<StmInitializer>(&bytes).is_ok())
         }
 
+        #[test]
+        fn test_initializer_zeroize(seed in any::<[u8;32]>()) {
+            let mut rng = ChaCha20Rng::from_seed(seed);
+            let params = StmParameters { m: 1, k: 1, phi_f: 1.0 };
+            let mut initializer = StmInitializer::setup(params, 1, &mut rng);
+
+            initializer.zeroize();
+            assert_eq!([0u8; 32], initializer.to_bytes()[32..64]);
+        }
+
         #[test]
         fn test_sig_serialize_deserialize(msg in any::<[u8;16]>()) {
             let params = StmParameters { m: 1, k: 1, phi_f: 0.2 };
@@ -1409,6 +1547,82 @@ mod tests {
         }
     }
 
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10))]
+
+        #[test]
+        fn test_versioned_multisig_serialize_deserialize(nparties in 2_usize..10,
+                                                         msg in any::<[u8;16]>(),
+                                                         truncated_at in any::<prop::sample::Index>()) {
+            let params = StmParameters { m: 10, k: 5, phi_f: 1.0 };
+            let ps = setup_equal_parties(params, nparties);
+            let clerk = StmClerk::from_signer(&ps[0]);
+
+            let all_ps: Vec<usize> = (0..nparties).collect();
+            let sigs = find_signatures(&msg, &ps, &all_ps);
+            if let Ok(aggr) = clerk.aggregate(&sigs, &msg) {
+                let bytes = aggr.to_versioned_bytes(AggregateSignatureType::Concatenation);
+                assert_eq!(aggr.to_bytes(), bytes[1..]);
+
+                let decoded = StmAggrSig::<D>::from_versioned_bytes(&bytes).unwrap();
+                assert!(decoded.verify(&msg, &clerk.compute_avk(), &params).is_ok());
+
+                for truncated_len in [0, 1, 9, 17, truncated_at.index(bytes.len()), bytes.len() - 1] {
+                    assert!(StmAggrSig::<D>::from_versioned_bytes(&bytes[..truncated_len]).is_err());
+                    if truncated_len > 0 {
+                        assert!(StmAggrSig::<D>::from_bytes(&bytes[1..truncated_len]).is_err());
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_versioned_multisig_from_random_bytes_does_not_panic(
+            bytes in vec(any::<u8>(), 0..2048)
+        ) {
+            let _ = StmAggrSig::<D>::from_versioned_bytes(&bytes);
+
+            let mut concatenation_bytes = vec![AggregateSignatureType::Concatenation.to_version_byte()];
+            concatenation_bytes.extend_from_slice(&bytes);
+            assert!(StmAggrSig::<D>::from_versioned_bytes(&concatenation_bytes).is_err());
+        }
+
+        #[cfg(feature = "compact-aggregate")]
+        #[test]
+        fn test_compact_multisig_serialize_deserialize(nparties in 2_usize..30,
+                                                       msg in any::<[u8;16]>()) {
+            let params = StmParameters { m: 50, k: 10, phi_f: 0.5 };
+            let ps = setup_equal_parties(params, nparties);
+            let clerk = StmClerk::from_signer(&ps[0]);
+
+            let all_ps: Vec<usize> = (0..nparties).collect();
+            let sigs = find_signatures(&msg, &ps, &all_ps);
+            if let Ok(aggr) = clerk.aggregate(&sigs, &msg) {
+                let bytes = aggr.to_versioned_bytes(AggregateSignatureType::Compact);
+                assert!(bytes.len() < aggr.to_bytes().len());
+
+                let decoded = StmAggrSig::<D>::from_versioned_bytes(&bytes).unwrap();
+                assert_eq!(aggr.signatures, decoded.signatures);
+                assert_eq!(aggr.batch_proof.to_bytes(), decoded.batch_proof.to_bytes());
+                assert!(decoded.verify(&msg, &clerk.compute_avk(), &params).is_ok());
+
+                for truncated_len in [0, 1, bytes.len() / 2, bytes.len() - 1] {
+                    assert!(StmAggrSig::<D>::from_versioned_bytes(&bytes[..truncated_len]).is_err());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_versioned_multisig_with_unsupported_encoding() {
+        let result = StmAggrSig::<D>::from_versioned_bytes(&[u8::MAX, 0, 0]);
+
+        assert!(matches!(
+            result,
+            Err(StmAggregateSignatureError::UnsupportedEncoding(u8::MAX))
+        ));
+    }
+
     /// Pick N between min and max, and then
     /// generate a vector of N stakes summing to N * tstake,
     /// plus a subset S of 0..N such that the sum of the stakes at indices
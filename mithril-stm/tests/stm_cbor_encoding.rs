@@ -0,0 +1,299 @@
+use mithril_stm::key_reg::KeyReg;
+use mithril_stm::stm::{
+    StmAggrSig, StmAggrVerificationKey, StmClerk, StmInitializer, StmParameters, StmSig,
+    StmVerificationKeyPoP,
+};
+use mithril_stm::CborEncodingError;
+
+use blake2::{digest::consts::U32, Blake2b};
+use rand_chacha::ChaCha20Rng;
+use rand_core::{RngCore, SeedableRng};
+
+type H = Blake2b<U32>;
+
+// Version 1 test vectors, computed from `fixture`. They must keep decoding to the same values
+// when a new version of the encoding is introduced.
+
+/// `StmParameters`, `[1, [8, 8, 0.9]]`.
+const PARAMETERS_V1: &str = "8201830808fb3feccccccccccccd";
+
+/// `StmVerificationKeyPoP` of the first party.
+const VERIFICATION_KEY_POP_V1: &str = concat!(
+    "820182586092117d44afc049b110b33c8aadd22560fcbc585836da10c35655d8",
+    "1a38432f7cfa0e53b49b1dd8f961af70438ab1db740119ed7aa17bb5e7b4a61e",
+    "34cfc52d59d88e058c29b0747c87a87c7222fa27841f34d210466bfe7d86cf00",
+    "c783ef8a1b5860a6e4b19b902a410c3702f4ed58f62b6a7e8a99b0ac4a34f1e9",
+    "e5982b0fb488d7ce3b5ba3ee116f57bb709380df0c65a79856c527a8fd7be126",
+    "053c01235304c171a84b3741e9cdf794fe389508cf9ea4787b17edbcc79f081c",
+    "fcadf70f5d91d7",
+);
+
+/// `StmSig` of the first party.
+const SIG_V1: &str = concat!(
+    "8201835830947607ab95dc7eddf4a353e90780f2625e75668f2d402264e29f97",
+    "b9d78560d40a12010b02886b8c42b6804dd0242802870001020304060702",
+);
+
+/// `StmAggrVerificationKey` of the three parties.
+const AGGR_VERIFICATION_KEY_V1: &str = concat!(
+    "820183582020dc7ee16072344aac97d728d3ba1a756137e96382679676d6e6a1",
+    "736cceec0d031829",
+);
+
+/// `StmAggrSig` of the signatures of two of the parties.
+const AGGR_SIG_V1: &str = concat!(
+    "8201828282835830948d9d2859658bfa66e631ab7959d1a848044ed6a8e69ff3",
+    "5d5522244ed26e8e7efcf98ddfc85e6cd080f80af04d20e98105018258609691",
+    "35faf77565af03c2861992b8a794c1c11900cfc00a3a71fe01cf2ba6c15249c1",
+    "3eba36b618d2ab613046f154800f163471b0fe396d39b121513d22e509a50334",
+    "0bc616cb8041600839e769581cb33d348fc28fcdfee8cc48d3627d5b83860d82",
+    "835830947607ab95dc7eddf4a353e90780f2625e75668f2d402264e29f97b9d7",
+    "8560d40a12010b02886b8c42b6804dd024280287000102030406070282586092",
+    "117d44afc049b110b33c8aadd22560fcbc585836da10c35655d81a38432f7cfa",
+    "0e53b49b1dd8f961af70438ab1db740119ed7aa17bb5e7b4a61e34cfc52d59d8",
+    "8e058c29b0747c87a87c7222fa27841f34d210466bfe7d86cf00c783ef8a1b11",
+    "8281582021c9a3f1213a2e10c14923a9e3b6e91522354874b81102116754d39c",
+    "7296310a820102",
+);
+
+struct Fixture {
+    msg: [u8; 16],
+    params: StmParameters,
+    vk_pop: StmVerificationKeyPoP,
+    sig: StmSig,
+    avk: StmAggrVerificationKey<H>,
+    aggr: StmAggrSig<H>,
+}
+
+fn fixture() -> Fixture {
+    let params = StmParameters {
+        m: 8,
+        k: 8,
+        phi_f: 0.9,
+    };
+    let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+    let mut msg = [0u8; 16];
+    rng.fill_bytes(&mut msg);
+
+    let initializers = (0..3)
+        .map(|_| StmInitializer::setup(params, 10 + rng.next_u64() % 10, &mut rng))
+        .collect::<Vec<_>>();
+    let mut key_reg = KeyReg::init();
+    for initializer in &initializers {
+        key_reg
+            .register(initializer.stake, initializer.verification_key())
+            .unwrap();
+    }
+    let closed_reg = key_reg.close::<H>();
+    let vk_pop = initializers[0].verification_key();
+    let sigs = initializers
+        .into_iter()
+        .filter_map(|initializer| {
+            initializer
+                .new_signer(closed_reg.clone())
+                .unwrap()
+                .sign(&msg)
+        })
+        .collect::<Vec<_>>();
+    let clerk = StmClerk::from_registration(&params, &closed_reg);
+
+    Fixture {
+        msg,
+        params,
+        vk_pop,
+        sig: sigs[0].clone(),
+        avk: clerk.compute_avk(),
+        aggr: clerk.aggregate(&sigs, &msg).unwrap(),
+    }
+}
+
+#[test]
+fn encoding_matches_test_vectors() {
+    let fixture = fixture();
+
+    assert_eq!(PARAMETERS_V1, hex::encode(fixture.params.to_cbor_bytes()));
+    assert_eq!(
+        VERIFICATION_KEY_POP_V1,
+        hex::encode(fixture.vk_pop.to_cbor_bytes())
+    );
+    assert_eq!(SIG_V1, hex::encode(fixture.sig.to_cbor_bytes()));
+    assert_eq!(
+        AGGR_VERIFICATION_KEY_V1,
+        hex::encode(fixture.avk.to_cbor_bytes())
+    );
+    assert_eq!(AGGR_SIG_V1, hex::encode(fixture.aggr.to_cbor_bytes()));
+}
+
+/// Head of a CBOR data item of the given major type, following RFC 8949 section 3.
+fn cbor_head(major_type: u8, argument: u64) -> Vec<u8> {
+    let initial_byte = |additional_info: u8| (major_type << 5) | additional_info;
+    match argument {
+        0..=23 => vec![initial_byte(argument as u8)],
+        24..=0xff => vec![initial_byte(24), argument as u8],
+        0x100..=0xffff => [
+            vec![initial_byte(25)],
+            (argument as u16).to_be_bytes().to_vec(),
+        ]
+        .concat(),
+        0x10000..=0xffff_ffff => [
+            vec![initial_byte(26)],
+            (argument as u32).to_be_bytes().to_vec(),
+        ]
+        .concat(),
+        _ => [vec![initial_byte(27)], argument.to_be_bytes().to_vec()].concat(),
+    }
+}
+
+const UINT: u8 = 0;
+const BSTR: u8 = 2;
+const ARRAY: u8 = 4;
+const FLOAT: u8 = 7;
+
+#[test]
+fn test_vectors_match_a_hand_written_encoding() {
+    let fixture = fixture();
+
+    // `[1, [m, k, phi_f]]`, with `phi_f` as a double as 0.9 has no shorter exact representation.
+    let params = [
+        cbor_head(ARRAY, 2),
+        cbor_head(UINT, 1),
+        cbor_head(ARRAY, 3),
+        cbor_head(UINT, 8),
+        cbor_head(UINT, 8),
+        vec![(FLOAT << 5) | 27],
+        0.9_f64.to_be_bytes().to_vec(),
+    ]
+    .concat();
+    assert_eq!(PARAMETERS_V1, hex::encode(params));
+
+    // `[1, [vk, pop]]`
+    let vk_pop_bytes = fixture.vk_pop.to_bytes();
+    let vk_pop = [
+        cbor_head(ARRAY, 2),
+        cbor_head(UINT, 1),
+        cbor_head(ARRAY, 2),
+        cbor_head(BSTR, 96),
+        vk_pop_bytes[..96].to_vec(),
+        cbor_head(BSTR, 96),
+        vk_pop_bytes[96..].to_vec(),
+    ]
+    .concat();
+    assert_eq!(VERIFICATION_KEY_POP_V1, hex::encode(vk_pop));
+
+    // `[1, [sigma, [* index], signer_index]]`
+    let mut sig = [
+        cbor_head(ARRAY, 2),
+        cbor_head(UINT, 1),
+        cbor_head(ARRAY, 3),
+        cbor_head(BSTR, 48),
+        fixture.sig.sigma.to_bytes().to_vec(),
+        cbor_head(ARRAY, fixture.sig.indexes.len() as u64),
+    ]
+    .concat();
+    for index in &fixture.sig.indexes {
+        sig.extend(cbor_head(UINT, *index));
+    }
+    sig.extend(cbor_head(UINT, fixture.sig.signer_index));
+    assert_eq!(SIG_V1, hex::encode(sig));
+}
+
+#[test]
+fn test_vectors_decode_to_the_encoded_values() {
+    let fixture = fixture();
+
+    let params = StmParameters::from_cbor_bytes(&hex::decode(PARAMETERS_V1).unwrap()).unwrap();
+    assert_eq!(fixture.params, params);
+
+    let vk_pop =
+        StmVerificationKeyPoP::from_cbor_bytes(&hex::decode(VERIFICATION_KEY_POP_V1).unwrap())
+            .unwrap();
+    assert_eq!(fixture.vk_pop.to_bytes(), vk_pop.to_bytes());
+    vk_pop.check().unwrap();
+
+    let sig = StmSig::from_cbor_bytes(&hex::decode(SIG_V1).unwrap()).unwrap();
+    assert_eq!(fixture.sig, sig);
+
+    let avk = StmAggrVerificationKey::<H>::from_cbor_bytes(
+        &hex::decode(AGGR_VERIFICATION_KEY_V1).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(fixture.avk, avk);
+
+    let aggr = StmAggrSig::<H>::from_cbor_bytes(&hex::decode(AGGR_SIG_V1).unwrap()).unwrap();
+    assert_eq!(fixture.aggr.to_bytes(), aggr.to_bytes());
+    aggr.verify(&fixture.msg, &avk, &params).unwrap();
+}
+
+#[test]
+fn other_versions_are_rejected() {
+    for vector in [
+        PARAMETERS_V1,
+        VERIFICATION_KEY_POP_V1,
+        SIG_V1,
+        AGGR_VERIFICATION_KEY_V1,
+        AGGR_SIG_V1,
+    ] {
+        let mut bytes = hex::decode(vector).unwrap();
+        // `[version, body]`: an array of two elements followed by the version.
+        assert_eq!([0x82, 0x01], bytes[..2]);
+
+        for version in [0x00, 0x02] {
+            bytes[1] = version;
+            let expected = Err(CborEncodingError::UnsupportedVersion(version.into()));
+
+            assert_eq!(expected, StmParameters::from_cbor_bytes(&bytes).map(|_| ()));
+            assert_eq!(
+                expected,
+                StmVerificationKeyPoP::from_cbor_bytes(&bytes).map(|_| ())
+            );
+            assert_eq!(expected, StmSig::from_cbor_bytes(&bytes).map(|_| ()));
+            assert_eq!(
+                expected,
+                StmAggrVerificationKey::<H>::from_cbor_bytes(&bytes).map(|_| ())
+            );
+            assert_eq!(
+                expected,
+                StmAggrSig::<H>::from_cbor_bytes(&bytes).map(|_| ())
+            );
+        }
+    }
+}
+
+#[test]
+fn non_canonical_encodings_are_rejected() {
+    let params = StmParameters {
+        m: 8,
+        k: 8,
+        phi_f: 0.5,
+    };
+    // `[1, [8, 8, 0.5]]`, the float is written in its shortest form (half precision).
+    let canonical = "8201830808f93800";
+    assert_eq!(canonical, hex::encode(params.to_cbor_bytes()));
+    assert_eq!(
+        Ok(params),
+        StmParameters::from_cbor_bytes(&hex::decode(canonical).unwrap())
+    );
+
+    for non_canonical in [
+        // Version written on two bytes
+        "82180183 0808f93800",
+        // `m` written on two bytes
+        "820183 1808 08f93800",
+        // Array of the parameters with an indefinite length
+        "82019f 0808f93800 ff",
+        // Envelope with an indefinite length
+        "9f0183 0808f93800 ff",
+        // Float written in single precision
+        "8201830808 fa3f000000",
+        // Float written in double precision
+        "8201830808 fb3fe0000000000000",
+    ] {
+        let bytes = hex::decode(non_canonical.replace(' ', "")).unwrap();
+
+        assert_eq!(
+            Err(CborEncodingError::NonCanonicalEncoding),
+            StmParameters::from_cbor_bytes(&bytes),
+            "non canonical encoding '{non_canonical}' should be rejected"
+        );
+    }
+}
@@ -98,6 +98,7 @@ mod tests {
     use warp::http::{Response, StatusCode};
     use warp::hyper::body::Bytes;
 
+    use mithril_common::messages::CardanoTransactionsProofsRequestMessage;
     use mithril_common::test_utils::apispec::APISpec;
 
     use crate::{default_values, CliArguments};
@@ -476,6 +477,41 @@ mod tests {
         test(task, PORT).await;
     }
 
+    #[tokio::test]
+    async fn post_ctx_proof() {
+        const PORT: u16 = 3015;
+        let task = tokio::spawn(async move {
+            // Yield back to Tokio's scheduler to ensure the web server is ready before going on.
+            yield_now().await;
+
+            let path = "/proof/cardano-transaction";
+            let message = CardanoTransactionsProofsRequestMessage::new(&[
+                default_values::proof_transaction_hashes()[0],
+            ]);
+            let url = BASE_URL.replace("PORT", &PORT.to_string());
+            let response = reqwest::Client::new()
+                .post(format!("{url}{path}"))
+                .body(serde_json::to_string(&message).unwrap())
+                .send()
+                .await
+                .unwrap();
+            let response = into_response(response).await;
+
+            APISpec::verify_conformity(
+                get_spec_files(),
+                "POST",
+                path,
+                "application/json",
+                &message,
+                &response,
+                &StatusCode::OK,
+            )
+            .map_err(|e| anyhow!(e))
+        });
+
+        test(task, PORT).await;
+    }
+
     #[tokio::test]
     async fn get_no_ctx_proof() {
         const PORT: u16 = 3014;
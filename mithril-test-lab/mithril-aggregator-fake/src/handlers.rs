@@ -1,6 +1,7 @@
 //! HTTP handlers module
 //! This module contains the controllers for the different routes and middlewares.
 
+use anyhow::Context;
 use axum::{
     body::Body,
     extract::{Path, Query, Request, State},
@@ -30,7 +31,10 @@ pub async fn aggregator_router() -> Router<SharedState> {
         .route("/artifact/snapshot/:digest", get(snapshot))
         .route("/artifact/cardano-transactions", get(ctx_snapshots))
         .route("/artifact/cardano-transaction/:hash", get(ctx_snapshot))
-        .route("/proof/cardano-transaction", get(ctx_proof))
+        .route(
+            "/proof/cardano-transaction",
+            get(ctx_proof).post(ctx_proof_post),
+        )
         .route("/certificates", get(certificates))
         .route("/certificate/:hash", get(certificate))
         .route("/statistics/snapshot", post(statistics))
@@ -187,6 +191,31 @@ pub async fn ctx_proof(
         })
 }
 
+#[derive(serde::Deserialize)]
+pub struct CardanoTransactionProofRequestBody {
+    transaction_hashes: Vec<String>,
+}
+
+/// HTTP: return a cardano transaction proof identified by the transaction hashes of a JSON body.
+pub async fn ctx_proof_post(
+    State(state): State<SharedState>,
+    body: String,
+) -> Result<Response<Body>, AppError> {
+    let app_state = state.read().await;
+    let request: CardanoTransactionProofRequestBody =
+        serde_json::from_str(&body).with_context(|| "Could not deserialize the proof request")?;
+    let transaction_hashes = request.transaction_hashes.join(",");
+
+    app_state
+        .get_ctx_proofs(&transaction_hashes)
+        .await?
+        .map(|s| s.into_response())
+        .ok_or_else(|| {
+            debug!("ctx proof ctx_hash={transaction_hashes} NOT FOUND.");
+            AppError::NotFound
+        })
+}
+
 /// HTTP: return OK when the client registers download statistics
 pub async fn statistics() -> Result<Response<Body>, AppError> {
     let response = Response::builder().status(StatusCode::CREATED);
@@ -214,6 +243,8 @@ pub async fn set_json_app_header(
 
 #[cfg(test)]
 mod tests {
+    use mithril_common::messages::CardanoTransactionsProofsRequestMessage;
+
     use crate::{default_values, shared_state::AppState};
 
     pub use super::*;
@@ -358,4 +389,19 @@ mod tests {
 
         assert_eq!(StatusCode::OK, response.status());
     }
+
+    #[tokio::test]
+    async fn existing_ctx_proof_hash_in_post_body() {
+        let state: State<SharedState> = State(AppState::default().into());
+        let body = serde_json::to_string(&CardanoTransactionsProofsRequestMessage::new(&[
+            default_values::proof_transaction_hashes()[0],
+        ]))
+        .unwrap();
+
+        let response = ctx_proof_post(state, body)
+            .await
+            .expect("The handler was expected to succeed since the ctx proof's hash does exist.");
+
+        assert_eq!(StatusCode::OK, response.status());
+    }
 }